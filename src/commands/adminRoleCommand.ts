@@ -0,0 +1,44 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction, Permissions} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+
+export class AdminRoleCommand extends AbstractCommand {
+    protected name = 'zkill-admin-role';
+
+    protected ROLE = 'role';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Setting the admin role is not possible in PM!');
+            return;
+        }
+        if (!interaction.memberPermissions?.has(Permissions.FLAGS.MANAGE_GUILD)) {
+            interaction.reply({content: 'Setting the admin role requires the Manage Server permission', ephemeral: true});
+            return;
+        }
+        const role = interaction.options.getRole(this.ROLE);
+        sub.setGuildAdminRole(interaction.guildId, role?.id);
+        interaction.reply({
+            content: role ?
+                `Members with the role ${role.name} can now manage subscriptions` :
+                'Only members with the Manage Channels permission can manage subscriptions now',
+            ephemeral: true
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Set the role that may manage subscriptions without the Manage Channels permission');
+        slashCommand.addRoleOption(option =>
+            option.setName(this.ROLE)
+                .setDescription('Bot admin role, leave empty to remove it')
+                .setRequired(false)
+        );
+        return slashCommand;
+    }
+
+}
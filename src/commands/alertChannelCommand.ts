@@ -0,0 +1,44 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction, Permissions} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+
+export class AlertChannelCommand extends AbstractCommand {
+    protected name = 'zkill-alert-channel';
+
+    protected CHANNEL = 'channel';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Setting the alert channel is not possible in PM!');
+            return;
+        }
+        if (!interaction.memberPermissions?.has(Permissions.FLAGS.MANAGE_GUILD)) {
+            interaction.reply({content: 'Setting the alert channel requires the Manage Server permission', ephemeral: true});
+            return;
+        }
+        const channel = interaction.options.getChannel(this.CHANNEL);
+        sub.setGuildAlertChannel(interaction.guildId, channel?.id);
+        interaction.reply({
+            content: channel ?
+                `Disabled subscriptions are reported in <#${channel.id}> now` :
+                'Disabled subscriptions are reported to the server owner now',
+            ephemeral: true
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Set the channel notified when the bot disables subscriptions it cannot post for');
+        slashCommand.addChannelOption(option =>
+            option.setName(this.CHANNEL)
+                .setDescription('Alert channel, leave empty to notify the server owner')
+                .setRequired(false)
+        );
+        return slashCommand;
+    }
+
+}
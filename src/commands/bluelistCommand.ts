@@ -0,0 +1,91 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {listAgeText, normalizeListName, parseEntityIds} from '../lib/entityLists';
+
+export class BluelistCommand extends AbstractCommand {
+    protected name = 'zkill-bluelist';
+
+    protected LIST = 'list';
+    protected IDS = 'ids';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Entity lists are not available in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const subCommand = interaction.options.getSubcommand(true);
+        const name = normalizeListName(interaction.options.getString(this.LIST, true));
+        if (name == null) {
+            interaction.reply({content: 'List names may only contain letters, digits, - and _', ephemeral: true});
+            return;
+        }
+        if (subCommand === 'show') {
+            const list = sub.getEntityLists(interaction.guildId)[name];
+            let reply = list ?
+                `Entity list ${listAgeText(name, sub.getEntityListsUpdatedAt(interaction.guildId)[name])} (${list.length} IDs): ${list.join(', ')}` :
+                `There is no entity list ${name}`;
+            if (reply.length > 1900) {
+                reply = reply.substring(0, 1900) + '...';
+            }
+            interaction.reply({content: reply, ephemeral: true});
+            return;
+        }
+        const ids = parseEntityIds(interaction.options.getString(this.IDS, true));
+        if (ids == null) {
+            interaction.reply({content: 'IDs have to be character, corporation or alliance IDs separated by commas', ephemeral: true});
+            return;
+        }
+        if (subCommand === 'add') {
+            const size = sub.addToEntityList(interaction.guildId, name, ids);
+            interaction.reply({content: `Entity list ${name} now has ${size} IDs`, ephemeral: true});
+            return;
+        }
+        const size = sub.removeFromEntityList(interaction.guildId, name, ids);
+        interaction.reply({
+            content: size == null ? `There is no entity list ${name}` :
+                size === 0 ? `Removed the emptied entity list ${name}` : `Entity list ${name} now has ${size} IDs`,
+            ephemeral: true,
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Manage named lists of character, corporation and alliance IDs, e.g. your blues');
+
+        for (const [name, description] of [['add', 'Add IDs to a list, creating it if needed'], ['remove', 'Remove IDs from a list']]) {
+            slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName(name)
+                .addStringOption(option =>
+                    option.setName(this.LIST)
+                        .setDescription('Name of the list')
+                        .setRequired(true)
+                )
+                .addStringOption(option =>
+                    option.setName(this.IDS)
+                        .setDescription('Character, corporation or alliance IDs separated by commas')
+                        .setRequired(true)
+                )
+                .setDescription(description));
+        }
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('show')
+            .addStringOption(option =>
+                option.setName(this.LIST)
+                    .setDescription('Name of the list')
+                    .setRequired(true)
+            )
+            .setDescription('Show the IDs on a list'));
+
+        return slashCommand;
+    }
+
+}
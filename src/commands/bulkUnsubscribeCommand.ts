@@ -0,0 +1,65 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {parseSubscriptionIds} from '../lib/subscriptionSearch';
+
+export class BulkUnsubscribeCommand extends AbstractCommand {
+    protected name = 'zkill-bulk-unsubscribe';
+
+    protected IDS = 'ids';
+    protected CHANNEL = 'channel';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Subscription is not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const idsValue = interaction.options.getString(this.IDS);
+        const channel = interaction.options.getChannel(this.CHANNEL);
+        if (idsValue == null && channel == null) {
+            interaction.reply({content: 'Give the IDs of subscriptions in this channel, a channel whose subscriptions are all removed, or both', ephemeral: true});
+            return;
+        }
+        const ids = idsValue != null ? parseSubscriptionIds(idsValue) : undefined;
+        if (ids === null) {
+            interaction.reply({content: 'IDs have to be numbers separated by commas or spaces, e.g. 1,4,7', ephemeral: true});
+            return;
+        }
+        const channelId = channel?.id ?? interaction.channelId;
+        const removed = sub.bulkUnsubscribe(interaction.guildId, channelId, ids);
+        let reply = removed.length > 0 ?
+            `Removed ${removed.length} subscriptions from <#${channelId}>: ${removed.join(', ')}` :
+            `No matching subscriptions in <#${channelId}>`;
+        const missing = (ids ?? []).filter(id => !removed.includes(`${SubscriptionType.PUBLIC}${id}`));
+        if (missing.length > 0) {
+            reply += `\nNot found: ${missing.join(', ')}`;
+        }
+        interaction.reply({content: reply, ephemeral: true});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Remove several subscriptions at once');
+        slashCommand.addStringOption(option =>
+            option.setName(this.IDS)
+                .setDescription('Comma separated IDs, all subscriptions of the channel if empty')
+                .setRequired(false)
+        );
+        slashCommand.addChannelOption(option =>
+            option.setName(this.CHANNEL)
+                .setDescription('Channel of the subscriptions, this channel if empty')
+                .setRequired(false)
+        );
+        return slashCommand;
+    }
+}
@@ -0,0 +1,152 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {LimitType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {normalizeListName, parseEntityIds} from '../lib/entityLists';
+import {validateLimitTypes} from '../lib/subscriptionValidation';
+
+export class CampaignCommand extends AbstractCommand {
+    protected name = 'zkill-campaign';
+
+    protected NAME = 'name';
+    protected ALLIANCE_IDS = 'alliance-ids';
+    protected CORPORATION_IDS = 'corporation-ids';
+    protected REGION_IDS = 'region-ids';
+    protected SYSTEM_IDS = 'system-ids';
+    protected MIN_VALUE = 'min-value';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            await interaction.reply('Campaigns are not possible in PM!');
+            return;
+        }
+        const subCommand = interaction.options.getSubcommand(true);
+        const name = normalizeListName(interaction.options.getString(this.NAME, true));
+        if (name == null) {
+            await interaction.reply({content: 'Campaign names have up to 32 letters, digits, - or _', ephemeral: true});
+            return;
+        }
+        if (subCommand === 'report') {
+            const campaign = sub.getCampaign(interaction.guildId, name);
+            if (!campaign) {
+                await interaction.reply({content: 'There is no campaign named ' + name, ephemeral: true});
+                return;
+            }
+            await interaction.deferReply();
+            await interaction.editReply({embeds: [await sub.campaignEmbed(name, campaign)]});
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            await interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        if (subCommand === 'stop') {
+            const campaign = await sub.stopCampaign(interaction.guildId, name);
+            await interaction.reply({
+                content: campaign ?
+                    `Stopped the campaign ${name}, its scoreboard stays available with /zkill-campaign report` :
+                    'There is no running campaign named ' + name,
+                ephemeral: true,
+            });
+            return;
+        }
+
+        const alliances = interaction.options.getString(this.ALLIANCE_IDS);
+        const corporations = interaction.options.getString(this.CORPORATION_IDS);
+        const allianceIds = alliances ? parseEntityIds(alliances) : [];
+        const corporationIds = corporations ? parseEntityIds(corporations) : [];
+        if (allianceIds == null || corporationIds == null || allianceIds.length + corporationIds.length === 0) {
+            await interaction.reply({content: 'A campaign needs the alliance or corporation IDs of your side', ephemeral: true});
+            return;
+        }
+        if (!sub.canSubscribe(interaction.guildId)) {
+            await interaction.reply({content: 'This server reached the maximum number of subscriptions on this instance', ephemeral: true});
+            return;
+        }
+        const limitTypes = new Map<LimitType, string>();
+        if (allianceIds.length > 0) {
+            limitTypes.set(LimitType.ALLIANCE, allianceIds.join(','));
+        }
+        if (corporationIds.length > 0) {
+            limitTypes.set(LimitType.CORPORATION, corporationIds.join(','));
+        }
+        const regions = interaction.options.getString(this.REGION_IDS);
+        if (regions) {
+            limitTypes.set(LimitType.REGION, regions);
+        }
+        const systems = interaction.options.getString(this.SYSTEM_IDS);
+        if (systems) {
+            limitTypes.set(LimitType.SYSTEM, systems);
+        }
+        const problems = validateLimitTypes(limitTypes, systemName => sub.isKnownSystemName(systemName));
+        if (problems.length > 0) {
+            await interaction.reply({content: 'The campaign would never count a kill:\n- ' + problems.join('\n- '), ephemeral: true});
+            return;
+        }
+        const started = sub.startCampaign(
+            interaction.guildId,
+            interaction.channelId,
+            name,
+            [...allianceIds, ...corporationIds],
+            limitTypes,
+            interaction.options.getNumber(this.MIN_VALUE) ?? 0,
+            interaction.user.id,
+        );
+        await interaction.reply({
+            content: started ?
+                `Started the campaign ${name}, its kills are posted in this channel and counted until /zkill-campaign stop` :
+                `The campaign ${name} is already running`,
+            ephemeral: true,
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Count the kills and losses of an operation, e.g. a war or a deployment');
+
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('start')
+            .setDescription('Post the kills of your side in this channel and count them')
+            .addStringOption(option =>
+                option.setName(this.NAME)
+                    .setDescription('Name of the campaign')
+                    .setRequired(true)
+            )
+            .addStringOption(option =>
+                option.setName(this.ALLIANCE_IDS)
+                    .setDescription('Alliance IDs of your side, comma separated')
+            )
+            .addStringOption(option =>
+                option.setName(this.CORPORATION_IDS)
+                    .setDescription('Corporation IDs of your side, comma separated')
+            )
+            .addStringOption(option =>
+                option.setName(this.REGION_IDS)
+                    .setDescription('Limit to region IDs, comma separated')
+            )
+            .addStringOption(option =>
+                option.setName(this.SYSTEM_IDS)
+                    .setDescription('Limit to system IDs, comma separated')
+            )
+            .addNumberOption(option =>
+                option.setName(this.MIN_VALUE)
+                    .setDescription('Minimum ISK value of the kills')
+            ));
+        for (const [subCommand, description] of [['stop', 'Stop counting and posting the kills of a campaign'], ['report', 'Show the scoreboard of a campaign']]) {
+            slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName(subCommand)
+                .setDescription(description)
+                .addStringOption(option =>
+                    option.setName(this.NAME)
+                        .setDescription('Name of the campaign')
+                        .setRequired(true)
+                ));
+        }
+
+        return slashCommand;
+    }
+
+}
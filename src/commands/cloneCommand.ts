@@ -0,0 +1,119 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {LimitType, SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {parseEntityIds} from '../lib/entityLists';
+
+export class CloneCommand extends AbstractCommand {
+    protected name = 'zkill-clone';
+
+    protected ID = 'id';
+    protected CHANNEL = 'channel';
+    protected NEW_ID = 'new-id';
+    protected ALLIANCE_IDS = 'alliance-ids';
+    protected CORPORATION_IDS = 'corporation-ids';
+    protected CHARACTER_IDS = 'character-ids';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Cloning is not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const id = String(interaction.options.getNumber(this.ID, true));
+        const newId = interaction.options.getNumber(this.NEW_ID);
+        const targetId = newId != null ? String(newId) : id;
+        const channel = interaction.options.getChannel(this.CHANNEL, true);
+        if (channel.id === interaction.channelId && targetId === id) {
+            interaction.reply({content: 'Cloning into the same channel needs a new ID', ephemeral: true});
+            return;
+        }
+        if (!sub.canSubscribe(interaction.guildId)) {
+            interaction.reply({content: 'This server reached the maximum number of subscriptions on this instance', ephemeral: true});
+            return;
+        }
+
+        // The entity filters can be replaced, e.g. to copy the filters of the alliance feed for a corporation
+        const overrides = new Map<LimitType, string>();
+        for (const [option, limitType] of <[string, LimitType][]>[
+            [this.ALLIANCE_IDS, LimitType.ALLIANCE],
+            [this.CORPORATION_IDS, LimitType.CORPORATION],
+            [this.CHARACTER_IDS, LimitType.CHARACTER],
+        ]) {
+            const value = interaction.options.getString(option);
+            if (value == null) {
+                continue;
+            }
+            const ids = parseEntityIds(value);
+            if (ids == null) {
+                interaction.reply({content: `${option} has to be a comma separated list of IDs`, ephemeral: true});
+                return;
+            }
+            overrides.set(limitType, ids.join(','));
+        }
+
+        const cloned = sub.cloneSubscriptionTo(
+            SubscriptionType.PUBLIC,
+            interaction.guildId,
+            interaction.channelId,
+            channel.id,
+            overrides,
+            id,
+            targetId,
+            interaction.user.id,
+        );
+        interaction.reply({
+            content: cloned ?
+                `Subscription ID: ${id} copied to <#${channel.id}> as ID: ${targetId}` :
+                `No subscription with ID ${id} found in this channel, or <#${channel.id}> already has a subscription with ID ${targetId}`,
+            ephemeral: true,
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Copy a subscription with all its filters to another channel');
+
+        slashCommand.addNumberOption(option =>
+            option.setName(this.ID)
+                .setDescription('ID of the public feed in this channel')
+                .setRequired(true)
+        );
+        slashCommand.addChannelOption(option =>
+            option.setName(this.CHANNEL)
+                .setDescription('Channel to copy the subscription to')
+                .setRequired(true)
+        );
+        slashCommand.addNumberOption(option =>
+            option.setName(this.NEW_ID)
+                .setDescription('ID of the copy, the same ID by default')
+                .setRequired(false)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.ALLIANCE_IDS)
+                .setDescription('Replace the alliance IDs of the copy, comma separated')
+                .setRequired(false)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.CORPORATION_IDS)
+                .setDescription('Replace the corporation IDs of the copy, comma separated')
+                .setRequired(false)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.CHARACTER_IDS)
+                .setDescription('Replace the character IDs of the copy, comma separated')
+                .setRequired(false)
+        );
+
+        return slashCommand;
+    }
+
+}
@@ -0,0 +1,299 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {parseHexColor, SubscriptionSettings, SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {generateFeedToken} from '../lib/feedServer';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {parseThumbnailSource, ThumbnailSource} from '../lib/images';
+import {StaleKillAction} from '../lib/killAge';
+import {DRY_RUN_OFF, DryRunMode, parseDryRunMode} from '../lib/dryRun';
+
+export class ConfigureCommand extends AbstractCommand {
+    protected name = 'zkill-configure';
+
+    protected ID = 'id';
+    protected SHOW_VALUE_BREAKDOWN = 'show-value-breakdown';
+    protected SUPPRESS_PODS_AFTER_LOSS = 'suppress-pods-after-loss';
+    protected BATTLE_REPORT_THRESHOLD = 'battle-report-threshold';
+    protected BATTLE_REPORT_THREADS = 'battle-report-threads';
+    protected ATOM_FEED = 'atom-feed';
+    protected PING_ROLE = 'ping-role';
+    protected PING_ROLE_VALUE = 'ping-role-value';
+    protected PING_HERE_VALUE = 'ping-here-value';
+    protected EMBED_COLOR = 'embed-color';
+    protected SRP_BUTTON = 'srp-button';
+    protected SHOW_FITTING = 'show-fitting';
+    protected LINK_BUTTONS = 'link-buttons';
+    protected THUMBNAIL = 'thumbnail';
+    protected MAX_KILL_AGE = 'max-kill-age';
+    protected STALE_KILLS = 'stale-kills';
+    protected MATCH_SUMMARY = 'match-summary';
+    protected DESCRIPTION = 'description';
+    protected DRY_RUN = 'dry-run';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Configuration is not possible in PM!');
+            return;
+        }
+        const locale = sub.getGuildLocale(interaction.guildId);
+        const yesNo = (value: boolean) => translate(locale, value ? 'command.yes' : 'command.no');
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(locale, 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const subCommand = interaction.options.getSubcommand(true) as SubscriptionType;
+        const id = interaction.options.getNumber(this.ID, true);
+        const showValueBreakdown = interaction.options.getBoolean(this.SHOW_VALUE_BREAKDOWN);
+        const suppressPodsAfterLoss = interaction.options.getBoolean(this.SUPPRESS_PODS_AFTER_LOSS);
+        const battleReportThreshold = interaction.options.getInteger(this.BATTLE_REPORT_THRESHOLD);
+        const battleReportThreads = interaction.options.getBoolean(this.BATTLE_REPORT_THREADS);
+        const atomFeed = interaction.options.getBoolean(this.ATOM_FEED);
+        const pingRole = interaction.options.getRole(this.PING_ROLE);
+        const pingRoleValue = interaction.options.getNumber(this.PING_ROLE_VALUE);
+        const pingHereValue = interaction.options.getNumber(this.PING_HERE_VALUE);
+        const embedColor = interaction.options.getString(this.EMBED_COLOR);
+        const srpButton = interaction.options.getBoolean(this.SRP_BUTTON);
+        const showFitting = interaction.options.getBoolean(this.SHOW_FITTING);
+        const linkButtons = interaction.options.getBoolean(this.LINK_BUTTONS);
+        const thumbnail = interaction.options.getString(this.THUMBNAIL);
+        const maxKillAge = interaction.options.getInteger(this.MAX_KILL_AGE);
+        const staleKills = interaction.options.getString(this.STALE_KILLS);
+        const matchSummary = interaction.options.getBoolean(this.MATCH_SUMMARY);
+        const description = interaction.options.getString(this.DESCRIPTION);
+        const dryRun = interaction.options.getString(this.DRY_RUN);
+
+        let reply = translate(locale, 'command.configure.done', {type: subCommand, id});
+        const settings: SubscriptionSettings = {};
+        if (showValueBreakdown != null) {
+            settings.showValueBreakdown = showValueBreakdown;
+            reply += '\n' + translate(locale, 'command.configure.showValueBreakdown', {value: yesNo(showValueBreakdown)});
+        }
+        if (suppressPodsAfterLoss != null) {
+            settings.suppressPodsAfterLoss = suppressPodsAfterLoss;
+            reply += '\n' + translate(locale, 'command.configure.suppressPodsAfterLoss', {value: yesNo(suppressPodsAfterLoss)});
+        }
+        if (battleReportThreshold != null) {
+            settings.battleReportThreshold = battleReportThreshold;
+            reply += '\n' + translate(locale, 'command.configure.battleReportThreshold', {value: battleReportThreshold});
+        }
+        if (battleReportThreads != null) {
+            settings.battleReportThreads = battleReportThreads;
+            reply += '\n' + translate(locale, 'command.configure.battleReportThreads', {value: yesNo(battleReportThreads)});
+        }
+        if (atomFeed != null) {
+            // Enabling again generates a new token, which invalidates the previous feed URL
+            settings.feedToken = atomFeed ? generateFeedToken() : undefined;
+            if (!settings.feedToken) {
+                reply += '\n' + translate(locale, 'command.configure.atomFeedDisabled');
+            } else {
+                const feedUrl = sub.getFeedUrl(settings.feedToken);
+                reply += '\n' + (feedUrl ? translate(locale, 'command.configure.atomFeed', {url: feedUrl}) : translate(locale, 'command.configure.atomFeedUnavailable'));
+            }
+        }
+        if (pingRole != null) {
+            settings.pingRoleId = pingRole.id;
+            reply += '\n' + translate(locale, 'command.configure.pingRole', {role: pingRole.name});
+        }
+        if (pingRoleValue != null) {
+            settings.pingRoleValue = pingRoleValue;
+            reply += '\n' + translate(locale, 'command.configure.pingRoleValue', {value: pingRoleValue});
+        }
+        if (pingHereValue != null) {
+            settings.pingHereValue = pingHereValue;
+            reply += '\n' + translate(locale, 'command.configure.pingHereValue', {value: pingHereValue});
+        }
+        if (embedColor != null) {
+            if (embedColor.trim() === '' || embedColor.trim().toLowerCase() === 'default') {
+                settings.embedColor = undefined;
+                reply += '\n' + translate(locale, 'command.configure.embedColorDefault');
+            } else {
+                const color = parseHexColor(embedColor);
+                if (color == null) {
+                    interaction.reply({content: translate(locale, 'command.configure.invalidEmbedColor'), ephemeral: true});
+                    return;
+                }
+                settings.embedColor = color;
+                reply += '\n' + translate(locale, 'command.configure.embedColor', {color});
+            }
+        }
+        if (srpButton != null) {
+            settings.srpButton = srpButton;
+            reply += '\n' + translate(locale, 'command.configure.srpButton', {value: yesNo(srpButton)});
+        }
+        if (showFitting != null) {
+            settings.showFitting = showFitting;
+            reply += '\n' + translate(locale, 'command.configure.showFitting', {value: yesNo(showFitting)});
+        }
+        if (linkButtons != null) {
+            settings.linkButtons = linkButtons;
+            reply += '\n' + translate(locale, 'command.configure.linkButtons', {value: yesNo(linkButtons)});
+        }
+        if (thumbnail != null) {
+            settings.thumbnailSource = parseThumbnailSource(thumbnail);
+            reply += '\n' + translate(locale, 'command.configure.thumbnail', {source: settings.thumbnailSource});
+        }
+        if (maxKillAge != null) {
+            settings.maxKillAgeMinutes = maxKillAge;
+            reply += '\n' + (maxKillAge > 0 ? translate(locale, 'command.configure.maxKillAge', {minutes: maxKillAge}) :
+                translate(locale, 'command.configure.maxKillAgeNone'));
+        }
+        if (staleKills != null) {
+            settings.staleKills = staleKills as StaleKillAction;
+            reply += '\n' + translate(locale, settings.staleKills === StaleKillAction.UNPINGED ?
+                'command.configure.staleKillsUnpinged' : 'command.configure.staleKillsSkipped');
+        }
+        if (matchSummary != null) {
+            settings.matchSummary = matchSummary;
+            reply += '\n' + translate(locale, 'command.configure.matchSummary', {value: yesNo(matchSummary)});
+        }
+        if (description != null) {
+            settings.description = description.trim().toLowerCase() === 'none' ? undefined : description.trim().substring(0, 200);
+            reply += '\n' + (settings.description != null ?
+                translate(locale, 'command.configure.description', {description: settings.description}) :
+                translate(locale, 'command.configure.descriptionNone'));
+        }
+        if (!sub.canPing(interaction.guildId) && (pingRole != null || pingRoleValue || pingHereValue)) {
+            reply += '\n' + translate(locale, 'command.configure.pingsDisabled');
+        }
+        if (dryRun != null) {
+            settings.dryRun = parseDryRunMode(dryRun) ?? undefined;
+            reply += '\n' + translate(locale, settings.dryRun === DryRunMode.DM ? 'command.configure.dryRunDm' :
+                settings.dryRun === DryRunMode.LOG ? 'command.configure.dryRunLog' : 'command.configure.dryRunOff');
+        }
+
+        if (!sub.configureSubscription(subCommand, interaction.guildId, interaction.channelId, settings, String(id))) {
+            reply = translate(locale, 'command.noSubscription', {id});
+        }
+        interaction.reply({content: reply, ephemeral: true});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Change the display settings of a zkill subscription');
+
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('public')
+            .addNumberOption(option =>
+                option.setName(this.ID)
+                    .setDescription('ID of the public feed')
+                    .setRequired(true)
+            )
+            .addBooleanOption(option =>
+                option.setName(this.SHOW_VALUE_BREAKDOWN)
+                    .setDescription('Show the hull and fit value next to the total value, posts the full embed')
+                    .setRequired(false)
+            )
+            .addBooleanOption(option =>
+                option.setName(this.SUPPRESS_PODS_AFTER_LOSS)
+                    .setDescription('Do not post pod kills if the ship loss of the pilot was posted shortly before')
+                    .setRequired(false)
+            )
+            .addIntegerOption(option =>
+                option.setName(this.BATTLE_REPORT_THRESHOLD)
+                    .setDescription('Post a battle summary after this many kills in one system within 15 minutes, 0 to disable')
+                    .setRequired(false)
+                    .setMinValue(0)
+            )
+            .addBooleanOption(option =>
+                option.setName(this.BATTLE_REPORT_THREADS)
+                    .setDescription('Post the kills after a battle summary into a thread')
+                    .setRequired(false)
+            )
+            .addBooleanOption(option =>
+                option.setName(this.ATOM_FEED)
+                    .setDescription('Serve the matched kills as an Atom feed, enabling again generates a new URL')
+                    .setRequired(false)
+            )
+            .addRoleOption(option =>
+                option.setName(this.PING_ROLE)
+                    .setDescription('Role to mention for kills worth at least ping-role-value')
+                    .setRequired(false)
+            )
+            .addNumberOption(option =>
+                option.setName(this.PING_ROLE_VALUE)
+                    .setDescription('Mention the ping role for kills worth at least this many ISK, 0 to disable')
+                    .setRequired(false)
+                    .setMinValue(0)
+            )
+            .addNumberOption(option =>
+                option.setName(this.PING_HERE_VALUE)
+                    .setDescription('Mention @here for kills worth at least this many ISK, 0 to disable')
+                    .setRequired(false)
+                    .setMinValue(0)
+            )
+            .addStringOption(option =>
+                option.setName(this.EMBED_COLOR)
+                    .setDescription('Embed color as hex, e.g. #FF8800, instead of red for losses and green for kills, or default')
+                    .setRequired(false)
+            )
+            .addBooleanOption(option =>
+                option.setName(this.SRP_BUTTON)
+                    .setDescription('Add a button to request ship replacement to the losses of pilots')
+                    .setRequired(false)
+            )
+            .addBooleanOption(option =>
+                option.setName(this.SHOW_FITTING)
+                    .setDescription('Attach the fit of the victim in EFT format, posts the full embed')
+                    .setRequired(false)
+            )
+            .addBooleanOption(option =>
+                option.setName(this.LINK_BUTTONS)
+                    .setDescription('Add buttons linking to zKillboard, dotlan, the related kills and EveWho')
+                    .setRequired(false)
+            )
+            .addStringOption(option =>
+                option.setName(this.THUMBNAIL)
+                    .setDescription('Image shown in the kill embeds, posts the full embed')
+                    .setRequired(false)
+                    .addChoices(
+                        {name: 'Ship render', value: ThumbnailSource.RENDER},
+                        {name: 'Ship icon', value: ThumbnailSource.ICON},
+                        {name: 'Victim alliance or corporation logo', value: ThumbnailSource.LOGO},
+                        {name: 'Victim portrait', value: ThumbnailSource.PORTRAIT},
+                    )
+            )
+            .addIntegerOption(option =>
+                option.setName(this.MAX_KILL_AGE)
+                    .setDescription('Kills older than this many minutes are skipped or posted without pings, 0 for no limit')
+                    .setRequired(false)
+                    .setMinValue(0)
+            )
+            .addStringOption(option =>
+                option.setName(this.STALE_KILLS)
+                    .setDescription('What happens to kills older than max-kill-age')
+                    .setRequired(false)
+                    .addChoices(
+                        {name: 'Skip them', value: StaleKillAction.SKIP},
+                        {name: 'Post them without pings', value: StaleKillAction.UNPINGED},
+                    )
+            )
+            .addBooleanOption(option =>
+                option.setName(this.MATCH_SUMMARY)
+                    .setDescription('Name the matched entity and ship in a text line above the embed, shown in notifications')
+                    .setRequired(false)
+            )
+            .addStringOption(option =>
+                option.setName(this.DESCRIPTION)
+                    .setDescription('What the subscription is for, found by /zkill-find, none to remove it')
+                    .setRequired(false)
+            )
+            .addStringOption(option =>
+                option.setName(this.DRY_RUN)
+                    .setDescription('Log the matched kills instead of posting them, off to go live')
+                    .setRequired(false)
+                    .addChoices(
+                        {name: 'Only log them', value: DryRunMode.LOG},
+                        {name: 'Log them and send them to the creator by direct message', value: DryRunMode.DM},
+                        {name: 'Off, post them', value: DRY_RUN_OFF},
+                    )
+            )
+            .setDescription('Configure a public feed in this channel'));
+
+        return slashCommand;
+    }
+
+}
@@ -2,13 +2,74 @@ import {Client} from 'discord.js';
 import { REST } from '@discordjs/rest';
 import { Routes } from 'discord-api-types/v9';
 import {SubscribeCommand} from './subscribeCommand';
+import {QuickSubscribeCommand} from './quickSubscribeCommand';
 import {AbstractCommand} from './abstractCommand';
-import {UnsubscribeCommand} from './unsubscribeCommand';
+import {handleUnsubscribeInteraction, UnsubscribeCommand} from './unsubscribeCommand';
+import {ResumeCommand} from './resumeCommand';
 import {HelpCommand} from './helpCommand';
+import {ConfigureCommand} from './configureCommand';
+import {FilterCommand} from './filterCommand';
+import {MirrorCommand} from './mirrorCommand';
+import {CloneCommand} from './cloneCommand';
+import {WebhookCommand} from './webhookCommand';
+import {NotifyCommand} from './notifyCommand';
+import {BluelistCommand} from './bluelistCommand';
+import {StatsCommand} from './statsCommand';
+import {LookupCommand} from './lookupCommand';
+import {LastSeenCommand} from './lastSeenCommand';
+import {SearchCommand} from './searchCommand';
+import {CampaignCommand} from './campaignCommand';
+import {ReportCommand} from './reportCommand';
+import {LanguageCommand} from './languageCommand';
+import {PreviewCommand} from './previewCommand';
+import {WhyCommand} from './whyCommand';
+import {DiagCommand} from './diagCommand';
+import {AdminRoleCommand} from './adminRoleCommand';
+import {AlertChannelCommand} from './alertChannelCommand';
+import {SettingsCommand} from './settingsCommand';
+import {SubscribeDmCommand} from './subscribeDmCommand';
+import {UnsubscribeDmCommand} from './unsubscribeDmCommand';
+import {handleSrpButton, SrpQueueCommand} from './srpQueueCommand';
+import {handleOnboardingInteraction} from './onboardingWizard';
+import {FilterBuilderCommand, handleFilterBuilderInteraction} from './filterBuilderCommand';
+import {BulkUnsubscribeCommand} from './bulkUnsubscribeCommand';
+import {FindCommand} from './findCommand';
+import {TrackCommand} from './trackCommand';
+import {UntrackCommand} from './untrackCommand';
 
 const commands: AbstractCommand[] = [
     new SubscribeCommand(),
+    new QuickSubscribeCommand(),
+    new FilterBuilderCommand(),
     new UnsubscribeCommand(),
+    new BulkUnsubscribeCommand(),
+    new FindCommand(),
+    new ResumeCommand(),
+    new ConfigureCommand(),
+    new FilterCommand(),
+    new MirrorCommand(),
+    new CloneCommand(),
+    new WebhookCommand(),
+    new NotifyCommand(),
+    new BluelistCommand(),
+    new StatsCommand(),
+    new LookupCommand(),
+    new LastSeenCommand(),
+    new SearchCommand(),
+    new CampaignCommand(),
+    new TrackCommand(),
+    new UntrackCommand(),
+    new ReportCommand(),
+    new LanguageCommand(),
+    new PreviewCommand(),
+    new WhyCommand(),
+    new DiagCommand(),
+    new AdminRoleCommand(),
+    new AlertChannelCommand(),
+    new SettingsCommand(),
+    new SubscribeDmCommand(),
+    new UnsubscribeDmCommand(),
+    new SrpQueueCommand(),
     new HelpCommand()
 ];
 
@@ -49,6 +110,17 @@ export function registerCommands (client: Client) {
     // When the client is ready, run this code (only once)
     client.once('ready', () => {
         client.on('interactionCreate', interaction => {
+            if (interaction.isButton() || interaction.isSelectMenu() || interaction.isModalSubmit()) {
+                // The filter builder uses all three, the unsubscribe menu a select menu and buttons, the rest belongs
+                // to the SRP buttons or the setup wizard
+                handleFilterBuilderInteraction(interaction).then(async handled => {
+                    if (handled || await handleUnsubscribeInteraction(interaction)) {
+                        return;
+                    }
+                    return interaction.isButton() ? handleSrpButton(interaction) : handleOnboardingInteraction(interaction);
+                }).catch(console.error);
+                return;
+            }
             if (!interaction.isCommand()) return;
             for(const command of commands) {
                 if(command.getName() === interaction.commandName) {
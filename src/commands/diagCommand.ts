@@ -0,0 +1,108 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {DeliveryDiagnostics, Subscription, ZKillSubscriber} from '../zKillSubscriber';
+import {SubscriptionCounters, topRejections} from '../lib/subscriptionStats';
+import {LatencySummary} from '../lib/deliveryLatency';
+import {filterListNames, listAgeText} from '../lib/entityLists';
+import {hitRate} from '../lib/boundedCache';
+
+function formatDuration(ms: number): string {
+    const seconds = Math.round(ms / 1000);
+    if (seconds < 120) {
+        return seconds + 's';
+    }
+    const minutes = Math.round(seconds / 60);
+    return minutes < 120 ? minutes + 'm' : Math.round(minutes / 60) + 'h';
+}
+
+function formatLatency(summary: LatencySummary | null): string {
+    if (!summary) {
+        return 'no kills yet';
+    }
+    return `p50 ${formatDuration(summary.p50)}, p90 ${formatDuration(summary.p90)}, p99 ${formatDuration(summary.p99)}, ` +
+        `max ${formatDuration(summary.max)} over ${summary.count}`;
+}
+
+// Latency from the kill to zKillboard delivering it tells a slow zKillboard, latency to the post a slow bot
+export function formatDeliveryDiagnostics(diagnostics: DeliveryDiagnostics): string {
+    const caches = Object.keys(diagnostics.cacheSizes).map(cache => `${cache} ${diagnostics.cacheSizes[cache]}`);
+    const hitRates = Object.keys(diagnostics.cacheStats).map(cache => {
+        const stats = diagnostics.cacheStats[cache];
+        const rate = hitRate(stats);
+        return `${cache} ${rate != null ? Math.round(rate * 100) + '%' : '-'}, ${stats.evictions} evicted`;
+    });
+    return [
+        'Kill to received: ' + formatLatency(diagnostics.received),
+        'Kill to posted: ' + formatLatency(diagnostics.posted),
+        `Queued kills: ${diagnostics.queuedKills}` + (diagnostics.queueId ? `, RedisQ queue ID: ${diagnostics.queueId}` : ''),
+        'Cached: ' + caches.join(', '),
+        'Cache hit rates: ' + hitRates.join('; '),
+    ].join('\n');
+}
+
+export function formatSubscriptionDiagnostics(
+    ident: string,
+    subscription: Subscription,
+    counters?: SubscriptionCounters,
+    listsUpdatedAt: { [name: string]: string } = {},
+    now = new Date(),
+): string {
+    let text = `**${ident}**`;
+    if (subscription.disabledAt) {
+        text += ` disabled since ${subscription.disabledAt.substring(0, 10)}: ${subscription.disabledReason}`;
+    }
+    const listNames = filterListNames(subscription.limitTypes);
+    if (listNames.length > 0) {
+        text += '\nEntity lists: ' + listNames.map(name => listAgeText(name, listsUpdatedAt[name], now)).join('; ');
+    }
+    if (!counters) {
+        return text + '\nNo kill evaluated yet';
+    }
+    text += `\nSince ${counters.since.substring(0, 10)}: ${counters.evaluated} evaluated, ${counters.matched} matched, ` +
+        `${counters.posted} posted, ${counters.pinged} pinged`;
+    text += `\nLast match: ${counters.lastMatchAt ? counters.lastMatchAt.substring(0, 16).replace('T', ' ') : 'never'}`;
+    const rejections = topRejections(counters, 3);
+    if (rejections.length > 0) {
+        text += '\nMost rejections: ' + rejections.map(([filter, count]) => `${filter} ${count}`).join(', ');
+    }
+    return text;
+}
+
+export class DiagCommand extends AbstractCommand {
+    protected name = 'zkill-diag';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            await interaction.reply('Diagnostics are not possible in PM!');
+            return;
+        }
+        const blocks: string[] = [formatDeliveryDiagnostics(sub.getDeliveryDiagnostics())];
+        const channel = await sub.listGuildChannelSubscriptions(interaction.guildId, interaction.channelId);
+        if (!channel || channel.subscriptions.size === 0) {
+            blocks.push('There are no subscriptions in this channel');
+        }
+        channel?.subscriptions.forEach((subscription, ident) => {
+            blocks.push(formatSubscriptionDiagnostics(
+                ident,
+                subscription,
+                sub.getSubscriptionCounters(interaction.guildId, interaction.channelId, ident),
+                sub.getEntityListsUpdatedAt(interaction.guildId),
+            ));
+        });
+        let reply = blocks.join('\n\n');
+        if (reply.length > 1900) {
+            reply = reply.substring(0, 1900) + '\n...';
+        }
+        await interaction.reply({content: reply, ephemeral: true});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        return new SlashCommandBuilder().setName(this.name)
+            .setDescription('Show how fast kills are delivered and how many the subscriptions of this channel posted');
+    }
+
+}
@@ -0,0 +1,215 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {
+    CommandInteraction,
+    Interaction,
+    MessageActionRow,
+    MessageButton,
+    MessageSelectMenu,
+    Modal,
+    TextInputComponent,
+} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {LimitType, resolveFactionIds, SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {expandShipAliases} from '../lib/shipGroups';
+import {validateLimitTypes} from '../lib/subscriptionValidation';
+import {
+    addCondition,
+    addOrGroup,
+    BuilderAction,
+    builderCustomId,
+    BUILDER_CONDITIONS,
+    draftName,
+    draftSubscriptions,
+    FilterBuilderSessions,
+    FilterDraft,
+    MIN_VALUE_CONDITION,
+    parseBuilderCustomId,
+    removeLastCondition,
+} from '../lib/filterBuilder';
+
+const CONDITION_VALUE = 'value';
+
+const sessions = new FilterBuilderSessions();
+
+// The builder message with the filter so far, a select menu to add a condition and the group and save buttons
+function builderMessage(sessionId: string, draft: FilterDraft, notice?: string): { content: string, components: MessageActionRow[] } {
+    let content = `**Filter:** ${draftName(draft)}\n` +
+        'Add conditions one at a time, all conditions of a group have to match. **Or group** starts an alternative ' +
+        'group, each group is saved as a subscription of this channel and a kill matching several is posted once.';
+    if (notice) {
+        content += `\n${notice}`;
+    }
+    return {
+        content,
+        components: [
+            new MessageActionRow().addComponents(new MessageSelectMenu()
+                .setCustomId(builderCustomId(BuilderAction.ADD, sessionId))
+                .setPlaceholder('Add a condition')
+                .addOptions(BUILDER_CONDITIONS.map(condition => ({label: condition.label, value: condition.key})))),
+            new MessageActionRow().addComponents(
+                new MessageButton().setCustomId(builderCustomId(BuilderAction.OR, sessionId)).setLabel('Or group').setStyle('SECONDARY'),
+                new MessageButton().setCustomId(builderCustomId(BuilderAction.UNDO, sessionId)).setLabel('Remove last').setStyle('SECONDARY'),
+                new MessageButton().setCustomId(builderCustomId(BuilderAction.SAVE, sessionId)).setLabel('Save').setStyle('PRIMARY'),
+                new MessageButton().setCustomId(builderCustomId(BuilderAction.CANCEL, sessionId)).setLabel('Cancel').setStyle('DANGER'),
+            ),
+        ],
+    };
+}
+
+// Ship aliases and militia names are stored as IDs, like the subscribe and filter commands do. Null if invalid
+function normalizeConditionValue(key: string, value: string): string | null {
+    if (key === LimitType.SHIP_INCLUSION_TYPE_ID || key === LimitType.SHIP_EXCLUSION_TYPE_ID) {
+        return expandShipAliases(value);
+    }
+    if (key === LimitType.FACTION) {
+        return resolveFactionIds(value);
+    }
+    if (key === MIN_VALUE_CONDITION) {
+        return /^\d+$/.test(value) ? value : null;
+    }
+    return value;
+}
+
+// Saves one subscription per group, the first takes the given ID
+function saveDraft(draft: FilterDraft): string {
+    const sub = ZKillSubscriber.getInstance();
+    const subscriptions = draftSubscriptions(draft);
+    const ids: string[] = [];
+    for (const {limitTypes, minValue} of subscriptions) {
+        if (!sub.canSubscribe(draft.guildId)) {
+            break;
+        }
+        const id = ids.length === 0 && draft.id != null ? draft.id : sub.freeSubscriptionId(SubscriptionType.PUBLIC, draft.guildId, draft.channelId);
+        sub.subscribe(
+            SubscriptionType.PUBLIC,
+            draft.guildId,
+            draft.channelId,
+            limitTypes,
+            {
+                inclusionLimitAlsoComparesAttacker: true,
+                inclusionLimitAlsoComparesAttackerWeapons: true,
+                exclusionLimitAlsoComparesAttacker: true,
+                exclusionLimitAlsoComparesAttackerWeapons: true,
+            },
+            id,
+            minValue,
+            draft.userId,
+        );
+        ids.push(id);
+    }
+    let reply = `Subscribed <#${draft.channelId}> to ${draftName(draft)} as ID: ${ids.join(', ')}`;
+    if (ids.length < subscriptions.length) {
+        reply += '\nThis server reached the maximum number of subscriptions on this instance, the other groups were not saved';
+    }
+    return reply + '\nAdjust them with /zkill-filter and /zkill-configure';
+}
+
+// Handles the select menu, buttons and modal of the builder, false if the interaction does not belong to it
+export async function handleFilterBuilderInteraction(interaction: Interaction): Promise<boolean> {
+    if (!interaction.isSelectMenu() && !interaction.isButton() && !interaction.isModalSubmit()) {
+        return false;
+    }
+    const state = parseBuilderCustomId(interaction.customId);
+    if (state == null) {
+        return false;
+    }
+    const draft = sessions.get(state.sessionId);
+    if (!draft || draft.userId !== interaction.user.id) {
+        await interaction.reply({content: 'This filter builder expired, start a new one with /zkill-builder', ephemeral: true});
+        return true;
+    }
+
+    if (interaction.isSelectMenu()) {
+        const kind = BUILDER_CONDITIONS.filter(condition => condition.key === interaction.values[0])[0];
+        await interaction.showModal(new Modal()
+            .setCustomId(builderCustomId(BuilderAction.VALUE, state.sessionId, kind.key))
+            .setTitle('Add a condition')
+            .addComponents(new MessageActionRow<TextInputComponent>().addComponents(new TextInputComponent()
+                .setCustomId(CONDITION_VALUE)
+                .setLabel(kind.label)
+                .setPlaceholder(kind.hint)
+                .setStyle('SHORT')
+                .setRequired(true))));
+        return true;
+    }
+
+    if (interaction.isModalSubmit()) {
+        const key = <string>state.key;
+        const value = normalizeConditionValue(key, interaction.fields.getTextInputValue(CONDITION_VALUE).trim());
+        if (value == null) {
+            await interaction.update(builderMessage(state.sessionId, draft, `⚠ ${key} has an invalid value`));
+            return true;
+        }
+        const group = draft.groups[draft.groups.length - 1];
+        const limitTypes = draftSubscriptions({...draft, groups: [[...group.filter(condition => condition.key !== key), {key, value}]]})[0].limitTypes;
+        const problems = validateLimitTypes(limitTypes, name => ZKillSubscriber.getInstance().isKnownSystemName(name));
+        if (problems.length > 0) {
+            await interaction.update(builderMessage(state.sessionId, draft, '⚠ The group would never post a kill: ' + problems.join(', ')));
+            return true;
+        }
+        addCondition(draft, key, value);
+        await interaction.update(builderMessage(state.sessionId, draft));
+        return true;
+    }
+
+    if (state.action === BuilderAction.OR) {
+        addOrGroup(draft);
+    } else if (state.action === BuilderAction.UNDO) {
+        removeLastCondition(draft);
+    } else if (state.action === BuilderAction.CANCEL) {
+        sessions.delete(state.sessionId);
+        await interaction.update({content: 'Cancelled the filter builder', components: []});
+        return true;
+    } else if (state.action === BuilderAction.SAVE) {
+        if (draftSubscriptions(draft).length === 0) {
+            await interaction.update(builderMessage(state.sessionId, draft, '⚠ Add a condition first'));
+            return true;
+        }
+        sessions.delete(state.sessionId);
+        await interaction.update({content: saveDraft(draft), components: []});
+        return true;
+    }
+    await interaction.update(builderMessage(state.sessionId, draft));
+    return true;
+}
+
+export class FilterBuilderCommand extends AbstractCommand {
+    protected name = 'zkill-builder';
+
+    protected ID = 'id';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Subscription is not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        if (!sub.canSubscribe(interaction.guildId)) {
+            interaction.reply({content: 'This server reached the maximum number of subscriptions on this instance', ephemeral: true});
+            return;
+        }
+        const id = interaction.options.getNumber(this.ID);
+        const sessionId = sessions.create(interaction.guildId, interaction.channelId, interaction.user.id, id != null ? String(id) : undefined);
+        const draft = <FilterDraft>sessions.get(sessionId);
+        interaction.reply({...builderMessage(sessionId, draft), ephemeral: true});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Build a subscription condition by condition, with Or groups');
+        slashCommand.addNumberOption(option =>
+            option.setName(this.ID)
+                .setDescription('ID for public feed, the next free IDs if empty')
+                .setRequired(false)
+        );
+        return slashCommand;
+    }
+}
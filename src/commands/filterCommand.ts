@@ -0,0 +1,184 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {LimitType, resolveFactionIds, SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {normalizeListNames} from '../lib/entityLists';
+import {parseNpcSpawnFilter} from '../lib/npcSpawns';
+import {parseGangSizeClasses} from '../lib/gangSize';
+import {parsePerspective} from '../lib/perspective';
+import {parseExchangeOutcome} from '../lib/exchangeOutcome';
+import {parseNameScopes} from '../lib/nameFragment';
+import {parseSecStatusRange} from '../lib/securityStatus';
+import {parseTechLevels} from '../lib/techLevel';
+import {validateLimitTypes} from '../lib/subscriptionValidation';
+
+// Filters that do not fit into the subscribe command anymore, Discord allows at most 25 options per command
+const EXTENDED_LIMIT_TYPES: { name: string, value: LimitType }[] = [
+    {name: 'Minimum attackers from the character/corporation/alliance filter', value: LimitType.MIN_ENTITY_ATTACKERS},
+    {name: 'Jumps from a region border, as regionId:maxJumps', value: LimitType.JUMPS_FROM_REGION_BORDER},
+    {name: 'Jumps from a system, as systemId:maxJumps', value: LimitType.JUMPS_FROM_SYSTEM},
+    {name: 'Jump drive range from a system, as systemName:maxLy or systemName:shipClass', value: LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME},
+    {name: 'Victim category: capsule, ship, structure, starbase, deployable, fighter', value: LimitType.VICTIM_CATEGORY},
+    {name: 'Near celestial, as kinds:maxDistanceKm, e.g. stargate,station:100', value: LimitType.NEAR_CELESTIAL},
+    {name: 'Victim or attacker faction IDs, or caldari, minmatar, amarr, gallente, militia', value: LimitType.FACTION},
+    {name: 'Character, corporation or alliance IDs of the victim, for losses only', value: LimitType.VICTIM_ENTITY},
+    {name: 'Market price of the victim hull in ISK, as min:max, either may be empty', value: LimitType.HULL_VALUE},
+    {name: 'Member count of the victim corporation, as min:max, either may be empty', value: LimitType.VICTIM_CORP_SIZE},
+    {name: 'NPC kills by officers, commanders, capitals or NPC group IDs', value: LimitType.NPC_SPAWN},
+    {name: 'Names the name fragment is looked for in: ship, pilot, corporation, alliance', value: LimitType.NAME_FRAGMENT_SCOPE},
+    {name: 'Gang size of the attackers: solo, small, mid, fleet, blob', value: LimitType.GANG_SIZE},
+    {name: 'Tech level of the victim ship: t1, t2, t3, faction, deadspace, officer', value: LimitType.VICTIM_TECH_LEVEL},
+    {name: 'Security status of an attacking pilot, as min:max, e.g. :-5 for outlaws', value: LimitType.ATTACKER_SEC_STATUS},
+    {name: 'Kills, losses or both of the own entities, see /zkill-settings', value: LimitType.PERSPECTIVE},
+    {name: 'Post after the window if the own entities won or lost the exchange, as won|lost:minutes', value: LimitType.EXCHANGE_OUTCOME},
+    {name: 'Entity lists the victim or an attacker has to be on, see /zkill-bluelist', value: LimitType.ENTITY_LIST_INCLUDE},
+    {name: 'Entity lists neither the victim nor an attacker may be on, see /zkill-bluelist', value: LimitType.ENTITY_LIST_EXCLUDE},
+    {name: 'Alliance IDs whose attackers are ignored and whose losses are not posted', value: LimitType.EXCLUDED_ALLIANCE},
+    {name: 'Corporation IDs whose attackers are ignored and whose losses are not posted', value: LimitType.EXCLUDED_CORPORATION},
+    {name: 'Ship group IDs whose attackers are ignored and whose losses are not posted', value: LimitType.EXCLUDED_SHIP_GROUP},
+];
+
+export class FilterCommand extends AbstractCommand {
+    protected name = 'zkill-filter';
+
+    protected ID = 'id';
+    protected LIMIT = 'limit';
+    protected VALUE = 'value';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Filtering is not possible in PM!');
+            return;
+        }
+        const locale = sub.getGuildLocale(interaction.guildId);
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(locale, 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const subCommand = interaction.options.getSubcommand(true) as SubscriptionType;
+        const id = interaction.options.getNumber(this.ID, true);
+        const limitType = interaction.options.getString(this.LIMIT, true) as LimitType;
+        let value = interaction.options.getString(this.VALUE);
+        if (limitType === LimitType.FACTION && value != null) {
+            value = resolveFactionIds(value);
+            if (value == null) {
+                interaction.reply({content: translate(locale, 'command.filter.invalidFaction'), ephemeral: true});
+                return;
+            }
+        }
+        if (limitType === LimitType.NPC_SPAWN && value != null && parseNpcSpawnFilter(value) == null) {
+            interaction.reply({content: translate(locale, 'command.filter.invalidNpcSpawn'), ephemeral: true});
+            return;
+        }
+        if (limitType === LimitType.NAME_FRAGMENT_SCOPE && value != null) {
+            const scopes = parseNameScopes(value);
+            if (scopes == null) {
+                interaction.reply({content: translate(locale, 'command.filter.invalidNameScope'), ephemeral: true});
+                return;
+            }
+            value = scopes.join(',');
+        }
+        if (limitType === LimitType.ATTACKER_SEC_STATUS && value != null && parseSecStatusRange(value) == null) {
+            interaction.reply({content: translate(locale, 'command.filter.invalidSecStatus'), ephemeral: true});
+            return;
+        }
+        if (limitType === LimitType.VICTIM_TECH_LEVEL && value != null && parseTechLevels(value) == null) {
+            interaction.reply({content: translate(locale, 'command.filter.invalidTechLevel'), ephemeral: true});
+            return;
+        }
+        if (limitType === LimitType.GANG_SIZE && value != null && parseGangSizeClasses(value) == null) {
+            interaction.reply({content: translate(locale, 'command.filter.invalidGangSize'), ephemeral: true});
+            return;
+        }
+        if (limitType === LimitType.PERSPECTIVE && value != null) {
+            const perspective = parsePerspective(value);
+            if (perspective == null) {
+                interaction.reply({content: translate(locale, 'command.filter.invalidPerspective'), ephemeral: true});
+                return;
+            }
+            if ((sub.getGuildSettings(interaction.guildId).ownEntityIds ?? []).length === 0) {
+                interaction.reply({content: translate(locale, 'command.filter.ownEntitiesMissing'), ephemeral: true});
+                return;
+            }
+            value = perspective;
+        }
+        if (limitType === LimitType.EXCHANGE_OUTCOME && value != null) {
+            const filter = parseExchangeOutcome(value);
+            if (filter == null) {
+                interaction.reply({content: translate(locale, 'command.filter.invalidExchangeOutcome'), ephemeral: true});
+                return;
+            }
+            if ((sub.getGuildSettings(interaction.guildId).ownEntityIds ?? []).length === 0) {
+                interaction.reply({content: translate(locale, 'command.filter.ownEntitiesMissing'), ephemeral: true});
+                return;
+            }
+            value = `${filter.require}:${filter.windowMinutes}`;
+        }
+        if ((limitType === LimitType.ENTITY_LIST_INCLUDE || limitType === LimitType.ENTITY_LIST_EXCLUDE) && value != null) {
+            const names = normalizeListNames(value);
+            const entityLists = sub.getEntityLists(interaction.guildId);
+            const unknown = (names ?? []).filter(name => entityLists[name] == null);
+            if (names == null || unknown.length > 0) {
+                interaction.reply({
+                    content: translate(locale, 'command.filter.unknownEntityList', {lists: unknown.join(', ') || value}),
+                    ephemeral: true,
+                });
+                return;
+            }
+            value = names.join(',');
+        }
+        const subscription = (await sub.listGuildChannelSubscriptions(interaction.guildId, interaction.channelId))
+            ?.subscriptions.get(`${subCommand}${id}`);
+        if (subscription && value != null) {
+            const limitTypes = new Map(subscription.limitTypes);
+            limitTypes.set(limitType, value);
+            const problems = validateLimitTypes(limitTypes, name => sub.isKnownSystemName(name));
+            if (problems.length > 0) {
+                await interaction.reply({content: translate(locale, 'command.filter.neverPosts') + '\n- ' + problems.join('\n- '), ephemeral: true});
+                return;
+            }
+        }
+
+        let reply: string;
+        if (!sub.setSubscriptionLimit(subCommand, interaction.guildId, interaction.channelId, limitType, value, String(id))) {
+            reply = translate(locale, 'command.noSubscription', {id});
+        } else if (value == null) {
+            reply = translate(locale, 'command.filter.removed', {limit: limitType, id});
+        } else {
+            reply = translate(locale, 'command.filter.set', {limit: limitType, value, id});
+        }
+        interaction.reply({content: reply, ephemeral: true});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Set additional filters on a zkill subscription');
+
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('public')
+            .addNumberOption(option =>
+                option.setName(this.ID)
+                    .setDescription('ID of the public feed')
+                    .setRequired(true)
+            )
+            .addStringOption(option =>
+                option.setName(this.LIMIT)
+                    .setDescription('Filter to set')
+                    .setRequired(true)
+                    .addChoices(...EXTENDED_LIMIT_TYPES)
+            )
+            .addStringOption(option =>
+                option.setName(this.VALUE)
+                    .setDescription('Value of the filter, leave empty to remove the filter')
+                    .setRequired(false)
+            )
+            .setDescription('Set a filter on a public feed in this channel'));
+
+        return slashCommand;
+    }
+
+}
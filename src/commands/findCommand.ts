@@ -0,0 +1,69 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {SubscriptionSearchResult} from '../lib/subscriptionSearch';
+
+// Discord messages have up to 2000 characters
+const MAX_REPLY_LENGTH = 1900;
+
+export function formatSearchResults(results: SubscriptionSearchResult[]): string {
+    const lines: string[] = [];
+    let length = 0;
+    for (const result of results) {
+        const description = result.subscription.description;
+        let line = `<#${result.channelId}> **${result.ident}**: ${result.matchedOn === 'description' ? description : result.matchedOn}`;
+        if (description && result.matchedOn !== 'description') {
+            line += ` (${description})`;
+        }
+        if (result.subscription.disabledAt) {
+            line += ', disabled';
+        }
+        if (length + line.length > MAX_REPLY_LENGTH) {
+            lines.push(`... and ${results.length - lines.length} more, search for a longer text`);
+            break;
+        }
+        lines.push(line);
+        length += line.length + 1;
+    }
+    return lines.join('\n');
+}
+
+export class FindCommand extends AbstractCommand {
+    protected name = 'zkill-find';
+
+    protected TEXT = 'text';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Searching subscriptions is not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const text = interaction.options.getString(this.TEXT, true);
+        const results = sub.findSubscriptions(interaction.guildId, text);
+        interaction.reply({
+            content: results.length > 0 ? formatSearchResults(results) : `No subscription of this server contains ${text}`,
+            ephemeral: true,
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Find the subscriptions of this server by ID, description or filter');
+        slashCommand.addStringOption(option =>
+            option.setName(this.TEXT)
+                .setDescription('Text to look for, e.g. an alliance ID, a filter name like perspective or a description')
+                .setRequired(true)
+        );
+        return slashCommand;
+    }
+}
@@ -0,0 +1,40 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {Locale, LOCALE_NAMES, parseLocale, translate} from '../lib/i18n';
+
+export class LanguageCommand extends AbstractCommand {
+    protected name = 'zkill-language';
+
+    protected LANGUAGE = 'language';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Changing the language is not possible in PM!');
+            return;
+        }
+        const locale = parseLocale(interaction.options.getString(this.LANGUAGE, true));
+        sub.setGuildLocale(interaction.guildId, locale);
+        interaction.reply({
+            content: translate(locale, 'command.languageSet', {language: LOCALE_NAMES[locale]}),
+            ephemeral: true
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Set the language of the kill messages in this server');
+        slashCommand.addStringOption(option =>
+            option.setName(this.LANGUAGE)
+                .setDescription('Language')
+                .setRequired(true)
+                .addChoices(...Object.values(Locale).map(locale => ({name: LOCALE_NAMES[locale], value: locale})))
+        );
+        return slashCommand;
+    }
+
+}
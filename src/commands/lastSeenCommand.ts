@@ -0,0 +1,62 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {IntelEntityType} from '../lib/zkbApi';
+
+export class LastSeenCommand extends AbstractCommand {
+    protected name = 'zkill-lastseen';
+
+    protected TYPE = 'type';
+    protected NAME = 'name';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            await interaction.reply('Sightings are not available in PM!');
+            return;
+        }
+        const type = interaction.options.getString(this.TYPE, true) as IntelEntityType;
+        const name = interaction.options.getString(this.NAME, true).trim();
+        await interaction.deferReply({ephemeral: true});
+        try {
+            const entity = await sub.resolveEntity(type, name);
+            if (entity == null) {
+                await interaction.editReply(`There is no ${type} named ${name}`);
+                return;
+            }
+            const sighting = sub.getLastSeen(interaction.guildId, entity.id);
+            if (!sighting) {
+                await interaction.editReply(`${entity.name} was not on a kill posted by the subscriptions of this server`);
+                return;
+            }
+            await interaction.editReply({embeds: [await sub.lastSeenEmbed(entity.name, sighting)]});
+        } catch (e) {
+            await interaction.editReply(`Failed to look up ${name}: ${e}`);
+        }
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Show where and when a watched character, corporation or alliance was last on a kill');
+        slashCommand.addStringOption(option =>
+            option.setName(this.TYPE)
+                .setDescription('Kind of entity')
+                .setRequired(true)
+                .addChoices(
+                    {name: 'Character', value: IntelEntityType.CHARACTER},
+                    {name: 'Corporation', value: IntelEntityType.CORPORATION},
+                    {name: 'Alliance', value: IntelEntityType.ALLIANCE},
+                )
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.NAME)
+                .setDescription('Exact name')
+                .setRequired(true)
+        );
+        return slashCommand;
+    }
+
+}
@@ -0,0 +1,86 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction, MessageEmbedOptions} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {EntityIntel, IntelEntityType, zkbEntityUrl} from '../lib/zkbApi';
+
+const IMAGE_PATHS: { [type in IntelEntityType]: string } = {
+    [IntelEntityType.CHARACTER]: 'characters/%1/portrait?size=128',
+    [IntelEntityType.CORPORATION]: 'corporations/%1/logo?size=128',
+    [IntelEntityType.ALLIANCE]: 'alliances/%1/logo?size=128',
+};
+
+export function intelEmbed(
+    type: IntelEntityType,
+    id: number,
+    name: string,
+    intel: EntityIntel,
+    abbreviate: (n: number) => unknown,
+): MessageEmbedOptions {
+    const ratio = (value: number | null) => value != null ? `${value}%` : '-';
+    const favoriteShips = intel.favoriteShips.map(ship => `${ship.shipName}: ${ship.kills}`).join('\n');
+    return {
+        title: name,
+        url: zkbEntityUrl(type, id),
+        thumbnail: {url: 'https://images.evetech.net/' + IMAGE_PATHS[type].replace('%1', id.toString())},
+        fields: [
+            {name: '__Kills__', value: `${intel.kills} (${intel.soloKills} solo)\n${abbreviate(intel.iskDestroyed)} ISK`, inline: true},
+            {name: '__Losses__', value: `${intel.losses}\n${abbreviate(intel.iskLost)} ISK`, inline: true},
+            {name: '__Danger / Gang__', value: `${ratio(intel.dangerRatio)} / ${ratio(intel.gangRatio)}`, inline: true},
+            {name: '__Favorite Ships__', value: favoriteShips || '-', inline: true},
+            {
+                name: '__Prime Time__',
+                value: intel.primeTimeHour != null ? `${('0' + intel.primeTimeHour).slice(-2)}:00 EVE time` : '-',
+                inline: true,
+            },
+        ],
+        footer: {text: 'zKillboard statistics'},
+    };
+}
+
+export class LookupCommand extends AbstractCommand {
+    protected name = 'zkill-lookup';
+
+    protected TYPE = 'type';
+    protected NAME = 'name';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        const type = interaction.options.getString(this.TYPE, true) as IntelEntityType;
+        const name = interaction.options.getString(this.NAME, true).trim();
+        await interaction.deferReply({ephemeral: true});
+        try {
+            const entity = await sub.resolveEntity(type, name);
+            if (entity == null) {
+                await interaction.editReply(`There is no ${type} named ${name}`);
+                return;
+            }
+            const intel = await sub.getEntityIntel(type, entity.id);
+            await interaction.editReply({embeds: [intelEmbed(type, entity.id, entity.name, intel, n => sub.abbreviateNumber(n))]});
+        } catch (e) {
+            await interaction.editReply(`Failed to look up ${name}: ${e}`);
+        }
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Show the zKillboard statistics of a character, corporation or alliance');
+        slashCommand.addStringOption(option =>
+            option.setName(this.TYPE)
+                .setDescription('Kind of entity')
+                .setRequired(true)
+                .addChoices(
+                    {name: 'Character', value: IntelEntityType.CHARACTER},
+                    {name: 'Corporation', value: IntelEntityType.CORPORATION},
+                    {name: 'Alliance', value: IntelEntityType.ALLIANCE},
+                )
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.NAME)
+                .setDescription('Exact name')
+                .setRequired(true)
+        );
+        return slashCommand;
+    }
+
+}
@@ -0,0 +1,117 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {MirrorChannel, SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+
+export class MirrorCommand extends AbstractCommand {
+    protected name = 'zkill-mirror';
+
+    protected ID = 'id';
+    protected CHANNEL = 'channel';
+    protected PING_ROLE = 'ping-role';
+    protected PING_ROLE_VALUE = 'ping-role-value';
+    protected PING_HERE_VALUE = 'ping-here-value';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Mirroring is not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const id = String(interaction.options.getNumber(this.ID, true));
+        const channel = interaction.options.getChannel(this.CHANNEL, true);
+        if (channel.id === interaction.channelId) {
+            interaction.reply({content: 'The subscription already posts to this channel', ephemeral: true});
+            return;
+        }
+
+        if (interaction.options.getSubcommand(true) === 'remove') {
+            const removed = sub.removeMirrorChannel(SubscriptionType.PUBLIC, interaction.guildId, interaction.channelId, channel.id, id);
+            interaction.reply({
+                content: removed ?
+                    `Subscription ID: ${id} no longer posts to <#${channel.id}>` :
+                    `Subscription ID: ${id} in this channel does not post to <#${channel.id}>`,
+                ephemeral: true,
+            });
+            return;
+        }
+
+        const mirror: MirrorChannel = {channelId: channel.id};
+        let reply = `Subscription ID: ${id} now also posts to <#${channel.id}>`;
+        const pingRole = interaction.options.getRole(this.PING_ROLE);
+        const pingRoleValue = interaction.options.getNumber(this.PING_ROLE_VALUE);
+        const pingHereValue = interaction.options.getNumber(this.PING_HERE_VALUE);
+        if (pingRole != null) {
+            mirror.pingRoleId = pingRole.id;
+            reply += '\nPing role: ' + pingRole.name;
+        }
+        if (pingRoleValue != null) {
+            mirror.pingRoleValue = pingRoleValue;
+            reply += '\nPing role from value: ' + pingRoleValue;
+        }
+        if (pingHereValue != null) {
+            mirror.pingHereValue = pingHereValue;
+            reply += '\nPing @here from value: ' + pingHereValue;
+        }
+        if (!sub.setMirrorChannel(SubscriptionType.PUBLIC, interaction.guildId, interaction.channelId, mirror, id)) {
+            reply = 'No subscription with ID ' + id + ' found in this channel';
+        }
+        interaction.reply({content: reply, ephemeral: true});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Post the kills of a subscription in this channel to another channel too');
+
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('add')
+            .addNumberOption(option =>
+                option.setName(this.ID)
+                    .setDescription('ID of the public feed in this channel')
+                    .setRequired(true)
+            )
+            .addChannelOption(option =>
+                option.setName(this.CHANNEL)
+                    .setDescription('Channel to post the kills to as well')
+                    .setRequired(true)
+            )
+            .addRoleOption(option =>
+                option.setName(this.PING_ROLE)
+                    .setDescription('Role to mention in that channel instead of the subscription\'s')
+                    .setRequired(false)
+            )
+            .addNumberOption(option =>
+                option.setName(this.PING_ROLE_VALUE)
+                    .setDescription('Mention the role in that channel for kills worth at least this many ISK, 0 to disable')
+                    .setRequired(false)
+            )
+            .addNumberOption(option =>
+                option.setName(this.PING_HERE_VALUE)
+                    .setDescription('Mention @here in that channel for kills worth at least this many ISK, 0 to disable')
+                    .setRequired(false)
+            )
+            .setDescription('Post the kills of a subscription to another channel too'));
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('remove')
+            .addNumberOption(option =>
+                option.setName(this.ID)
+                    .setDescription('ID of the public feed in this channel')
+                    .setRequired(true)
+            )
+            .addChannelOption(option =>
+                option.setName(this.CHANNEL)
+                    .setDescription('Channel to stop posting to')
+                    .setRequired(true)
+            )
+            .setDescription('Stop posting the kills of a subscription to another channel'));
+
+        return slashCommand;
+    }
+
+}
@@ -0,0 +1,95 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {NotifierKind, NotifierTarget} from '../lib/notifiers';
+
+const NOTIFIER_NAMES: { [kind in NotifierKind]: string } = {
+    [NotifierKind.MATRIX]: 'Matrix room',
+    [NotifierKind.TELEGRAM]: 'Telegram chat',
+};
+
+export class NotifyCommand extends AbstractCommand {
+    protected name = 'zkill-notify';
+
+    protected ID = 'id';
+    protected BACKEND = 'backend';
+    protected TARGET = 'target';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Notifications are not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const id = String(interaction.options.getNumber(this.ID, true));
+        const target: NotifierTarget = {
+            kind: interaction.options.getString(this.BACKEND, true) as NotifierKind,
+            target: interaction.options.getString(this.TARGET, true).trim(),
+        };
+        const targetName = `${NOTIFIER_NAMES[target.kind]} ${target.target}`;
+
+        if (interaction.options.getSubcommand(true) === 'remove') {
+            const removed = sub.removeNotifierTarget(SubscriptionType.PUBLIC, interaction.guildId, interaction.channelId, target, id);
+            interaction.reply({
+                content: removed ?
+                    `Subscription ID: ${id} no longer posts to the ${targetName}` :
+                    `Subscription ID: ${id} in this channel does not post to the ${targetName}`,
+                ephemeral: true,
+            });
+            return;
+        }
+
+        if (!sub.hasNotifier(target.kind)) {
+            interaction.reply({content: `This bot instance is not connected to ${target.kind}`, ephemeral: true});
+            return;
+        }
+        const reply = sub.setNotifierTarget(SubscriptionType.PUBLIC, interaction.guildId, interaction.channelId, target, id) ?
+            `Subscription ID: ${id} now also posts to the ${targetName}` :
+            'No subscription with ID ' + id + ' found in this channel';
+        interaction.reply({content: reply, ephemeral: true});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Post the kills of a subscription in this channel to Matrix or Telegram');
+
+        for (const [subCommand, description] of [
+            ['add', 'Post the kills of a subscription to a Matrix room or Telegram chat too'],
+            ['remove', 'Stop posting the kills of a subscription to a Matrix room or Telegram chat'],
+        ]) {
+            slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName(subCommand)
+                .addNumberOption(option =>
+                    option.setName(this.ID)
+                        .setDescription('ID of the public feed in this channel')
+                        .setRequired(true)
+                )
+                .addStringOption(option =>
+                    option.setName(this.BACKEND)
+                        .setDescription('Chat system')
+                        .setRequired(true)
+                        .addChoices(
+                            {name: 'Matrix', value: NotifierKind.MATRIX},
+                            {name: 'Telegram', value: NotifierKind.TELEGRAM},
+                        )
+                )
+                .addStringOption(option =>
+                    option.setName(this.TARGET)
+                        .setDescription('Matrix room ID like !abc:example.org, or Telegram chat ID')
+                        .setRequired(true)
+                )
+                .setDescription(description));
+        }
+
+        return slashCommand;
+    }
+
+}
@@ -0,0 +1,124 @@
+import {
+    Guild,
+    Interaction,
+    MessageActionRow,
+    MessageSelectMenu,
+    Modal,
+    TextChannel,
+    TextInputComponent,
+} from 'discord.js';
+import {LimitType, SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions, missingChannelPermissions} from '../lib/permissions';
+import {onboardingCustomId, OnboardingStep, parseOnboardingCustomId} from '../lib/onboarding';
+import {entityActivityTemplate} from '../lib/templates';
+import {IntelEntityType} from '../lib/zkbApi';
+
+// Select menus have up to 25 options
+const MAX_CHANNEL_OPTIONS = 25;
+const ENTITY_NAME = 'name';
+
+// Text channels the bot can post the kill embeds in
+function postableChannels(guild: Guild): TextChannel[] {
+    return guild.channels.cache
+        .filter((channel): channel is TextChannel => channel.type === 'GUILD_TEXT')
+        .filter(channel => guild.me != null && missingChannelPermissions(guild.me.permissionsIn(channel)).length === 0)
+        .sort((a, b) => a.rawPosition - b.rawPosition)
+        .first(MAX_CHANNEL_OPTIONS);
+}
+
+// Posts the setup wizard in the system channel of a new server, or sends it to the owner if the bot cannot post there
+export async function sendOnboardingWizard(guild: Guild) {
+    const channels = postableChannels(guild);
+    let content = 'Thanks for adding zKill Activity! Pick the channel to post kills in, then enter your alliance or ' +
+        'corporation to get a starter subscription for its kills and losses. /zkill-help lists everything else.';
+    const components: MessageActionRow[] = [];
+    if (channels.length > 0) {
+        components.push(new MessageActionRow().addComponents(new MessageSelectMenu()
+            .setCustomId(onboardingCustomId({step: OnboardingStep.CHANNEL, guildId: guild.id}))
+            .setPlaceholder('Channel for the kills')
+            .addOptions(channels.map(channel => ({label: '#' + channel.name, value: channel.id})))));
+    } else {
+        content += '\nThe bot cannot post in any text channel yet, it needs View Channel, Send Messages and Embed Links.';
+    }
+    const systemChannel = guild.systemChannel;
+    if (systemChannel && guild.me && missingChannelPermissions(guild.me.permissionsIn(systemChannel)).length === 0) {
+        await systemChannel.send({content, components});
+        return;
+    }
+    const owner = await guild.fetchOwner();
+    await owner.send({content: `**${guild.name}**: ${content}`, components});
+}
+
+// Handles the select menu and the modal of the setup wizard, false if the interaction does not belong to it
+export async function handleOnboardingInteraction(interaction: Interaction): Promise<boolean> {
+    if (!interaction.isSelectMenu() && !interaction.isModalSubmit()) {
+        return false;
+    }
+    const state = parseOnboardingCustomId(interaction.customId);
+    if (state == null) {
+        return false;
+    }
+    const sub = ZKillSubscriber.getInstance();
+    const locale = sub.getGuildLocale(state.guildId);
+    const guild = interaction.client.guilds.cache.get(state.guildId);
+    // In the server the usual subscription permissions apply, in a DM only the owner got the wizard
+    const permitted = interaction.inGuild() ?
+        canManageSubscriptions(interaction, sub.getGuildSettings(state.guildId)) :
+        guild?.ownerId === interaction.user.id;
+    if (!guild || !permitted) {
+        await interaction.reply({content: translate(locale, 'command.notPermitted'), ephemeral: true});
+        return true;
+    }
+
+    if (interaction.isSelectMenu()) {
+        const channelId = interaction.values[0];
+        await interaction.showModal(new Modal()
+            .setCustomId(onboardingCustomId({step: OnboardingStep.ENTITY, guildId: state.guildId, channelId}))
+            .setTitle('Starter subscription')
+            .addComponents(new MessageActionRow<TextInputComponent>().addComponents(new TextInputComponent()
+                .setCustomId(ENTITY_NAME)
+                .setLabel('Alliance or corporation name')
+                .setStyle('SHORT')
+                .setRequired(true))));
+        return true;
+    }
+
+    const channelId = state.channelId;
+    if (channelId == null) {
+        return true;
+    }
+    if (!sub.canSubscribe(state.guildId)) {
+        await interaction.reply({content: 'This server reached the maximum number of subscriptions on this instance', ephemeral: true});
+        return true;
+    }
+    await interaction.deferReply({ephemeral: true});
+    const name = interaction.fields.getTextInputValue(ENTITY_NAME).trim();
+    // Alliances first, a corporation rarely has the exact name of an alliance
+    const alliance = await sub.resolveEntity(IntelEntityType.ALLIANCE, name);
+    const entity = alliance ?? await sub.resolveEntity(IntelEntityType.CORPORATION, name);
+    if (!entity) {
+        await interaction.editReply(`No alliance or corporation named ${name} found, pick the channel again to retry`);
+        return true;
+    }
+    const template = entityActivityTemplate(alliance ? LimitType.ALLIANCE : LimitType.CORPORATION, entity.id);
+    const id = sub.freeSubscriptionId(SubscriptionType.PUBLIC, state.guildId, channelId);
+    sub.subscribe(
+        SubscriptionType.PUBLIC,
+        state.guildId,
+        channelId,
+        template.limitTypes,
+        {
+            inclusionLimitAlsoComparesAttacker: true,
+            inclusionLimitAlsoComparesAttackerWeapons: true,
+            exclusionLimitAlsoComparesAttacker: true,
+            exclusionLimitAlsoComparesAttackerWeapons: true,
+        },
+        id,
+        template.minValue,
+        interaction.user.id,
+    );
+    await interaction.editReply(`Subscribed <#${channelId}> to the kills and losses of ${entity.name} as ID: ${id}\n` +
+        'Adjust it there with /zkill-filter and /zkill-configure, or add more with /zkill-quicksub');
+    return true;
+}
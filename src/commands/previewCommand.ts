@@ -0,0 +1,81 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {Subscription, SubscriptionEvaluation, ZkData, ZKillSubscriber} from '../zKillSubscriber';
+
+// Accepts a plain kill ID or a zKillboard link like https://zkillboard.com/kill/123456789/
+export function parseKillId(kill: string): number | null {
+    const match = /^(?:https?:\/\/(?:www\.)?zkillboard\.com\/kill\/)?(\d+)\/?$/.exec(kill.trim());
+    return match ? Number(match[1]) : null;
+}
+
+export class PreviewCommand extends AbstractCommand {
+    protected name = 'zkill-preview';
+
+    protected KILL = 'kill';
+    protected HASH = 'hash';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            await interaction.reply('Preview is not possible in PM!');
+            return;
+        }
+        const killId = parseKillId(interaction.options.getString(this.KILL, true));
+        if (killId == null) {
+            await interaction.reply({content: 'Not a kill ID or zKillboard kill link', ephemeral: true});
+            return;
+        }
+        const channel = await sub.listGuildChannelSubscriptions(interaction.guildId, interaction.channelId);
+        if (!channel || channel.subscriptions.size === 0) {
+            await interaction.reply({content: 'There are no subscriptions in this channel', ephemeral: true});
+            return;
+        }
+        await interaction.deferReply({ephemeral: true});
+
+        let data: ZkData;
+        try {
+            data = await sub.fetchKill(killId, interaction.options.getString(this.HASH) ?? undefined);
+        } catch (e) {
+            await interaction.editReply(`Failed to fetch kill ${killId}: ${e}`);
+            return;
+        }
+        const context = sub.createKillContext(data);
+        let summary = `Kill ${killId} in this channel:\n`;
+        let firstMatch: [Subscription, SubscriptionEvaluation] | null = null;
+        for (const [ident, subscription] of Array.from(channel.subscriptions.entries())) {
+            const evaluation = await sub.evaluateSubscription(subscription, data, context, interaction.guildId);
+            if (evaluation.matched) {
+                summary += `✅ ${ident}: would be posted\n`;
+                firstMatch = firstMatch ?? [subscription, evaluation];
+            } else {
+                summary += `❌ ${ident}: rejected by ${evaluation.rejectedBy}\n`;
+            }
+        }
+        if (!firstMatch) {
+            await interaction.editReply(summary);
+            return;
+        }
+        const message = await sub.previewMessage(interaction.guildId, interaction.channelId, firstMatch[0], data, firstMatch[1]);
+        await interaction.editReply({content: summary, embeds: message.embeds});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Show whether and how a kill would be posted by the subscriptions in this channel');
+        slashCommand.addStringOption(option =>
+            option.setName(this.KILL)
+                .setDescription('zKillboard link or kill ID')
+                .setRequired(true)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.HASH)
+                .setDescription('Killmail hash, looked up on zKillboard if empty')
+                .setRequired(false)
+        );
+        return slashCommand;
+    }
+
+}
@@ -0,0 +1,149 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {
+    allianceLossesTemplate,
+    BIG_KILL_VALUE,
+    bigKillsTemplate,
+    capitalsInRangeTemplate,
+    npcSpawnsTemplate,
+    SubscriptionTemplate,
+} from '../lib/templates';
+
+export class QuickSubscribeCommand extends AbstractCommand {
+    protected name = 'zkill-quicksub';
+
+    protected ID = 'id';
+    protected MIN_VALUE = 'min-value';
+    protected ALLIANCE_IDS = 'alliance-ids';
+    protected SYSTEM = 'system';
+    protected RANGE = 'range';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Subscription is not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        if (!sub.canSubscribe(interaction.guildId)) {
+            interaction.reply({content: 'This server reached the maximum number of subscriptions on this instance', ephemeral: true});
+            return;
+        }
+        const template = interaction.options.getSubcommand(true);
+        const id = interaction.options.getNumber(this.ID, true);
+        const minValue = interaction.options.getNumber(this.MIN_VALUE) ?? undefined;
+
+        let subscription: SubscriptionTemplate | null;
+        if (template === 'alliance-losses') {
+            subscription = allianceLossesTemplate(interaction.options.getString(this.ALLIANCE_IDS, true), minValue);
+        } else if (template === 'capitals-in-range') {
+            subscription = capitalsInRangeTemplate(
+                interaction.options.getString(this.SYSTEM, true),
+                interaction.options.getString(this.RANGE) ?? '10',
+                minValue,
+            );
+            if (subscription == null) {
+                interaction.reply({content: 'Range has to be light years or blackops, jf, rorqual, carrier, dread, fax, super or titan', ephemeral: true});
+                return;
+            }
+        } else if (template === 'npc-spawns') {
+            subscription = npcSpawnsTemplate(minValue);
+        } else {
+            subscription = bigKillsTemplate(minValue);
+        }
+
+        sub.subscribe(
+            SubscriptionType.PUBLIC,
+            interaction.guildId,
+            interaction.channelId,
+            subscription.limitTypes,
+            {
+                inclusionLimitAlsoComparesAttacker: true,
+                inclusionLimitAlsoComparesAttackerWeapons: true,
+                exclusionLimitAlsoComparesAttacker: true,
+                exclusionLimitAlsoComparesAttackerWeapons: true,
+            },
+            String(id),
+            subscription.minValue,
+            interaction.user.id,
+        );
+
+        let reply = translate(sub.getGuildLocale(interaction.guildId), 'command.subscribed', {channel: SubscriptionType.PUBLIC});
+        reply += ' ID: ' + id + ' from template ' + template;
+        subscription.limitTypes.forEach((value, limitType) => {
+            reply += '\n' + limitType + ': ' + value;
+        });
+        if (subscription.minValue) {
+            reply += '\nMin Value: ' + subscription.minValue.toLocaleString('en');
+        }
+        reply += '\nAdjust it with /zkill-filter and /zkill-configure';
+        interaction.reply({content: reply, ephemeral: true});
+    }
+
+    private subcommand(name: string, description: string): SlashCommandSubcommandBuilder {
+        return new SlashCommandSubcommandBuilder().setName(name)
+            .setDescription(description)
+            .addNumberOption(option =>
+                option.setName(this.ID)
+                    .setDescription('ID for public feed')
+                    .setRequired(true)
+            );
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Subscribe to zkill with a preset for a common use');
+
+        slashCommand.addSubcommand(this.subcommand('alliance-losses', 'Losses of your alliance')
+            .addStringOption(option =>
+                option.setName(this.ALLIANCE_IDS)
+                    .setDescription('Alliance id, comma seperated ids')
+                    .setRequired(true)
+            )
+            .addNumberOption(option =>
+                option.setName(this.MIN_VALUE)
+                    .setDescription('Minimum isk to show the entry')
+                    .setRequired(false)
+            ));
+        slashCommand.addSubcommand(this.subcommand('capitals-in-range', 'Capitals killing or dying within jump range of a system')
+            .addStringOption(option =>
+                option.setName(this.SYSTEM)
+                    .setDescription('Name of the system, e.g. your staging')
+                    .setRequired(true)
+            )
+            .addStringOption(option =>
+                option.setName(this.RANGE)
+                    .setDescription('Light years or a ship class like dread or titan, 10 LY if empty')
+                    .setRequired(false)
+            )
+            .addNumberOption(option =>
+                option.setName(this.MIN_VALUE)
+                    .setDescription('Minimum isk to show the entry')
+                    .setRequired(false)
+            ));
+        slashCommand.addSubcommand(this.subcommand('npc-spawns', 'Kills by officer and faction commander NPC spawns')
+            .addNumberOption(option =>
+                option.setName(this.MIN_VALUE)
+                    .setDescription('Minimum isk to show the entry')
+                    .setRequired(false)
+            ));
+        slashCommand.addSubcommand(this.subcommand('big-kills', `Kills worth at least ${BIG_KILL_VALUE / 1e9} billion isk`)
+            .addNumberOption(option =>
+                option.setName(this.MIN_VALUE)
+                    .setDescription('Minimum isk to show the entry instead')
+                    .setRequired(false)
+            ));
+
+        return slashCommand;
+    }
+
+}
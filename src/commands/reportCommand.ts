@@ -0,0 +1,83 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {describeSchedule, Report, ReportSchedule, WEEKDAYS} from '../lib/reports';
+
+export class ReportCommand extends AbstractCommand {
+    protected name = 'zkill-report';
+
+    protected SCHEDULE = 'schedule';
+    protected HOUR = 'hour';
+    protected WEEKDAY = 'weekday';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Reports are not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        if (interaction.options.getSubcommand(true) === 'remove') {
+            const removed = sub.getReport(interaction.guildId, interaction.channelId) != null;
+            sub.setReport(interaction.guildId, interaction.channelId, null);
+            interaction.reply({content: removed ? 'Removed the report of this channel' : 'There is no report in this channel', ephemeral: true});
+            return;
+        }
+        const report: Report = {
+            schedule: interaction.options.getString(this.SCHEDULE, true) as ReportSchedule,
+            hour: interaction.options.getInteger(this.HOUR) ?? 8,
+            lastRun: new Date().toISOString(),
+        };
+        if (report.schedule === ReportSchedule.WEEKLY) {
+            report.weekday = interaction.options.getInteger(this.WEEKDAY) ?? 1;
+        }
+        sub.setReport(interaction.guildId, interaction.channelId, report);
+        interaction.reply({
+            content: `The kills posted in this channel are summarized ${describeSchedule(report)}`,
+            ephemeral: true,
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Post a periodic summary of the kills posted in this channel');
+
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('set')
+            .addStringOption(option =>
+                option.setName(this.SCHEDULE)
+                    .setDescription('How often the report is posted')
+                    .setRequired(true)
+                    .addChoices(
+                        {name: 'Daily', value: ReportSchedule.DAILY},
+                        {name: 'Weekly', value: ReportSchedule.WEEKLY},
+                    )
+            )
+            .addIntegerOption(option =>
+                option.setName(this.HOUR)
+                    .setDescription('UTC hour the report is posted at, defaults to 8')
+                    .setMinValue(0)
+                    .setMaxValue(23)
+                    .setRequired(false)
+            )
+            .addIntegerOption(option =>
+                option.setName(this.WEEKDAY)
+                    .setDescription('Day of weekly reports, defaults to monday')
+                    .setRequired(false)
+                    .addChoices(...WEEKDAYS.map((name, value) => ({name, value})))
+            )
+            .setDescription('Set the report of this channel, replacing an existing one'));
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('remove')
+            .setDescription('Remove the report of this channel'));
+
+        return slashCommand;
+    }
+
+}
@@ -0,0 +1,37 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+
+export class ResumeCommand extends AbstractCommand {
+    protected name = 'zkill-resume';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Resuming is not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const resumed = sub.resumeChannel(interaction.guildId, interaction.channelId);
+        interaction.reply({
+            content: resumed > 0 ?
+                `Resumed ${resumed} subscriptions in this channel` :
+                'There are no disabled subscriptions in this channel',
+            ephemeral: true,
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        return new SlashCommandBuilder().setName(this.name)
+            .setDescription('Resume the subscriptions in this channel the bot disabled because it could not post');
+    }
+
+}
@@ -0,0 +1,101 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {IntelEntityType} from '../lib/zkbApi';
+import {KillQuery, MAX_SEARCH_DAYS} from '../lib/killSearch';
+
+export class SearchCommand extends AbstractCommand {
+    protected name = 'zkill-search';
+
+    protected ENTITY_TYPE = 'entity-type';
+    protected ENTITY_NAME = 'entity-name';
+    protected SYSTEM = 'system';
+    protected SHIP_GROUP_ID = 'ship-group-id';
+    protected MIN_VALUE = 'min-value';
+    protected DAYS = 'days';
+    protected PAGE = 'page';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        const days = interaction.options.getInteger(this.DAYS) ?? 7;
+        const now = Date.now();
+        const query: KillQuery = {from: now - days * 24 * 60 * 60 * 1000, to: now};
+        await interaction.deferReply({ephemeral: true});
+        try {
+            const entityName = interaction.options.getString(this.ENTITY_NAME);
+            if (entityName) {
+                const type = (interaction.options.getString(this.ENTITY_TYPE) ?? IntelEntityType.CHARACTER) as IntelEntityType;
+                const entity = await sub.resolveEntity(type, entityName.trim());
+                if (entity == null) {
+                    await interaction.editReply(`There is no ${type} named ${entityName}`);
+                    return;
+                }
+                query.entityId = entity.id;
+            }
+            const systemName = interaction.options.getString(this.SYSTEM);
+            if (systemName) {
+                const systemId = await sub.resolveSystemId(systemName.trim());
+                if (systemId == null) {
+                    await interaction.editReply(`There is no system named ${systemName}`);
+                    return;
+                }
+                query.systemId = systemId;
+            }
+            query.shipGroupId = interaction.options.getInteger(this.SHIP_GROUP_ID) ?? undefined;
+            query.minValue = interaction.options.getNumber(this.MIN_VALUE) ?? undefined;
+            const result = await sub.searchKillArchive(query);
+            if (result == null) {
+                await interaction.editReply('This bot instance does not archive kills');
+                return;
+            }
+            await interaction.editReply({embeds: [await sub.killSearchEmbed(result, interaction.options.getInteger(this.PAGE) ?? 1)]});
+        } catch (e) {
+            await interaction.editReply(`Failed to search the archived kills: ${e}`);
+        }
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Search the kills archived by this bot');
+        slashCommand.addStringOption(option =>
+            option.setName(this.ENTITY_TYPE)
+                .setDescription('Kind of the entity, character by default')
+                .addChoices(
+                    {name: 'Character', value: IntelEntityType.CHARACTER},
+                    {name: 'Corporation', value: IntelEntityType.CORPORATION},
+                    {name: 'Alliance', value: IntelEntityType.ALLIANCE},
+                )
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.ENTITY_NAME)
+                .setDescription('Exact name of the victim or an attacker')
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.SYSTEM)
+                .setDescription('Exact name of the system')
+        );
+        slashCommand.addIntegerOption(option =>
+            option.setName(this.SHIP_GROUP_ID)
+                .setDescription('Group ID of the victim ship, e.g. 419 for combat battlecruisers')
+        );
+        slashCommand.addNumberOption(option =>
+            option.setName(this.MIN_VALUE)
+                .setDescription('Minimum ISK value')
+                .setMinValue(0)
+        );
+        slashCommand.addIntegerOption(option =>
+            option.setName(this.DAYS)
+                .setDescription(`Search the last days, 7 by default, at most ${MAX_SEARCH_DAYS}`)
+                .setMinValue(1)
+                .setMaxValue(MAX_SEARCH_DAYS)
+        );
+        slashCommand.addIntegerOption(option =>
+            option.setName(this.PAGE)
+                .setDescription('Page of the results, 10 kills per page')
+                .setMinValue(1)
+        );
+        return slashCommand;
+    }
+
+}
@@ -0,0 +1,249 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {CommandInteraction, Permissions} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {GuildSettings, ZKillSubscriber} from '../zKillSubscriber';
+import {Locale, LOCALE_NAMES, parseLocale} from '../lib/i18n';
+import {parseEntityIds} from '../lib/entityLists';
+import {EmbedMode} from '../lib/embedDisplay';
+import {mapProvider, MapProviderKind} from '../lib/mapLinks';
+import {ValueFormat} from '../lib/valueFormat';
+
+export function formatGuildSettings(settings: GuildSettings): string {
+    const role = (roleId?: string) => roleId ? `<@&${roleId}>` : 'not set';
+    const subscriberRoles = settings.subscriberRoleIds ?? [];
+    return [
+        'Language: ' + LOCALE_NAMES[parseLocale(settings.locale)],
+        'Admin role: ' + role(settings.adminRoleId),
+        'Subscriber roles: ' + (subscriberRoles.length > 0 ? subscriberRoles.map(roleId => `<@&${roleId}>`).join(', ') : 'none'),
+        'Alert channel: ' + (settings.alertChannelId ? `<#${settings.alertChannelId}>` : 'server owner'),
+        'Own entities: ' + ((settings.ownEntityIds ?? []).join(', ') || 'none'),
+        'Default ping role: ' + role(settings.defaultPingRoleId) +
+        (settings.defaultPingRoleValue != null ? ` from ${settings.defaultPingRoleValue} ISK` : ''),
+        'Embeds: ' + (settings.embedMode ?? EmbedMode.FULL) + (settings.hideThumbnails ? ', without thumbnails' : '') +
+        (settings.colorBlind ? ', color blind friendly' : ''),
+        'Map links: ' + mapProvider(settings.mapProvider).label,
+        'ISK values: ' + (settings.valueFormat ?? ValueFormat.SHORT) + (settings.showPlexValue ? ', with PLEX' : ''),
+        'Overlapping subscriptions: ' + (settings.postDuplicates ? 'one message each' : 'one message naming all of them'),
+    ].join('\n');
+}
+
+export class SettingsCommand extends AbstractCommand {
+    protected name = 'zkill-settings';
+
+    protected LANGUAGE = 'language';
+    protected ADMIN_ROLE = 'admin-role';
+    protected ADD_SUBSCRIBER_ROLE = 'add-subscriber-role';
+    protected REMOVE_SUBSCRIBER_ROLE = 'remove-subscriber-role';
+    protected ALERT_CHANNEL = 'alert-channel';
+    protected OWN_ENTITY_IDS = 'own-entity-ids';
+    protected DEFAULT_PING_ROLE = 'default-ping-role';
+    protected DEFAULT_PING_ROLE_VALUE = 'default-ping-role-value';
+    protected EMBED_MODE = 'embed-mode';
+    protected HIDE_THUMBNAILS = 'hide-thumbnails';
+    protected COLOR_BLIND = 'color-blind';
+    protected MAP_PROVIDER = 'map-provider';
+    protected POST_DUPLICATES = 'post-duplicates';
+    protected VALUE_FORMAT = 'value-format';
+    protected SHOW_PLEX_VALUE = 'show-plex-value';
+    protected CLEAR = 'clear';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Server settings are not available in PM!');
+            return;
+        }
+        if (!interaction.memberPermissions?.has(Permissions.FLAGS.MANAGE_GUILD)) {
+            interaction.reply({content: 'Changing the server settings requires the Manage Server permission', ephemeral: true});
+            return;
+        }
+        const current = sub.getGuildSettings(interaction.guildId);
+        if (interaction.options.getSubcommand(true) === 'show') {
+            interaction.reply({content: formatGuildSettings(current), ephemeral: true, allowedMentions: {parse: []}});
+            return;
+        }
+
+        const settings: GuildSettings = {};
+        const language = interaction.options.getString(this.LANGUAGE);
+        if (language != null) {
+            settings.locale = parseLocale(language);
+        }
+        const adminRole = interaction.options.getRole(this.ADMIN_ROLE);
+        if (adminRole != null) {
+            settings.adminRoleId = adminRole.id;
+        }
+        const addSubscriberRole = interaction.options.getRole(this.ADD_SUBSCRIBER_ROLE);
+        const removeSubscriberRole = interaction.options.getRole(this.REMOVE_SUBSCRIBER_ROLE);
+        if (addSubscriberRole != null || removeSubscriberRole != null) {
+            const subscriberRoleIds = (current.subscriberRoleIds ?? [])
+                .filter(roleId => roleId !== addSubscriberRole?.id && roleId !== removeSubscriberRole?.id);
+            if (addSubscriberRole != null && addSubscriberRole.id !== removeSubscriberRole?.id) {
+                subscriberRoleIds.push(addSubscriberRole.id);
+            }
+            settings.subscriberRoleIds = subscriberRoleIds;
+        }
+        const alertChannel = interaction.options.getChannel(this.ALERT_CHANNEL);
+        if (alertChannel != null) {
+            settings.alertChannelId = alertChannel.id;
+        }
+        const ownEntities = interaction.options.getString(this.OWN_ENTITY_IDS);
+        if (ownEntities != null) {
+            const ownEntityIds = parseEntityIds(ownEntities);
+            if (ownEntityIds == null) {
+                interaction.reply({content: 'Own entities have to be character, corporation or alliance IDs', ephemeral: true});
+                return;
+            }
+            settings.ownEntityIds = ownEntityIds;
+        }
+        const defaultPingRole = interaction.options.getRole(this.DEFAULT_PING_ROLE);
+        if (defaultPingRole != null) {
+            settings.defaultPingRoleId = defaultPingRole.id;
+        }
+        const defaultPingRoleValue = interaction.options.getNumber(this.DEFAULT_PING_ROLE_VALUE);
+        if (defaultPingRoleValue != null) {
+            settings.defaultPingRoleValue = defaultPingRoleValue;
+        }
+        const embedMode = interaction.options.getString(this.EMBED_MODE);
+        if (embedMode != null) {
+            settings.embedMode = embedMode as EmbedMode;
+        }
+        const hideThumbnails = interaction.options.getBoolean(this.HIDE_THUMBNAILS);
+        if (hideThumbnails != null) {
+            settings.hideThumbnails = hideThumbnails;
+        }
+        const colorBlind = interaction.options.getBoolean(this.COLOR_BLIND);
+        if (colorBlind != null) {
+            settings.colorBlind = colorBlind;
+        }
+        const provider = interaction.options.getString(this.MAP_PROVIDER);
+        if (provider != null) {
+            settings.mapProvider = provider as MapProviderKind;
+        }
+        const postDuplicates = interaction.options.getBoolean(this.POST_DUPLICATES);
+        if (postDuplicates != null) {
+            settings.postDuplicates = postDuplicates;
+        }
+        const valueFormat = interaction.options.getString(this.VALUE_FORMAT);
+        if (valueFormat != null) {
+            settings.valueFormat = valueFormat as ValueFormat;
+        }
+        const showPlexValue = interaction.options.getBoolean(this.SHOW_PLEX_VALUE);
+        if (showPlexValue != null) {
+            settings.showPlexValue = showPlexValue;
+        }
+        // Removes a setting, like leaving the option empty does in the single setting commands
+        const clear = interaction.options.getString(this.CLEAR);
+        if (clear != null) {
+            Object.assign(settings, {[clear]: undefined});
+        }
+
+        if (Object.keys(settings).length === 0) {
+            interaction.reply({content: 'No settings given, use the show subcommand to see the current ones', ephemeral: true});
+            return;
+        }
+        sub.updateGuildSettings(interaction.guildId, settings);
+        interaction.reply({
+            content: 'Server settings updated:\n' + formatGuildSettings(sub.getGuildSettings(interaction.guildId)),
+            ephemeral: true,
+            allowedMentions: {parse: []},
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Show or change the settings of this server');
+
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('show')
+            .setDescription('Show the settings of this server'));
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('set')
+            .setDescription('Change the settings of this server, options left empty are kept')
+            .addStringOption(option =>
+                option.setName(this.LANGUAGE)
+                    .setDescription('Language of the kill messages and command replies')
+                    .addChoices(...Object.values(Locale).map(locale => ({name: LOCALE_NAMES[locale], value: locale})))
+            )
+            .addRoleOption(option =>
+                option.setName(this.ADMIN_ROLE)
+                    .setDescription('Role that may manage subscriptions without the Manage Channels permission')
+            )
+            .addRoleOption(option =>
+                option.setName(this.ADD_SUBSCRIBER_ROLE)
+                    .setDescription('Add a role that may manage subscriptions')
+            )
+            .addRoleOption(option =>
+                option.setName(this.REMOVE_SUBSCRIBER_ROLE)
+                    .setDescription('Remove a role that may manage subscriptions')
+            )
+            .addChannelOption(option =>
+                option.setName(this.ALERT_CHANNEL)
+                    .setDescription('Channel notified when the bot disables subscriptions')
+            )
+            .addStringOption(option =>
+                option.setName(this.OWN_ENTITY_IDS)
+                    .setDescription('Character, corporation and alliance IDs of your side, for the perspective filter')
+            )
+            .addRoleOption(option =>
+                option.setName(this.DEFAULT_PING_ROLE)
+                    .setDescription('Ping role of new subscriptions')
+            )
+            .addNumberOption(option =>
+                option.setName(this.DEFAULT_PING_ROLE_VALUE)
+                    .setDescription('Ping role value of new subscriptions')
+            )
+            .addStringOption(option =>
+                option.setName(this.EMBED_MODE)
+                    .setDescription('Full kill embeds, or compact ones of a single line')
+                    .addChoices({name: 'Full', value: EmbedMode.FULL}, {name: 'Compact', value: EmbedMode.COMPACT})
+            )
+            .addBooleanOption(option =>
+                option.setName(this.HIDE_THUMBNAILS)
+                    .setDescription('Leave the ship images out of the kill embeds')
+            )
+            .addBooleanOption(option =>
+                option.setName(this.COLOR_BLIND)
+                    .setDescription('Prefix the titles with KILL or LOSS and use blue and orange instead of green and red')
+            )
+            .addStringOption(option =>
+                option.setName(this.MAP_PROVIDER)
+                    .setDescription('Site the system links of the kill messages point to')
+                    .addChoices(
+                        {name: 'Dotlan', value: MapProviderKind.DOTLAN},
+                        {name: 'Gatecheck', value: MapProviderKind.GATECHECK},
+                        {name: 'EVE-Scout Thera connections', value: MapProviderKind.EVE_SCOUT},
+                    )
+            )
+            .addBooleanOption(option =>
+                option.setName(this.POST_DUPLICATES)
+                    .setDescription('Post a kill once per matching subscription instead of once per channel')
+            )
+            .addStringOption(option =>
+                option.setName(this.VALUE_FORMAT)
+                    .setDescription('Abbreviated ISK values like 1.5bil, or full ones with thousands separators')
+                    .addChoices({name: 'Short', value: ValueFormat.SHORT}, {name: 'Full', value: ValueFormat.FULL})
+            )
+            .addBooleanOption(option =>
+                option.setName(this.SHOW_PLEX_VALUE)
+                    .setDescription('Show the ISK values in PLEX too, at the current market price')
+            )
+            .addStringOption(option =>
+                option.setName(this.CLEAR)
+                    .setDescription('Remove a setting')
+                    .addChoices(
+                        {name: 'Language', value: 'locale'},
+                        {name: 'Admin role', value: 'adminRoleId'},
+                        {name: 'Subscriber roles', value: 'subscriberRoleIds'},
+                        {name: 'Alert channel', value: 'alertChannelId'},
+                        {name: 'Own entities', value: 'ownEntityIds'},
+                        {name: 'Default ping role', value: 'defaultPingRoleId'},
+                        {name: 'Default ping role value', value: 'defaultPingRoleValue'},
+                        {name: 'Map links', value: 'mapProvider'},
+                        {name: 'ISK value format', value: 'valueFormat'},
+                    )
+            ));
+
+        return slashCommand;
+    }
+
+}
@@ -0,0 +1,106 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {ButtonInteraction, CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {parseSrpButtonId, SrpRequest, SrpStatus} from '../lib/srp';
+
+export function formatSrpRequest(request: SrpRequest, abbreviate: (n: number) => unknown): string {
+    return `[${request.killmailId}](${request.url}) ${request.pilotName}: ${request.shipName}, ` +
+        `${abbreviate(request.totalValue)} ISK, requested by <@${request.requestedBy}>`;
+}
+
+// Handles a click on the "Request SRP" button of a loss embed, false if the button is not an SRP button
+export async function handleSrpButton(interaction: ButtonInteraction): Promise<boolean> {
+    const killmailId = parseSrpButtonId(interaction.customId);
+    if (killmailId == null) {
+        return false;
+    }
+    const sub = ZKillSubscriber.getInstance();
+    if (!interaction.inGuild()) {
+        return true;
+    }
+    const locale = sub.getGuildLocale(interaction.guildId);
+    await interaction.deferReply({ephemeral: true});
+    try {
+        const request = await sub.requestSrp(interaction.guildId, killmailId, interaction.user.id);
+        await interaction.editReply(request ?
+            translate(locale, 'srp.requested', {ship: request.shipName, pilot: request.pilotName}) :
+            translate(locale, 'srp.alreadyRequested'));
+    } catch (e) {
+        await interaction.editReply(`Failed to request ship replacement for kill ${killmailId}: ${e}`);
+    }
+    return true;
+}
+
+export class SrpQueueCommand extends AbstractCommand {
+    protected name = 'zkill-srp-queue';
+
+    protected KILL = 'kill';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('The SRP queue is not available in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const queue = sub.getSrpQueue();
+        const subCommand = interaction.options.getSubcommand(true);
+        if (subCommand === 'list') {
+            const pending = queue.pending(interaction.guildId);
+            if (pending.length === 0) {
+                interaction.reply({content: 'There are no pending SRP requests', ephemeral: true});
+                return;
+            }
+            let reply = `${pending.length} pending SRP requests:\n`;
+            for (const request of pending) {
+                const line = formatSrpRequest(request, n => sub.formatValue(interaction.guildId, n)) + '\n';
+                if (reply.length + line.length > 1900) {
+                    reply += '...';
+                    break;
+                }
+                reply += line;
+            }
+            interaction.reply({content: reply, ephemeral: true, allowedMentions: {parse: []}});
+            return;
+        }
+        const killmailId = interaction.options.getInteger(this.KILL, true);
+        const status = subCommand === 'approve' ? SrpStatus.APPROVED : SrpStatus.DENIED;
+        const request = queue.review(interaction.guildId, killmailId, status, interaction.user.id);
+        if (!request) {
+            interaction.reply({content: 'No pending SRP request for kill ' + killmailId, ephemeral: true});
+            return;
+        }
+        interaction.reply({
+            content: `SRP request for kill ${killmailId} of ${request.pilotName} ${status}`,
+            ephemeral: true,
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Review the ship replacement requests of this server');
+
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('list')
+            .setDescription('List the pending ship replacement requests'));
+        for (const [name, description] of [['approve', 'Approve a ship replacement request'], ['deny', 'Deny a ship replacement request']]) {
+            slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName(name)
+                .addIntegerOption(option =>
+                    option.setName(this.KILL)
+                        .setDescription('Kill ID of the request')
+                        .setRequired(true)
+                )
+                .setDescription(description));
+        }
+
+        return slashCommand;
+    }
+
+}
@@ -0,0 +1,82 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+
+export class StatsCommand extends AbstractCommand {
+    protected name = 'zkill-stats';
+
+    protected DAYS = 'days';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            await interaction.reply('Statistics are not possible in PM!');
+            return;
+        }
+        const days = interaction.options.getInteger(this.DAYS) ?? 30;
+        const summary = sub.getKillStats().summarize(interaction.guildId, days);
+        if (summary.kills === 0) {
+            await interaction.reply({content: `No kills were posted in the last ${days} days`, ephemeral: true});
+            return;
+        }
+        await interaction.deferReply();
+
+        let topSystems = '';
+        for (const system of summary.topSystems) {
+            let name = String(system.id);
+            try {
+                name = (await sub.getSystemData(system.id)).systemName;
+            } catch (e) {
+                console.log(e);
+            }
+            topSystems += `${name}: ${system.count}\n`;
+        }
+        let topVictimAlliances = '';
+        for (const alliance of summary.topVictimAlliances) {
+            let name = String(alliance.id);
+            try {
+                name = await sub.getNameForAlliance(alliance.id);
+            } catch (e) {
+                console.log(e);
+            }
+            topVictimAlliances += `${name}: ${alliance.count}\n`;
+        }
+        let killsPerDay = '';
+        Array.from(summary.killsPerDay.entries()).sort().forEach(([day, count]) => {
+            killsPerDay += `${day}: ${count}\n`;
+        });
+
+        await interaction.editReply({
+            embeds: [{
+                title: `Kill statistics of the last ${days} days`,
+                description: `${summary.kills} kills, ${sub.abbreviateNumber(summary.totalValue)} ISK destroyed`,
+                fields: [
+                    {name: '__Top Systems__', value: topSystems || '-', inline: true},
+                    {name: '__Top Victim Alliances__', value: topVictimAlliances || '-', inline: true},
+                    {name: '__Kills per Day__', value: '```' + killsPerDay.substring(0, 1000) + '```', inline: false},
+                ],
+            }]
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Leaderboard of the kills posted in this server');
+        slashCommand.addIntegerOption(option =>
+            option.setName(this.DAYS)
+                .setDescription('Period in days, defaults to 30')
+                .setRequired(false)
+                .addChoices(
+                    {name: '1 day', value: 1},
+                    {name: '7 days', value: 7},
+                    {name: '30 days', value: 30},
+                    {name: '90 days', value: 90},
+                )
+        );
+        return slashCommand;
+    }
+
+}
@@ -1,7 +1,21 @@
 import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
 import {CommandInteraction} from 'discord.js';
 import {AbstractCommand} from './abstractCommand';
-import {LimitType, SubscriptionType, SubscriptionFlags, ZKillSubscriber} from '../zKillSubscriber';
+import {LimitType, SpaceType, SubscriptionType, SubscriptionFlags, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {expandShipAliases} from '../lib/shipGroups';
+import {DryRunMode} from '../lib/dryRun';
+import {validateLimitTypes} from '../lib/subscriptionValidation';
+
+// Start and end hour of a range like 18-2, null if either is not an hour
+export function parseTimeRange(value: string): [string, string] | null {
+    const match = /^\s*(\d{1,2})\s*-\s*(\d{1,2})\s*$/.exec(value);
+    if (!match || Number(match[1]) > 23 || Number(match[2]) > 23) {
+        return null;
+    }
+    return [String(Number(match[1])), String(Number(match[2]))];
+}
 
 export class SubscribeCommand extends AbstractCommand {
     protected name = 'zkill-subscribe';
@@ -18,11 +32,12 @@ export class SubscribeCommand extends AbstractCommand {
     protected LIMIT_SECURITY_MIN_INCL = 'limit-security-min-incl';
     protected LIMIT_SECURITY_MAX_EXCL = 'limit-security-max-excl';
     protected LIMIT_SECURITY_MIN_EXCL = 'limit-security-min-excl';
+    protected LIMIT_SPACE_TYPE = 'limit-space-type';
+    protected LIMIT_ATTACKER_AVG_SEC_MAX = 'limit-attacker-avg-sec-max';
     protected LIMIT_ALLIANCE_IDS = 'limit-alliance-ids';
     protected LIMIT_CORPORATION_IDS = 'limit-corporation-ids';
     protected LIMIT_CHARACTER_IDS = 'limit-character-ids';
-    protected LIMIT_TIME_RANGE_START = 'limit-time-range-start';
-    protected LIMIT_TIME_RANGE_END = 'limit-time-range-end';
+    protected LIMIT_TIME_RANGE = 'limit-time-range';
     protected INCLUSION_LIMIT_COMPARES_ATTACKERS = 'in-limit-compares-attackers';
     protected INCLUSION_LIMIT_COMPARES_ATTACKER_WEAPONS = 'in-limit-compares-attacker-weps';
     protected EXCLUSION_LIMIT_COMPARES_ATTACKERS = 'ex-limit-compares-attackers';
@@ -30,6 +45,7 @@ export class SubscribeCommand extends AbstractCommand {
     protected REQUIRED_NAME_FRAGMENT = 'required-name-fragment';
     protected NPC_ONLY = 'npc-only';
     protected LY_RANGE_TO_SYSTEM_WITH_NAME = 'ly-to-sys-by-name';
+    protected DRY_RUN = 'dry-run';
 
     executeCommand(interaction: CommandInteraction): void {
         const sub = ZKillSubscriber.getInstance();
@@ -39,6 +55,14 @@ export class SubscribeCommand extends AbstractCommand {
             interaction.reply('Subscription is not possible in PM!');
             return;
         }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        if (!sub.canSubscribe(interaction.guildId)) {
+            interaction.reply({content: 'This server reached the maximum number of subscriptions on this instance', ephemeral: true});
+            return;
+        }
         const subCommand = interaction.options.getSubcommand(true) as SubscriptionType;
         const id = interaction.options.getNumber(this.ID, true);
         const minValue = interaction.options.getNumber(this.MIN_VALUE);
@@ -49,14 +73,27 @@ export class SubscribeCommand extends AbstractCommand {
         const limitRegion = interaction.options.getString(this.LIMIT_REGION_IDS);
         const limitConstellation = interaction.options.getString(this.LIMIT_CONSTELLATION_IDS);
         const limitSystem = interaction.options.getString(this.LIMIT_SYSTEM_IDS);
-        const limitShipsIncluded = interaction.options.getString(this.LIMIT_INCLUDED_SHIP_IDS);
-        const limitShipsExcluded = interaction.options.getString(this.LIMIT_EXCLUDED_SHIP_IDS);
+        let limitShipsIncluded = interaction.options.getString(this.LIMIT_INCLUDED_SHIP_IDS);
+        let limitShipsExcluded = interaction.options.getString(this.LIMIT_EXCLUDED_SHIP_IDS);
+        // Aliases are stored as type IDs, so the filters never see them
+        if (limitShipsIncluded) {
+            limitShipsIncluded = expandShipAliases(limitShipsIncluded);
+        }
+        if (limitShipsExcluded) {
+            limitShipsExcluded = expandShipAliases(limitShipsExcluded);
+        }
+        if (limitShipsIncluded === null && interaction.options.getString(this.LIMIT_INCLUDED_SHIP_IDS) ||
+            limitShipsExcluded === null && interaction.options.getString(this.LIMIT_EXCLUDED_SHIP_IDS)) {
+            interaction.reply({content: 'Ship IDs have to be type IDs or capitals, supers, structures, industrials or blops', ephemeral: true});
+            return;
+        }
         const limitSecurityMaxExcl = interaction.options.getString(this.LIMIT_SECURITY_MAX_EXCL);
         const limitSecurityMinExcl = interaction.options.getString(this.LIMIT_SECURITY_MIN_EXCL);
         const limitSecurityMaxIncl = interaction.options.getString(this.LIMIT_SECURITY_MAX_INCL);
         const limitSecurityMinIncl = interaction.options.getString(this.LIMIT_SECURITY_MIN_INCL);
-        const timeRangeStart = interaction.options.getString(this.LIMIT_TIME_RANGE_START);
-        const timeRangeEnd = interaction.options.getString(this.LIMIT_TIME_RANGE_END);
+        const limitSpaceType = interaction.options.getString(this.LIMIT_SPACE_TYPE);
+        const limitAttackerAvgSecMax = interaction.options.getString(this.LIMIT_ATTACKER_AVG_SEC_MAX);
+        const timeRange = interaction.options.getString(this.LIMIT_TIME_RANGE);
         const requiredNameFragment = interaction.options.getString(this.REQUIRED_NAME_FRAGMENT);
         // set to false if null
         const npcOnly = interaction.options.getBoolean(this.NPC_ONLY) ?? false;
@@ -65,8 +102,9 @@ export class SubscribeCommand extends AbstractCommand {
         const exclusionLimitComparesAttackers = interaction.options.getBoolean(this.EXCLUSION_LIMIT_COMPARES_ATTACKERS) ?? true;
         const exclusionLimitComparesAttackerWeapons = interaction.options.getBoolean(this.EXCLUSION_LIMIT_COMPARES_ATTACKER_WEAPONS) ?? true;
         const LyRangeToSystemWithName = interaction.options.getString(this.LY_RANGE_TO_SYSTEM_WITH_NAME);
+        const dryRun = interaction.options.getString(this.DRY_RUN) as DryRunMode | null;
 
-        let reply = 'We subscribed to zkillboard channel: ' + interaction.options.getSubcommand();
+        let reply = translate(sub.getGuildLocale(interaction.guildId), 'command.subscribed', {channel: interaction.options.getSubcommand()});
         const limitTypes = new Map<LimitType, string>();
         if (npcOnly) {
             limitTypes.set(LimitType.NPC_ONLY, String(npcOnly));
@@ -120,17 +158,27 @@ export class SubscribeCommand extends AbstractCommand {
             limitTypes.set(LimitType.SECURITY_MIN_EXCLUSIVE, limitSecurityMinExcl);
             reply += '\nMin Security filter: - ' + limitSecurityMinExcl;
         }
+        if (limitSpaceType) {
+            limitTypes.set(LimitType.SPACE_TYPE, limitSpaceType);
+            reply += '\nSpace type filter: + ' + limitSpaceType;
+        }
+        if (limitAttackerAvgSecMax) {
+            limitTypes.set(LimitType.ATTACKER_AVG_SEC_STATUS_MAX, limitAttackerAvgSecMax);
+            reply += '\nMax attacker average security status: + ' + limitAttackerAvgSecMax;
+        }
         if (minNumInvolved) {
             limitTypes.set(LimitType.MIN_NUM_INVOLVED, minNumInvolved.toString());
             reply += '\nMin Num Involved: + ' + minNumInvolved;
         }
-        if (timeRangeStart) {
-            limitTypes.set(LimitType.TIME_RANGE_START, timeRangeStart);
-            reply += '\nTime Range Start: + ' + timeRangeStart;
-        }
-        if (timeRangeEnd) {
-            limitTypes.set(LimitType.TIME_RANGE_END, timeRangeEnd);
-            reply += '\nTime Range End: + ' + timeRangeEnd;
+        if (timeRange) {
+            const hours = parseTimeRange(timeRange);
+            if (hours == null) {
+                interaction.reply({content: 'The time range has to be two hours between 0 and 23, e.g. 18-2', ephemeral: true});
+                return;
+            }
+            limitTypes.set(LimitType.TIME_RANGE_START, hours[0]);
+            limitTypes.set(LimitType.TIME_RANGE_END, hours[1]);
+            reply += '\nTime Range: + ' + hours[0] + ' - ' + hours[1];
         }
         if (requiredNameFragment) {
             limitTypes.set(LimitType.NAME_FRAGMENT, requiredNameFragment);
@@ -141,6 +189,12 @@ export class SubscribeCommand extends AbstractCommand {
             reply += '\nLY Range to system with name: + ' + LyRangeToSystemWithName;
         }
 
+        const problems = validateLimitTypes(limitTypes, name => sub.isKnownSystemName(name));
+        if (problems.length > 0) {
+            interaction.reply({content: 'The subscription would never post a kill:\n- ' + problems.join('\n- '), ephemeral: true});
+            return;
+        }
+
         // use SubscriptionFlags type
         const flags: SubscriptionFlags = {
             inclusionLimitAlsoComparesAttacker: inclusionLimitComparesAttackers,
@@ -157,7 +211,13 @@ export class SubscribeCommand extends AbstractCommand {
             flags,
             id ? String(id) : undefined,
             minValue ? minValue : 0,
+            interaction.user.id,
         );
+        if (dryRun) {
+            sub.configureSubscription(subCommand, interaction.guildId, interaction.channelId, {dryRun}, id ? String(id) : undefined);
+            reply += '\nDry run: matched kills are ' + (dryRun === DryRunMode.DM ? 'logged and sent to you by direct message' : 'logged') +
+                ' instead of posted, go live with /zkill-configure dry-run off';
+        }
 
         if (id) {
             reply += ' ID: ' + id;
@@ -186,12 +246,12 @@ export class SubscribeCommand extends AbstractCommand {
             )
             .addStringOption(option =>
                 option.setName(this.LIMIT_INCLUDED_SHIP_IDS)
-                    .setDescription('Limit to ship id, comma seperated ids')
+                    .setDescription('Limit to ship id, comma seperated ids or capitals, supers, structures, industrials, blops')
                     .setRequired(false)
             )
             .addStringOption(option =>
                 option.setName(this.LIMIT_EXCLUDED_SHIP_IDS)
-                    .setDescription('Limit to ship id, comma seperated ids')
+                    .setDescription('Limit to ship id, comma seperated ids or capitals, supers, structures, industrials, blops')
                     .setRequired(false)
             )
             .addStringOption(option =>
@@ -234,6 +294,24 @@ export class SubscribeCommand extends AbstractCommand {
                     .setDescription('Limit to a minimum security, exclusive')
                     .setRequired(false)
             )
+            .addStringOption(option =>
+                option.setName(this.LIMIT_SPACE_TYPE)
+                    .setDescription('Limit to a class of space, instead of a security range')
+                    .setRequired(false)
+                    .addChoices(
+                        {name: 'Highsec', value: SpaceType.HIGHSEC},
+                        {name: 'Lowsec', value: SpaceType.LOWSEC},
+                        {name: 'Nullsec', value: SpaceType.NULLSEC},
+                        {name: 'Wormhole', value: SpaceType.WORMHOLE},
+                        {name: 'Pochven', value: SpaceType.POCHVEN},
+                        {name: 'Abyssal', value: SpaceType.ABYSSAL},
+                    )
+            )
+            .addStringOption(option =>
+                option.setName(this.LIMIT_ATTACKER_AVG_SEC_MAX)
+                    .setDescription('Limit to attacking fleets with an average security status at or below this value')
+                    .setRequired(false)
+            )
             .addStringOption(option =>
                 option.setName(this.LIMIT_CONSTELLATION_IDS)
                     .setDescription('Limit to constellation id, comma seperated ids')
@@ -245,13 +323,8 @@ export class SubscribeCommand extends AbstractCommand {
                     .setRequired(false)
             )
             .addStringOption(option =>
-                option.setName(this.LIMIT_TIME_RANGE_START)
-                    .setDescription('Limit to time range start, integer value between 0 - 23 hours')
-                    .setRequired(false)
-            )
-            .addStringOption(option =>
-                option.setName(this.LIMIT_TIME_RANGE_END)
-                    .setDescription('Limit to time range end, integer value between 0 - 23 hours')
+                option.setName(this.LIMIT_TIME_RANGE)
+                    .setDescription('Limit to the hours from start to end, 0 - 23 each, e.g. 18-2')
                     .setRequired(false)
             )
             .addNumberOption(option =>
@@ -289,6 +362,15 @@ export class SubscribeCommand extends AbstractCommand {
                     .setDescription('Enable if attackers should be considered when rejecting mails')
                     .setRequired(false)
             )
+            .addStringOption(option =>
+                option.setName(this.DRY_RUN)
+                    .setDescription('Log the matched kills instead of posting them, to try the filter first')
+                    .setRequired(false)
+                    .addChoices(
+                        {name: 'Only log them', value: DryRunMode.LOG},
+                        {name: 'Log them and send them to me by direct message', value: DryRunMode.DM},
+                    )
+            )
             .setDescription('Subscribe public feed to channel'));
 
         return slashCommand;
@@ -0,0 +1,109 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {LimitType, ZKillSubscriber} from '../zKillSubscriber';
+import {DryRunMode} from '../lib/dryRun';
+
+// Personal subscriptions, delivered as direct messages, usable from any server the bot is in or from a direct message
+export class SubscribeDmCommand extends AbstractCommand {
+    protected name = 'zkill-subscribe-dm';
+
+    protected ID = 'id';
+    protected MIN_VALUE = 'min-value';
+    protected LIMIT_CHARACTER_IDS = 'limit-character-ids';
+    protected LIMIT_CORPORATION_IDS = 'limit-corporation-ids';
+    protected LIMIT_ALLIANCE_IDS = 'limit-alliance-ids';
+    protected LIMIT_REGION_IDS = 'limit-region-ids';
+    protected DRY_RUN = 'dry-run';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        const userId = interaction.user.id;
+        if (!sub.canSubscribeDm(userId)) {
+            interaction.reply({content: 'You reached the maximum number of direct message subscriptions on this instance', ephemeral: true});
+            return;
+        }
+        const id = interaction.options.getNumber(this.ID, true);
+        const minValue = interaction.options.getNumber(this.MIN_VALUE);
+        const limitCharacter = interaction.options.getString(this.LIMIT_CHARACTER_IDS);
+        const limitCorporation = interaction.options.getString(this.LIMIT_CORPORATION_IDS);
+        const limitAlliance = interaction.options.getString(this.LIMIT_ALLIANCE_IDS);
+        const limitRegion = interaction.options.getString(this.LIMIT_REGION_IDS);
+        const dryRun = interaction.options.getString(this.DRY_RUN) as DryRunMode | null;
+
+        let reply = 'Kills of this subscription will be sent to you as direct messages. ID: ' + id;
+        const limitTypes = new Map<LimitType, string>();
+        if (limitCharacter) {
+            limitTypes.set(LimitType.CHARACTER, limitCharacter);
+            reply += '\nCharacter filter: + ' + limitCharacter;
+        }
+        if (limitCorporation) {
+            limitTypes.set(LimitType.CORPORATION, limitCorporation);
+            reply += '\nCorporation filter: + ' + limitCorporation;
+        }
+        if (limitAlliance) {
+            limitTypes.set(LimitType.ALLIANCE, limitAlliance);
+            reply += '\nAlliance filter: + ' + limitAlliance;
+        }
+        if (limitRegion) {
+            limitTypes.set(LimitType.REGION, limitRegion);
+            reply += '\nRegion filter: + ' + limitRegion;
+        }
+        if (minValue) {
+            reply += '\nMin Value: ' + minValue.toLocaleString('en');
+        }
+        if (dryRun) {
+            reply += '\nDry run: matched kills are ' + (dryRun === DryRunMode.DM ? 'logged and sent to you as a link' : 'logged') +
+                ' instead of the full kill, subscribe again without dry run to go live';
+        }
+
+        sub.subscribeDm(userId, limitTypes, String(id), minValue ? minValue : 0, dryRun ?? undefined);
+        interaction.reply({content: reply, ephemeral: true});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Receive kills as direct messages, e.g. the kills and losses of your own characters');
+        slashCommand.addNumberOption(option =>
+            option.setName(this.ID)
+                .setDescription('ID of the subscription, used to unsubscribe')
+                .setRequired(true)
+        );
+        slashCommand.addNumberOption(option =>
+            option.setName(this.MIN_VALUE)
+                .setDescription('Minimum isk to show the entry')
+                .setRequired(false)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.LIMIT_CHARACTER_IDS)
+                .setDescription('Limit to character id, comma seperated ids')
+                .setRequired(false)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.LIMIT_CORPORATION_IDS)
+                .setDescription('Limit to corporation id, comma seperated ids')
+                .setRequired(false)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.LIMIT_ALLIANCE_IDS)
+                .setDescription('Limit to alliance id, comma seperated ids')
+                .setRequired(false)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.LIMIT_REGION_IDS)
+                .setDescription('Limit to region id, comma seperated ids')
+                .setRequired(false)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.DRY_RUN)
+                .setDescription('Log the matched kills instead of sending them, to try the filter first')
+                .setRequired(false)
+                .addChoices(
+                    {name: 'Only log them', value: DryRunMode.LOG},
+                    {name: 'Log them and send me a link to each', value: DryRunMode.DM},
+                )
+        );
+        return slashCommand;
+    }
+
+}
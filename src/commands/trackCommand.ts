@@ -0,0 +1,99 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {IntelEntityType} from '../lib/zkbApi';
+import {newTracking, trackingSubscriptionId} from '../lib/entityTracking';
+
+// Tried in this order for names without a type, a corporation rarely has the exact name of an alliance
+const NAME_TYPES = [IntelEntityType.ALLIANCE, IntelEntityType.CORPORATION, IntelEntityType.CHARACTER];
+
+// ID and exact name of the entity, null if there is none with the name
+async function resolveTrackedEntity(type: IntelEntityType | null, entity: string): Promise<{ type: IntelEntityType, id: number, name: string } | null> {
+    const sub = ZKillSubscriber.getInstance();
+    if (/^\d+$/.test(entity) && type != null) {
+        const id = Number(entity);
+        const name = type === IntelEntityType.ALLIANCE ? sub.getNameForAlliance(id) :
+            type === IntelEntityType.CORPORATION ? sub.getNameForCorporation(id) : sub.getNameForCharacter(id);
+        return {type, id, name: await name.catch(() => entity)};
+    }
+    for (const candidate of type != null ? [type] : NAME_TYPES) {
+        const resolved = await sub.resolveEntity(candidate, entity);
+        if (resolved) {
+            return {type: candidate, ...resolved};
+        }
+    }
+    return null;
+}
+
+export class TrackCommand extends AbstractCommand {
+    protected name = 'zkill-track';
+
+    protected ENTITY = 'entity';
+    protected TYPE = 'type';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            await interaction.reply('Subscription is not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            await interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        if (!sub.canSubscribe(interaction.guildId)) {
+            await interaction.reply({content: 'This server reached the maximum number of subscriptions on this instance', ephemeral: true});
+            return;
+        }
+        const entity = interaction.options.getString(this.ENTITY, true).trim();
+        const type = <IntelEntityType | null>interaction.options.getString(this.TYPE);
+        if (/^\d+$/.test(entity) && type == null) {
+            await interaction.reply({content: 'Pick the type of the entity to track it by ID', ephemeral: true});
+            return;
+        }
+        await interaction.deferReply({ephemeral: true});
+        try {
+            const resolved = await resolveTrackedEntity(type, entity);
+            if (resolved == null) {
+                await interaction.editReply(`There is no ${type ?? 'alliance, corporation or character'} named ${entity}`);
+                return;
+            }
+            const tracking = newTracking(resolved.type, resolved.id, resolved.name);
+            if (!sub.trackEntity(interaction.guildId, interaction.channelId, tracking, interaction.user.id)) {
+                await interaction.editReply(`This channel already tracks ${resolved.name}`);
+                return;
+            }
+            await interaction.editReply(`Tracking ${resolved.name} in this channel as ID: ${trackingSubscriptionId(resolved.id)}\n` +
+                'Its kills are posted green and its losses red, with the ISK destroyed and lost since now in the footer. ' +
+                'Stop with /zkill-untrack');
+        } catch (e) {
+            await interaction.editReply(`Failed to look up ${entity}: ${e}`);
+        }
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Post every kill and loss of a character, corporation or alliance with running ISK totals');
+        slashCommand.addStringOption(option =>
+            option.setName(this.ENTITY)
+                .setDescription('Exact name or ID')
+                .setRequired(true)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.TYPE)
+                .setDescription('Kind of entity, needed for IDs. Names are looked up as alliance, corporation, then character')
+                .setRequired(false)
+                .addChoices(
+                    {name: 'Character', value: IntelEntityType.CHARACTER},
+                    {name: 'Corporation', value: IntelEntityType.CORPORATION},
+                    {name: 'Alliance', value: IntelEntityType.ALLIANCE},
+                )
+        );
+        return slashCommand;
+    }
+}
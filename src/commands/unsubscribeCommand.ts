@@ -1,24 +1,115 @@
 import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
-import {CommandInteraction} from 'discord.js';
+import {CommandInteraction, Interaction, MessageActionRow, MessageButton, MessageSelectMenu} from 'discord.js';
 import {AbstractCommand} from './abstractCommand';
 import {SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {
+    MAX_MENU_OPTIONS,
+    parseUnsubscribeCustomId,
+    UnsubscribeAction,
+    unsubscribeCustomId,
+    unsubscribeOptions,
+    UnsubscribeSessions,
+} from '../lib/unsubscribeMenu';
+
+const sessions = new UnsubscribeSessions();
+
+// Handles the select menu and the confirm and cancel buttons, false if the interaction does not belong to them
+export async function handleUnsubscribeInteraction(interaction: Interaction): Promise<boolean> {
+    if (!interaction.isSelectMenu() && !interaction.isButton()) {
+        return false;
+    }
+    const state = parseUnsubscribeCustomId(interaction.customId);
+    if (state == null) {
+        return false;
+    }
+    const selection = sessions.get(state.sessionId);
+    if (!selection || selection.userId !== interaction.user.id) {
+        await interaction.reply({content: 'This menu expired, open a new one with /zkill-unsubscribe', ephemeral: true});
+        return true;
+    }
+
+    if (interaction.isSelectMenu()) {
+        const ids = interaction.values;
+        sessions.select(state.sessionId, ids);
+        await interaction.update({
+            content: `Remove ${ids.length} subscription${ids.length === 1 ? '' : 's'} from <#${selection.channelId}>: ${ids.join(', ')}?`,
+            components: [new MessageActionRow().addComponents(
+                new MessageButton().setCustomId(unsubscribeCustomId(UnsubscribeAction.CONFIRM, state.sessionId)).setLabel('Remove').setStyle('DANGER'),
+                new MessageButton().setCustomId(unsubscribeCustomId(UnsubscribeAction.CANCEL, state.sessionId)).setLabel('Cancel').setStyle('SECONDARY'),
+            )],
+        });
+        return true;
+    }
+
+    sessions.delete(state.sessionId);
+    if (state.action === UnsubscribeAction.CANCEL || selection.ids.length === 0) {
+        await interaction.update({content: 'Nothing was removed', components: []});
+        return true;
+    }
+    const removed = ZKillSubscriber.getInstance().bulkUnsubscribe(selection.guildId, selection.channelId, selection.ids);
+    let reply = removed.length > 0 ?
+        `Removed ${removed.length} subscriptions from <#${selection.channelId}>: ${removed.join(', ')}` :
+        `No matching subscriptions in <#${selection.channelId}>`;
+    const missing = selection.ids.filter(id => !removed.includes(`${SubscriptionType.PUBLIC}${id}`));
+    if (missing.length > 0) {
+        reply += `\nAlready removed: ${missing.join(', ')}`;
+    }
+    await interaction.update({content: reply, components: []});
+    return true;
+}
 
 export class UnsubscribeCommand extends AbstractCommand {
     protected name = 'zkill-unsubscribe';
 
-    executeCommand(interaction: CommandInteraction): void {
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
         const sub = ZKillSubscriber.getInstance();
         if(!interaction.inGuild()) {
             // @ts-ignore
             interaction.reply('Subscription is not possible in PM!');
             return;
         }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
         const subCommand = interaction.options.getSubcommand(true) as SubscriptionType;
         const id = interaction.options.getString('id', false);
-        sub.unsubscribe(subCommand, interaction.guildId, interaction.channelId, id ? id : undefined);
+        if (id) {
+            const removed = await sub.unsubscribe(subCommand, interaction.guildId, interaction.channelId, id);
+            interaction.reply({
+                content: translate(sub.getGuildLocale(interaction.guildId), removed ? 'command.unsubscribed' : 'command.notSubscribed', {
+                    channel: interaction.options.getSubcommand(),
+                    id,
+                }),
+                ephemeral: true
+            });
+            return;
+        }
+
+        // Without an ID the subscriptions of the channel are offered to pick from, so a typo cannot remove nothing
+        const channel = await sub.listGuildChannelSubscriptions(interaction.guildId, interaction.channelId);
+        const options = channel ? unsubscribeOptions(channel.subscriptions) : [];
+        if (options.length === 0) {
+            interaction.reply({content: 'This channel has no subscriptions', ephemeral: true});
+            return;
+        }
+        const sessionId = sessions.create(interaction.guildId, interaction.channelId, interaction.user.id);
+        let content = 'Pick the subscriptions to remove from this channel, you confirm before they are removed.';
+        const total = channel ? channel.subscriptions.size : 0;
+        if (total > MAX_MENU_OPTIONS) {
+            content += `\nOnly the first ${MAX_MENU_OPTIONS} of ${total} are listed, remove the others by ID or with /zkill-bulk-unsubscribe`;
+        }
         interaction.reply({
-            content: 'Unsubscribed to zkillboard channel: ' + interaction.options.getSubcommand() + ' ' + interaction.options.getString('id'),
-            ephemeral: true
+            content,
+            components: [new MessageActionRow().addComponents(new MessageSelectMenu()
+                .setCustomId(unsubscribeCustomId(UnsubscribeAction.SELECT, sessionId))
+                .setPlaceholder('Subscriptions to remove')
+                .setMinValues(1)
+                .setMaxValues(options.length)
+                .addOptions(options))],
+            ephemeral: true,
         });
     }
 
@@ -29,8 +120,8 @@ export class UnsubscribeCommand extends AbstractCommand {
             .setDescription('Unsubscribe feed from channel')
             .addStringOption(option =>
                 option.setName('id')
-                    .setDescription('ID for the feed')
-                    .setRequired(true)
+                    .setDescription('ID for the feed, pick from the subscriptions of the channel if empty')
+                    .setRequired(false)
             ));
         return slashCommand;
 
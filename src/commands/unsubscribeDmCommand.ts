@@ -0,0 +1,31 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+
+export class UnsubscribeDmCommand extends AbstractCommand {
+    protected name = 'zkill-unsubscribe-dm';
+
+    protected ID = 'id';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        const id = interaction.options.getNumber(this.ID, true);
+        const reply = sub.unsubscribeDm(interaction.user.id, String(id)) ?
+            'Unsubscribed from direct message subscription ID: ' + id :
+            'You have no direct message subscription with ID ' + id;
+        interaction.reply({content: reply, ephemeral: true});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Stop receiving the kills of a subscription as direct messages');
+        slashCommand.addNumberOption(option =>
+            option.setName(this.ID)
+                .setDescription('ID of the subscription')
+                .setRequired(true)
+        );
+        return slashCommand;
+    }
+
+}
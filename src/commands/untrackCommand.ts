@@ -0,0 +1,46 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+
+export class UntrackCommand extends AbstractCommand {
+    protected name = 'zkill-untrack';
+
+    protected ENTITY = 'entity';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            await interaction.reply('Subscription is not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            await interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const entity = interaction.options.getString(this.ENTITY, true);
+        const tracking = await sub.untrackEntity(interaction.guildId, interaction.channelId, entity);
+        await interaction.reply({
+            content: tracking ?
+                `Stopped tracking ${tracking.name}, ${sub.abbreviateNumber(tracking.iskDestroyed)} ISK destroyed and ` +
+                `${sub.abbreviateNumber(tracking.iskLost)} ISK lost since ${tracking.since.substring(0, 10)}` :
+                `This channel does not track ${entity}`,
+            ephemeral: true,
+        });
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Stop posting the kills of an entity tracked with /zkill-track in this channel');
+        slashCommand.addStringOption(option =>
+            option.setName(this.ENTITY)
+                .setDescription('Name or ID of the tracked entity')
+                .setRequired(true)
+        );
+        return slashCommand;
+    }
+}
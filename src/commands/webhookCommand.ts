@@ -0,0 +1,111 @@
+import {SlashCommandBuilder, SlashCommandSubcommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {SubscriptionType, ZKillSubscriber} from '../zKillSubscriber';
+import {translate} from '../lib/i18n';
+import {canManageSubscriptions} from '../lib/permissions';
+import {redactWebhookUrl, Webhook, webhookKind, WebhookKind} from '../lib/webhooks';
+
+export class WebhookCommand extends AbstractCommand {
+    protected name = 'zkill-webhook';
+
+    protected ID = 'id';
+    protected URL = 'url';
+    protected SECRET = 'secret';
+    protected ONLY = 'only';
+
+    executeCommand(interaction: CommandInteraction): void {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            interaction.reply('Webhooks are not possible in PM!');
+            return;
+        }
+        if (!canManageSubscriptions(interaction, sub.getGuildSettings(interaction.guildId))) {
+            interaction.reply({content: translate(sub.getGuildLocale(interaction.guildId), 'command.notPermitted'), ephemeral: true});
+            return;
+        }
+        const id = String(interaction.options.getNumber(this.ID, true));
+        const url = interaction.options.getString(this.URL, true).trim();
+
+        if (interaction.options.getSubcommand(true) === 'remove') {
+            const removed = sub.removeWebhook(SubscriptionType.PUBLIC, interaction.guildId, interaction.channelId, url, id);
+            interaction.reply({
+                content: removed ?
+                    `Subscription ID: ${id} no longer posts to ${redactWebhookUrl(url)}` :
+                    `Subscription ID: ${id} in this channel does not post to this webhook`,
+                ephemeral: true,
+            });
+            return;
+        }
+
+        const kind = webhookKind(url);
+        if (kind == null) {
+            interaction.reply({content: 'Webhooks have to be HTTPS URLs', ephemeral: true});
+            return;
+        }
+        const webhook: Webhook = {url, kind};
+        let reply = `Subscription ID: ${id} now also posts to the ${kind === WebhookKind.DISCORD ? 'Discord' : 'JSON'} webhook ${redactWebhookUrl(url)}`;
+        const secret = interaction.options.getString(this.SECRET);
+        if (secret) {
+            if (kind === WebhookKind.DISCORD) {
+                interaction.reply({content: 'Discord webhooks are not signed, leave the secret empty', ephemeral: true});
+                return;
+            }
+            webhook.secret = secret;
+            reply += '\nThe body is signed with HMAC-SHA256 in the X-Signature-256 header';
+        }
+        const only = interaction.options.getBoolean(this.ONLY) ?? false;
+        if (only) {
+            reply += '\nKills are no longer posted in this channel';
+        }
+        if (!sub.setWebhook(SubscriptionType.PUBLIC, interaction.guildId, interaction.channelId, webhook, only, id)) {
+            reply = 'No subscription with ID ' + id + ' found in this channel';
+        }
+        interaction.reply({content: reply, ephemeral: true});
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Post the kills of a subscription in this channel to a webhook');
+
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('add')
+            .addNumberOption(option =>
+                option.setName(this.ID)
+                    .setDescription('ID of the public feed in this channel')
+                    .setRequired(true)
+            )
+            .addStringOption(option =>
+                option.setName(this.URL)
+                    .setDescription('Discord webhook URL, or any HTTPS URL to receive the killmail as JSON')
+                    .setRequired(true)
+            )
+            .addStringOption(option =>
+                option.setName(this.SECRET)
+                    .setDescription('Key to sign the JSON body with, for generic webhooks')
+                    .setRequired(false)
+            )
+            .addBooleanOption(option =>
+                option.setName(this.ONLY)
+                    .setDescription('Only post to the webhooks, not to this channel')
+                    .setRequired(false)
+            )
+            .setDescription('Post the kills of a subscription to a webhook too'));
+        slashCommand.addSubcommand(new SlashCommandSubcommandBuilder().setName('remove')
+            .addNumberOption(option =>
+                option.setName(this.ID)
+                    .setDescription('ID of the public feed in this channel')
+                    .setRequired(true)
+            )
+            .addStringOption(option =>
+                option.setName(this.URL)
+                    .setDescription('URL of the webhook')
+                    .setRequired(true)
+            )
+            .setDescription('Stop posting the kills of a subscription to a webhook'));
+
+        return slashCommand;
+    }
+
+}
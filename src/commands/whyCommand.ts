@@ -0,0 +1,84 @@
+import {SlashCommandBuilder} from '@discordjs/builders';
+import {CommandInteraction} from 'discord.js';
+import {AbstractCommand} from './abstractCommand';
+import {parseKillId} from './previewCommand';
+import {SubscriptionEvaluation, SubscriptionType, ZkData, ZKillSubscriber} from '../zKillSubscriber';
+
+// One line per evaluated filter, the last failing line is the filter that rejected the kill
+export function formatTrace(killId: number, ident: string, evaluation: SubscriptionEvaluation): string {
+    let text = `Kill ${killId} against ${ident}:\n`;
+    for (const entry of evaluation.trace) {
+        text += `${entry.passed ? '✅' : '❌'} ${entry.filter}: ${entry.detail}\n`;
+    }
+    if (evaluation.matched) {
+        text += 'Would be posted';
+    } else if (evaluation.trace.every(entry => entry.passed)) {
+        text += `Not posted: ${evaluation.rejectedBy}`;
+    } else {
+        text += `Rejected by ${evaluation.rejectedBy}`;
+    }
+    return text;
+}
+
+export class WhyCommand extends AbstractCommand {
+    protected name = 'zkill-why';
+
+    protected KILL = 'kill';
+    protected ID = 'id';
+    protected HASH = 'hash';
+
+    async executeCommand(interaction: CommandInteraction): Promise<void> {
+        const sub = ZKillSubscriber.getInstance();
+        if (!interaction.inGuild()) {
+            // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+            // @ts-ignore
+            await interaction.reply('Explaining kills is not possible in PM!');
+            return;
+        }
+        const killId = parseKillId(interaction.options.getString(this.KILL, true));
+        if (killId == null) {
+            await interaction.reply({content: 'Not a kill ID or zKillboard kill link', ephemeral: true});
+            return;
+        }
+        const ident = `${SubscriptionType.PUBLIC}${interaction.options.getString(this.ID, true)}`;
+        const channel = await sub.listGuildChannelSubscriptions(interaction.guildId, interaction.channelId);
+        const subscription = channel?.subscriptions.get(ident);
+        if (!subscription) {
+            await interaction.reply({content: `There is no subscription ${ident} in this channel`, ephemeral: true});
+            return;
+        }
+        await interaction.deferReply({ephemeral: true});
+
+        let data: ZkData;
+        try {
+            data = await sub.fetchKill(killId, interaction.options.getString(this.HASH) ?? undefined);
+        } catch (e) {
+            await interaction.editReply(`Failed to fetch kill ${killId}: ${e}`);
+            return;
+        }
+        const evaluation = await sub.evaluateSubscription(subscription, data, sub.createKillContext(data), interaction.guildId);
+        await interaction.editReply(formatTrace(killId, ident, evaluation));
+    }
+
+    getCommand(): SlashCommandBuilder {
+        const slashCommand = new SlashCommandBuilder().setName(this.name)
+            .setDescription('Explain filter by filter why a subscription in this channel did or did not post a kill');
+        slashCommand.addStringOption(option =>
+            option.setName(this.KILL)
+                .setDescription('zKillboard link or kill ID')
+                .setRequired(true)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.ID)
+                .setDescription('ID of the public feed')
+                .setRequired(true)
+        );
+        slashCommand.addStringOption(option =>
+            option.setName(this.HASH)
+                .setDescription('Killmail hash, looked up on zKillboard if empty')
+                .setRequired(false)
+        );
+        return slashCommand;
+    }
+
+}
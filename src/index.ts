@@ -1,39 +1,120 @@
 // Require the necessary discord.js classes
 import {Client, Intents} from 'discord.js';
 import {registerCommands} from './commands/deployCommands';
+import {sendOnboardingWizard} from './commands/onboardingWizard';
 import {ZKillSubscriber} from './zKillSubscriber';
+import {ConfigLintExitCode, lintConfig, reportConfigLint} from './lib/configLint';
+import {LeaderLease} from './lib/leaderLease';
+import {logger} from './lib/logger';
+import {runMigration} from './lib/configMigration';
+import {runEval} from './lib/offlineEval';
+import {runPreload} from './lib/cachePreload';
 
 process.setMaxListeners(100);
 
-// Create a new client instance
-const client = new Client({intents: [Intents.FLAGS.GUILDS]});
+// Converts the configs of older versions into the config directory and exits, see README
+const migrateIndex = process.argv.indexOf('--migrate');
+if (migrateIndex !== -1) {
+    process.exit(runMigration(process.argv[migrateIndex + 1], process.argv[migrateIndex + 2]));
+}
 
-registerCommands(client);
-const sub = ZKillSubscriber.getInstance(client)
-    .withConfig()
-    .withSystems()
-    .withShips()
-    .withNames();
+function start(lease: LeaderLease | null) {
+    // Create a new client instance
+    const client = new Client({intents: [Intents.FLAGS.GUILDS]});
 
-// When the client is ready, run this code (only once)
-client.once('ready', () => {
-    console.log(`Ready on ${client.guilds.cache.size} servers!`);
-});
+    registerCommands(client);
+    const sub = ZKillSubscriber.getInstance(client)
+        .withConfig()
+        .withDisabledPurge()
+        .withDmSubscriptions()
+        .withSystems()
+        .withShips()
+        .withCategories()
+        .withNames()
+        .withTickers()
+        .withSubscriptionStats()
+        .withSde()
+        .withStarMap()
+        .withMarketPrices()
+        .withReports()
+        .withFeedServer()
+        .withHealthCheck();
 
-client.on('guildDelete', guild => {
-    if (guild.name === undefined) return;
-    sub.unsubscribeGuild(guild.id);
+    // When the client is ready, run this code (only once)
+    client.once('ready', () => {
+        console.log(`Ready on ${client.guilds.cache.size} servers!`);
+        sub.resolveAllShipGroups().catch(console.error);
+        sub.reconcileChannels()
+            .then(count => logger.warn(`${count} subscribed channels cannot be posted in`))
+            .catch(console.error);
+    });
 
-    console.log(`Got kicked from a Server!\n- Name: ${guild.name}\n- Member Count: ${guild.memberCount}\nI'm now in ${client.guilds.cache.size} Servers!`);
-});
+    client.on('guildDelete', guild => {
+        if (guild.name === undefined) return;
+        sub.unsubscribeGuild(guild.id);
 
-//joined a server
-client.on('guildCreate', guild => {
-    if (guild.name === undefined) return;
+        console.log(`Got kicked from a Server!\n- Name: ${guild.name}\n- Member Count: ${guild.memberCount}\nI'm now in ${client.guilds.cache.size} Servers!`);
+    });
 
-    console.log(`Joined new Server!\n- Name: ${guild.name}\n- Member Count: ${guild.memberCount}\nI'm now in ${client.guilds.cache.size} Servers!`);
-    //Your other stuff like adding to guildArray
-});
+    //joined a server
+    client.on('guildCreate', guild => {
+        if (guild.name === undefined) return;
 
-// Login to Discord with your client's token
-client.login(process.env.DISCORD_BOT_TOKEN);
+        console.log(`Joined new Server!\n- Name: ${guild.name}\n- Member Count: ${guild.memberCount}\nI'm now in ${client.guilds.cache.size} Servers!`);
+        sendOnboardingWizard(guild).catch(e => logger.error(`Failed to send the setup wizard to ${guild.name}: ${e}`));
+    });
+
+    // Write the pending cache changes before exiting
+    for (const signal of ['SIGINT', 'SIGTERM']) {
+        process.once(signal, () => {
+            sub.flushCaches();
+            lease?.release();
+            process.exit(0);
+        });
+    }
+
+    // Login to Discord with your client's token
+    client.login(process.env.DISCORD_BOT_TOKEN);
+
+    // A replica that took over posts from now on, this one stops before both post the same kills
+    lease?.keepRenewing(() => {
+        logger.error('lost the leader lease, exiting');
+        sub.flushCaches();
+        process.exit(1);
+    });
+}
+
+function main() {
+    // Validate the configuration before touching Discord, so broken deployments fail fast
+    const lintExitCode = reportConfigLint(lintConfig());
+    if (lintExitCode !== ConfigLintExitCode.OK) {
+        process.exit(lintExitCode);
+    }
+    if (process.argv.includes('--check-only')) {
+        process.exit(ConfigLintExitCode.OK);
+    }
+
+    // With HA_LEASE_FILE set the replicas wait as standby until they hold the lease, so only one receives and posts kills
+    const lease = LeaderLease.fromEnv();
+    if (lease == null) {
+        start(null);
+    } else {
+        logger.info(`waiting for the leader lease ${lease.file} as ${lease.instanceId}`);
+        lease.waitForLeadership().then(() => {
+            logger.info(`holding the leader lease ${lease.file}`);
+            start(lease);
+        });
+    }
+}
+
+// Evaluates subscriptions against a kill file without Discord or network and exits, see README
+const evalIndex = process.argv.indexOf('--eval');
+if (evalIndex !== -1) {
+    runEval(process.argv[evalIndex + 1], process.argv[evalIndex + 2], process.argv[evalIndex + 3])
+        .then(exitCode => process.exit(exitCode));
+} else if (process.argv.includes('--preload')) {
+    // Fills the lookup caches from the static data dumps and exits, see README
+    runPreload(process.argv[process.argv.indexOf('--preload') + 1]).then(ok => process.exit(ok ? 0 : 1));
+} else {
+    main();
+}
@@ -0,0 +1,67 @@
+import {ZkData} from '../zKillSubscriber';
+
+export interface Battle {
+    channelId: string;
+    systemId: number;
+    kills: ZkData[];
+    // Time of the latest kill, the battle ends once no kill happened for the window
+    lastKillTime: number;
+    reported: boolean;
+    // Thread the kills after the summary are posted into, if enabled
+    threadId?: string;
+}
+
+const DEFAULT_WINDOW_MS = 15 * 60 * 1000;
+
+// Groups kills posted to a channel that happen in the same system within a rolling time window
+export class BattleDetector {
+    private windowMs: number;
+    // Mapping of `${channelId}_${systemId}` to the running battle
+    private battles = new Map<string, Battle>();
+
+    constructor(windowMs = DEFAULT_WINDOW_MS) {
+        this.windowMs = windowMs;
+    }
+
+    // Adds the kill to the battle of its system and returns the battle
+    add(channelId: string, data: ZkData): Battle {
+        const key = `${channelId}_${data.solar_system_id}`;
+        const killTime = new Date(data.killmail_time).getTime();
+        this.prune(killTime);
+        let battle = this.battles.get(key);
+        if (!battle) {
+            battle = {channelId, systemId: data.solar_system_id, kills: [], lastKillTime: killTime, reported: false};
+            this.battles.set(key, battle);
+        }
+        if (!battle.kills.some(kill => kill.killmail_id === data.killmail_id)) {
+            battle.kills.push(data);
+        }
+        battle.lastKillTime = Math.max(battle.lastKillTime, killTime);
+        return battle;
+    }
+
+    get(channelId: string, systemId: number): Battle | undefined {
+        return this.battles.get(`${channelId}_${systemId}`);
+    }
+
+    private prune(now: number) {
+        Array.from(this.battles.entries()).forEach(([key, battle]) => {
+            if (now - battle.lastKillTime > this.windowMs) {
+                this.battles.delete(key);
+            }
+        });
+    }
+}
+
+// Link to the related kills on br.evetools.org of a system, starting at the hour of the given time
+export function strRelatedKills(systemId: number, time: Date): string {
+    const pad = (n: number) => ('0' + n).slice(-2);
+    const hour = `${time.getUTCFullYear()}${pad(time.getUTCMonth() + 1)}${pad(time.getUTCDate())}${pad(time.getUTCHours())}00`;
+    return `https://br.evetools.org/related/${systemId}/${hour}`;
+}
+
+// Link to the related kills on br.evetools.org, starting at the hour of the first kill
+export function strBattleReport(battle: Battle): string {
+    const first = new Date(Math.min(...battle.kills.map(kill => new Date(kill.killmail_time).getTime())));
+    return strRelatedKills(battle.systemId, first);
+}
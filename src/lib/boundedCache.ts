@@ -0,0 +1,87 @@
+// Lookups and evictions of a cache since the start, shown by /zkill-diag
+export interface CacheStats {
+    size: number;
+    hits: number;
+    misses: number;
+    evictions: number;
+}
+
+interface CacheEntry<V> {
+    value: V;
+    storedAt: number;
+}
+
+// Share of the lookups answered from the cache, null before the first lookup
+export function hitRate(stats: CacheStats): number | null {
+    const lookups = stats.hits + stats.misses;
+    return lookups > 0 ? stats.hits / lookups : null;
+}
+
+// Map with a maximum size evicting the least recently used entries, and optionally entries older than the TTL, so
+// the lookups of months of kills do not grow the memory and the cache files without bound
+export class BoundedCache<K, V> {
+    readonly maxSize: number;
+    // 0 keeps entries until they are evicted for space
+    readonly ttlMs: number;
+    // Insertion order is the recency order, the least recently used entry comes first
+    private entries = new Map<K, CacheEntry<V>>();
+    private hits = 0;
+    private misses = 0;
+    private evictions = 0;
+
+    constructor(maxSize: number, ttlMs = 0) {
+        this.maxSize = Math.max(1, maxSize);
+        this.ttlMs = ttlMs;
+    }
+
+    get size(): number {
+        return this.entries.size;
+    }
+
+    // Counts as a hit or miss and marks the entry as recently used
+    get(key: K, now = Date.now()): V | undefined {
+        const entry = this.entries.get(key);
+        if (entry && this.ttlMs > 0 && now - entry.storedAt > this.ttlMs) {
+            this.entries.delete(key);
+            this.evictions++;
+        } else if (entry) {
+            this.entries.delete(key);
+            this.entries.set(key, entry);
+            this.hits++;
+            return entry.value;
+        }
+        this.misses++;
+        return undefined;
+    }
+
+    set(key: K, value: V, now = Date.now()) {
+        this.entries.delete(key);
+        this.entries.set(key, {value, storedAt: now});
+        while (this.entries.size > this.maxSize) {
+            this.entries.delete(this.entries.keys().next().value);
+            this.evictions++;
+        }
+    }
+
+    delete(key: K): boolean {
+        return this.entries.delete(key);
+    }
+
+    // All entries from the least to the most recently used, without counting lookups
+    toArray(): [K, V][] {
+        const entries: [K, V][] = [];
+        this.entries.forEach((entry, key) => entries.push([key, entry.value]));
+        return entries;
+    }
+
+    // Content of the cache file, evicted entries are left out
+    toJson(): string {
+        const object: { [key: string]: V } = {};
+        this.entries.forEach((entry, key) => object[String(key)] = entry.value);
+        return JSON.stringify(object);
+    }
+
+    stats(): CacheStats {
+        return {size: this.entries.size, hits: this.hits, misses: this.misses, evictions: this.evictions};
+    }
+}
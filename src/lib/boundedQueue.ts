@@ -0,0 +1,50 @@
+// FIFO queue with a fixed capacity connecting the kill ingestion with the delivery workers
+export class BoundedQueue<T> {
+    readonly capacity: number;
+    private items: T[] = [];
+    // Consumers waiting for an item and producers waiting for free space
+    private takers: ((item: T) => void)[] = [];
+    private pushers: (() => void)[] = [];
+
+    constructor(capacity: number) {
+        this.capacity = Math.max(1, capacity);
+    }
+
+    get size(): number {
+        return this.items.length;
+    }
+
+    // Adds the item if there is space, returns false if the queue is full
+    tryPush(item: T): boolean {
+        const taker = this.takers.shift();
+        if (taker) {
+            taker(item);
+            return true;
+        }
+        if (this.items.length >= this.capacity) {
+            return false;
+        }
+        this.items.push(item);
+        return true;
+    }
+
+    // Waits until there is space for the item
+    async push(item: T): Promise<void> {
+        while (!this.tryPush(item)) {
+            await new Promise<void>(resolve => this.pushers.push(resolve));
+        }
+    }
+
+    // Waits until an item is available
+    async take(): Promise<T> {
+        if (this.items.length > 0) {
+            const item = <T>this.items.shift();
+            const pusher = this.pushers.shift();
+            if (pusher) {
+                pusher();
+            }
+            return item;
+        }
+        return new Promise<T>(resolve => this.takers.push(resolve));
+    }
+}
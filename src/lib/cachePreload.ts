@@ -0,0 +1,87 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {Axios} from 'axios';
+import {
+    loadSde,
+    loadSdeSystems,
+    SDE_CONSTELLATIONS_FILE,
+    SDE_GROUPS_FILE,
+    SDE_REGIONS_FILE,
+    SDE_SYSTEMS_FILE,
+    SDE_TYPES_FILE,
+} from './sde';
+import {logger} from './logger';
+
+export const FUZZWORK_CSV_URL = 'https://www.fuzzwork.co.uk/dump/latest/csv/';
+
+// Dumps the caches are preloaded from
+export const PRELOAD_FILES = [SDE_TYPES_FILE, SDE_GROUPS_FILE, SDE_SYSTEMS_FILE, SDE_CONSTELLATIONS_FILE, SDE_REGIONS_FILE];
+
+// Number of entries taken from the dumps per cache file
+export interface PreloadResult {
+    systems: number;
+    ships: number;
+    categories: number;
+    names: number;
+}
+
+// Downloads the dumps missing in the directory from fuzzwork, returns the downloaded files
+export async function downloadSdeDumps(dir: string, axios = new Axios({timeout: 120000})): Promise<string[]> {
+    const downloaded: string[] = [];
+    for (const file of PRELOAD_FILES) {
+        if (fs.existsSync(path.join(dir, file))) {
+            continue;
+        }
+        const response = await axios.get(FUZZWORK_CSV_URL + file);
+        if (response.status !== 200) {
+            throw new Error(`failed to download ${file}: HTTP ${response.status}`);
+        }
+        fs.writeFileSync(path.join(dir, file), response.data, 'utf8');
+        downloaded.push(file);
+    }
+    return downloaded;
+}
+
+// Writes the entries over the cache file, entries of the file the dumps do not cover, like character names, are kept
+function mergeCacheFile<V>(file: string, entries: Map<number, V>): number {
+    let cached: { [id: string]: V } = {};
+    if (fs.existsSync(file)) {
+        try {
+            cached = JSON.parse(fs.readFileSync(file, 'utf8'));
+        } catch (e) {
+            logger.error(`${file}: failed to parse, it is rebuilt from the dumps`);
+        }
+    }
+    entries.forEach((value, id) => cached[String(id)] = value);
+    fs.writeFileSync(file, JSON.stringify(cached), 'utf8');
+    return entries.size;
+}
+
+// Fills systems.json, ships.json, categories.json and names.json from the dumps in one pass, so a fresh deployment
+// does not look up every type and system of its first kills on ESI
+export function preloadCaches(sdeDir: string, base_dir = './config/'): PreloadResult {
+    const sde = loadSde(sdeDir);
+    return {
+        systems: mergeCacheFile(path.join(base_dir, 'systems.json'), loadSdeSystems(sdeDir)),
+        ships: mergeCacheFile(path.join(base_dir, 'ships.json'), sde.typeGroups),
+        categories: mergeCacheFile(path.join(base_dir, 'categories.json'), sde.groupCategories),
+        names: mergeCacheFile(path.join(base_dir, 'names.json'), sde.typeNames),
+    };
+}
+
+// Downloads the missing dumps into the directory and preloads the caches from them, false if that failed
+export async function runPreload(sdeDir = process.env.SDE_DIR || './config/', base_dir = './config/'): Promise<boolean> {
+    try {
+        const downloaded = await downloadSdeDumps(sdeDir);
+        if (downloaded.length > 0) {
+            logger.info(`downloaded ${downloaded.join(', ')} to ${sdeDir}`);
+        }
+        const result = preloadCaches(sdeDir, base_dir);
+        logger.info(`preloaded ${result.systems} systems, ${result.ships} ship groups, ${result.categories} categories ` +
+            `and ${result.names} names into ${base_dir}`);
+        return true;
+    } catch (e) {
+        logger.error(`failed to preload the caches: ${e}`);
+        return false;
+    }
+}
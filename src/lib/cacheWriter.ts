@@ -0,0 +1,51 @@
+import * as fs from 'fs';
+import {logger} from './logger';
+
+// Writes cache files in the background. A cache miss only marks its file dirty, the file is written at most once per
+// interval with the latest content, so processing a kill never waits for the whole map to be serialized to disk.
+export class CacheWriter {
+    readonly intervalMs: number;
+    // Mapping of file path to the serializer of its latest content
+    private dirty = new Map<string, () => string>();
+    private timer: ReturnType<typeof setTimeout> | null = null;
+    private writeFile: (file: string, content: string) => void;
+
+    constructor(intervalMs = 30000, writeFile = (file: string, content: string) => fs.writeFileSync(file, content, 'utf8')) {
+        this.intervalMs = intervalMs;
+        this.writeFile = writeFile;
+    }
+
+    markDirty(file: string, serialize: () => string) {
+        this.dirty.set(file, serialize);
+        if (this.timer == null) {
+            this.timer = setTimeout(() => this.flush(), this.intervalMs);
+            // Pending writes do not keep the process alive, shutdown flushes them
+            this.timer.unref?.();
+        }
+    }
+
+    // Drops the pending write of the file, e.g. once it was written right away or deleted
+    discard(file: string) {
+        this.dirty.delete(file);
+    }
+
+    isDirty(file: string): boolean {
+        return this.dirty.has(file);
+    }
+
+    flush() {
+        if (this.timer != null) {
+            clearTimeout(this.timer);
+            this.timer = null;
+        }
+        const dirty = Array.from(this.dirty.entries());
+        this.dirty.clear();
+        for (const [file, serialize] of dirty) {
+            try {
+                this.writeFile(file, serialize());
+            } catch (e) {
+                logger.error(`failed to write ${file}: ${e}`);
+            }
+        }
+    }
+}
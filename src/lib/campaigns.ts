@@ -0,0 +1,78 @@
+import {ZkData} from '../zKillSubscriber';
+
+// Running totals of the kills a campaign subscription posted, e.g. during a war or a deployment
+export interface Campaign {
+    channelId: string;
+    // ID of the subscription posting the kills, removed when the campaign is stopped
+    subscriptionId: string;
+    // Alliance and corporation IDs of the own side, their losses count as losses and their pilots as participants
+    friendlyIds: number[];
+    // ISO timestamps, the totals are frozen once stoppedAt is set
+    startedAt: string;
+    stoppedAt?: string;
+    kills: number;
+    losses: number;
+    iskDestroyed: number;
+    iskLost: number;
+    // Mapping of the character IDs of the own pilots to the number of kills they were on
+    participants: { [characterId: string]: number };
+}
+
+export type Campaigns = { [name: string]: Campaign };
+
+export function campaignSubscriptionId(name: string): string {
+    return 'campaign-' + name;
+}
+
+export function newCampaign(channelId: string, name: string, friendlyIds: number[], now = new Date()): Campaign {
+    return {
+        channelId,
+        subscriptionId: campaignSubscriptionId(name),
+        friendlyIds,
+        startedAt: now.toISOString(),
+        kills: 0,
+        losses: 0,
+        iskDestroyed: 0,
+        iskLost: 0,
+        participants: {},
+    };
+}
+
+function isFriendly(campaign: Campaign, entity: { corporation_id?: number, alliance_id?: number }): boolean {
+    return (entity.corporation_id != null && campaign.friendlyIds.includes(entity.corporation_id)) ||
+        (entity.alliance_id != null && campaign.friendlyIds.includes(entity.alliance_id));
+}
+
+// Adds the kill to the totals, unless the campaign is stopped
+export function recordCampaignKill(campaign: Campaign, data: ZkData) {
+    if (campaign.stoppedAt) {
+        return;
+    }
+    if (isFriendly(campaign, data.victim)) {
+        campaign.losses++;
+        campaign.iskLost += data.zkb.totalValue;
+        return;
+    }
+    campaign.kills++;
+    campaign.iskDestroyed += data.zkb.totalValue;
+    data.attackers
+        .filter(attacker => attacker.character_id != null && isFriendly(campaign, attacker))
+        .forEach(attacker => {
+            const key = String(attacker.character_id);
+            campaign.participants[key] = (campaign.participants[key] ?? 0) + 1;
+        });
+}
+
+// Share of the destroyed ISK in percent, null before the first kill or loss
+export function iskEfficiency(campaign: Campaign): number | null {
+    const total = campaign.iskDestroyed + campaign.iskLost;
+    return total > 0 ? Math.round(campaign.iskDestroyed / total * 1000) / 10 : null;
+}
+
+// Character IDs of the pilots on the most kills, most first
+export function topParticipants(campaign: Campaign, count: number): { characterId: number, kills: number }[] {
+    return Object.keys(campaign.participants)
+        .map(key => ({characterId: Number(key), kills: campaign.participants[key]}))
+        .sort((a, b) => b.kills - a.kills)
+        .slice(0, count);
+}
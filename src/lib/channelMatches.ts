@@ -0,0 +1,40 @@
+import {escalationMention, SubscriptionSettings} from '../zKillSubscriber';
+
+// A subscription of a guild channel that matched a kill
+export interface ChannelMatch {
+    guildId: string;
+    channelId: string;
+}
+
+// Groups the matches by guild channel, in the order the channels first matched. Unless `separate` is set the
+// subscriptions of a channel share one message, with it every match is a group of its own
+export function groupByChannel<T extends ChannelMatch>(matches: T[], separate: (guildId: string) => boolean): T[][] {
+    const groups = new Map<string, T[]>();
+    const result: T[][] = [];
+    matches.forEach(match => {
+        if (separate(match.guildId)) {
+            result.push([match]);
+            return;
+        }
+        const key = `${match.guildId}/${match.channelId}`;
+        let group = groups.get(key);
+        if (!group) {
+            group = [];
+            groups.set(key, group);
+            result.push(group);
+        }
+        group.push(match);
+    });
+    return result;
+}
+
+// Every distinct ping of the subscriptions sharing a message, @here covers the roles
+export function mergedMention(subscriptions: SubscriptionSettings[], totalValue: number): string | null {
+    const mentions = subscriptions
+        .map(subscription => escalationMention(subscription, totalValue))
+        .filter((mention, index, all): mention is string => mention != null && all.indexOf(mention) === index);
+    if (mentions.includes('@here')) {
+        return '@here';
+    }
+    return mentions.length > 0 ? mentions.join(' ') : null;
+}
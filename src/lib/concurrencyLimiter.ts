@@ -0,0 +1,36 @@
+// Runs at most `limit` tasks at the same time, further tasks wait by priority, highest first, then in order of arrival
+export class ConcurrencyLimiter {
+    readonly limit: number;
+    private running = 0;
+    private queue: { priority: number, resolve: () => void }[] = [];
+
+    constructor(limit: number) {
+        this.limit = Math.max(1, limit);
+    }
+
+    async run<T>(task: () => Promise<T>, priority = 0): Promise<T> {
+        if (this.running >= this.limit) {
+            // The finishing task hands its slot over, so running is not incremented here
+            await new Promise<void>(resolve => {
+                const index = this.queue.findIndex(waiting => waiting.priority < priority);
+                this.queue.splice(index >= 0 ? index : this.queue.length, 0, {priority, resolve});
+            });
+        } else {
+            this.running++;
+        }
+        try {
+            return await task();
+        } finally {
+            const next = this.queue.shift();
+            if (next) {
+                next.resolve();
+            } else {
+                this.running--;
+            }
+        }
+    }
+
+    get pending(): number {
+        return this.queue.length;
+    }
+}
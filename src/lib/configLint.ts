@@ -0,0 +1,281 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {CelestialKind, LimitType, parseHexColor, ShipCategory, SpaceType} from '../zKillSubscriber';
+import {Locale} from './i18n';
+import {parseLyRange} from './jumpRange';
+import {parseValueRange} from './marketPrices';
+import {parseGangSizeClasses} from './gangSize';
+import {parseNameScopes} from './nameFragment';
+import {parseSecStatusRange} from './securityStatus';
+import {parseTechLevels} from './techLevel';
+import {parsePerspective} from './perspective';
+import {parseExchangeOutcome} from './exchangeOutcome';
+import {SDE_TYPES_FILE} from './sde';
+import {parseEncryptionKey} from './tokenStore';
+import {logger} from './logger';
+
+export enum ConfigLintExitCode {
+    OK = 0,
+    INVALID_ENVIRONMENT = 10,
+    INVALID_SUBSCRIPTIONS = 11,
+    STORAGE_UNAVAILABLE = 12,
+}
+
+export interface ConfigLintIssue {
+    // Warnings are reported, but do not prevent the bot from starting
+    fatal: boolean;
+    exitCode: ConfigLintExitCode;
+    message: string;
+}
+
+// Limit types holding a comma separated list of numeric IDs
+const ID_LIST_LIMIT_TYPES = [
+    LimitType.REGION,
+    LimitType.CONSTELLATION,
+    LimitType.SYSTEM,
+    LimitType.SHIP_INCLUSION_TYPE_ID,
+    LimitType.SHIP_EXCLUSION_TYPE_ID,
+    LimitType.ALLIANCE,
+    LimitType.CORPORATION,
+    LimitType.CHARACTER,
+    LimitType.FACTION,
+    LimitType.VICTIM_ENTITY,
+    LimitType.EXCLUDED_ALLIANCE,
+    LimitType.EXCLUDED_CORPORATION,
+    LimitType.EXCLUDED_SHIP_GROUP,
+];
+
+// Limit types holding a single number
+const NUMERIC_LIMIT_TYPES = [
+    LimitType.SECURITY_MAX_INCLUSIVE,
+    LimitType.SECURITY_MIN_INCLUSIVE,
+    LimitType.SECURITY_MAX_EXCLUSIVE,
+    LimitType.SECURITY_MIN_EXCLUSIVE,
+    LimitType.MIN_NUM_INVOLVED,
+    LimitType.TIME_RANGE_START,
+    LimitType.TIME_RANGE_END,
+    LimitType.ATTACKER_AVG_SEC_STATUS_MAX,
+    LimitType.MIN_ENTITY_ATTACKERS,
+];
+
+const CACHE_FILES = ['systems.json', 'ships.json', 'categories.json', 'names.json', 'tickers.json'];
+
+function fatal(exitCode: ConfigLintExitCode, message: string): ConfigLintIssue {
+    return {fatal: true, exitCode, message};
+}
+
+function warning(exitCode: ConfigLintExitCode, message: string): ConfigLintIssue {
+    return {fatal: false, exitCode, message};
+}
+
+export function lintEnvironment(env: NodeJS.ProcessEnv = process.env): ConfigLintIssue[] {
+    const issues: ConfigLintIssue[] = [];
+    const token = env.DISCORD_BOT_TOKEN;
+    if (!token) {
+        issues.push(fatal(ConfigLintExitCode.INVALID_ENVIRONMENT, 'DISCORD_BOT_TOKEN is not set'));
+    } else if (!/^[\w-]+\.[\w-]+\.[\w-]+$/.test(token)) {
+        issues.push(fatal(ConfigLintExitCode.INVALID_ENVIRONMENT, 'DISCORD_BOT_TOKEN does not look like a Discord bot token'));
+    }
+    const clientId = env.DISCORD_CLIENT_ID;
+    if (!clientId) {
+        issues.push(fatal(ConfigLintExitCode.INVALID_ENVIRONMENT, 'DISCORD_CLIENT_ID is not set'));
+    } else if (!/^\d{17,20}$/.test(clientId)) {
+        issues.push(fatal(ConfigLintExitCode.INVALID_ENVIRONMENT, `DISCORD_CLIENT_ID is not a valid snowflake: ${clientId}`));
+    }
+    if (env.ZKILL_SOURCE && env.ZKILL_SOURCE !== 'websocket' && env.ZKILL_SOURCE !== 'redisq') {
+        issues.push(fatal(ConfigLintExitCode.INVALID_ENVIRONMENT, `ZKILL_SOURCE must be websocket or redisq: ${env.ZKILL_SOURCE}`));
+    }
+    try {
+        parseEncryptionKey(env.TOKEN_ENCRYPTION_KEY);
+    } catch (e) {
+        issues.push(fatal(ConfigLintExitCode.INVALID_ENVIRONMENT, (<Error>e).message));
+    }
+    if (env.SDE_DIR && !fs.existsSync(path.join(env.SDE_DIR, SDE_TYPES_FILE))) {
+        issues.push(warning(ConfigLintExitCode.INVALID_ENVIRONMENT, `SDE_DIR has no ${SDE_TYPES_FILE}, types are looked up on ESI`));
+    }
+    return issues;
+}
+
+export function lintStorage(base_dir = './config/'): ConfigLintIssue[] {
+    try {
+        fs.accessSync(base_dir, fs.constants.R_OK | fs.constants.W_OK);
+    } catch (e) {
+        return [fatal(ConfigLintExitCode.STORAGE_UNAVAILABLE, `config directory ${base_dir} is not readable and writable: ${e}`)];
+    }
+    return [];
+}
+
+// Problem with the format of a limit type value, null if the value is valid
+export function limitTypeProblem(key: string, value: string): string | null {
+    const knownLimitTypes = Object.values(LimitType) as string[];
+    if (knownLimitTypes.indexOf(key) === -1) {
+        return 'is not a known limit type';
+    } else if (ID_LIST_LIMIT_TYPES.indexOf(key as LimitType) !== -1) {
+        if (value.split(',').every(id => id.trim() === '')) {
+            return 'is an empty ID list';
+        }
+        const invalid = value.split(',').filter(id => !/^\d+$/.test(id.trim()));
+        if (invalid.length > 0) {
+            return `contains invalid IDs: ${invalid.join(',')}`;
+        }
+    } else if (NUMERIC_LIMIT_TYPES.indexOf(key as LimitType) !== -1) {
+        if (value.trim() === '' || isNaN(Number(value))) {
+            return `is not a number: ${value}`;
+        }
+    } else if (key === LimitType.JUMPS_FROM_REGION_BORDER) {
+        if (!/^\d+:\d+$/.test(value)) {
+            return `is not in the format regionId:maxJumps: ${value}`;
+        }
+    } else if (key === LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME) {
+        if (parseLyRange(value) == null) {
+            return `is not in the format systemName:maxLy or systemName:shipClass: ${value}`;
+        }
+    } else if (key === LimitType.JUMPS_FROM_SYSTEM) {
+        if (!/^\d+:\d+$/.test(value)) {
+            return `is not in the format systemId:maxJumps: ${value}`;
+        }
+    } else if (key === LimitType.HULL_VALUE || key === LimitType.VICTIM_CORP_SIZE) {
+        if (parseValueRange(value) == null) {
+            return `is not in the format min:max: ${value}`;
+        }
+    } else if (key === LimitType.VICTIM_TECH_LEVEL) {
+        if (parseTechLevels(value) == null) {
+            return `has an unknown tech level: ${value}`;
+        }
+    } else if (key === LimitType.ATTACKER_SEC_STATUS) {
+        if (parseSecStatusRange(value) == null) {
+            return `is not in the format min:max from -10 to 10: ${value}`;
+        }
+    } else if (key === LimitType.PERSPECTIVE) {
+        if (parsePerspective(value) == null) {
+            return `is not kills, losses or both: ${value}`;
+        }
+    } else if (key === LimitType.EXCHANGE_OUTCOME) {
+        if (parseExchangeOutcome(value) == null) {
+            return `is not in the format won|lost:windowMinutes: ${value}`;
+        }
+    } else if (key === LimitType.NAME_FRAGMENT_SCOPE) {
+        if (parseNameScopes(value) == null) {
+            return `contains unknown name scopes: ${value}`;
+        }
+    } else if (key === LimitType.GANG_SIZE) {
+        if (parseGangSizeClasses(value) == null) {
+            return `contains unknown gang sizes: ${value}`;
+        }
+    } else if (key === LimitType.VICTIM_CATEGORY) {
+        const categories = Object.values(ShipCategory) as string[];
+        const invalid = value.split(',').filter(category => categories.indexOf(category) === -1);
+        if (invalid.length > 0) {
+            return `contains unknown categories: ${invalid.join(',')}`;
+        }
+    } else if (key === LimitType.NEAR_CELESTIAL) {
+        const kinds = Object.values(CelestialKind) as string[];
+        const match = /^([a-z,]+):(\d+(\.\d+)?)$/.exec(value);
+        if (!match) {
+            return `is not in the format kinds:maxDistanceKm: ${value}`;
+        } else if (match[1].split(',').some(kind => kinds.indexOf(kind) === -1)) {
+            return `contains unknown celestial kinds: ${match[1]}`;
+        }
+    } else if (key === LimitType.SPACE_TYPE) {
+        const spaceTypes = Object.values(SpaceType) as string[];
+        const invalid = value.split(',').filter(spaceType => spaceTypes.indexOf(spaceType) === -1);
+        if (invalid.length > 0) {
+            return `contains unknown space types: ${invalid.join(',')}`;
+        }
+    }
+    return null;
+}
+
+function lintLimitTypes(file: string, ident: string, limitTypes: any): ConfigLintIssue[] {
+    const issues: ConfigLintIssue[] = [];
+    for (const key of Object.keys(limitTypes)) {
+        const problem = limitTypeProblem(key, String(limitTypes[key]));
+        if (problem != null) {
+            issues.push(fatal(ConfigLintExitCode.INVALID_SUBSCRIPTIONS, `${file}: subscription ${ident}: ${key} ${problem}`));
+        }
+    }
+    return issues;
+}
+
+export function lintSubscriptionFile(base_dir: string, file: string): ConfigLintIssue[] {
+    let parsed: any;
+    try {
+        parsed = JSON.parse(fs.readFileSync(base_dir + file, 'utf8'));
+    } catch (e) {
+        return [fatal(ConfigLintExitCode.INVALID_SUBSCRIPTIONS, `${file}: failed to parse: ${e}`)];
+    }
+    if (parsed == null || typeof parsed.channels !== 'object') {
+        return [fatal(ConfigLintExitCode.INVALID_SUBSCRIPTIONS, `${file}: missing channels`)];
+    }
+    const issues: ConfigLintIssue[] = [];
+    if (parsed.locale != null && (Object.values(Locale) as string[]).indexOf(parsed.locale) === -1) {
+        issues.push(warning(ConfigLintExitCode.INVALID_SUBSCRIPTIONS, `${file}: unknown locale ${parsed.locale}, English is used`));
+    }
+    for (const channelId of Object.keys(parsed.channels)) {
+        const subscriptions = parsed.channels[channelId]?.subscriptions;
+        if (subscriptions == null || typeof subscriptions !== 'object') {
+            issues.push(fatal(ConfigLintExitCode.INVALID_SUBSCRIPTIONS, `${file}: channel ${channelId} has no subscriptions`));
+            continue;
+        }
+        for (const ident of Object.keys(subscriptions)) {
+            const subscription = subscriptions[ident];
+            if (!subscription.subType) {
+                issues.push(fatal(ConfigLintExitCode.INVALID_SUBSCRIPTIONS, `${file}: subscription ${ident} has no subType`));
+            }
+            if (subscription.minValue != null && isNaN(Number(subscription.minValue))) {
+                issues.push(fatal(ConfigLintExitCode.INVALID_SUBSCRIPTIONS, `${file}: subscription ${ident} has an invalid minValue`));
+            }
+            if (subscription.embedColor != null && parseHexColor(String(subscription.embedColor)) == null) {
+                issues.push(warning(ConfigLintExitCode.INVALID_SUBSCRIPTIONS, `${file}: subscription ${ident} has an invalid embedColor, Discord rejects its messages`));
+            }
+            if (subscription.limitTypes != null) {
+                issues.push(...lintLimitTypes(file, ident, subscription.limitTypes));
+            }
+        }
+    }
+    return issues;
+}
+
+export function lintConfig(base_dir = './config/', env: NodeJS.ProcessEnv = process.env): ConfigLintIssue[] {
+    const issues: ConfigLintIssue[] = [...lintEnvironment(env)];
+    const storageIssues = lintStorage(base_dir);
+    if (storageIssues.length > 0) {
+        return issues.concat(storageIssues);
+    }
+    const files = fs.readdirSync(base_dir, {withFileTypes: true});
+    for (const file of files) {
+        if (file.name.match(/\d+\.json$/)) {
+            issues.push(...lintSubscriptionFile(base_dir, file.name));
+        }
+    }
+    for (const cacheFile of CACHE_FILES) {
+        if (!fs.existsSync(base_dir + cacheFile)) {
+            continue;
+        }
+        try {
+            JSON.parse(fs.readFileSync(base_dir + cacheFile, 'utf8'));
+        } catch (e) {
+            issues.push(warning(ConfigLintExitCode.STORAGE_UNAVAILABLE, `${cacheFile}: failed to parse, the cache will be rebuilt`));
+        }
+    }
+    return issues;
+}
+
+// Prints a summary of the issues and returns the exit code of the first fatal issue
+export function reportConfigLint(issues: ConfigLintIssue[]): ConfigLintExitCode {
+    const fatalIssues = issues.filter(issue => issue.fatal);
+    const warnings = issues.filter(issue => !issue.fatal);
+    for (const issue of warnings) {
+        logger.warn(`config warning: ${issue.message}`);
+    }
+    for (const issue of fatalIssues) {
+        logger.error(`config error: ${issue.message}`);
+    }
+    const summary = `config check finished with ${fatalIssues.length} error(s) and ${warnings.length} warning(s)`;
+    if (fatalIssues.length > 0) {
+        logger.error(summary);
+    } else {
+        logger.info(summary);
+    }
+    return fatalIssues.length > 0 ? fatalIssues[0].exitCode : ConfigLintExitCode.OK;
+}
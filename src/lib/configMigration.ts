@@ -0,0 +1,139 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {ConfigLintExitCode, limitTypeProblem} from './configLint';
+import {logger} from './logger';
+
+// Converted guild config with what was changed or could not be converted
+export interface ConfigMigration {
+    // Null if the file is no guild config at all
+    config: any | null;
+    notes: string[];
+}
+
+// Guild ID a config or backup file belongs to, e.g. `888224317991706685-radar-backup-20240524.json`
+export function guildIdOfFile(file: string): string | null {
+    const match = /^(\d{17,20})(\D.*)?\.json$/.exec(path.basename(file));
+    return match ? match[1] : null;
+}
+
+// Converts one subscription, null if it has to be dropped. Limit types the bot does not understand drop the whole
+// subscription, as dropping only the limit type would post kills the subscription never matched
+function migrateSubscription(ident: string, legacy: any, notes: string[]): any | null {
+    if (!legacy || !legacy.subType) {
+        notes.push(`subscription ${ident} has no subType and was dropped`);
+        return null;
+    }
+    const subscription = {...legacy};
+    if (subscription.id != null) {
+        subscription.id = String(subscription.id);
+    }
+    const minValue = Number(subscription.minValue ?? 0);
+    if (isNaN(minValue)) {
+        notes.push(`subscription ${ident} has an invalid minValue ${subscription.minValue}, it was set to 0`);
+    }
+    subscription.minValue = isNaN(minValue) ? 0 : minValue;
+
+    const limitTypes: { [key: string]: string } = {};
+    for (const key of Object.keys(legacy.limitTypes ?? {})) {
+        const value = String(legacy.limitTypes[key]);
+        const problem = limitTypeProblem(key, value);
+        if (problem != null) {
+            notes.push(`subscription ${ident} was dropped, ${key} ${problem}`);
+            return null;
+        }
+        limitTypes[key] = value;
+    }
+    // Older versions kept the minimum number of attackers next to the limit types
+    if (subscription.minNumInvolved != null) {
+        if (Number(subscription.minNumInvolved) > 1 && limitTypes.minNumInvolved == null) {
+            limitTypes.minNumInvolved = String(subscription.minNumInvolved);
+            notes.push(`subscription ${ident}: minNumInvolved was moved to the limit types`);
+        }
+        delete subscription.minNumInvolved;
+    }
+    subscription.limitTypes = limitTypes;
+    return subscription;
+}
+
+// Converts a guild config written by an older version of the bot to the current format. Numeric IDs become strings,
+// subscriptions are stored under the ident /zkill-unsubscribe looks them up by and the subscriptions this version
+// cannot evaluate are reported and left out
+export function migrateGuildConfig(parsed: any): ConfigMigration {
+    if (parsed == null || typeof parsed.channels !== 'object') {
+        return {config: null, notes: ['missing channels, this is no guild config']};
+    }
+    const notes: string[] = [];
+    const channels: { [channelId: string]: any } = {};
+    for (const channelId of Object.keys(parsed.channels)) {
+        const legacySubscriptions = parsed.channels[channelId]?.subscriptions;
+        if (legacySubscriptions == null || typeof legacySubscriptions !== 'object') {
+            notes.push(`channel ${channelId} has no subscriptions and was dropped`);
+            continue;
+        }
+        const subscriptions: { [ident: string]: any } = {};
+        for (const key of Object.keys(legacySubscriptions)) {
+            const subscription = migrateSubscription(key, legacySubscriptions[key], notes);
+            if (subscription == null) {
+                continue;
+            }
+            let ident = `${subscription.subType}${subscription.id ?? ''}`;
+            if (ident !== key && (subscriptions[ident] != null || legacySubscriptions[ident] != null)) {
+                notes.push(`subscription ${key} in channel ${channelId} has the ID of ${ident}, it keeps its ident and cannot be unsubscribed by ID`);
+                ident = key;
+            } else if (ident !== key) {
+                notes.push(`subscription ${key} in channel ${channelId} was renamed to ${ident}`);
+            }
+            subscriptions[ident] = subscription;
+        }
+        channels[channelId] = {...parsed.channels[channelId], subscriptions};
+    }
+    return {config: {...parsed, channels}, notes};
+}
+
+// Migrates a legacy config file or every JSON file of a directory into the config directory. Existing guild configs
+// are never overwritten. Returns the exit code, INVALID_SUBSCRIPTIONS if a file could not be migrated
+export function runMigration(input: string | undefined, base_dir = './config/'): ConfigLintExitCode {
+    if (!input || !fs.existsSync(input)) {
+        logger.error('usage: --migrate <legacy config file or directory> [config directory]');
+        return ConfigLintExitCode.INVALID_SUBSCRIPTIONS;
+    }
+    const files = fs.statSync(input).isDirectory()
+        ? fs.readdirSync(input).filter(file => file.endsWith('.json')).map(file => path.join(input, file))
+        : [input];
+    let failed = 0;
+    for (const file of files) {
+        const guildId = guildIdOfFile(file);
+        const target = path.join(base_dir, `${guildId}.json`);
+        if (guildId == null) {
+            logger.error(`${file}: skipped, the file name does not start with a guild ID`);
+            failed++;
+            continue;
+        }
+        if (fs.existsSync(target)) {
+            logger.error(`${file}: skipped, ${target} already exists`);
+            failed++;
+            continue;
+        }
+        let migration: ConfigMigration;
+        try {
+            migration = migrateGuildConfig(JSON.parse(fs.readFileSync(file, 'utf8')));
+        } catch (e) {
+            migration = {config: null, notes: [`failed to parse: ${e}`]};
+        }
+        if (migration.config == null) {
+            migration.notes.forEach(note => logger.error(`${file}: ${note}`));
+            failed++;
+            continue;
+        }
+        migration.notes.forEach(note => logger.warn(`${file}: ${note}`));
+        fs.writeFileSync(target, JSON.stringify(migration.config), 'utf8');
+        logger.info(`${file}: written to ${target}`);
+    }
+    const summary = `migrated ${files.length - failed} of ${files.length} file(s)`;
+    if (failed > 0) {
+        logger.error(summary);
+    } else {
+        logger.info(summary);
+    }
+    return failed > 0 ? ConfigLintExitCode.INVALID_SUBSCRIPTIONS : ConfigLintExitCode.OK;
+}
@@ -0,0 +1,82 @@
+// Percentiles of the last latencies, in milliseconds
+export interface LatencySummary {
+    count: number;
+    p50: number;
+    p90: number;
+    p99: number;
+    max: number;
+}
+
+// Nearest rank percentile of ascending samples
+export function percentile(sorted: number[], p: number): number {
+    const rank = Math.ceil(p / 100 * sorted.length);
+    return sorted[Math.min(sorted.length - 1, Math.max(0, rank - 1))];
+}
+
+// Keeps the last `capacity` latencies, older ones are overwritten
+export class RollingLatency {
+    readonly capacity: number;
+    private samples: number[] = [];
+    private next = 0;
+
+    constructor(capacity = 1000) {
+        this.capacity = capacity;
+    }
+
+    record(ms: number) {
+        if (this.samples.length < this.capacity) {
+            this.samples.push(ms);
+        } else {
+            this.samples[this.next] = ms;
+        }
+        this.next = (this.next + 1) % this.capacity;
+    }
+
+    // Null before the first sample
+    summary(): LatencySummary | null {
+        if (this.samples.length === 0) {
+            return null;
+        }
+        const sorted = this.samples.slice().sort((a, b) => a - b);
+        return {
+            count: sorted.length,
+            p50: percentile(sorted, 50),
+            p90: percentile(sorted, 90),
+            p99: percentile(sorted, 99),
+            max: sorted[sorted.length - 1],
+        };
+    }
+}
+
+// Time from the kill happening to the bot receiving it from zKillboard, and to the bot posting it, to tell a slow
+// zKillboard from a slow bot
+export class DeliveryLatency {
+    readonly received: RollingLatency;
+    // Every post counts, a kill posted to several channels counts once per channel
+    readonly posted: RollingLatency;
+
+    constructor(capacity = 1000) {
+        this.received = new RollingLatency(capacity);
+        this.posted = new RollingLatency(capacity);
+    }
+
+    recordReceived(killmailTime: string, now = Date.now()) {
+        const age = killAgeMs(killmailTime, now);
+        if (age != null) {
+            this.received.record(age);
+        }
+    }
+
+    recordPosted(killmailTime: string, now = Date.now()) {
+        const age = killAgeMs(killmailTime, now);
+        if (age != null) {
+            this.posted.record(age);
+        }
+    }
+}
+
+// Null for unparsable times, clock skew between the bot and the game server can make young kills look from the future
+export function killAgeMs(killmailTime: string, now = Date.now()): number | null {
+    const time = Date.parse(killmailTime);
+    return isNaN(time) ? null : Math.max(0, now - time);
+}
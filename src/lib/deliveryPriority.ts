@@ -0,0 +1,17 @@
+// Order in which the waiting kill messages are sent while the delivery is backed up, e.g. after a zKillboard outage
+export enum DeliveryPriority {
+    NORMAL = 0,
+    SUPERCAPITAL = 1,
+    // Messages mentioning a role or @here, someone is waiting for them
+    PING = 2,
+}
+
+// Titans and supercarriers
+const SUPERCAPITAL_GROUP_IDS = [30, 659];
+
+export function deliveryPriority(pings: boolean, victimGroupId: number | null): DeliveryPriority {
+    if (pings) {
+        return DeliveryPriority.PING;
+    }
+    return victimGroupId != null && SUPERCAPITAL_GROUP_IDS.includes(victimGroupId) ? DeliveryPriority.SUPERCAPITAL : DeliveryPriority.NORMAL;
+}
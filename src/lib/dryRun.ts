@@ -0,0 +1,25 @@
+import {ZkData} from '../zKillSubscriber';
+
+// What happens to the kills a subscription in dry run matches, it posts nothing until the dry run is turned off
+export enum DryRunMode {
+    // Logged only
+    LOG = 'log',
+    // Logged and sent to the member who created the subscription by direct message
+    DM = 'dm',
+}
+
+// Value of the dry run options that takes the subscription live
+export const DRY_RUN_OFF = 'off';
+
+// The mode, undefined for off, null if the value is neither
+export function parseDryRunMode(value: string): DryRunMode | undefined | null {
+    if (value === DRY_RUN_OFF) {
+        return undefined;
+    }
+    return value === DryRunMode.LOG || value === DryRunMode.DM ? value : null;
+}
+
+// e.g. `Dry run of public7 in <#123>: would have posted https://zkillboard.com/kill/115787551/ (2.35b ISK)`
+export function dryRunText(ident: string, target: string, data: ZkData, value: string): string {
+    return `Dry run of ${ident} in ${target}: would have posted ${data.zkb.url} (${value} ISK)`;
+}
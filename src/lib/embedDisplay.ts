@@ -0,0 +1,70 @@
+import {ColorResolvable, MessageEmbedOptions} from 'discord.js';
+
+export enum EmbedMode {
+    FULL = 'full',
+    // One line with the linked title, the author line and the footer, easier to skim on mobile
+    COMPACT = 'compact',
+}
+
+// Display settings of a guild, applied to every kill embed after it is built
+export interface DisplaySettings {
+    embedMode?: EmbedMode;
+    hideThumbnails?: boolean;
+    // Prefixes the title with KILL or LOSS and uses blue and orange instead of green and red
+    colorBlind?: boolean;
+}
+
+export enum KillOutcome {
+    KILL = 'kill',
+    LOSS = 'loss',
+}
+
+// Okabe-Ito blue and orange, told apart with every common form of color blindness
+export const COLOR_BLIND_COLORS: { [outcome in KillOutcome]: ColorResolvable } = {
+    [KillOutcome.KILL]: '#0072B2',
+    [KillOutcome.LOSS]: '#E69F00',
+};
+
+// The embed colors of the filters are green for kills and red for losses, other colors tell neither
+export function killOutcome(color: ColorResolvable | undefined): KillOutcome | null {
+    if (color === 'GREEN') {
+        return KillOutcome.KILL;
+    }
+    return color === 'RED' ? KillOutcome.LOSS : null;
+}
+
+export function parseEmbedMode(value: string): EmbedMode | null {
+    const mode = value.trim().toLowerCase();
+    return (Object.values(EmbedMode) as string[]).includes(mode) ? mode as EmbedMode : null;
+}
+
+// `outcomeLabel` is the translated KILL or LOSS, `customColor` keeps a color the subscription chose itself
+export function applyDisplaySettings(
+    embed: MessageEmbedOptions,
+    settings: DisplaySettings,
+    outcome: KillOutcome | null,
+    outcomeLabel: string,
+    customColor = false,
+): MessageEmbedOptions {
+    const result: MessageEmbedOptions = {...embed};
+    if (settings.colorBlind && outcome != null) {
+        if (result.title) {
+            result.title = `${outcomeLabel}: ${result.title}`;
+        }
+        if (!customColor) {
+            result.color = COLOR_BLIND_COLORS[outcome];
+        }
+    }
+    if (settings.hideThumbnails) {
+        delete result.thumbnail;
+    }
+    if (settings.embedMode === EmbedMode.COMPACT) {
+        const title = result.title ? (result.url ? `**[${result.title}](${result.url})**` : `**${result.title}**`) : '';
+        const parts = [title, result.author?.name, result.footer?.text].filter((part): part is string => !!part);
+        return {
+            description: parts.join(' • ').replace(/\n/g, ' '),
+            color: result.color,
+        };
+    }
+    return result;
+}
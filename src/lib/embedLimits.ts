@@ -0,0 +1,94 @@
+import {EmbedFieldData, MessageEmbedOptions} from 'discord.js';
+
+// Limits of Discord, a message with an embed exceeding any of them is rejected
+export const EMBED_LIMITS = {
+    title: 256,
+    description: 4096,
+    fields: 25,
+    fieldName: 256,
+    fieldValue: 1024,
+    footer: 2048,
+    author: 256,
+    // Title, description, field names and values, footer and author together
+    total: 6000,
+};
+
+// Shortened texts keep at least this many characters before other texts are shortened further or fields dropped
+const MIN_SHORTENED_LENGTH = 64;
+
+// Cuts the text to the limit, at the last line break if that keeps most of it, so links and lines stay whole. A code
+// block cut open, like the attacker affiliations, is closed. The cut is marked with … linking to the kill if a URL is given
+export function truncateText(text: string, max: number, url?: string): string {
+    if (text.length <= max) {
+        return text;
+    }
+    const marker = url ? `[…](${url})` : '…';
+    const closingFence = '```\n';
+    const reserved = marker.length + (text.indexOf('```') !== -1 ? closingFence.length + 1 : 0);
+    let cut = text.substring(0, Math.max(0, max - reserved));
+    const lineBreak = cut.lastIndexOf('\n');
+    if (lineBreak > cut.length / 2) {
+        cut = cut.substring(0, lineBreak + 1);
+    }
+    if ((cut.match(/```/g) ?? []).length % 2 === 1) {
+        cut += (cut.endsWith('\n') ? '' : '\n') + closingFence;
+    }
+    return cut + marker;
+}
+
+export function embedLength(embed: MessageEmbedOptions): number {
+    return (embed.title?.length ?? 0) +
+        (embed.description?.length ?? 0) +
+        (embed.footer?.text?.length ?? 0) +
+        (embed.author?.name?.length ?? 0) +
+        (embed.fields ?? []).reduce((length, field) => length + field.name.length + field.value.length, 0);
+}
+
+// Truncates the embed to the limits instead of letting Discord reject it, e.g. the attacker affiliations of large
+// fleet fights. Over the total limit the longest texts are shortened first, then the last fields are dropped
+export function fitEmbed(embed: MessageEmbedOptions, url?: string): MessageEmbedOptions {
+    const result: MessageEmbedOptions = {...embed};
+    if (result.title) {
+        result.title = truncateText(result.title, EMBED_LIMITS.title);
+    }
+    if (result.description) {
+        result.description = truncateText(result.description, EMBED_LIMITS.description, url);
+    }
+    if (result.footer?.text) {
+        result.footer = {...result.footer, text: truncateText(result.footer.text, EMBED_LIMITS.footer)};
+    }
+    if (result.author?.name) {
+        result.author = {...result.author, name: truncateText(result.author.name, EMBED_LIMITS.author)};
+    }
+    const fields: EmbedFieldData[] = (result.fields ?? []).slice(0, EMBED_LIMITS.fields).map(field => ({
+        ...field,
+        name: truncateText(field.name, EMBED_LIMITS.fieldName),
+        value: truncateText(field.value, EMBED_LIMITS.fieldValue, url),
+    }));
+    if (result.fields) {
+        result.fields = fields;
+    }
+
+    let excess = embedLength(result) - EMBED_LIMITS.total;
+    while (excess > 0) {
+        let longest: EmbedFieldData | null = null;
+        for (const field of fields) {
+            if (field.value.length > MIN_SHORTENED_LENGTH && (longest == null || field.value.length > longest.value.length)) {
+                longest = field;
+            }
+        }
+        const descriptionLength = result.description?.length ?? 0;
+        const longestLength = longest != null ? longest.value.length : 0;
+        if (descriptionLength > MIN_SHORTENED_LENGTH && descriptionLength >= longestLength) {
+            result.description = truncateText(<string>result.description, Math.max(MIN_SHORTENED_LENGTH, descriptionLength - excess), url);
+        } else if (longest != null) {
+            longest.value = truncateText(longest.value, Math.max(MIN_SHORTENED_LENGTH, longestLength - excess), url);
+        } else if (fields.length > 0) {
+            fields.pop();
+        } else {
+            break;
+        }
+        excess = embedLength(result) - EMBED_LIMITS.total;
+    }
+    return result;
+}
@@ -0,0 +1,62 @@
+import {LimitType, ZkData} from '../zKillSubscriber';
+import {involvedEntityIds} from './subscriptionIndex';
+
+// Named lists of character, corporation and alliance IDs of a guild, e.g. the blue alliances
+export type EntityLists = { [name: string]: number[] };
+
+// Lists unchanged for longer are flagged by /zkill-diag, standings change often and outdated blues drop the wrong kills
+export const STALE_LIST_DAYS = 30;
+
+// List names are case insensitive and referenced by the entity list filters
+export function normalizeListName(name: string): string | null {
+    const normalized = name.trim().toLowerCase();
+    return /^[a-z0-9_-]{1,32}$/.test(normalized) ? normalized : null;
+}
+
+// Comma separated list names of the entity list filters, null if any of them is not a valid name
+export function normalizeListNames(value: string): string[] | null {
+    const names = value.split(',').map(normalizeListName);
+    if (names.some(name => name == null)) {
+        return null;
+    }
+    return (<string[]>names).filter((name, index) => names.indexOf(name) === index);
+}
+
+// Union of the named lists, e.g. the blues of every alliance of a coalition. Unknown lists are empty
+export function mergeEntityLists(entityLists: EntityLists, names: string[]): number[] {
+    return names.reduce((ids: number[], name) => ids.concat(entityLists[name] ?? []), []);
+}
+
+// IDs separated by commas or whitespace, null if any part is not an ID
+export function parseEntityIds(value: string): number[] | null {
+    const parts = value.split(/[\s,]+/).filter(part => part !== '');
+    if (parts.length === 0 || parts.some(part => !/^\d+$/.test(part))) {
+        return null;
+    }
+    return parts.map(Number);
+}
+
+// True if the victim or an attacker, by character, corporation or alliance, is on the list
+export function isOnEntityList(list: number[], data: ZkData): boolean {
+    const ids = new Set(list);
+    return involvedEntityIds(data).some(id => ids.has(id));
+}
+
+// Names of the lists the entity list filters of a subscription use
+export function filterListNames(limitTypes: Map<LimitType, string>): string[] {
+    return [LimitType.ENTITY_LIST_INCLUDE, LimitType.ENTITY_LIST_EXCLUDE]
+        .map(limitType => limitTypes.get(limitType))
+        .filter((names): names is string => names != null)
+        .reduce((all: string[], names) => all.concat(names.split(',')), []);
+}
+
+// When the list last changed, e.g. `blues changed 45 days ago, may be outdated`. Lists changed before the time was
+// recorded have no timestamp
+export function listAgeText(name: string, updatedAt: string | undefined, now = new Date()): string {
+    if (!updatedAt) {
+        return `${name} changed at an unknown time`;
+    }
+    const days = Math.floor((now.getTime() - Date.parse(updatedAt)) / (24 * 60 * 60 * 1000));
+    const age = days < 1 ? `${name} changed today` : `${name} changed ${days} ${days === 1 ? 'day' : 'days'} ago`;
+    return days >= STALE_LIST_DAYS ? age + ', may be outdated' : age;
+}
@@ -0,0 +1,51 @@
+import {LimitType, ZkData} from '../zKillSubscriber';
+import {IntelEntityType} from './zkbApi';
+
+// A character, corporation or alliance whose kills and losses a subscription relays, with running ISK totals
+export interface EntityTracking {
+    type: IntelEntityType;
+    entityId: number;
+    name: string;
+    // ISO timestamp the totals count from
+    since: string;
+    iskDestroyed: number;
+    iskLost: number;
+}
+
+export function trackingSubscriptionId(entityId: number): string {
+    return 'track-' + entityId;
+}
+
+export function trackingLimitType(type: IntelEntityType): LimitType {
+    if (type === IntelEntityType.ALLIANCE) {
+        return LimitType.ALLIANCE;
+    }
+    return type === IntelEntityType.CORPORATION ? LimitType.CORPORATION : LimitType.CHARACTER;
+}
+
+export function newTracking(type: IntelEntityType, entityId: number, name: string, now = new Date()): EntityTracking {
+    return {type, entityId, name, since: now.toISOString(), iskDestroyed: 0, iskLost: 0};
+}
+
+export function isTrackedLoss(tracking: EntityTracking, data: ZkData): boolean {
+    if (tracking.type === IntelEntityType.ALLIANCE) {
+        return data.victim.alliance_id === tracking.entityId;
+    }
+    if (tracking.type === IntelEntityType.CORPORATION) {
+        return data.victim.corporation_id === tracking.entityId;
+    }
+    return data.victim.character_id === tracking.entityId;
+}
+
+// The totals with the kill added, the kill counts as destroyed unless the tracked entity is the victim
+export function withTrackedKill(tracking: EntityTracking, data: ZkData): EntityTracking {
+    return isTrackedLoss(tracking, data) ?
+        {...tracking, iskLost: tracking.iskLost + data.zkb.totalValue} :
+        {...tracking, iskDestroyed: tracking.iskDestroyed + data.zkb.totalValue};
+}
+
+// True if the text is the ID or the name of the tracked entity, ignoring case
+export function matchesTrackedEntity(tracking: EntityTracking, text: string): boolean {
+    const value = text.trim().toLowerCase();
+    return value === String(tracking.entityId) || value === tracking.name.toLowerCase();
+}
@@ -3,9 +3,14 @@ import pack from 'bin-pack';
 import {AccessToken, AuthorizationCode} from 'simple-oauth2';
 import promptSync from 'prompt-sync';
 import {exec} from 'child_process';
-import fs from 'fs';
-import {ClosestCelestial, SolarSystem} from '../zKillSubscriber';
+import {ClosestCelestial, SolarSystem, ZkData} from '../zKillSubscriber';
 import * as util from 'util';
+import {MarketPrice} from './marketPrices';
+import {TokenStore} from './tokenStore';
+import {SsoCallbackServer} from './ssoCallback';
+import {META_GROUP_ATTRIBUTE_ID} from './techLevel';
+import {randomBytes} from 'crypto';
+import {logger} from './logger';
 
 
 const ESI_URL = 'https://esi.evetech.net/latest/';
@@ -13,9 +18,13 @@ const GET_SOLAR_SYSTEM_URL = 'universe/systems/%1/';
 const GET_CONSTELLATION_URL = 'universe/constellations/%1/';
 const GET_REGION_URL = 'universe/regions/%1/';
 const GET_TYPE_DATA_URL = 'universe/types/%1/';
+const GET_GROUP_DATA_URL = 'universe/groups/%1/';
 const GET_ALLIANCE_URL = 'alliances/%1/';
 const GET_CORPORATION_URL = 'corporations/%1/';
 const GET_CHARACTER_URL = 'characters/%1/';
+const GET_KILLMAIL_URL = 'killmails/%1/%2/';
+const GET_MARKET_PRICES_URL = 'markets/prices/';
+const GET_IDS_URL = 'universe/ids/';
 
 interface Token {
     access_token: string;
@@ -32,6 +41,8 @@ export class EsiClient {
     private token?: Token;
     private contractScopes: string;
     private config: EveSSOConfig;
+    // Lookups fail instead of reaching ESI, for evaluating filters without network
+    private offline = false;
 
     constructor() {
         this.axios = new Axios({baseURL: ESI_URL, responseType: 'json', transformResponse: data => JSON.parse(data)});
@@ -56,34 +67,45 @@ export class EsiClient {
     async eveSsoLogin() {
 
         const client = new AuthorizationCode(this.config);
-        // eslint-disable-next-line @typescript-eslint/no-unused-vars
+        // Without a callback server the code is copied from the redirect to the pyfa callback page
+        const callbackServer = SsoCallbackServer.fromEnv();
+        const redirectUri = callbackServer?.redirectUri ?? 'https://pyfa-org.github.io/Pyfa/callback';
+        const state = randomBytes(16).toString('hex');
 
         const authorizationUri = client.authorizeURL({
-            redirect_uri: 'https://pyfa-org.github.io/Pyfa/callback',
+            redirect_uri: redirectUri,
             scope: this.contractScopes,
-            state: '1231231231231'
+            state,
         });
+        // Listening before the browser opens, the redirect can be quicker than the prompt
+        const callbackCode = callbackServer?.waitForCode(state);
 
         // eslint-disable-next-line @typescript-eslint/no-unused-vars
         const browserProcess = exec(`open '${authorizationUri}'`);
 
-        const prompt = promptSync({sigint: true});
-        const grantCode = prompt('Please enter your grant code: ');
+        let grantCode: string;
+        if (callbackCode) {
+            logger.info(`Waiting for the EVE SSO redirect to ${redirectUri}`);
+            grantCode = await callbackCode;
+        } else {
+            const prompt = promptSync({sigint: true});
+            grantCode = prompt('Please enter your grant code: ');
+        }
 
         const tokenParams = {
             code: grantCode,
-            redirect_uri: 'https://pyfa-org.github.io/Pyfa/callback',
+            redirect_uri: redirectUri,
             scope: this.contractScopes,
         };
 
         let accessToken: AccessToken;
         try {
             accessToken = await client.getToken(tokenParams);
-            // write the token to a file
-            console.log('Access Token:', accessToken.token);
-            fs.writeFileSync('accessToken.json', JSON.stringify(accessToken));
+            // write the token to a file, only its expiry is logged
+            logger.info(`access token valid until ${accessToken.token.expires_at}`);
+            TokenStore.fromEnv().save(JSON.stringify(accessToken));
         } catch (error: any) {
-            console.log('Access Token Error', error.message);
+            logger.error('Access Token Error', error.message);
             throw error;
         }
     }
@@ -91,7 +113,11 @@ export class EsiClient {
     async eveSsoRefresh() {
         const client = new AuthorizationCode(this.config);
         // load token from file
-        const accessTokenJSONString = fs.readFileSync('accessToken.json', 'utf8');
+        const tokenStore = TokenStore.fromEnv();
+        const accessTokenJSONString = tokenStore.load();
+        if (accessTokenJSONString == null) {
+            throw new Error('No access token stored, log in with eveSsoLogin first');
+        }
         let accessToken = client.createToken(JSON.parse(accessTokenJSONString));
         if (accessToken.expired()) {
             try {
@@ -99,23 +125,39 @@ export class EsiClient {
                     scope: this.contractScopes,
                 };
                 accessToken = await accessToken.refresh(refreshParams);
-                console.log('Access Token:', accessToken.token);
-                fs.writeFileSync('accessToken.json', JSON.stringify(accessToken));
+                logger.info(`refreshed the access token, valid until ${accessToken.token.expires_at}`);
+                tokenStore.save(JSON.stringify(accessToken));
                 return accessToken.token;
             } catch (error: any) {
-                console.log('Error refreshing access token: ', error.message);
+                logger.error('Error refreshing access token: ', error.message);
                 throw new Error('Access Token refresh Error');
             }
         } else {
-            console.log('Access Token:', accessToken.token);
             return accessToken.token;
         }
     }
 
+    setOffline() {
+        this.offline = true;
+    }
+
     async fetch(path: string): Promise<AxiosResponse<any, any>> {
+        if (this.offline) {
+            throw new Error(`ESI is not reachable offline: ${path}`);
+        }
         return await this.axios.get(path);
     }
 
+    // True if ESI answers its status endpoint
+    async isReachable(): Promise<boolean> {
+        try {
+            const response = await this.fetch('status/');
+            return response.status === 200;
+        } catch (e) {
+            return false;
+        }
+    }
+
     async getSystemInfo(systemId: number): Promise<SolarSystem> {
         const systemData = await this.fetch(GET_SOLAR_SYSTEM_URL.replace('%1', systemId.toString()));
         if (systemData.data.error) {
@@ -159,6 +201,63 @@ export class EsiClient {
         return Number.parseInt(itemData.data.group_id);
     }
 
+    // Null for types without a meta group attribute, which are Tech I
+    async getTypeMetaGroupId(typeId: number): Promise<number | null> {
+        const itemData = await this.fetch(GET_TYPE_DATA_URL.replace('%1', typeId.toString()));
+        if (itemData.data.error) {
+            throw new Error('ITEM_FETCH_ERROR');
+        }
+        const attribute = (itemData.data.dogma_attributes ?? [])
+            .find((attribute: { attribute_id: number }) => attribute.attribute_id === META_GROUP_ATTRIBUTE_ID);
+        return attribute != null ? Number(attribute.value) : null;
+    }
+
+    async getGroupCategoryId(groupId: number): Promise<number> {
+        const groupData = await this.fetch(GET_GROUP_DATA_URL.replace('%1', groupId.toString()));
+        if (groupData.data.error) {
+            throw new Error('GROUP_FETCH_ERROR');
+        }
+        return Number.parseInt(groupData.data.category_id);
+    }
+
+    async getGroupName(groupId: number): Promise<string> {
+        const groupData = await this.fetch(GET_GROUP_DATA_URL.replace('%1', groupId.toString()));
+        if (groupData.data.error) {
+            throw new Error('GROUP_FETCH_ERROR');
+        }
+        return groupData.data.name;
+    }
+
+    // The ESI killmail, without the zKillboard metadata
+    async getKillmail(killmailId: number, hash: string): Promise<Omit<ZkData, 'zkb'>> {
+        const killmailData = await this.fetch(GET_KILLMAIL_URL.replace('%1', killmailId.toString()).replace('%2', hash));
+        if (killmailData.data.error) {
+            throw new Error('KILLMAIL_FETCH_ERROR: ' + killmailData.data.error);
+        }
+        return killmailData.data;
+    }
+
+    // IDs of the exact name, per category, e.g. characters or corporations
+    async getIdsForName(name: string): Promise<{ [category: string]: { id: number, name: string }[] }> {
+        if (this.offline) {
+            throw new Error(`ESI is not reachable offline: ${GET_IDS_URL}`);
+        }
+        const idsData = await this.axios.post(GET_IDS_URL, JSON.stringify([name]), {headers: {'Content-Type': 'application/json'}});
+        if (idsData.status !== 200 || idsData.data == null || idsData.data.error) {
+            throw new Error('IDS_FETCH_ERROR');
+        }
+        return idsData.data;
+    }
+
+    // Average and adjusted prices of all types, ESI refreshes them about once an hour
+    async getMarketPrices(): Promise<MarketPrice[]> {
+        const pricesData = await this.fetch(GET_MARKET_PRICES_URL);
+        if (!Array.isArray(pricesData.data)) {
+            throw new Error('MARKET_PRICES_FETCH_ERROR: ' + pricesData.data.error);
+        }
+        return pricesData.data;
+    }
+
     async getAllianceName(allianceId: number): Promise<string> {
         const itemData = await this.fetch(GET_ALLIANCE_URL.replace('%1', allianceId.toString()));
         if (itemData.data.error) {
@@ -167,6 +266,14 @@ export class EsiClient {
         return itemData.data.name;
     }
 
+    async getAllianceTicker(allianceId: number): Promise<string> {
+        const itemData = await this.fetch(GET_ALLIANCE_URL.replace('%1', allianceId.toString()));
+        if (itemData.data.error) {
+            throw new Error('ITEM_FETCH_ERROR');
+        }
+        return itemData.data.ticker;
+    }
+
     async getCorporationName(corporationId: number): Promise<string> {
         const itemData = await this.fetch(GET_CORPORATION_URL.replace('%1', corporationId.toString()));
         if (itemData.data.error) {
@@ -175,6 +282,22 @@ export class EsiClient {
         return itemData.data.name;
     }
 
+    async getCorporationTicker(corporationId: number): Promise<string> {
+        const itemData = await this.fetch(GET_CORPORATION_URL.replace('%1', corporationId.toString()));
+        if (itemData.data.error) {
+            throw new Error('ITEM_FETCH_ERROR');
+        }
+        return itemData.data.ticker;
+    }
+
+    async getCorporationMemberCount(corporationId: number): Promise<number> {
+        const itemData = await this.fetch(GET_CORPORATION_URL.replace('%1', corporationId.toString()));
+        if (itemData.data.error) {
+            throw new Error('ITEM_FETCH_ERROR');
+        }
+        return Number(itemData.data.member_count);
+    }
+
     async getCharacterName(characterId: number): Promise<string> {
         const itemData = await this.fetch(GET_CHARACTER_URL.replace('%1', characterId.toString()));
         if (itemData.data.error) {
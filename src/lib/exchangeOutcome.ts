@@ -0,0 +1,100 @@
+import {ZkData} from '../zKillSubscriber';
+import {ownSide, Perspective} from './perspective';
+
+// Net ISK direction of the kills and losses of the own entities, WON if they destroyed more than they lost
+export enum ExchangeResult {
+    WON = 'won',
+    LOST = 'lost',
+}
+
+export interface ExchangeOutcomeFilter {
+    require: ExchangeResult;
+    // The exchange counts the kills and losses in the system this many minutes before and after the matched kill
+    windowMinutes: number;
+}
+
+export interface ExchangeBalance {
+    destroyed: number;
+    lost: number;
+}
+
+export const DEFAULT_EXCHANGE_WINDOW_MINUTES = 30;
+// Longer windows would keep the kills, and the posts waiting for them, in memory for too long
+export const MAX_EXCHANGE_WINDOW_MINUTES = 360;
+
+// `won` or `lost` with an optional window, e.g. `won:60`. Null if invalid
+export function parseExchangeOutcome(value: string): ExchangeOutcomeFilter | null {
+    const match = /^(won|lost)(:(\d+))?$/.exec(value.trim().toLowerCase());
+    if (!match) {
+        return null;
+    }
+    const windowMinutes = match[3] != null ? Number(match[3]) : DEFAULT_EXCHANGE_WINDOW_MINUTES;
+    if (windowMinutes < 1 || windowMinutes > MAX_EXCHANGE_WINDOW_MINUTES) {
+        return null;
+    }
+    return {require: match[1] as ExchangeResult, windowMinutes};
+}
+
+// Null for an even exchange, e.g. while neither side lost anything
+export function exchangeResult(balance: ExchangeBalance): ExchangeResult | null {
+    if (balance.destroyed === balance.lost) {
+        return null;
+    }
+    return balance.destroyed > balance.lost ? ExchangeResult.WON : ExchangeResult.LOST;
+}
+
+interface ExchangeEntry {
+    killmailId: number;
+    systemId: number;
+    time: number;
+    value: number;
+    side: Perspective.KILLS | Perspective.LOSSES;
+}
+
+// Short-term memory of the kills and losses of the own entities of every guild, so the outcome of an exchange is
+// known once its window has passed. Kills are kept for twice the longest window
+export class ExchangeTracker {
+    private retentionMs: number;
+    // Mapping of guild ID to the kills and losses of its own entities, in the order they were received
+    private entries = new Map<string, ExchangeEntry[]>();
+
+    constructor(retentionMs = 2 * MAX_EXCHANGE_WINDOW_MINUTES * 60 * 1000) {
+        this.retentionMs = retentionMs;
+    }
+
+    // Remembers the kill if an own entity of the guild is on either side, returns whether it was
+    record(guildId: string, ownEntityIds: number[], data: ZkData, now = Date.now()): boolean {
+        const side = ownSide(ownEntityIds, data);
+        const entries = (this.entries.get(guildId) ?? []).filter(entry => now - entry.time <= this.retentionMs);
+        if (side != null && !entries.some(entry => entry.killmailId === data.killmail_id)) {
+            entries.push({
+                killmailId: data.killmail_id,
+                systemId: data.solar_system_id,
+                time: Date.parse(data.killmail_time),
+                value: data.zkb.totalValue,
+                side,
+            });
+        }
+        if (entries.length > 0) {
+            this.entries.set(guildId, entries);
+        } else {
+            this.entries.delete(guildId);
+        }
+        return side != null;
+    }
+
+    // ISK destroyed and lost by the own entities of the guild in the system between from and to
+    balance(guildId: string, systemId: number, from: number, to: number): ExchangeBalance {
+        const balance = {destroyed: 0, lost: 0};
+        (this.entries.get(guildId) ?? [])
+            .filter(entry => entry.systemId === systemId && entry.time >= from && entry.time <= to)
+            .forEach(entry => {
+                if (entry.side === Perspective.KILLS) {
+                    balance.destroyed += entry.value;
+                } else {
+                    balance.lost += entry.value;
+                }
+            });
+        return balance;
+    }
+}
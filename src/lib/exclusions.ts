@@ -0,0 +1,60 @@
+import {LimitType, ZkData} from '../zKillSubscriber';
+
+// Entities whose attackers are ignored, unlike the Not filters they veto single attackers instead of the whole kill
+export interface EntityExclusions {
+    allianceIds: number[];
+    corporationIds: number[];
+    shipGroupIds: number[];
+}
+
+export interface ExclusionResult {
+    // The kill without the excluded attackers, the other filters see only the remaining ones
+    data: ZkData;
+    removedAttackers: number;
+    // Set if the victim is excluded or no attacker remains
+    rejectedBy: LimitType | null;
+}
+
+// The limit type excluding the entity, null if it is not excluded
+export function exclusionOf(
+    exclusions: EntityExclusions,
+    entity: { alliance_id?: number | null, corporation_id?: number | null },
+    shipGroupId?: number,
+): LimitType | null {
+    if (entity.alliance_id != null && exclusions.allianceIds.includes(entity.alliance_id)) {
+        return LimitType.EXCLUDED_ALLIANCE;
+    }
+    if (entity.corporation_id != null && exclusions.corporationIds.includes(entity.corporation_id)) {
+        return LimitType.EXCLUDED_CORPORATION;
+    }
+    if (shipGroupId != null && exclusions.shipGroupIds.includes(shipGroupId)) {
+        return LimitType.EXCLUDED_SHIP_GROUP;
+    }
+    return null;
+}
+
+// The ship group IDs are those of the victim's ship and of each attacker's ship, in the order of the attackers
+export function applyExclusions(
+    data: ZkData,
+    exclusions: EntityExclusions,
+    victimShipGroupId?: number,
+    attackerShipGroupIds: (number | undefined)[] = [],
+): ExclusionResult {
+    const victimExclusion = exclusionOf(exclusions, data.victim, victimShipGroupId);
+    if (victimExclusion != null) {
+        return {data, removedAttackers: 0, rejectedBy: victimExclusion};
+    }
+    let lastExclusion: LimitType | null = null;
+    const attackers = data.attackers.filter((attacker, index) => {
+        const exclusion = exclusionOf(exclusions, attacker, attackerShipGroupIds[index]);
+        if (exclusion != null) {
+            lastExclusion = exclusion;
+        }
+        return exclusion == null;
+    });
+    return {
+        data: {...data, attackers},
+        removedAttackers: data.attackers.length - attackers.length,
+        rejectedBy: attackers.length === 0 && data.attackers.length > 0 ? lastExclusion : null,
+    };
+}
@@ -0,0 +1,129 @@
+import * as http from 'http';
+import {randomBytes} from 'crypto';
+import {imageContentType, ImageProxy} from './images';
+import {logger} from './logger';
+
+export interface AtomEntry {
+    id: string;
+    title: string;
+    link: string;
+    // ISO timestamp
+    updated: string;
+    summary: string;
+}
+
+export interface AtomFeed {
+    title: string;
+    selfLink: string;
+    entries: AtomEntry[];
+}
+
+// Returns the feed of the subscription the token belongs to, or null if the token is unknown
+export type FeedResolver = (token: string) => Promise<AtomFeed | null>;
+
+const FEED_PATH = /^\/feed\/([A-Za-z0-9_-]+)\.atom$/;
+const IMAGE_PATH = /^\/images\/([^/]+)$/;
+
+export function generateFeedToken(): string {
+    return randomBytes(18).toString('base64').replace(/\+/g, '-').replace(/\//g, '_');
+}
+
+function escapeXml(text: string): string {
+    return text.replace(/&/g, '&amp;')
+        .replace(/</g, '&lt;')
+        .replace(/>/g, '&gt;')
+        .replace(/"/g, '&quot;')
+        .replace(/'/g, '&apos;');
+}
+
+export function renderAtomFeed(feed: AtomFeed): string {
+    const updated = feed.entries.length > 0 ? feed.entries[0].updated : new Date(0).toISOString();
+    let xml = '<?xml version="1.0" encoding="utf-8"?>\n';
+    xml += '<feed xmlns="http://www.w3.org/2005/Atom">\n';
+    xml += `  <title>${escapeXml(feed.title)}</title>\n`;
+    xml += `  <id>${escapeXml(feed.selfLink)}</id>\n`;
+    xml += `  <link rel="self" href="${escapeXml(feed.selfLink)}"/>\n`;
+    xml += `  <updated>${updated}</updated>\n`;
+    xml += '  <author><name>zk-activity</name></author>\n';
+    for (const entry of feed.entries) {
+        xml += '  <entry>\n';
+        xml += `    <id>${escapeXml(entry.id)}</id>\n`;
+        xml += `    <title>${escapeXml(entry.title)}</title>\n`;
+        xml += `    <link href="${escapeXml(entry.link)}"/>\n`;
+        xml += `    <updated>${entry.updated}</updated>\n`;
+        xml += `    <summary>${escapeXml(entry.summary)}</summary>\n`;
+        xml += '  </entry>\n';
+    }
+    xml += '</feed>\n';
+    return xml;
+}
+
+// Serves the recent matches of subscriptions with a feed token as Atom feeds under /feed/<token>.atom, and the
+// proxied images under /images/<file>
+export class FeedServer {
+    readonly port: number;
+    // Public URL the feed links are built from, e.g. https://zk.example.com
+    readonly baseUrl: string;
+    private resolver: FeedResolver;
+    private imageProxy: ImageProxy | null;
+    private server: http.Server | null = null;
+
+    constructor(port: number, baseUrl: string, resolver: FeedResolver, imageProxy: ImageProxy | null = null) {
+        this.port = port;
+        this.baseUrl = baseUrl.replace(/\/+$/, '');
+        this.resolver = resolver;
+        this.imageProxy = imageProxy;
+    }
+
+    // Null if FEED_PORT is not set, feeds are disabled in that case
+    static fromEnv(resolver: FeedResolver, env: NodeJS.ProcessEnv = process.env, imageProxy: ImageProxy | null = null): FeedServer | null {
+        if (!env.FEED_PORT) {
+            return null;
+        }
+        const port = Number(env.FEED_PORT);
+        return new FeedServer(port, env.FEED_BASE_URL || `http://localhost:${port}`, resolver, imageProxy);
+    }
+
+    feedUrl(token: string): string {
+        return `${this.baseUrl}/feed/${token}.atom`;
+    }
+
+    start() {
+        this.server = http.createServer((request, response) => {
+            this.handle(request, response).catch(e => {
+                logger.error(`failed to serve ${request.url}: ${e}`);
+                response.writeHead(500);
+                response.end();
+            });
+        });
+        this.server.listen(this.port);
+        logger.info(`Serving Atom feeds on port ${this.port}`);
+    }
+
+    stop() {
+        this.server?.close();
+        this.server = null;
+    }
+
+    private async handle(request: http.IncomingMessage, response: http.ServerResponse) {
+        const imageMatch = request.method === 'GET' ? IMAGE_PATH.exec(request.url || '') : null;
+        if (imageMatch) {
+            const image = this.imageProxy?.read(imageMatch[1]);
+            if (image) {
+                // The copies never change, the file name is the hash of the original URL
+                response.writeHead(200, {'Content-Type': imageContentType(image), 'Cache-Control': 'public, max-age=86400'});
+                response.end(image);
+                return;
+            }
+        }
+        const match = request.method === 'GET' ? FEED_PATH.exec(request.url || '') : null;
+        const feed = match ? await this.resolver(match[1]) : null;
+        if (!feed) {
+            response.writeHead(404, {'Content-Type': 'text/plain'});
+            response.end('Not found');
+            return;
+        }
+        response.writeHead(200, {'Content-Type': 'application/atom+xml; charset=utf-8'});
+        response.end(renderAtomFeed(feed));
+    }
+}
@@ -0,0 +1,181 @@
+import {randomBytes} from 'crypto';
+import {LimitType} from '../zKillSubscriber';
+
+// Stands for the minimum value of the subscription, which is no limit type
+export const MIN_VALUE_CONDITION = 'minValue';
+
+export interface BuilderConditionKind {
+    key: LimitType | typeof MIN_VALUE_CONDITION;
+    // Modal labels have at most 45 characters
+    label: string;
+    // Placeholder of the value input, at most 100 characters
+    hint: string;
+}
+
+// Conditions of the builder's select menu, Discord allows at most 25 options. The others are set with /zkill-filter
+export const BUILDER_CONDITIONS: BuilderConditionKind[] = [
+    {key: MIN_VALUE_CONDITION, label: 'Minimum value in ISK', hint: 'e.g. 1000000000'},
+    {key: LimitType.ALLIANCE, label: 'Alliance IDs', hint: 'Comma separated, victim or attacker'},
+    {key: LimitType.CORPORATION, label: 'Corporation IDs', hint: 'Comma separated, victim or attacker'},
+    {key: LimitType.CHARACTER, label: 'Character IDs', hint: 'Comma separated, victim or attacker'},
+    {key: LimitType.REGION, label: 'Region IDs', hint: 'Comma separated, e.g. 10000002'},
+    {key: LimitType.CONSTELLATION, label: 'Constellation IDs', hint: 'Comma separated, e.g. 20000020'},
+    {key: LimitType.SYSTEM, label: 'System IDs', hint: 'Comma separated, e.g. 30000142'},
+    {key: LimitType.SHIP_INCLUSION_TYPE_ID, label: 'Ship type IDs', hint: 'Comma separated IDs or capitals, supers, structures, industrials, blops'},
+    {key: LimitType.SHIP_EXCLUSION_TYPE_ID, label: 'Excluded ship type IDs', hint: 'Comma separated IDs or capitals, supers, structures, industrials, blops'},
+    {key: LimitType.SPACE_TYPE, label: 'Space types', hint: 'highsec, lowsec, nullsec, wormhole, pochven, abyssal'},
+    {key: LimitType.SECURITY_MIN_INCLUSIVE, label: 'Minimum security status of the system', hint: 'e.g. 0.5'},
+    {key: LimitType.SECURITY_MAX_INCLUSIVE, label: 'Maximum security status of the system', hint: 'e.g. 0.4'},
+    {key: LimitType.MIN_NUM_INVOLVED, label: 'Minimum number of attackers', hint: 'e.g. 10'},
+    {key: LimitType.NPC_ONLY, label: 'NPC kills only', hint: 'true'},
+    {key: LimitType.NAME_FRAGMENT, label: 'Name fragment of the matched ships', hint: 'e.g. Caldari'},
+    {key: LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME, label: 'Jump range from a system', hint: 'systemName:maxLy or systemName:shipClass, e.g. Jita:dread'},
+    {key: LimitType.JUMPS_FROM_SYSTEM, label: 'Gate jumps from a system', hint: 'systemId:maxJumps, e.g. 30000142:5'},
+    {key: LimitType.VICTIM_CATEGORY, label: 'Victim category', hint: 'capsule, ship, structure, starbase, deployable, fighter'},
+    {key: LimitType.VICTIM_TECH_LEVEL, label: 'Tech level of the victim ship', hint: 't1, t2, t3, faction, deadspace, officer'},
+    {key: LimitType.HULL_VALUE, label: 'Market price of the victim hull', hint: 'min:max in ISK, either may be empty'},
+    {key: LimitType.FACTION, label: 'Faction IDs or militias', hint: 'caldari, minmatar, amarr, gallente, militia or faction IDs'},
+    {key: LimitType.GANG_SIZE, label: 'Gang size of the attackers', hint: 'solo, small, mid, fleet, blob'},
+    {key: LimitType.ATTACKER_SEC_STATUS, label: 'Security status of an attacking pilot', hint: 'min:max, e.g. :-5'},
+    {key: LimitType.PERSPECTIVE, label: 'Kills or losses of the own entities', hint: 'kills, losses or both'},
+    {key: LimitType.ENTITY_LIST_INCLUDE, label: 'Entity lists of /zkill-bluelist', hint: 'Comma separated list names'},
+];
+
+export interface BuilderCondition {
+    key: string;
+    value: string;
+}
+
+// A filter being built. The groups are alternatives, a kill matches if it matches every condition of one of them
+export interface FilterDraft {
+    guildId: string;
+    channelId: string;
+    userId: string;
+    // ID of the first subscription, the other groups take the next free IDs
+    id?: string;
+    groups: BuilderCondition[][];
+    updatedAt: number;
+}
+
+// Replaces a condition of the same kind in the current group, the last one
+export function addCondition(draft: FilterDraft, key: string, value: string) {
+    const group = draft.groups[draft.groups.length - 1];
+    const existing = group.filter(condition => condition.key === key)[0];
+    if (existing) {
+        existing.value = value;
+    } else {
+        group.push({key, value});
+    }
+}
+
+// Starts an Or group, unless the current group has no conditions yet
+export function addOrGroup(draft: FilterDraft) {
+    if (draft.groups[draft.groups.length - 1].length > 0) {
+        draft.groups.push([]);
+    }
+}
+
+// Removes the last condition, or the empty last group
+export function removeLastCondition(draft: FilterDraft) {
+    const group = draft.groups[draft.groups.length - 1];
+    if (group.length > 0) {
+        group.pop();
+    } else if (draft.groups.length > 1) {
+        draft.groups.pop();
+    }
+}
+
+function conditionName(condition: BuilderCondition): string {
+    return `${condition.key} = ${condition.value}`;
+}
+
+// Readable form of the filter, e.g. `(alliance = 1 AND minValue = 5) OR region = 10000002`
+export function draftName(draft: FilterDraft): string {
+    const groups = draft.groups.filter(group => group.length > 0);
+    if (groups.length === 0) {
+        return 'every kill';
+    }
+    return groups.map(group => {
+        const name = group.map(conditionName).join(' AND ');
+        return groups.length > 1 && group.length > 1 ? `(${name})` : name;
+    }).join(' OR ');
+}
+
+// One subscription per group with conditions, the minimum value is kept apart from the limit types
+export function draftSubscriptions(draft: FilterDraft): { limitTypes: Map<LimitType, string>, minValue: number }[] {
+    return draft.groups.filter(group => group.length > 0).map(group => {
+        const limitTypes = new Map<LimitType, string>();
+        let minValue = 0;
+        group.forEach(condition => {
+            if (condition.key === MIN_VALUE_CONDITION) {
+                minValue = Number(condition.value);
+            } else {
+                limitTypes.set(<LimitType>condition.key, condition.value);
+            }
+        });
+        return {limitTypes, minValue};
+    });
+}
+
+export enum BuilderAction {
+    // Select menu of the condition kinds
+    ADD = 'add',
+    // Modal asking for the value of a condition
+    VALUE = 'value',
+    OR = 'or',
+    UNDO = 'undo',
+    SAVE = 'save',
+    CANCEL = 'cancel',
+}
+
+const BUILDER_PREFIX = 'fbuild';
+
+export function builderCustomId(action: BuilderAction, sessionId: string, key?: string): string {
+    return [BUILDER_PREFIX, action, sessionId, key ?? ''].join('_');
+}
+
+// Null if the custom ID does not belong to the filter builder
+export function parseBuilderCustomId(customId: string): { action: BuilderAction, sessionId: string, key?: string } | null {
+    const match = /^fbuild_(add|value|or|undo|save|cancel)_([0-9a-f]+)_(\w*)$/.exec(customId);
+    if (!match) {
+        return null;
+    }
+    return {action: <BuilderAction>match[1], sessionId: match[2], key: match[3] !== '' ? match[3] : undefined};
+}
+
+// Drafts stop being editable once their ephemeral message expires after 15 minutes
+const SESSION_TTL_MS = 15 * 60 * 1000;
+
+// Drafts of the running builders, kept in memory only
+export class FilterBuilderSessions {
+    private drafts = new Map<string, FilterDraft>();
+
+    create(guildId: string, channelId: string, userId: string, id?: string, now = Date.now()): string {
+        this.prune(now);
+        const sessionId = randomBytes(6).toString('hex');
+        this.drafts.set(sessionId, {guildId, channelId, userId, id, groups: [[]], updatedAt: now});
+        return sessionId;
+    }
+
+    // Marks the draft as used, so it lives another 15 minutes
+    get(sessionId: string, now = Date.now()): FilterDraft | undefined {
+        this.prune(now);
+        const draft = this.drafts.get(sessionId);
+        if (draft) {
+            draft.updatedAt = now;
+        }
+        return draft;
+    }
+
+    delete(sessionId: string) {
+        this.drafts.delete(sessionId);
+    }
+
+    private prune(now: number) {
+        Array.from(this.drafts.entries()).forEach(([sessionId, draft]) => {
+            if (now - draft.updatedAt > SESSION_TTL_MS) {
+                this.drafts.delete(sessionId);
+            }
+        });
+    }
+}
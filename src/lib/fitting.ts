@@ -0,0 +1,71 @@
+import {VictimItem} from '../zKillSubscriber';
+
+export enum FittingSlot {
+    LOW = 'low',
+    MID = 'mid',
+    HIGH = 'high',
+    RIG = 'rig',
+    SUBSYSTEM = 'subsystem',
+    DRONE = 'drone',
+    CARGO = 'cargo',
+}
+
+// Inventory flag ranges of the SDE, in the order EFT lists the sections
+const SLOT_FLAGS: [FittingSlot, number, number][] = [
+    [FittingSlot.LOW, 11, 18],
+    [FittingSlot.MID, 19, 26],
+    [FittingSlot.HIGH, 27, 34],
+    [FittingSlot.RIG, 92, 99],
+    [FittingSlot.SUBSYSTEM, 125, 132],
+    [FittingSlot.DRONE, 87, 87],
+    [FittingSlot.CARGO, 5, 5],
+];
+
+export const CHARGE_CATEGORY_ID = 8;
+
+// Null for flags that are not part of a fit, like the fleet hangar or implants
+export function getFittingSlot(flag: number): FittingSlot | null {
+    const slot = SLOT_FLAGS.find(([, first, last]) => flag >= first && flag <= last);
+    return slot ? slot[0] : null;
+}
+
+function quantity(item: VictimItem): number {
+    return (item.quantity_dropped ?? 0) + (item.quantity_destroyed ?? 0);
+}
+
+// Renders the victim's items in EFT format, `names` and `charges` hold the type names and the type IDs of charges.
+// Charges share the flag of the module they are loaded into and are appended to it, like EFT does.
+export function formatEft(
+    shipName: string,
+    items: VictimItem[],
+    names: Map<number, string>,
+    charges: Set<number>,
+): string {
+    const name = (typeId: number) => names.get(typeId) ?? String(typeId);
+    const sections: string[] = [];
+    for (const [slot, first, last] of SLOT_FLAGS) {
+        const slotItems = items.filter(item => item.flag >= first && item.flag <= last);
+        if (slotItems.length === 0) {
+            continue;
+        }
+        const lines: string[] = [];
+        if (slot === FittingSlot.DRONE || slot === FittingSlot.CARGO) {
+            const counts = new Map<number, number>();
+            slotItems.forEach(item => counts.set(item.item_type_id, (counts.get(item.item_type_id) ?? 0) + quantity(item)));
+            counts.forEach((count, typeId) => lines.push(`${name(typeId)} x${count}`));
+        } else {
+            for (let flag = first; flag <= last; flag++) {
+                const inFlag = slotItems.filter(item => item.flag === flag);
+                const fitted = inFlag.find(item => !charges.has(item.item_type_id));
+                const charge = inFlag.find(item => charges.has(item.item_type_id));
+                if (fitted) {
+                    lines.push(charge ? `${name(fitted.item_type_id)}, ${name(charge.item_type_id)}` : name(fitted.item_type_id));
+                }
+            }
+        }
+        if (lines.length > 0) {
+            sections.push(lines.join('\n'));
+        }
+    }
+    return [`[${shipName}, Loss]`, ...sections].join('\n\n');
+}
@@ -0,0 +1,68 @@
+// Ship classes of the fleet summary with the inventory group IDs they stand for, largest ships first
+export const SHIP_CLASSES: { singular: string, plural: string, groupIds: number[] }[] = [
+    {singular: 'titan', plural: 'titans', groupIds: [30]},
+    {singular: 'super', plural: 'supers', groupIds: [659]},
+    {singular: 'carrier', plural: 'carriers', groupIds: [547]},
+    {singular: 'dread', plural: 'dreads', groupIds: [485, 4594]},
+    {singular: 'FAX', plural: 'FAXes', groupIds: [1538]},
+    {singular: 'marauder', plural: 'marauders', groupIds: [900]},
+    {singular: 'black ops', plural: 'black ops', groupIds: [898]},
+    {singular: 'battleship', plural: 'battleships', groupIds: [27]},
+    {singular: 'command ship', plural: 'command ships', groupIds: [540]},
+    {singular: 'battlecruiser', plural: 'battlecruisers', groupIds: [419, 1201]},
+    {singular: 'T3C', plural: 'T3Cs', groupIds: [963]},
+    {singular: 'HAC', plural: 'HACs', groupIds: [358]},
+    {singular: 'HIC', plural: 'HICs', groupIds: [894]},
+    {singular: 'recon', plural: 'recons', groupIds: [833, 906]},
+    {singular: 'logi', plural: 'logis', groupIds: [832]},
+    {singular: 'cruiser', plural: 'cruisers', groupIds: [26, 1972]},
+    {singular: 'T3D', plural: 'T3Ds', groupIds: [1305]},
+    {singular: 'command destroyer', plural: 'command destroyers', groupIds: [1534]},
+    {singular: 'dictor', plural: 'dictors', groupIds: [541]},
+    {singular: 'destroyer', plural: 'destroyers', groupIds: [420]},
+    {singular: 'bomber', plural: 'bombers', groupIds: [834]},
+    {singular: 'interceptor', plural: 'interceptors', groupIds: [831]},
+    {singular: 'assault frigate', plural: 'assault frigates', groupIds: [324]},
+    {singular: 'covert ops', plural: 'covert ops', groupIds: [830]},
+    {singular: 'EAF', plural: 'EAFs', groupIds: [893]},
+    {singular: 'logi frigate', plural: 'logi frigates', groupIds: [1527]},
+    {singular: 'frigate', plural: 'frigates', groupIds: [25, 1283]},
+    {singular: 'industrial', plural: 'industrials', groupIds: [28, 380, 463, 513, 543, 883, 902, 941, 1202]},
+    {singular: 'pod', plural: 'pods', groupIds: [29, 31, 237]},
+];
+
+// Number of classes listed, the ships of the smaller classes are summed up as others
+const MAX_CLASSES = 6;
+
+// Summary like `2 dreads, 5 HACs, 12 frigates` of the attackers' ship groups, null if no group is known.
+// Structures, NPCs and unknown groups count as others
+export function fleetComposition(groupIds: (number | null)[], maxClasses = MAX_CLASSES): string | null {
+    const counts = SHIP_CLASSES.map(() => 0);
+    let others = 0;
+    for (const groupId of groupIds) {
+        const index = groupId != null ? SHIP_CLASSES.findIndex(shipClass => shipClass.groupIds.includes(groupId)) : -1;
+        if (index >= 0) {
+            counts[index]++;
+        } else {
+            others++;
+        }
+    }
+    const parts: string[] = [];
+    counts.forEach((count, index) => {
+        if (count === 0) {
+            return;
+        }
+        if (parts.length < maxClasses) {
+            parts.push(`${count} ${count === 1 ? SHIP_CLASSES[index].singular : SHIP_CLASSES[index].plural}`);
+        } else {
+            others += count;
+        }
+    });
+    if (parts.length === 0) {
+        return null;
+    }
+    if (others > 0) {
+        parts.push(`${others} ${others === 1 ? 'other' : 'others'}`);
+    }
+    return parts.join(', ');
+}
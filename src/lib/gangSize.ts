@@ -0,0 +1,35 @@
+import {ZkData} from '../zKillSubscriber';
+
+export enum GangSizeClass {
+    SOLO = 'solo',
+    SMALL = 'small',
+    MID = 'mid',
+    FLEET = 'fleet',
+    BLOB = 'blob',
+}
+
+// Largest number of attacking pilots of each class, in ascending order, larger kills are blobs
+const GANG_SIZE_LIMITS: [GangSizeClass, number][] = [
+    [GangSizeClass.SOLO, 1],
+    [GangSizeClass.SMALL, 5],
+    [GangSizeClass.MID, 30],
+    [GangSizeClass.FLEET, 100],
+];
+
+// Counts the attacking pilots, NPCs and structures only count on kills without any pilot
+export function attackingPilotCount(data: ZkData): number {
+    const pilots = data.attackers.filter(attacker => attacker.character_id != null).length;
+    return pilots > 0 ? pilots : data.attackers.length;
+}
+
+export function classifyGangSize(pilotCount: number): GangSizeClass {
+    const limit = GANG_SIZE_LIMITS.find(([, max]) => pilotCount <= max);
+    return limit ? limit[0] : GangSizeClass.BLOB;
+}
+
+// Comma separated classes, null if an entry is not a class
+export function parseGangSizeClasses(value: string): GangSizeClass[] | null {
+    const classes = value.split(',').map(part => part.trim().toLowerCase());
+    const known = Object.values(GangSizeClass) as string[];
+    return classes.every(part => known.includes(part)) ? classes as GangSizeClass[] : null;
+}
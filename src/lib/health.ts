@@ -0,0 +1,124 @@
+import * as http from 'http';
+import {logger} from './logger';
+
+// Sources of the health report, implemented by the subscriber
+export interface HealthProbe {
+    // Time a kill was last received from the kill source, null if none since the start
+    lastKillAt(): number | null;
+    discordConnected(): boolean;
+    esiReachable(): Promise<boolean>;
+}
+
+export interface HealthReport {
+    ok: boolean;
+    lastKillAt: string | null;
+    secondsSinceLastKill: number | null;
+    discordConnected: boolean;
+    esiReachable: boolean;
+}
+
+// True if no kill was received for longer than maxKillAgeMs, counting from the start until the first kill
+export function isKillStreamStalled(lastKillAt: number | null, startedAt: number, maxKillAgeMs: number, now = Date.now()): boolean {
+    return now - (lastKillAt ?? startedAt) > maxKillAgeMs;
+}
+
+export async function buildHealthReport(probe: HealthProbe, startedAt: number, maxKillAgeMs: number, now = Date.now()): Promise<HealthReport> {
+    const lastKillAt = probe.lastKillAt();
+    const discordConnected = probe.discordConnected();
+    const esiReachable = await probe.esiReachable();
+    return {
+        ok: discordConnected && esiReachable && !isKillStreamStalled(lastKillAt, startedAt, maxKillAgeMs, now),
+        lastKillAt: lastKillAt != null ? new Date(lastKillAt).toISOString() : null,
+        secondsSinceLastKill: lastKillAt != null ? Math.floor((now - lastKillAt) / 1000) : null,
+        discordConnected,
+        esiReachable,
+    };
+}
+
+// Serves GET /healthz, 200 with the report if healthy and 503 otherwise, for liveness probes
+export class HealthServer {
+    readonly port: number;
+    // No kill for this long marks the bot unhealthy
+    readonly maxKillAgeMs: number;
+    private probe: HealthProbe;
+    private startedAt = Date.now();
+    private server: http.Server | null = null;
+
+    constructor(port: number, maxKillAgeMs: number, probe: HealthProbe) {
+        this.port = port;
+        this.maxKillAgeMs = maxKillAgeMs;
+        this.probe = probe;
+    }
+
+    // Null if HEALTH_PORT is not set
+    static fromEnv(probe: HealthProbe, env: NodeJS.ProcessEnv = process.env): HealthServer | null {
+        if (!env.HEALTH_PORT) {
+            return null;
+        }
+        return new HealthServer(Number(env.HEALTH_PORT), Number(env.WATCHDOG_MINUTES || 15) * 60 * 1000, probe);
+    }
+
+    start() {
+        this.server = http.createServer((request, response) => {
+            this.handle(request, response).catch(e => {
+                logger.error(`failed to serve ${request.url}: ${e}`);
+                response.writeHead(500);
+                response.end();
+            });
+        });
+        this.server.listen(this.port);
+        logger.info(`Serving health checks on port ${this.port}`);
+    }
+
+    stop() {
+        this.server?.close();
+        this.server = null;
+    }
+
+    private async handle(request: http.IncomingMessage, response: http.ServerResponse) {
+        if (request.method !== 'GET' || (request.url || '').split('?')[0] !== '/healthz') {
+            response.writeHead(404, {'Content-Type': 'text/plain'});
+            response.end('Not found');
+            return;
+        }
+        const report = await buildHealthReport(this.probe, this.startedAt, this.maxKillAgeMs);
+        response.writeHead(report.ok ? 200 : 503, {'Content-Type': 'application/json'});
+        response.end(JSON.stringify(report));
+    }
+}
+
+// Checks every minute whether kills still arrive, reports a stall once until kills arrive again
+export class KillWatchdog {
+    readonly maxKillAgeMs: number;
+    private probe: HealthProbe;
+    private onStall: (minutes: number) => void;
+    private startedAt = Date.now();
+    private stalled = false;
+    private timer: ReturnType<typeof setInterval> | null = null;
+
+    constructor(maxKillAgeMs: number, probe: HealthProbe, onStall: (minutes: number) => void) {
+        this.maxKillAgeMs = maxKillAgeMs;
+        this.probe = probe;
+        this.onStall = onStall;
+    }
+
+    start() {
+        this.timer = setInterval(() => this.check(), 60 * 1000);
+    }
+
+    stop() {
+        if (this.timer) {
+            clearInterval(this.timer);
+            this.timer = null;
+        }
+    }
+
+    check(now = Date.now()) {
+        const lastKillAt = this.probe.lastKillAt();
+        const stalled = isKillStreamStalled(lastKillAt, this.startedAt, this.maxKillAgeMs, now);
+        if (stalled && !this.stalled) {
+            this.onStall(Math.floor((now - (lastKillAt ?? this.startedAt)) / 60000));
+        }
+        this.stalled = stalled;
+    }
+}
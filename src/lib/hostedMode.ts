@@ -0,0 +1,67 @@
+import {MessageMentionOptions} from 'discord.js';
+
+// Limits for publicly hosted instances shared by many communities, configured through the environment
+export class HostedMode {
+    readonly enabled: boolean;
+    readonly maxSubscriptionsPerGuild: number;
+    readonly maxPostsPerDay: number;
+    // Guilds an operator approved to mention roles and @everyone
+    readonly pingApprovedGuilds: string[];
+
+    // Mapping of guild ID to the number of posts on the current UTC day
+    private postCounts = new Map<string, { day: string, count: number }>();
+
+    constructor(enabled: boolean, maxSubscriptionsPerGuild: number, maxPostsPerDay: number, pingApprovedGuilds: string[]) {
+        this.enabled = enabled;
+        this.maxSubscriptionsPerGuild = maxSubscriptionsPerGuild;
+        this.maxPostsPerDay = maxPostsPerDay;
+        this.pingApprovedGuilds = pingApprovedGuilds;
+    }
+
+    static fromEnv(env: NodeJS.ProcessEnv = process.env): HostedMode {
+        return new HostedMode(
+            env.HOSTED_MODE === 'true',
+            Number(env.HOSTED_MAX_SUBSCRIPTIONS_PER_GUILD || 25),
+            Number(env.HOSTED_MAX_POSTS_PER_DAY || 500),
+            (env.HOSTED_PING_APPROVED_GUILDS || '').split(',').filter(id => id !== ''),
+        );
+    }
+
+    canSubscribe(currentSubscriptions: number): boolean {
+        return !this.enabled || currentSubscriptions < this.maxSubscriptionsPerGuild;
+    }
+
+    // Counts a post towards the daily quota, returns false if the quota is used up
+    tryConsumePost(guildId: string, now = new Date()): boolean {
+        if (!this.enabled) {
+            return true;
+        }
+        const day = now.toISOString().substring(0, 10);
+        const posts = this.postCounts.get(guildId);
+        if (!posts || posts.day !== day) {
+            this.postCounts.set(guildId, {day, count: 1});
+            return true;
+        }
+        if (posts.count >= this.maxPostsPerDay) {
+            return false;
+        }
+        posts.count++;
+        return true;
+    }
+
+    // Gives a post back to the quota if it could not be sent
+    refundPost(guildId: string, now = new Date()) {
+        const posts = this.postCounts.get(guildId);
+        if (posts && posts.day === now.toISOString().substring(0, 10) && posts.count > 0) {
+            posts.count--;
+        }
+    }
+
+    canPing(guildId: string): boolean {
+        return !this.enabled || this.pingApprovedGuilds.includes(guildId);
+    }
+
+    allowedMentions(guildId: string): MessageMentionOptions | undefined {
+        return this.canPing(guildId) ? undefined : {parse: []};
+    }
+}
@@ -0,0 +1,422 @@
+export enum Locale {
+    EN = 'en',
+    DE = 'de',
+    RU = 'ru',
+}
+
+export const LOCALE_NAMES: { [locale in Locale]: string } = {
+    [Locale.EN]: 'English',
+    [Locale.DE]: 'Deutsch',
+    [Locale.RU]: 'Русский',
+};
+
+type Strings = { [key: string]: string };
+
+const EN: Strings = {
+    'embed.location.celestial': 'on {celestial} {distance} away',
+    'embed.location.system': 'in {system} ({region})',
+    'embed.location.abyssal': 'Abyssal Deadspace pocket',
+    'embed.jumpRange': '{distance} LY from {system}, in jump range of {classes}',
+    'embed.lastSeen': '{name} last seen {time} ago in {system}',
+    'embed.alliance': 'Alliance',
+    'embed.corporation': 'Corp',
+    'embed.corpSize': '({count}-man corp)',
+    'embed.gangSize.solo': 'Solo',
+    'embed.gangSize.small': 'Small gang',
+    'embed.gangSize.mid': 'Mid gang',
+    'embed.gangSize.fleet': 'Fleet',
+    'embed.gangSize.blob': 'Blob',
+    'embed.outcome.kill': 'KILL',
+    'embed.outcome.loss': 'LOSS',
+    'embed.pilot': 'Pilot',
+    'embed.engagement': '__Engagement__ - {count} pilots involved',
+    'embed.fleet': '**Fleet:** {composition}',
+    'embed.engagement.avgSecStatus': ', average security status {value}',
+    'embed.engagement.trackedAttackers': ', {count} from tracked entities',
+    'embed.attacker': '__Attacker (Final Blow)__',
+    'embed.victim': '__Victim__',
+    'embed.preview.victimTickers': 'Victim: {tickers}',
+    'embed.preview.attackerTickers': 'Final blow: {tickers}',
+    'embed.postedLater': 'posted {time} later',
+    'embed.fleetKilled': '{count}+ ships killed {ship} in {system} ({region})',
+    'embed.mostCommonShips': '{count}x `{ship}` most common ships in the fleet, {time}',
+    'embed.died': 'Died {time}',
+    'embed.diedTo': 'Died to {count}x `{ship}`, {time}',
+    'embed.attacking': '{ship} attacking in {system} ({region})',
+    'embed.destroyed': '`{ship}` destroyed, {time}',
+    'embed.killedIn': '{ship} killed in {system} ({region})',
+    'embed.value': 'Value: {total}',
+    'embed.valueBreakdown': 'Hull {hull} / Fit {fit} / Total {total}',
+    'embed.valueBreakdownNoHull': 'Fit {fit} / Total {total}',
+    'embed.fitting': '__Fitting__',
+    'embed.structure': '__Structure__',
+    'embed.structure.loot': 'Loot dropped: {value}',
+    'embed.structure.core.dropped': 'Quantum core dropped',
+    'embed.structure.core.destroyed': 'Quantum core destroyed',
+    'embed.structure.core.none': 'No quantum core fitted',
+    'structure.kind.citadel': 'Citadel',
+    'structure.kind.engineeringComplex': 'Engineering Complex',
+    'structure.kind.refinery': 'Refinery',
+    'structure.kind.flex': 'FLEX structure',
+    'structure.kind.moonDrill': 'Moon Drill',
+    'embed.eveTime': 'EVE Time',
+    'embed.matchedBy': 'Matched by {subscriptions}',
+    'embed.tracking': 'Since {since}: {destroyed} ISK destroyed, {lost} ISK lost',
+    'summary.lost': '{entity} lost their {ship} in {system}',
+    'summary.killed': '{entity} killed {victim}\'s {ship} in {system}',
+    'summary.killedWith': '{entity} ({attackerShip}) killed {victim}\'s {ship} in {system}',
+    'srp.request': 'Request SRP',
+    'srp.requested': 'Requested ship replacement for the {ship} of {pilot}',
+    'srp.alreadyRequested': 'Ship replacement for this loss was already requested',
+    'time.year.one': '1 year',
+    'time.year.other': '{count} years',
+    'time.month.one': '1 month',
+    'time.month.other': '{count} months',
+    'time.week.one': '1 week',
+    'time.week.other': '{count} weeks',
+    'time.day.one': '1 day',
+    'time.day.other': '{count} days',
+    'time.hour.one': '1 hour',
+    'time.hour.other': '{count} hours',
+    'time.minute.one': '1 minute',
+    'time.minute.other': '{count} minutes',
+    'time.second.one': '1 second',
+    'time.second.other': '{count} seconds',
+    'command.subscribed': 'We subscribed to zkillboard channel: {channel}',
+    'command.unsubscribed': 'Unsubscribed to zkillboard channel: {channel} {id}',
+    'command.notSubscribed': 'This channel has no {channel} subscription with ID {id}, leave the ID empty to pick from its subscriptions',
+    'command.languageSet': 'Kill messages and replies in this server are now in {language}',
+    'command.notPermitted': 'Managing subscriptions requires the Manage Channels permission or the bot admin role',
+    'command.yes': 'yes',
+    'command.no': 'no',
+    'command.noSubscription': 'No subscription with ID {id} found in this channel',
+    'command.filter.set': 'Set filter {limit}: {value} on subscription ID: {id}',
+    'command.filter.removed': 'Removed filter {limit} from subscription ID: {id}',
+    'command.filter.neverPosts': 'The subscription would never post a kill:',
+    'command.filter.invalidFaction': 'Factions have to be faction IDs or caldari, minmatar, amarr, gallente or militia',
+    'command.filter.invalidNpcSpawn': 'NPC spawns have to be NPC group IDs or officers, commanders or capitals',
+    'command.filter.invalidNameScope': 'Name scopes have to be ship, pilot, corporation or alliance',
+    'command.filter.invalidSecStatus': 'Security status ranges have to be min:max from -10 to 10, e.g. :-5',
+    'command.filter.invalidTechLevel': 'Tech levels have to be t1, t2, t3, faction, deadspace or officer',
+    'command.filter.invalidGangSize': 'Gang sizes have to be solo, small, mid, fleet or blob',
+    'command.filter.invalidPerspective': 'The perspective has to be kills, losses or both',
+    'command.filter.invalidExchangeOutcome': 'The exchange outcome has to be won or lost with an optional window of up to 360 minutes, e.g. won:60',
+    'command.filter.ownEntitiesMissing': 'Set the own entities of this server with /zkill-settings first',
+    'command.filter.unknownEntityList': 'There is no entity list {lists} on this server, create it with /zkill-bluelist add',
+    'command.configure.done': 'Configured subscription: {type} ID: {id}',
+    'command.configure.showValueBreakdown': 'Show value breakdown: {value}',
+    'command.configure.suppressPodsAfterLoss': 'Suppress pods after loss: {value}',
+    'command.configure.battleReportThreshold': 'Battle report threshold: {value}',
+    'command.configure.battleReportThreads': 'Battle report threads: {value}',
+    'command.configure.atomFeed': 'Atom feed: {url}',
+    'command.configure.atomFeedDisabled': 'Atom feed: disabled',
+    'command.configure.atomFeedUnavailable': 'Atom feed: this bot instance does not serve feeds',
+    'command.configure.pingRole': 'Ping role: {role}',
+    'command.configure.pingRoleValue': 'Ping role from value: {value}',
+    'command.configure.pingHereValue': 'Ping @here from value: {value}',
+    'command.configure.pingsDisabled': 'Pings are not enabled for this server on this bot instance, kills are posted without mentions',
+    'command.configure.embedColor': 'Embed color: {color}',
+    'command.configure.embedColorDefault': 'Embed color: default',
+    'command.configure.invalidEmbedColor': 'Embed color has to be a hex color like #FF8800, or default',
+    'command.configure.srpButton': 'SRP button on losses: {value}',
+    'command.configure.showFitting': 'Show fitting: {value}',
+    'command.configure.linkButtons': 'Link buttons: {value}',
+    'command.configure.thumbnail': 'Thumbnail: {source}',
+    'command.configure.maxKillAge': 'Maximum kill age: {minutes} minutes',
+    'command.configure.maxKillAgeNone': 'Maximum kill age: none',
+    'command.configure.staleKillsUnpinged': 'Older kills: posted without pings',
+    'command.configure.staleKillsSkipped': 'Older kills: skipped',
+    'command.configure.matchSummary': 'Match summary above the embed: {value}',
+    'command.configure.description': 'Description: {description}',
+    'command.configure.descriptionNone': 'Description: none',
+    'command.configure.dryRunDm': 'Dry run: logged and sent to the creator by direct message',
+    'command.configure.dryRunLog': 'Dry run: logged',
+    'command.configure.dryRunOff': 'Dry run: off, matched kills are posted',
+};
+
+const DE: Strings = {
+    'embed.location.celestial': 'bei {celestial}, {distance} entfernt',
+    'embed.location.system': 'in {system} ({region})',
+    'embed.location.abyssal': 'Tasche im Abgrund-Totraum',
+    'embed.jumpRange': '{distance} LJ von {system}, in Sprungreichweite von {classes}',
+    'embed.lastSeen': '{name} zuletzt vor {time} in {system} gesehen',
+    'embed.alliance': 'Allianz',
+    'embed.corporation': 'Corp',
+    'embed.corpSize': '({count} Mitglieder)',
+    'embed.gangSize.solo': 'Solo',
+    'embed.gangSize.small': 'Kleine Gang',
+    'embed.gangSize.mid': 'Mittlere Gang',
+    'embed.gangSize.fleet': 'Flotte',
+    'embed.gangSize.blob': 'Blob',
+    'embed.outcome.kill': 'ABSCHUSS',
+    'embed.outcome.loss': 'VERLUST',
+    'embed.pilot': 'Pilot',
+    'embed.engagement': '__Gefecht__ - {count} Piloten beteiligt',
+    'embed.fleet': '**Flotte:** {composition}',
+    'embed.engagement.avgSecStatus': ', durchschnittlicher Sicherheitsstatus {value}',
+    'embed.engagement.trackedAttackers': ', {count} von verfolgten Gruppen',
+    'embed.attacker': '__Angreifer (Todesstoß)__',
+    'embed.victim': '__Opfer__',
+    'embed.preview.victimTickers': 'Opfer: {tickers}',
+    'embed.preview.attackerTickers': 'Todesstoß: {tickers}',
+    'embed.postedLater': '{time} später gepostet',
+    'embed.fleetKilled': '{count}+ Schiffe zerstörten {ship} in {system} ({region})',
+    'embed.mostCommonShips': '{count}x `{ship}` häufigste Schiffe der Flotte, {time}',
+    'embed.died': 'Zerstört, {time}',
+    'embed.diedTo': 'Zerstört von {count}x `{ship}`, {time}',
+    'embed.attacking': '{ship} greift an in {system} ({region})',
+    'embed.destroyed': '`{ship}` zerstört, {time}',
+    'embed.killedIn': '{ship} zerstört in {system} ({region})',
+    'embed.value': 'Wert: {total}',
+    'embed.valueBreakdown': 'Rumpf {hull} / Fit {fit} / Gesamt {total}',
+    'embed.valueBreakdownNoHull': 'Fit {fit} / Gesamt {total}',
+    'embed.fitting': '__Ausrüstung__',
+    'embed.structure': '__Struktur__',
+    'embed.structure.loot': 'Gedroppter Loot: {value}',
+    'embed.structure.core.dropped': 'Quantenkern gedroppt',
+    'embed.structure.core.destroyed': 'Quantenkern zerstört',
+    'embed.structure.core.none': 'Kein Quantenkern eingebaut',
+    'structure.kind.citadel': 'Zitadelle',
+    'structure.kind.engineeringComplex': 'Ingenieurskomplex',
+    'structure.kind.refinery': 'Raffinerie',
+    'structure.kind.flex': 'FLEX-Struktur',
+    'structure.kind.moonDrill': 'Mondbohrer',
+    'embed.eveTime': 'EVE-Zeit',
+    'embed.matchedBy': 'Gefunden von {subscriptions}',
+    'embed.tracking': 'Seit {since}: {destroyed} ISK zerstört, {lost} ISK verloren',
+    'summary.lost': '{entity} verlor {ship} in {system}',
+    'summary.killed': '{entity} zerstörte {ship} von {victim} in {system}',
+    'summary.killedWith': '{entity} ({attackerShip}) zerstörte {ship} von {victim} in {system}',
+    'srp.request': 'SRP beantragen',
+    'srp.requested': 'Schiffsersatz für die {ship} von {pilot} beantragt',
+    'srp.alreadyRequested': 'Schiffsersatz für diesen Verlust wurde bereits beantragt',
+    'time.year.one': '1 Jahr',
+    'time.year.other': '{count} Jahre',
+    'time.month.one': '1 Monat',
+    'time.month.other': '{count} Monate',
+    'time.week.one': '1 Woche',
+    'time.week.other': '{count} Wochen',
+    'time.day.one': '1 Tag',
+    'time.day.other': '{count} Tage',
+    'time.hour.one': '1 Stunde',
+    'time.hour.other': '{count} Stunden',
+    'time.minute.one': '1 Minute',
+    'time.minute.other': '{count} Minuten',
+    'time.second.one': '1 Sekunde',
+    'time.second.other': '{count} Sekunden',
+    'command.subscribed': 'zKillboard-Kanal abonniert: {channel}',
+    'command.unsubscribed': 'zKillboard-Kanal abbestellt: {channel} {id}',
+    'command.notSubscribed': 'Dieser Kanal hat kein {channel}-Abonnement mit der ID {id}, ohne ID kannst du aus seinen Abonnements wählen',
+    'command.languageSet': 'Killmails und Antworten in diesem Server sind jetzt auf {language}',
+    'command.notPermitted': 'Zum Verwalten von Abonnements ist die Berechtigung „Kanäle verwalten“ oder die Bot-Admin-Rolle nötig',
+    'command.yes': 'ja',
+    'command.no': 'nein',
+    'command.noSubscription': 'In diesem Kanal gibt es kein Abonnement mit der ID {id}',
+    'command.filter.set': 'Filter {limit}: {value} für Abonnement-ID {id} gesetzt',
+    'command.filter.removed': 'Filter {limit} von Abonnement-ID {id} entfernt',
+    'command.filter.neverPosts': 'Das Abonnement würde nie einen Kill posten:',
+    'command.filter.invalidFaction': 'Fraktionen müssen Fraktions-IDs oder caldari, minmatar, amarr, gallente oder militia sein',
+    'command.filter.invalidNpcSpawn': 'NPC-Spawns müssen NPC-Gruppen-IDs oder officers, commanders oder capitals sein',
+    'command.filter.invalidNameScope': 'Namensbereiche müssen ship, pilot, corporation oder alliance sein',
+    'command.filter.invalidSecStatus': 'Sicherheitsstatus-Bereiche müssen min:max von -10 bis 10 sein, z. B. :-5',
+    'command.filter.invalidTechLevel': 'Tech-Level müssen t1, t2, t3, faction, deadspace oder officer sein',
+    'command.filter.invalidGangSize': 'Gruppengrößen müssen solo, small, mid, fleet oder blob sein',
+    'command.filter.invalidPerspective': 'Die Perspektive muss kills, losses oder both sein',
+    'command.filter.invalidExchangeOutcome': 'Der Ausgang des Gefechts muss won oder lost sein, optional mit einem Zeitfenster von bis zu 360 Minuten, z. B. won:60',
+    'command.filter.ownEntitiesMissing': 'Lege zuerst mit /zkill-settings die eigenen Entitäten dieses Servers fest',
+    'command.filter.unknownEntityList': 'Auf diesem Server gibt es keine Entitätenliste {lists}, erstelle sie mit /zkill-bluelist add',
+    'command.configure.done': 'Abonnement konfiguriert: {type} ID: {id}',
+    'command.configure.showValueBreakdown': 'Wertaufschlüsselung anzeigen: {value}',
+    'command.configure.suppressPodsAfterLoss': 'Kapseln nach Verlust unterdrücken: {value}',
+    'command.configure.battleReportThreshold': 'Schwelle für Kampfberichte: {value}',
+    'command.configure.battleReportThreads': 'Threads für Kampfberichte: {value}',
+    'command.configure.atomFeed': 'Atom-Feed: {url}',
+    'command.configure.atomFeedDisabled': 'Atom-Feed: deaktiviert',
+    'command.configure.atomFeedUnavailable': 'Atom-Feed: diese Bot-Instanz stellt keine Feeds bereit',
+    'command.configure.pingRole': 'Ping-Rolle: {role}',
+    'command.configure.pingRoleValue': 'Ping-Rolle ab Wert: {value}',
+    'command.configure.pingHereValue': '@here-Ping ab Wert: {value}',
+    'command.configure.pingsDisabled': 'Pings sind für diesen Server auf dieser Bot-Instanz nicht aktiviert, Kills werden ohne Erwähnungen gepostet',
+    'command.configure.embedColor': 'Embed-Farbe: {color}',
+    'command.configure.embedColorDefault': 'Embed-Farbe: Standard',
+    'command.configure.invalidEmbedColor': 'Die Embed-Farbe muss eine Hex-Farbe wie #FF8800 oder default sein',
+    'command.configure.srpButton': 'SRP-Button bei Verlusten: {value}',
+    'command.configure.showFitting': 'Fitting anzeigen: {value}',
+    'command.configure.linkButtons': 'Link-Buttons: {value}',
+    'command.configure.thumbnail': 'Vorschaubild: {source}',
+    'command.configure.maxKillAge': 'Maximales Kill-Alter: {minutes} Minuten',
+    'command.configure.maxKillAgeNone': 'Maximales Kill-Alter: keins',
+    'command.configure.staleKillsUnpinged': 'Ältere Kills: ohne Pings gepostet',
+    'command.configure.staleKillsSkipped': 'Ältere Kills: übersprungen',
+    'command.configure.matchSummary': 'Treffer-Zusammenfassung über dem Embed: {value}',
+    'command.configure.description': 'Beschreibung: {description}',
+    'command.configure.descriptionNone': 'Beschreibung: keine',
+    'command.configure.dryRunDm': 'Probelauf: protokolliert und dem Ersteller per Direktnachricht geschickt',
+    'command.configure.dryRunLog': 'Probelauf: protokolliert',
+    'command.configure.dryRunOff': 'Probelauf: aus, passende Kills werden gepostet',
+};
+
+// Abbreviated units avoid the Russian plural forms
+const RU: Strings = {
+    'embed.location.celestial': 'у {celestial}, {distance}',
+    'embed.location.system': 'в {system} ({region})',
+    'embed.location.abyssal': 'Карман Бездны',
+    'embed.jumpRange': '{distance} св. лет от {system}, в радиусе прыжка: {classes}',
+    'embed.lastSeen': '{name} в последний раз: {time} назад в {system}',
+    'embed.alliance': 'Альянс',
+    'embed.corporation': 'Корп.',
+    'embed.corpSize': '({count} чел.)',
+    'embed.gangSize.solo': 'Соло',
+    'embed.gangSize.small': 'Малая группа',
+    'embed.gangSize.mid': 'Средняя группа',
+    'embed.gangSize.fleet': 'Флот',
+    'embed.gangSize.blob': 'Блоб',
+    'embed.outcome.kill': 'УБИЙСТВО',
+    'embed.outcome.loss': 'ПОТЕРЯ',
+    'embed.pilot': 'Пилот',
+    'embed.engagement': '__Бой__ - участвовало пилотов: {count}',
+    'embed.fleet': '**Флот:** {composition}',
+    'embed.engagement.avgSecStatus': ', средний статус безопасности {value}',
+    'embed.engagement.trackedAttackers': ', из отслеживаемых: {count}',
+    'embed.attacker': '__Атакующий (последний удар)__',
+    'embed.victim': '__Жертва__',
+    'embed.preview.victimTickers': 'Жертва: {tickers}',
+    'embed.preview.attackerTickers': 'Последний удар: {tickers}',
+    'embed.postedLater': 'опубликовано спустя {time}',
+    'embed.fleetKilled': '{count}+ кораблей уничтожили {ship} в {system} ({region})',
+    'embed.mostCommonShips': '{count}x `{ship}` — самые частые корабли флота, {time}',
+    'embed.died': 'Уничтожен, {time}',
+    'embed.diedTo': 'Уничтожен {count}x `{ship}`, {time}',
+    'embed.attacking': '{ship} атакует в {system} ({region})',
+    'embed.destroyed': '`{ship}` уничтожен, {time}',
+    'embed.killedIn': '{ship} уничтожен в {system} ({region})',
+    'embed.value': 'Стоимость: {total}',
+    'embed.valueBreakdown': 'Корпус {hull} / Оснащение {fit} / Всего {total}',
+    'embed.valueBreakdownNoHull': 'Оснащение {fit} / Всего {total}',
+    'embed.fitting': '__Фит__',
+    'embed.structure': '__Структура__',
+    'embed.structure.loot': 'Выпало лута: {value}',
+    'embed.structure.core.dropped': 'Квантовое ядро выпало',
+    'embed.structure.core.destroyed': 'Квантовое ядро уничтожено',
+    'embed.structure.core.none': 'Квантовое ядро не установлено',
+    'structure.kind.citadel': 'Цитадель',
+    'structure.kind.engineeringComplex': 'Инженерный комплекс',
+    'structure.kind.refinery': 'Очистительный завод',
+    'structure.kind.flex': 'FLEX-структура',
+    'structure.kind.moonDrill': 'Лунный бур',
+    'embed.eveTime': 'Время EVE',
+    'embed.matchedBy': 'Совпадение в {subscriptions}',
+    'embed.tracking': 'С {since}: уничтожено {destroyed} ISK, потеряно {lost} ISK',
+    'summary.lost': '{entity} потеряли {ship} в {system}',
+    'summary.killed': '{entity} уничтожили {ship} ({victim}) в {system}',
+    'summary.killedWith': '{entity} ({attackerShip}) уничтожили {ship} ({victim}) в {system}',
+    'srp.request': 'Запросить SRP',
+    'srp.requested': 'Запрошена компенсация за {ship} пилота {pilot}',
+    'srp.alreadyRequested': 'Компенсация за эту потерю уже запрошена',
+    'time.year.one': '1 г.',
+    'time.year.other': '{count} г.',
+    'time.month.one': '1 мес.',
+    'time.month.other': '{count} мес.',
+    'time.week.one': '1 нед.',
+    'time.week.other': '{count} нед.',
+    'time.day.one': '1 дн.',
+    'time.day.other': '{count} дн.',
+    'time.hour.one': '1 ч',
+    'time.hour.other': '{count} ч',
+    'time.minute.one': '1 мин',
+    'time.minute.other': '{count} мин',
+    'time.second.one': '1 сек',
+    'time.second.other': '{count} сек',
+    'command.subscribed': 'Подписка на канал zkillboard: {channel}',
+    'command.unsubscribed': 'Подписка на канал zkillboard отменена: {channel} {id}',
+    'command.notSubscribed': 'В этом канале нет подписки {channel} с ID {id}, оставьте ID пустым, чтобы выбрать из его подписок',
+    'command.languageSet': 'Язык сообщений и ответов на этом сервере: {language}',
+    'command.notPermitted': 'Для управления подписками нужно право «Управлять каналами» или роль администратора бота',
+    'command.yes': 'да',
+    'command.no': 'нет',
+    'command.noSubscription': 'В этом канале нет подписки с ID {id}',
+    'command.filter.set': 'Фильтр {limit}: {value} установлен для подписки с ID {id}',
+    'command.filter.removed': 'Фильтр {limit} удалён из подписки с ID {id}',
+    'command.filter.neverPosts': 'Подписка никогда не опубликует ни одного килла:',
+    'command.filter.invalidFaction': 'Фракции задаются ID фракций или caldari, minmatar, amarr, gallente, militia',
+    'command.filter.invalidNpcSpawn': 'NPC задаются ID групп NPC или officers, commanders, capitals',
+    'command.filter.invalidNameScope': 'Области поиска имени: ship, pilot, corporation или alliance',
+    'command.filter.invalidSecStatus': 'Диапазон статуса безопасности задаётся как min:max от -10 до 10, например :-5',
+    'command.filter.invalidTechLevel': 'Тех. уровни: t1, t2, t3, faction, deadspace или officer',
+    'command.filter.invalidGangSize': 'Размер группы: solo, small, mid, fleet или blob',
+    'command.filter.invalidPerspective': 'Перспектива: kills, losses или both',
+    'command.filter.invalidExchangeOutcome': 'Исход размена задаётся как won или lost с необязательным окном до 360 минут, например won:60',
+    'command.filter.ownEntitiesMissing': 'Сначала задайте свои сущности этого сервера командой /zkill-settings',
+    'command.filter.unknownEntityList': 'На этом сервере нет списка сущностей {lists}, создайте его командой /zkill-bluelist add',
+    'command.configure.done': 'Подписка настроена: {type} ID: {id}',
+    'command.configure.showValueBreakdown': 'Разбивка стоимости: {value}',
+    'command.configure.suppressPodsAfterLoss': 'Скрывать капсулы после потери: {value}',
+    'command.configure.battleReportThreshold': 'Порог боевого отчёта: {value}',
+    'command.configure.battleReportThreads': 'Ветки для боевых отчётов: {value}',
+    'command.configure.atomFeed': 'Atom-лента: {url}',
+    'command.configure.atomFeedDisabled': 'Atom-лента: отключена',
+    'command.configure.atomFeedUnavailable': 'Atom-лента: этот экземпляр бота не раздаёт ленты',
+    'command.configure.pingRole': 'Роль для пинга: {role}',
+    'command.configure.pingRoleValue': 'Пинг роли от стоимости: {value}',
+    'command.configure.pingHereValue': 'Пинг @here от стоимости: {value}',
+    'command.configure.pingsDisabled': 'Пинги для этого сервера на этом экземпляре бота не включены, киллы публикуются без упоминаний',
+    'command.configure.embedColor': 'Цвет эмбеда: {color}',
+    'command.configure.embedColorDefault': 'Цвет эмбеда: по умолчанию',
+    'command.configure.invalidEmbedColor': 'Цвет эмбеда задаётся в hex, например #FF8800, или default',
+    'command.configure.srpButton': 'Кнопка SRP при потерях: {value}',
+    'command.configure.showFitting': 'Показывать фит: {value}',
+    'command.configure.linkButtons': 'Кнопки-ссылки: {value}',
+    'command.configure.thumbnail': 'Миниатюра: {source}',
+    'command.configure.maxKillAge': 'Максимальный возраст килла: {minutes} мин',
+    'command.configure.maxKillAgeNone': 'Максимальный возраст килла: без ограничения',
+    'command.configure.staleKillsUnpinged': 'Старые киллы: публикуются без пингов',
+    'command.configure.staleKillsSkipped': 'Старые киллы: пропускаются',
+    'command.configure.matchSummary': 'Сводка совпадения над эмбедом: {value}',
+    'command.configure.description': 'Описание: {description}',
+    'command.configure.descriptionNone': 'Описание: нет',
+    'command.configure.dryRunDm': 'Пробный запуск: в лог и создателю в личные сообщения',
+    'command.configure.dryRunLog': 'Пробный запуск: в лог',
+    'command.configure.dryRunOff': 'Пробный запуск: выключен, совпавшие киллы публикуются',
+};
+
+const STRINGS: { [locale in Locale]: Strings } = {
+    [Locale.EN]: EN,
+    [Locale.DE]: DE,
+    [Locale.RU]: RU,
+};
+
+// Unknown values fall back to English
+export function parseLocale(value?: string): Locale {
+    return (Object.values(Locale) as string[]).indexOf(value || '') !== -1 ? value as Locale : Locale.EN;
+}
+
+// Looks up the string in the locale, falling back to English, and fills in the {placeholders}
+export function translate(locale: Locale, key: string, params: { [name: string]: string | number | undefined } = {}): string {
+    const template = STRINGS[locale][key] ?? EN[key] ?? key;
+    return template.replace(/{(\w+)}/g, (placeholder, name) => params[name] != null ? String(params[name]) : placeholder);
+}
+
+// Largest whole unit of the duration, e.g. `3 hours`
+export function relativeTimeText(locale: Locale, diffMs: number): string {
+    const seconds = Math.floor(diffMs / 1000);
+    const minutes = Math.floor(seconds / 60);
+    const hours = Math.floor(minutes / 60);
+    const days = Math.floor(hours / 24);
+    const weeks = Math.floor(days / 7);
+    const months = Math.floor(weeks / 4);
+    const years = Math.floor(months / 12);
+    const units: [string, number][] = [
+        ['year', years],
+        ['month', months],
+        ['week', weeks],
+        ['day', days],
+        ['hour', hours],
+        ['minute', minutes],
+    ];
+    for (const [unit, count] of units) {
+        if (count >= 1) {
+            return translate(locale, `time.${unit}.${count === 1 ? 'one' : 'other'}`, {count});
+        }
+    }
+    return translate(locale, `time.second.${seconds > 1 ? 'other' : 'one'}`, {count: seconds});
+}
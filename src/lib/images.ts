@@ -0,0 +1,221 @@
+import {Axios} from 'axios';
+import * as fs from 'fs';
+import * as path from 'path';
+import {createHash} from 'crypto';
+
+const IMAGE_URL = 'https://images.evetech.net/';
+// Shown if neither the type nor its group has an image
+export const GENERIC_IMAGE_URL = 'https://zkillboard.com/img/eve-question.png';
+
+// What the kill embed shows as thumbnail, an image of the ship type or of the victim
+export enum ThumbnailSource {
+    RENDER = 'render',
+    ICON = 'icon',
+    LOGO = 'logo',
+    PORTRAIT = 'portrait',
+}
+
+// The pilot, corporation and alliance the portrait and logo thumbnails show
+export interface ThumbnailOwner {
+    character_id?: number | null;
+    corporation_id?: number | null;
+    alliance_id?: number | null;
+}
+
+export function parseThumbnailSource(value?: string): ThumbnailSource {
+    return Object.values(ThumbnailSource).includes(value as ThumbnailSource) ? value as ThumbnailSource : ThumbnailSource.RENDER;
+}
+
+const CHECK_TTL_MS = 24 * 60 * 60 * 1000;
+// Images are not checked for this long after the image server was unreachable, only proxied copies are used meanwhile
+const OUTAGE_MS = 5 * 60 * 1000;
+
+// Returns true if the image exists, false if it is broken and null if that could not be determined
+export type ImageCheck = (url: string) => Promise<boolean | null>;
+
+const axios = new Axios({timeout: 3000});
+
+async function headCheck(url: string): Promise<boolean | null> {
+    try {
+        const response = await axios.head(url);
+        if (response.status >= 200 && response.status < 300) {
+            return true;
+        }
+        return response.status === 404 ? false : null;
+    } catch (e) {
+        return null;
+    }
+}
+
+const PROXY_FILE = /^[0-9a-f]{40}\.img$/;
+
+// Content type of a proxied image, the image server serves PNG and JPEG
+export function imageContentType(image: Buffer): string {
+    return image[0] === 0xff && image[1] === 0xd8 ? 'image/jpeg' : 'image/png';
+}
+
+// Local copies of the images, served by the feed server, so the embeds keep their images while the image server is down
+export class ImageProxy {
+    readonly dir: string;
+    // Public URL of the feed server, e.g. https://zk.example.com
+    readonly baseUrl: string;
+    private downloads = new Set<string>();
+
+    constructor(dir: string, baseUrl: string) {
+        this.dir = dir;
+        this.baseUrl = baseUrl.replace(/\/+$/, '');
+    }
+
+    // Null unless IMAGE_PROXY_DIR is set and the feed server is enabled to serve the copies
+    static fromEnv(env: NodeJS.ProcessEnv = process.env): ImageProxy | null {
+        if (!env.IMAGE_PROXY_DIR || !env.FEED_PORT) {
+            return null;
+        }
+        return new ImageProxy(env.IMAGE_PROXY_DIR, env.FEED_BASE_URL || `http://localhost:${env.FEED_PORT}`);
+    }
+
+    static fileName(url: string): string {
+        return createHash('sha1').update(url).digest('hex') + '.img';
+    }
+
+    // URL of the local copy, null if the image was not downloaded yet
+    proxiedUrl(url: string): string | null {
+        const name = ImageProxy.fileName(url);
+        return fs.existsSync(path.join(this.dir, name)) ? `${this.baseUrl}/images/${name}` : null;
+    }
+
+    // Downloads the image in the background, failures are retried on the next use
+    store(url: string) {
+        if (this.downloads.has(url)) {
+            return;
+        }
+        this.downloads.add(url);
+        axios.get(url, {responseType: 'arraybuffer'}).then(response => {
+            if (response.status === 200) {
+                fs.mkdirSync(this.dir, {recursive: true});
+                const file = path.join(this.dir, ImageProxy.fileName(url));
+                fs.writeFileSync(file + '.tmp', Buffer.from(response.data));
+                fs.renameSync(file + '.tmp', file);
+            }
+        }).catch(e => console.log(`failed to proxy image ${url}: ${e}`)).then(() => this.downloads.delete(url));
+    }
+
+    // The local copy of the file name of a proxied URL, null for unknown or invalid names
+    read(name: string): Buffer | null {
+        if (!PROXY_FILE.test(name)) {
+            return null;
+        }
+        try {
+            return fs.readFileSync(path.join(this.dir, name));
+        } catch (e) {
+            return null;
+        }
+    }
+}
+
+// Picks the first working image of a fallback chain, remembering the result of every HEAD check for a day
+export class Images {
+    private check: ImageCheck;
+    private proxy: ImageProxy | null;
+    // Mapping of URL to the check result and the time it was checked
+    private checked = new Map<string, { ok: boolean, time: number }>();
+    // Time the image server was last found unreachable
+    private unreachableAt = -OUTAGE_MS;
+
+    constructor(check: ImageCheck = headCheck, proxy: ImageProxy | null = null) {
+        this.check = check;
+        this.proxy = proxy;
+    }
+
+    // Fallback chain for a type: render, icon, icon of another type in the same group, generic image. The icon
+    // source leaves out the render
+    typeImageChain(typeId: number, groupTypeId?: number, source = ThumbnailSource.RENDER): string[] {
+        const chain = [`${IMAGE_URL}types/${typeId}/icon`];
+        if (source !== ThumbnailSource.ICON) {
+            chain.unshift(`${IMAGE_URL}types/${typeId}/render?size=128`);
+        }
+        if (groupTypeId != null && groupTypeId !== typeId) {
+            chain.push(`${IMAGE_URL}types/${groupTypeId}/icon`);
+        }
+        return chain;
+    }
+
+    // Portrait of the pilot or logo of the alliance, else the corporation, empty if the owner has none of them,
+    // e.g. structures without a pilot. The type image chain is the fallback
+    ownerImageChain(owner: ThumbnailOwner, source: ThumbnailSource): string[] {
+        if (source === ThumbnailSource.PORTRAIT && owner.character_id != null) {
+            return [`${IMAGE_URL}characters/${owner.character_id}/portrait?size=128`];
+        }
+        if (source === ThumbnailSource.LOGO && owner.alliance_id != null) {
+            return [`${IMAGE_URL}alliances/${owner.alliance_id}/logo?size=128`];
+        }
+        if (source === ThumbnailSource.LOGO && owner.corporation_id != null) {
+            return [`${IMAGE_URL}corporations/${owner.corporation_id}/logo?size=128`];
+        }
+        return [];
+    }
+
+    // Alliance logo, else corporation logo, else the icon of the type, e.g. for the author line of the kill embeds
+    affiliationImageChain(owner: ThumbnailOwner, typeId: number): string[] {
+        const chain: string[] = [];
+        if (owner.alliance_id != null) {
+            chain.push(`${IMAGE_URL}alliances/${owner.alliance_id}/logo?size=64`);
+        }
+        if (owner.corporation_id != null) {
+            chain.push(`${IMAGE_URL}corporations/${owner.corporation_id}/logo?size=64`);
+        }
+        chain.push(`${IMAGE_URL}types/${typeId}/icon`);
+        return chain;
+    }
+
+    isDown(now = Date.now()): boolean {
+        return now - this.unreachableAt < OUTAGE_MS;
+    }
+
+    isKnownBad(url: string, now = Date.now()): boolean {
+        const result = this.checked.get(url);
+        return result != null && !result.ok && now - result.time < CHECK_TTL_MS;
+    }
+
+    // While the image server is down only proxied copies are used, the generic image is hosted elsewhere
+    async firstAvailable(chain: string[], now = Date.now()): Promise<string> {
+        for (const url of chain) {
+            if (this.isDown(now)) {
+                const proxied = this.proxy?.proxiedUrl(url);
+                if (proxied) {
+                    return proxied;
+                }
+                continue;
+            }
+            const cached = this.checked.get(url);
+            if (cached && now - cached.time < CHECK_TTL_MS) {
+                if (cached.ok) {
+                    return this.available(url);
+                }
+                continue;
+            }
+            const ok = await this.check(url);
+            if (ok == null) {
+                this.unreachableAt = now;
+                continue;
+            }
+            this.checked.set(url, {ok, time: now});
+            if (ok) {
+                return this.available(url);
+            }
+        }
+        return GENERIC_IMAGE_URL;
+    }
+
+    // The proxied copy of a working image, downloading it on first use
+    private available(url: string): string {
+        if (this.proxy == null) {
+            return url;
+        }
+        const proxied = this.proxy.proxiedUrl(url);
+        if (proxied == null) {
+            this.proxy.store(url);
+        }
+        return proxied ?? url;
+    }
+}
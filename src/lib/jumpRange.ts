@@ -0,0 +1,70 @@
+export enum JumpShipClass {
+    BLACK_OPS = 'blackops',
+    JUMP_FREIGHTER = 'jf',
+    RORQUAL = 'rorqual',
+    CARRIER = 'carrier',
+    DREADNOUGHT = 'dread',
+    FORCE_AUXILIARY = 'fax',
+    SUPERCARRIER = 'super',
+    TITAN = 'titan',
+}
+
+// Mapping of JumpShipClass to the jump range in light-years without Jump Drive Calibration
+const BASE_JUMP_RANGE_LY: { [shipClass in JumpShipClass]: number } = {
+    [JumpShipClass.BLACK_OPS]: 4.0,
+    [JumpShipClass.JUMP_FREIGHTER]: 5.0,
+    [JumpShipClass.RORQUAL]: 5.0,
+    [JumpShipClass.CARRIER]: 3.5,
+    [JumpShipClass.DREADNOUGHT]: 3.5,
+    [JumpShipClass.FORCE_AUXILIARY]: 3.5,
+    [JumpShipClass.SUPERCARRIER]: 3.0,
+    [JumpShipClass.TITAN]: 3.0,
+};
+
+// Classes shown as able to reach a kill, the industrial classes are left out
+const COMBAT_JUMP_SHIP_CLASSES = [
+    JumpShipClass.BLACK_OPS,
+    JumpShipClass.CARRIER,
+    JumpShipClass.DREADNOUGHT,
+    JumpShipClass.FORCE_AUXILIARY,
+    JumpShipClass.SUPERCARRIER,
+    JumpShipClass.TITAN,
+];
+
+export const LIGHT_YEAR_IN_METERS = 9460730472580800;
+
+// Jump Drive Calibration adds 20% of the base range per level
+export function jumpRangeLy(shipClass: JumpShipClass, calibrationLevel = 5): number {
+    return BASE_JUMP_RANGE_LY[shipClass] * (1 + 0.2 * calibrationLevel);
+}
+
+export interface LyRange {
+    systemName: string;
+    maxLy: number;
+    // Set if the range was given as a ship class instead of a distance
+    shipClass?: JumpShipClass;
+}
+
+// Parses `systemName:maxLy` or `systemName:shipClass`, null if the value is in neither format
+export function parseLyRange(value: string, calibrationLevel = 5): LyRange | null {
+    const separator = value.lastIndexOf(':');
+    if (separator <= 0) {
+        return null;
+    }
+    const systemName = value.substring(0, separator).trim();
+    const range = value.substring(separator + 1).trim().toLowerCase();
+    if ((Object.values(JumpShipClass) as string[]).indexOf(range) !== -1) {
+        const shipClass = range as JumpShipClass;
+        return {systemName, maxLy: jumpRangeLy(shipClass, calibrationLevel), shipClass};
+    }
+    const maxLy = Number(range);
+    if (range === '' || isNaN(maxLy) || maxLy <= 0) {
+        return null;
+    }
+    return {systemName, maxLy};
+}
+
+// Combat capital classes with the distance in their jump range
+export function classesInJumpRange(distanceLy: number, calibrationLevel = 5): JumpShipClass[] {
+    return COMBAT_JUMP_SHIP_CLASSES.filter(shipClass => jumpRangeLy(shipClass, calibrationLevel) >= distanceLy);
+}
@@ -0,0 +1,19 @@
+// What happens to a matched kill older than the maximum kill age, e.g. a backlog zKillboard sends after an outage
+export enum StaleKillAction {
+    // Logged, but not posted
+    SKIP = 'skip',
+    // Posted without mentioning the ping role or @here
+    UNPINGED = 'unpinged',
+}
+
+// The subscription's maximum age in minutes, or MAX_KILL_AGE_MINUTES for subscriptions without one. Null if kills of
+// any age are posted
+export function maxKillAgeMinutes(subscriptionMinutes: number | undefined, defaultMinutes: number | null): number | null {
+    const minutes = subscriptionMinutes ?? defaultMinutes;
+    return minutes != null && minutes > 0 ? minutes : null;
+}
+
+// True if the kill happened more than maxAgeMinutes before now
+export function isStaleKill(killmailTime: string, maxAgeMinutes: number | null, now = Date.now()): boolean {
+    return maxAgeMinutes != null && now - Date.parse(killmailTime) > maxAgeMinutes * 60 * 1000;
+}
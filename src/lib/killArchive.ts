@@ -0,0 +1,211 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import * as zlib from 'zlib';
+import {createHash, createHmac} from 'crypto';
+import {Axios} from 'axios';
+import {ZkData} from '../zKillSubscriber';
+import {logger} from './logger';
+
+export enum ArchiveMode {
+    // Every kill received from zKillboard
+    ALL = 'all',
+    // Only kills a subscription matched, each once
+    MATCHED = 'matched',
+}
+
+// S3 compatible bucket the finished days are uploaded to, e.g. AWS, MinIO or Cloudflare R2
+export interface S3Config {
+    endpoint: string;
+    bucket: string;
+    region: string;
+    accessKeyId: string;
+    secretAccessKey: string;
+    // Prepended to the file names, e.g. `zk-activity/`
+    prefix: string;
+}
+
+const FILE_PATTERN = /^kills-(\d{4}-\d{2}-\d{2})\.ndjson\.gz$/;
+const UPLOADED_SUFFIX = '.uploaded';
+
+export function archiveFileName(day: string): string {
+    return `kills-${day}.ndjson.gz`;
+}
+
+// UTC day of the time, `YYYY-MM-DD`
+export function archiveDay(time: number): string {
+    return new Date(time).toISOString().substring(0, 10);
+}
+
+function sha256Hex(data: string | Buffer): string {
+    return createHash('sha256').update(data).digest('hex');
+}
+
+function hmac(key: string | Buffer, data: string): Buffer {
+    return createHmac('sha256', key).update(data).digest();
+}
+
+// Path style PUT request signed with AWS Signature Version 4
+export function signS3Put(config: S3Config, key: string, body: Buffer, now = new Date()): { url: string, headers: { [name: string]: string } } {
+    const url = new URL(`${config.endpoint.replace(/\/+$/, '')}/${config.bucket}/${key.split('/').map(encodeURIComponent).join('/')}`);
+    const amzDate = now.toISOString().replace(/[:-]|\.\d{3}/g, '');
+    const date = amzDate.substring(0, 8);
+    const payloadHash = sha256Hex(body);
+    const signedHeaders = 'host;x-amz-content-sha256;x-amz-date';
+    const canonicalRequest = [
+        'PUT',
+        url.pathname,
+        '',
+        `host:${url.host}`,
+        `x-amz-content-sha256:${payloadHash}`,
+        `x-amz-date:${amzDate}`,
+        '',
+        signedHeaders,
+        payloadHash,
+    ].join('\n');
+    const scope = `${date}/${config.region}/s3/aws4_request`;
+    const stringToSign = ['AWS4-HMAC-SHA256', amzDate, scope, sha256Hex(canonicalRequest)].join('\n');
+    const signingKey = hmac(hmac(hmac(hmac('AWS4' + config.secretAccessKey, date), config.region), 's3'), 'aws4_request');
+    const signature = createHmac('sha256', signingKey).update(stringToSign).digest('hex');
+    return {
+        url: url.toString(),
+        headers: {
+            'Authorization': `AWS4-HMAC-SHA256 Credential=${config.accessKeyId}/${scope}, SignedHeaders=${signedHeaders}, Signature=${signature}`,
+            'Content-Type': 'application/gzip',
+            'x-amz-content-sha256': payloadHash,
+            'x-amz-date': amzDate,
+        },
+    };
+}
+
+// Reads the kills of a day, null if the day was not archived
+export function readArchiveDay(dir: string, day: string): ZkData[] | null {
+    const file = path.join(dir, archiveFileName(day));
+    if (!fs.existsSync(file)) {
+        return null;
+    }
+    // Every flush appends a gzip member, gunzip reads them as one stream
+    return zlib.gunzipSync(fs.readFileSync(file)).toString('utf8')
+        .split('\n')
+        .filter(line => line !== '')
+        .map(line => JSON.parse(line));
+}
+
+// Writes the kills as gzip compressed NDJSON, one file per UTC day. Kills are buffered and appended once a minute and
+// on shutdown, finished days are uploaded to the S3 bucket if one is configured. Uploads start with the bot and when
+// the day changes, failed ones are tried again with the next flush
+export class KillArchive {
+    readonly dir: string;
+    readonly mode: ArchiveMode;
+    private s3: S3Config | null;
+    private buffer: string[] = [];
+    // Kill IDs of the current day, a kill matched by several subscriptions is archived once
+    private day: string | null = null;
+    private archived = new Set<number>();
+    private timer: ReturnType<typeof setTimeout> | null = null;
+    private axios = new Axios({timeout: 60000});
+    private uploading = false;
+    private uploadFailed = false;
+
+    constructor(dir: string, mode: ArchiveMode, s3: S3Config | null) {
+        this.dir = dir;
+        this.mode = mode;
+        this.s3 = s3;
+        fs.mkdirSync(dir, {recursive: true});
+        this.upload();
+    }
+
+    // Null if ARCHIVE_DIR is not set, the S3 upload needs ARCHIVE_S3_ENDPOINT, ARCHIVE_S3_BUCKET and the keys
+    static fromEnv(env: NodeJS.ProcessEnv = process.env): KillArchive | null {
+        if (!env.ARCHIVE_DIR) {
+            return null;
+        }
+        const s3 = env.ARCHIVE_S3_ENDPOINT && env.ARCHIVE_S3_BUCKET && env.ARCHIVE_S3_ACCESS_KEY_ID && env.ARCHIVE_S3_SECRET_ACCESS_KEY ? {
+            endpoint: env.ARCHIVE_S3_ENDPOINT,
+            bucket: env.ARCHIVE_S3_BUCKET,
+            region: env.ARCHIVE_S3_REGION || 'us-east-1',
+            accessKeyId: env.ARCHIVE_S3_ACCESS_KEY_ID,
+            secretAccessKey: env.ARCHIVE_S3_SECRET_ACCESS_KEY,
+            prefix: env.ARCHIVE_S3_PREFIX || '',
+        } : null;
+        return new KillArchive(env.ARCHIVE_DIR, env.ARCHIVE_KILLS === ArchiveMode.MATCHED ? ArchiveMode.MATCHED : ArchiveMode.ALL, s3);
+    }
+
+    // Archives the kill if the stage, receiving or matching it, is the one of the archive mode
+    record(data: ZkData, stage: ArchiveMode, now = Date.now()) {
+        if (stage !== this.mode) {
+            return;
+        }
+        const day = archiveDay(now);
+        if (day !== this.day) {
+            this.flush();
+            this.day = day;
+            this.archived.clear();
+            this.upload();
+        }
+        if (this.archived.has(data.killmail_id)) {
+            return;
+        }
+        this.archived.add(data.killmail_id);
+        this.buffer.push(JSON.stringify(data));
+        if (this.timer == null) {
+            this.timer = setTimeout(() => this.flush(), 60 * 1000);
+            this.timer.unref?.();
+        }
+    }
+
+    flush() {
+        if (this.timer != null) {
+            clearTimeout(this.timer);
+            this.timer = null;
+        }
+        if (this.uploadFailed) {
+            this.upload();
+        }
+        if (this.buffer.length === 0 || this.day == null) {
+            return;
+        }
+        const lines = this.buffer.join('\n') + '\n';
+        this.buffer = [];
+        try {
+            fs.appendFileSync(path.join(this.dir, archiveFileName(this.day)), zlib.gzipSync(lines));
+        } catch (e) {
+            logger.error(`failed to write the kill archive: ${e}`);
+        }
+    }
+
+    private upload() {
+        if (!this.s3 || this.uploading) {
+            return;
+        }
+        this.uploading = true;
+        this.uploadFinishedDays(<S3Config>this.s3)
+            .then(() => {
+                this.uploadFailed = false;
+            })
+            .catch(e => {
+                logger.error(`failed to upload the kill archive: ${e}`);
+                this.uploadFailed = true;
+            })
+            .then(() => {
+                this.uploading = false;
+            });
+    }
+
+    // Days before the current one not uploaded yet, including the ones missed while the bot was down
+    private async uploadFinishedDays(s3: S3Config) {
+        const today = archiveDay(Date.now());
+        for (const file of fs.readdirSync(this.dir).sort()) {
+            const match = FILE_PATTERN.exec(file);
+            if (!match || match[1] >= today || match[1] === this.day || fs.existsSync(path.join(this.dir, file + UPLOADED_SUFFIX))) {
+                continue;
+            }
+            const body = fs.readFileSync(path.join(this.dir, file));
+            const request = signS3Put(s3, s3.prefix + file, body);
+            const response = await this.axios.put(request.url, body, {headers: request.headers});
+            if (response.status < 200 || response.status >= 300) {
+                throw new Error(`S3 answered ${response.status} for ${file}`);
+            }
+            fs.writeFileSync(path.join(this.dir, file + UPLOADED_SUFFIX), new Date().toISOString());
+        }
+    }
+}
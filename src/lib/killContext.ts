@@ -0,0 +1,53 @@
+import {ClosestCelestial, SolarSystem, ZkData} from '../zKillSubscriber';
+
+// Cached lookups the context is built from, usually backed by the on-disk caches and ESI
+export interface KillContextResolvers {
+    system: (systemId: number) => Promise<SolarSystem>;
+    groupId: (typeId: number) => Promise<number>;
+    categoryId: (groupId: number) => Promise<number>;
+    name: (typeId: number) => Promise<string>;
+    celestial: (data: ZkData) => Promise<ClosestCelestial>;
+}
+
+// Lookups for a single killmail, shared by all subscriptions evaluating it. Every lookup is resolved
+// at most once per kill, concurrent subscriptions wait for the same pending lookup.
+export class KillContext {
+    readonly data: ZkData;
+    private resolvers: KillContextResolvers;
+    private lookups = new Map<string, Promise<any>>();
+
+    constructor(data: ZkData, resolvers: KillContextResolvers) {
+        this.data = data;
+        this.resolvers = resolvers;
+    }
+
+    private memoize<T>(key: string, lookup: () => Promise<T>): Promise<T> {
+        let pending = this.lookups.get(key);
+        if (!pending) {
+            pending = lookup();
+            this.lookups.set(key, pending);
+        }
+        return pending;
+    }
+
+    system(): Promise<SolarSystem> {
+        return this.memoize('system', () => this.resolvers.system(this.data.solar_system_id));
+    }
+
+    groupId(typeId: number): Promise<number> {
+        return this.memoize(`group_${typeId}`, () => this.resolvers.groupId(typeId));
+    }
+
+    categoryId(groupId: number): Promise<number> {
+        return this.memoize(`category_${groupId}`, () => this.resolvers.categoryId(groupId));
+    }
+
+    name(typeId: number): Promise<string> {
+        return this.memoize(`name_${typeId}`, () => this.resolvers.name(typeId));
+    }
+
+    // Nearest celestial to the victim
+    celestial(): Promise<ClosestCelestial> {
+        return this.memoize('celestial', () => this.resolvers.celestial(this.data));
+    }
+}
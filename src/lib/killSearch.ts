@@ -0,0 +1,79 @@
+import {ZkData} from '../zKillSubscriber';
+import {involvedEntityIds} from './subscriptionIndex';
+import {archiveDay, readArchiveDay} from './killArchive';
+
+// Criteria of /zkill-search, unset criteria match every kill
+export interface KillQuery {
+    // Character, corporation or alliance ID of the victim or an attacker
+    entityId?: number;
+    systemId?: number;
+    // Group of the victim's ship
+    shipGroupId?: number;
+    minValue?: number;
+    // Kill times, as milliseconds since the epoch
+    from: number;
+    to: number;
+}
+
+export interface KillSearchResult {
+    // Newest first
+    kills: ZkData[];
+    totalValue: number;
+}
+
+export const SEARCH_PAGE_SIZE = 10;
+export const MAX_SEARCH_DAYS = 90;
+
+// UTC days from the first to the last time, oldest first
+export function daysBetween(from: number, to: number): string[] {
+    const days: string[] = [];
+    for (let time = from; archiveDay(time) <= archiveDay(to); time += 24 * 60 * 60 * 1000) {
+        days.push(archiveDay(time));
+    }
+    return days;
+}
+
+// The criteria known without a lookup, the ship group is checked afterwards
+function matchesQuery(data: ZkData, query: KillQuery): boolean {
+    const time = Date.parse(data.killmail_time);
+    return time >= query.from && time <= query.to &&
+        (query.systemId == null || data.solar_system_id === query.systemId) &&
+        (query.minValue == null || data.zkb.totalValue >= query.minValue) &&
+        (query.entityId == null || involvedEntityIds(data).includes(query.entityId));
+}
+
+// Searches the archived days of the time range. Kills are archived on the day they arrived, which can be the day
+// after the kill, so the day after the range is read as well
+export async function searchArchive(
+    dir: string,
+    query: KillQuery,
+    groupOf: (typeId: number) => Promise<number | null>,
+): Promise<KillSearchResult> {
+    const kills: ZkData[] = [];
+    const seen = new Set<number>();
+    for (const day of daysBetween(query.from, query.to + 24 * 60 * 60 * 1000)) {
+        for (const data of readArchiveDay(dir, day) ?? []) {
+            if (seen.has(data.killmail_id) || !matchesQuery(data, query)) {
+                continue;
+            }
+            if (query.shipGroupId != null && await groupOf(data.victim.ship_type_id) !== query.shipGroupId) {
+                continue;
+            }
+            seen.add(data.killmail_id);
+            kills.push(data);
+        }
+    }
+    kills.sort((a, b) => Date.parse(b.killmail_time) - Date.parse(a.killmail_time));
+    return {kills, totalValue: kills.reduce((total, data) => total + data.zkb.totalValue, 0)};
+}
+
+export function pageCount(total: number, size = SEARCH_PAGE_SIZE): number {
+    return Math.max(1, Math.ceil(total / size));
+}
+
+// Page numbers start at 1, pages after the last one show the last one
+export function pageOf<T>(items: T[], page: number, size = SEARCH_PAGE_SIZE): T[] {
+    const last = pageCount(items.length, size);
+    const start = (Math.min(Math.max(page, 1), last) - 1) * size;
+    return items.slice(start, start + size);
+}
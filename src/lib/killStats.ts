@@ -0,0 +1,135 @@
+import * as fs from 'fs';
+import {logger} from './logger';
+
+export interface KillStatsEntry {
+    killmailId: number;
+    // ISO timestamp of the kill
+    time: string;
+    channelId: string;
+    subscriptionId?: string;
+    systemId: number;
+    victimAllianceId?: number;
+    victimCorporationId?: number;
+    totalValue: number;
+    // True if the kill was posted as a loss of the subscribed entities
+    loss?: boolean;
+    // Distinct alliances of the attackers
+    attackerAllianceIds?: number[];
+}
+
+export interface KillStatsSummary {
+    kills: number;
+    totalValue: number;
+    // Value of the kills posted as losses, included in totalValue
+    lostValue: number;
+    // Sorted by count, highest first
+    topSystems: { id: number, count: number }[];
+    topVictimAlliances: { id: number, count: number }[];
+    // Alliances attacking in the losses, counted once per loss
+    topHostileAlliances: { id: number, count: number }[];
+    // Mapping of YYYY-MM-DD to the number of kills on that day
+    killsPerDay: Map<string, number>;
+}
+
+const RETENTION_DAYS = 90;
+
+function topCounts(ids: (number | undefined)[], limit: number): { id: number, count: number }[] {
+    const counts = new Map<number, number>();
+    for (const id of ids) {
+        if (id != null) {
+            counts.set(id, (counts.get(id) || 0) + 1);
+        }
+    }
+    return Array.from(counts.entries())
+        .map(([id, count]) => ({id, count}))
+        .sort((a, b) => b.count - a.count)
+        .slice(0, limit);
+}
+
+// Matched kills per guild, persisted in one file per guild so the leaderboard survives restarts
+export class KillStats {
+    private base_dir: string;
+    private guilds = new Map<string, KillStatsEntry[]>();
+
+    constructor(base_dir = './config/stats/') {
+        this.base_dir = base_dir;
+    }
+
+    private load(guildId: string): KillStatsEntry[] {
+        let entries = this.guilds.get(guildId);
+        if (entries) {
+            return entries;
+        }
+        entries = [];
+        const file = this.base_dir + guildId + '.json';
+        if (fs.existsSync(file)) {
+            try {
+                entries = JSON.parse(fs.readFileSync(file, 'utf8'));
+            } catch (e) {
+                logger.error(`failed to parse ${file}`);
+            }
+        }
+        this.guilds.set(guildId, entries);
+        return entries;
+    }
+
+    private save(guildId: string) {
+        if (!fs.existsSync(this.base_dir)) {
+            fs.mkdirSync(this.base_dir, {recursive: true});
+        }
+        fs.writeFileSync(this.base_dir + guildId + '.json', JSON.stringify(this.load(guildId)), 'utf8');
+    }
+
+    record(guildId: string, entry: KillStatsEntry) {
+        const cutoff = Date.now() - RETENTION_DAYS * 24 * 60 * 60 * 1000;
+        const entries = this.load(guildId).filter(existing => new Date(existing.time).getTime() >= cutoff);
+        if (entries.some(existing => existing.killmailId === entry.killmailId && existing.subscriptionId === entry.subscriptionId)) {
+            return;
+        }
+        entries.push(entry);
+        this.guilds.set(guildId, entries);
+        this.save(guildId);
+    }
+
+    // Entries of the last given number of days, each kill counted once even if several subscriptions matched it.
+    // Limited to the kills posted in one channel if channelId is given
+    entries(guildId: string, days: number, now = new Date(), channelId?: string): KillStatsEntry[] {
+        const cutoff = now.getTime() - days * 24 * 60 * 60 * 1000;
+        const seen = new Set<number>();
+        return this.load(guildId).filter(entry => {
+            if (new Date(entry.time).getTime() < cutoff || seen.has(entry.killmailId) ||
+                (channelId != null && entry.channelId !== channelId)) {
+                return false;
+            }
+            seen.add(entry.killmailId);
+            return true;
+        });
+    }
+
+    // Latest entries posted by one subscription, newest first
+    subscriptionEntries(guildId: string, channelId: string, subscriptionId: string, limit: number): KillStatsEntry[] {
+        return this.load(guildId)
+            .filter(entry => entry.channelId === channelId && entry.subscriptionId === subscriptionId)
+            .sort((a, b) => new Date(b.time).getTime() - new Date(a.time).getTime())
+            .slice(0, limit);
+    }
+
+    summarize(guildId: string, days: number, now = new Date(), channelId?: string): KillStatsSummary {
+        const entries = this.entries(guildId, days, now, channelId);
+        const losses = entries.filter(entry => entry.loss);
+        const killsPerDay = new Map<string, number>();
+        for (const entry of entries) {
+            const day = entry.time.substring(0, 10);
+            killsPerDay.set(day, (killsPerDay.get(day) || 0) + 1);
+        }
+        return {
+            kills: entries.length,
+            totalValue: entries.reduce((total, entry) => total + entry.totalValue, 0),
+            lostValue: losses.reduce((total, entry) => total + entry.totalValue, 0),
+            topSystems: topCounts(entries.map(entry => entry.systemId), 5),
+            topVictimAlliances: topCounts(entries.map(entry => entry.victimAllianceId), 5),
+            topHostileAlliances: topCounts(([] as number[]).concat(...losses.map(entry => entry.attackerAllianceIds ?? [])), 5),
+            killsPerDay,
+        };
+    }
+}
@@ -0,0 +1,126 @@
+import * as fs from 'fs';
+import {LimitType, ZkData} from '../zKillSubscriber';
+import {IntelEntityType} from './zkbApi';
+import {logger} from './logger';
+
+// Where and in what a watched entity was last on a kill
+export interface Sighting {
+    type: IntelEntityType;
+    systemId: number;
+    shipTypeId?: number;
+    // ISO timestamp of the kill
+    time: string;
+    killmailId: number;
+    url: string;
+    // True if the entity was the victim
+    loss: boolean;
+    // The sighting before, so the embed of a kill can show it after the kill itself was recorded
+    previous?: Sighting;
+}
+
+// The entities of the character, corporation and alliance filters, the ones the guild watches
+export function watchedEntities(limitTypes: Map<LimitType, string>): Map<number, IntelEntityType> {
+    // Built on each call, the subscriber imports this module before LimitType is defined
+    const watchedLimitTypes: [LimitType, IntelEntityType][] = [
+        [LimitType.CHARACTER, IntelEntityType.CHARACTER],
+        [LimitType.CORPORATION, IntelEntityType.CORPORATION],
+        [LimitType.ALLIANCE, IntelEntityType.ALLIANCE],
+    ];
+    const entities = new Map<number, IntelEntityType>();
+    for (const [limitType, type] of watchedLimitTypes) {
+        (limitTypes.get(limitType) ?? '').split(',')
+            .filter(id => /^\d+$/.test(id.trim()))
+            .forEach(id => entities.set(Number(id), type));
+    }
+    return entities;
+}
+
+// Sightings of the watched entities on the kill, the victim before the attackers
+export function sightingsOf(data: ZkData, watched: Map<number, IntelEntityType>): Map<number, Sighting> {
+    const sightings = new Map<number, Sighting>();
+    const participants = [{entity: data.victim, loss: true}, ...data.attackers.map(attacker => ({entity: attacker, loss: false}))];
+    for (const {entity, loss} of participants) {
+        for (const id of [entity.character_id, entity.corporation_id, entity.alliance_id]) {
+            const type = id != null ? watched.get(id) : undefined;
+            if (id == null || type == null || sightings.has(id)) {
+                continue;
+            }
+            sightings.set(id, {
+                type,
+                systemId: data.solar_system_id,
+                shipTypeId: entity.ship_type_id,
+                time: data.killmail_time,
+                killmailId: data.killmail_id,
+                url: data.zkb.url,
+                loss,
+            });
+        }
+    }
+    return sightings;
+}
+
+// The last sighting per watched entity and guild, persisted in one file per guild
+export class LastSeen {
+    private base_dir: string;
+    private guilds = new Map<string, { [entityId: string]: Sighting }>();
+
+    constructor(base_dir = './config/last_seen/') {
+        this.base_dir = base_dir;
+    }
+
+    file(guildId: string): string {
+        return this.base_dir + guildId + '.json';
+    }
+
+    private load(guildId: string): { [entityId: string]: Sighting } {
+        let sightings = this.guilds.get(guildId);
+        if (sightings) {
+            return sightings;
+        }
+        sightings = {};
+        const file = this.file(guildId);
+        if (fs.existsSync(file)) {
+            try {
+                sightings = JSON.parse(fs.readFileSync(file, 'utf8'));
+            } catch (e) {
+                logger.error(`failed to parse ${file}`);
+            }
+        }
+        this.guilds.set(guildId, <{ [entityId: string]: Sighting }>sightings);
+        return <{ [entityId: string]: Sighting }>sightings;
+    }
+
+    toJson(guildId: string): string {
+        return JSON.stringify(this.load(guildId));
+    }
+
+    get(guildId: string, entityId: number): Sighting | undefined {
+        return this.load(guildId)[String(entityId)];
+    }
+
+    // The sighting before the given kill, e.g. for the embed of that kill
+    before(guildId: string, entityId: number, killmailId: number): Sighting | undefined {
+        const sighting = this.get(guildId, entityId);
+        return sighting?.killmailId === killmailId ? sighting.previous : sighting;
+    }
+
+    // Kills can arrive out of order, older sightings do not replace newer ones. False if nothing changed
+    record(guildId: string, sightings: Map<number, Sighting>): boolean {
+        const guild = this.load(guildId);
+        let changed = false;
+        sightings.forEach((sighting, entityId) => {
+            const key = String(entityId);
+            const last = guild[key];
+            if (last && (last.killmailId === sighting.killmailId || Date.parse(last.time) > Date.parse(sighting.time))) {
+                return;
+            }
+            guild[key] = last ? {...sighting, previous: {...last, previous: undefined}} : sighting;
+            changed = true;
+        });
+        // The cache writer writes the file, but does not create its directory
+        if (changed && !fs.existsSync(this.base_dir)) {
+            fs.mkdirSync(this.base_dir, {recursive: true});
+        }
+        return changed;
+    }
+}
@@ -0,0 +1,157 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import {randomBytes} from 'crypto';
+import {logger} from './logger';
+
+// Content of the lease file
+export interface Lease {
+    holder: string;
+    // Milliseconds since the epoch, the lease is free afterwards unless renewed
+    expiresAt: number;
+}
+
+export function readLease(file: string): Lease | null {
+    try {
+        const lease = JSON.parse(fs.readFileSync(file, 'utf8'));
+        return typeof lease.holder === 'string' && typeof lease.expiresAt === 'number' ? lease : null;
+    } catch (e) {
+        return null;
+    }
+}
+
+// Leader election between bot replicas sharing a directory, e.g. the config volume. Only the holder of the lease
+// receives and posts kills, a standby takes over once the leader stopped renewing it. With the same RedisQ queue ID,
+// which the shared config directory provides, zKillboard keeps the kills of the failover for the new leader
+export class LeaderLease {
+    readonly file: string;
+    readonly instanceId: string;
+    readonly ttlMs: number;
+    private timer: ReturnType<typeof setInterval> | null = null;
+
+    constructor(file: string, instanceId: string, ttlMs: number) {
+        this.file = file;
+        this.instanceId = instanceId;
+        this.ttlMs = ttlMs;
+    }
+
+    // Null unless HA_LEASE_FILE is set, a single instance needs no lease
+    static fromEnv(env: NodeJS.ProcessEnv = process.env): LeaderLease | null {
+        if (!env.HA_LEASE_FILE) {
+            return null;
+        }
+        return new LeaderLease(
+            env.HA_LEASE_FILE,
+            env.HA_INSTANCE_ID || `${os.hostname()}-${randomBytes(4).toString('hex')}`,
+            Math.max(5, Number(env.HA_LEASE_SECONDS || 30)) * 1000,
+        );
+    }
+
+    // Takes or renews the lease if it is free, expired or already ours. The lease is read and written while holding
+    // the exclusively created lock file, so of two replicas taking over at once the second sees the lease of the first.
+    // Throws while another replica holds the lock, which is not a lost lease
+    tryAcquire(now = Date.now()): boolean {
+        return this.withLock(now, () => {
+            const current = readLease(this.file);
+            if (current != null && current.holder !== this.instanceId && current.expiresAt > now) {
+                return false;
+            }
+            this.write(now + this.ttlMs);
+            return true;
+        });
+    }
+
+    // Resolves once this instance leads, trying again every third of the lease time
+    waitForLeadership(): Promise<void> {
+        return new Promise(resolve => {
+            const attempt = () => {
+                if (this.tryAcquireSafely()) {
+                    resolve();
+                } else {
+                    setTimeout(attempt, this.ttlMs / 3);
+                }
+            };
+            attempt();
+        });
+    }
+
+    // Renews the lease every third of the lease time. onLost is called once another replica took it over, or once the
+    // lease expired while the file could not be written
+    keepRenewing(onLost: () => void) {
+        let renewedAt = Date.now();
+        this.timer = setInterval(() => {
+            let lost: boolean;
+            try {
+                lost = !this.tryAcquire();
+                if (!lost) {
+                    renewedAt = Date.now();
+                }
+            } catch (e) {
+                logger.error(`failed to renew the leader lease ${this.file}: ${e}`);
+                lost = Date.now() - renewedAt >= this.ttlMs;
+            }
+            if (lost) {
+                this.stop();
+                onLost();
+            }
+        }, this.ttlMs / 3);
+    }
+
+    stop() {
+        if (this.timer != null) {
+            clearInterval(this.timer);
+            this.timer = null;
+        }
+    }
+
+    // Lets a standby take over right away instead of waiting for the lease to expire
+    release() {
+        this.stop();
+        try {
+            this.withLock(Date.now(), () => {
+                if (readLease(this.file)?.holder === this.instanceId) {
+                    this.write(0);
+                }
+            });
+        } catch (e) {
+            logger.error(`failed to release the leader lease ${this.file}: ${e}`);
+        }
+    }
+
+    // The temporary file is renamed so a replica never reads a partly written lease
+    private write(expiresAt: number) {
+        const temporary = `${this.file}.${this.instanceId}.tmp`;
+        fs.writeFileSync(temporary, JSON.stringify({holder: this.instanceId, expiresAt}), 'utf8');
+        fs.renameSync(temporary, this.file);
+    }
+
+    private withLock<T>(now: number, callback: () => T): T {
+        const lockFile = `${this.file}.lock`;
+        let fd: number;
+        try {
+            fd = fs.openSync(lockFile, 'wx');
+        } catch (e) {
+            // A replica that died while holding the lock leaves it behind, it is only held for a read and a write
+            if ((<NodeJS.ErrnoException>e).code !== 'EEXIST' || now - fs.statSync(lockFile).mtimeMs < this.ttlMs) {
+                throw e;
+            }
+            fs.unlinkSync(lockFile);
+            fd = fs.openSync(lockFile, 'wx');
+        }
+        try {
+            return callback();
+        } finally {
+            fs.closeSync(fd);
+            fs.unlinkSync(lockFile);
+        }
+    }
+
+    // A standby keeps waiting while the shared directory is unavailable
+    private tryAcquireSafely(): boolean {
+        try {
+            return this.tryAcquire();
+        } catch (e) {
+            logger.error(`failed to update the leader lease ${this.file}: ${e}`);
+            return false;
+        }
+    }
+}
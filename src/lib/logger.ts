@@ -0,0 +1,68 @@
+import {AsyncLocalStorage} from 'async_hooks';
+import * as util from 'util';
+
+export interface LogContext {
+    kill_id?: number;
+    guild_id?: string;
+    channel_id?: string;
+    subscription_id?: string;
+    // Recipient of a direct message subscription
+    user_id?: string;
+}
+
+const contextStorage = new AsyncLocalStorage<LogContext>();
+
+function isJsonFormat(): boolean {
+    return (process.env.LOG_FORMAT || '').toLowerCase() === 'json';
+}
+
+function textPrefix(context: LogContext): string {
+    let prefix = '';
+    if (context.kill_id != null) prefix += `[Kill: ${context.kill_id}]`;
+    if (context.guild_id != null) prefix += `[Guild: ${context.guild_id}]`;
+    if (context.user_id != null) prefix += `[User: ${context.user_id}]`;
+    if (context.subscription_id != null) prefix += `[Sub: ${context.subscription_id}]`;
+    return prefix === '' ? prefix : prefix + ' ';
+}
+
+// Warnings and errors go to stderr
+function write(level: 'info' | 'warn' | 'error', args: any[]) {
+    const context = contextStorage.getStore() || {};
+    const message = util.format(...args);
+    if (isJsonFormat()) {
+        const line = JSON.stringify({
+            timestamp: new Date().toISOString(),
+            level,
+            message,
+            ...context,
+        });
+        if (level === 'info') {
+            console.log(line);
+        } else {
+            console.error(line);
+        }
+    } else if (level !== 'info') {
+        console.error(textPrefix(context) + message);
+    } else {
+        console.log(textPrefix(context) + message);
+    }
+}
+
+export const logger = {
+    info(...args: any[]) {
+        write('info', args);
+    },
+    warn(...args: any[]) {
+        write('warn', args);
+    },
+    error(...args: any[]) {
+        write('error', args);
+    },
+};
+
+// Runs the callback with the given fields attached to every log line written within it,
+// including lines written from awaited calls. Nested calls extend the outer context.
+export function withLogContext<T>(context: LogContext, callback: () => T): T {
+    const parent = contextStorage.getStore() || {};
+    return contextStorage.run({...parent, ...context}, callback);
+}
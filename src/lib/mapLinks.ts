@@ -0,0 +1,62 @@
+// Sites the system and region links of the kill messages point to, selectable per guild
+export enum MapProviderKind {
+    DOTLAN = 'dotlan',
+    GATECHECK = 'gatecheck',
+    EVE_SCOUT = 'eve-scout',
+}
+
+export interface MapProvider {
+    // Label of the link button
+    label: string;
+    // Null if the site needs the name, callers fall back to dotlan then
+    systemUrl(systemId: number, systemName?: string): string | null;
+    // Null if the site has no region pages
+    regionUrl(regionId: number, regionName?: string): string | null;
+}
+
+// dotlan addresses systems and regions by name, with underscores for spaces
+function dotlanName(name: string): string {
+    return encodeURIComponent(name.replace(/ /g, '_'));
+}
+
+const dotlan: MapProvider = {
+    label: 'Dotlan',
+    systemUrl: (systemId, systemName) => `https://evemaps.dotlan.net/system/${systemName ? dotlanName(systemName) : systemId}`,
+    regionUrl: (regionId, regionName) => `https://evemaps.dotlan.net/region/${regionName ? dotlanName(regionName) : regionId}`,
+};
+
+// Gate routes with the recent kills on the way, e.g. to check a route into the system for gate camps
+const gatecheck: MapProvider = {
+    label: 'Gatecheck',
+    systemUrl: (systemId, systemName) => systemName ? `https://eve-gatecheck.space/eve/#${encodeURIComponent(systemName)}` : null,
+    regionUrl: () => null,
+};
+
+// The current Thera and Turnur wormhole connections, to find a shortcut to the system
+const eveScout: MapProvider = {
+    label: 'EVE-Scout',
+    systemUrl: () => 'https://www.eve-scout.com/#/thera',
+    regionUrl: () => null,
+};
+
+const PROVIDERS: { [kind in MapProviderKind]: MapProvider } = {
+    [MapProviderKind.DOTLAN]: dotlan,
+    [MapProviderKind.GATECHECK]: gatecheck,
+    [MapProviderKind.EVE_SCOUT]: eveScout,
+};
+
+export function parseMapProvider(value?: string): MapProviderKind {
+    return Object.values(MapProviderKind).includes(value as MapProviderKind) ? value as MapProviderKind : MapProviderKind.DOTLAN;
+}
+
+export function mapProvider(kind?: MapProviderKind): MapProvider {
+    return PROVIDERS[parseMapProvider(kind)];
+}
+
+export function providerSystemUrl(kind: MapProviderKind | undefined, systemId: number, systemName?: string): string {
+    return mapProvider(kind).systemUrl(systemId, systemName) ?? dotlan.systemUrl(systemId, systemName) as string;
+}
+
+export function providerRegionUrl(kind: MapProviderKind | undefined, regionId: number, regionName?: string): string {
+    return mapProvider(kind).regionUrl(regionId, regionName) ?? dotlan.regionUrl(regionId, regionName) as string;
+}
@@ -0,0 +1,90 @@
+import {logger} from './logger';
+
+// One entry of the ESI /markets/prices/ list
+export interface MarketPrice {
+    type_id: number;
+    average_price?: number;
+    adjusted_price?: number;
+}
+
+export interface ValueRange {
+    min: number | null;
+    max: number | null;
+}
+
+// Value format `min:max` in ISK, either side may be left empty, e.g. `1000000000:` for at least 1 billion
+export function parseValueRange(value: string): ValueRange | null {
+    const parts = value.split(':');
+    if (parts.length !== 2) {
+        return null;
+    }
+    if (parts.some(part => part.trim() !== '' && !/^\d+(\.\d+)?$/.test(part.trim()))) {
+        return null;
+    }
+    const [min, max] = parts.map(part => part.trim() === '' ? null : Number(part));
+    if (min == null && max == null) {
+        return null;
+    }
+    return {min, max};
+}
+
+export function isInValueRange(range: ValueRange, value: number): boolean {
+    return (range.min == null || value >= range.min) && (range.max == null || value <= range.max);
+}
+
+// Current market prices of all types, replaced as a whole on every refresh so lookups never see a partial list
+export class MarketPriceCache {
+    readonly refreshMs: number;
+    private fetchPrices: () => Promise<MarketPrice[]>;
+    private prices = new Map<number, number>();
+    private refreshedAt: number | null = null;
+    private timer: ReturnType<typeof setInterval> | null = null;
+
+    constructor(refreshMs: number, fetchPrices: () => Promise<MarketPrice[]>) {
+        this.refreshMs = refreshMs;
+        this.fetchPrices = fetchPrices;
+    }
+
+    static fromEnv(fetchPrices: () => Promise<MarketPrice[]>, env: NodeJS.ProcessEnv = process.env): MarketPriceCache {
+        return new MarketPriceCache(Number(env.MARKET_PRICE_REFRESH_MINUTES || 60) * 60 * 1000, fetchPrices);
+    }
+
+    start() {
+        this.refresh().catch(e => logger.warn(`failed to refresh market prices: ${e}`));
+        this.timer = setInterval(() => {
+            this.refresh().catch(e => logger.warn(`failed to refresh market prices: ${e}`));
+        }, this.refreshMs);
+    }
+
+    stop() {
+        if (this.timer) {
+            clearInterval(this.timer);
+            this.timer = null;
+        }
+    }
+
+    async refresh(now = Date.now()) {
+        const prices = new Map<number, number>();
+        for (const price of await this.fetchPrices()) {
+            // The average price is missing for types that are not traded, the adjusted price is always there
+            const value = price.average_price ?? price.adjusted_price;
+            if (value != null) {
+                prices.set(price.type_id, value);
+            }
+        }
+        this.prices = prices;
+        this.refreshedAt = now;
+    }
+
+    // Null if the type has no price or the prices were not loaded yet
+    price(typeId?: number): number | null {
+        if (typeId == null) {
+            return null;
+        }
+        return this.prices.get(typeId) ?? null;
+    }
+
+    lastRefresh(): number | null {
+        return this.refreshedAt;
+    }
+}
@@ -0,0 +1,9 @@
+import {Attacker, ZkData} from '../zKillSubscriber';
+
+// The attacker a kill summary names: the first one that is one of the given characters, corporations or alliances,
+// e.g. of the subscription's entity filters, else the one with the final blow
+export function summaryAttacker(data: ZkData, entityIds: number[]): Attacker | undefined {
+    const matching = data.attackers.filter(attacker =>
+        [attacker.character_id, attacker.corporation_id, attacker.alliance_id].some(id => id != null && entityIds.includes(id)));
+    return matching[0] ?? data.attackers.filter(attacker => attacker.final_blow)[0] ?? data.attackers[0];
+}
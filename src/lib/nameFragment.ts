@@ -0,0 +1,27 @@
+// Names the required name fragment is looked for in
+export enum NameScope {
+    // Names of the matched ship types, only with the ship type filter
+    SHIP = 'ship',
+    PILOT = 'pilot',
+    CORPORATION = 'corporation',
+    ALLIANCE = 'alliance',
+}
+
+// Null if a value is not a scope
+export function parseNameScopes(value: string): NameScope[] | null {
+    const scopes = value.split(',').map(scope => scope.trim().toLowerCase()).filter(scope => scope !== '');
+    if (scopes.length === 0 || scopes.some(scope => !(Object.values(NameScope) as string[]).includes(scope))) {
+        return null;
+    }
+    return scopes.filter((scope, index) => scopes.indexOf(scope) === index) as NameScope[];
+}
+
+// Without a pilot, corporation or alliance scope the fragment stays part of the ship type filter
+export function hasEntityNameScope(scopes: NameScope[]): boolean {
+    return scopes.some(scope => scope !== NameScope.SHIP);
+}
+
+// Ignores the case, pilot and corporation names are typed in any case
+export function containsNameFragment(name: string, fragment: string): boolean {
+    return name.toLowerCase().includes(fragment.toLowerCase());
+}
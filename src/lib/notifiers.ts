@@ -0,0 +1,129 @@
+import {Axios} from 'axios';
+import {randomBytes} from 'crypto';
+import {MessageEmbedOptions} from 'discord.js';
+
+export enum NotifierKind {
+    MATRIX = 'matrix',
+    TELEGRAM = 'telegram',
+}
+
+// Where a subscription posts outside of Discord, the target is a Matrix room ID or a Telegram chat ID
+export interface NotifierTarget {
+    kind: NotifierKind;
+    target: string;
+}
+
+// Sends the kill messages to a chat system other than Discord
+export interface Notifier {
+    send(embeds: MessageEmbedOptions[], target: string): Promise<void>;
+}
+
+function escapeHtml(text: string): string {
+    return text.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;').replace(/"/g, '&quot;');
+}
+
+// The embeds use Discord markdown: links, bold, underline and code
+export function markdownToHtml(markdown: string): string {
+    return escapeHtml(markdown)
+        .replace(/\[([^\]]+)\]\(([^)\s]+)\)/g, '<a href="$2">$1</a>')
+        .replace(/\*\*([^*]+)\*\*/g, '<b>$1</b>')
+        .replace(/__([^_]+)__/g, '<b>$1</b>')
+        .replace(/`([^`]+)`/g, '<code>$1</code>');
+}
+
+export function markdownToText(markdown: string): string {
+    return markdown
+        .replace(/\[([^\]]+)\]\(([^)\s]+)\)/g, '$1 ($2)')
+        .replace(/\*\*([^*]+)\*\*/g, '$1')
+        .replace(/__([^_]+)__/g, '$1')
+        .replace(/`([^`]+)`/g, '$1');
+}
+
+// Renders the embeds as lines of author, linked title, description, fields and footer, shared by all notifiers
+export function renderEmbeds(embeds: MessageEmbedOptions[], html: boolean): string {
+    const render = html ? markdownToHtml : markdownToText;
+    const blocks = embeds.map(embed => {
+        const lines: string[] = [];
+        if (embed.author?.name) {
+            lines.push(render(embed.author.name));
+        }
+        if (embed.title) {
+            const title = render(embed.title);
+            if (embed.url) {
+                lines.push(html ? `<b><a href="${escapeHtml(embed.url)}">${title}</a></b>` : `${title} (${embed.url})`);
+            } else {
+                lines.push(html ? `<b>${title}</b>` : title);
+            }
+        }
+        if (embed.description) {
+            lines.push(render(embed.description));
+        }
+        for (const field of embed.fields ?? []) {
+            lines.push(`${render(field.name)}\n${render(field.value)}`);
+        }
+        if (embed.footer?.text) {
+            lines.push(html ? `<i>${render(embed.footer.text)}</i>` : render(embed.footer.text));
+        }
+        return lines.join('\n');
+    });
+    return blocks.join('\n\n');
+}
+
+export class TelegramNotifier implements Notifier {
+    private axios: Axios;
+
+    constructor(botToken: string) {
+        this.axios = new Axios({baseURL: `https://api.telegram.org/bot${botToken}/`, timeout: 10000});
+    }
+
+    async send(embeds: MessageEmbedOptions[], chatId: string) {
+        const response = await this.axios.post('sendMessage', JSON.stringify({
+            chat_id: chatId,
+            text: renderEmbeds(embeds, true),
+            parse_mode: 'HTML',
+            disable_web_page_preview: true,
+        }), {headers: {'Content-Type': 'application/json'}});
+        if (response.status !== 200) {
+            throw new Error(`Telegram answered ${response.status}: ${response.data}`);
+        }
+    }
+}
+
+export class MatrixNotifier implements Notifier {
+    private axios: Axios;
+
+    constructor(homeserverUrl: string, accessToken: string) {
+        this.axios = new Axios({
+            baseURL: homeserverUrl.replace(/\/+$/, '') + '/_matrix/client/v3/',
+            headers: {Authorization: `Bearer ${accessToken}`},
+            timeout: 10000,
+        });
+    }
+
+    async send(embeds: MessageEmbedOptions[], roomId: string) {
+        // The transaction ID makes retries of the same request idempotent
+        const txnId = randomBytes(16).toString('hex');
+        const response = await this.axios.put(`rooms/${encodeURIComponent(roomId)}/send/m.room.message/${txnId}`, JSON.stringify({
+            msgtype: 'm.text',
+            body: renderEmbeds(embeds, false),
+            format: 'org.matrix.custom.html',
+            formatted_body: renderEmbeds(embeds, true).replace(/\n/g, '<br>'),
+        }), {headers: {'Content-Type': 'application/json'}});
+        if (response.status !== 200) {
+            throw new Error(`Matrix answered ${response.status}: ${response.data}`);
+        }
+    }
+}
+
+// The backends configured by the bot operator, TELEGRAM_BOT_TOKEN enables Telegram, MATRIX_HOMESERVER_URL and
+// MATRIX_ACCESS_TOKEN enable Matrix
+export function notifiersFromEnv(env: NodeJS.ProcessEnv = process.env): Map<NotifierKind, Notifier> {
+    const notifiers = new Map<NotifierKind, Notifier>();
+    if (env.TELEGRAM_BOT_TOKEN) {
+        notifiers.set(NotifierKind.TELEGRAM, new TelegramNotifier(env.TELEGRAM_BOT_TOKEN));
+    }
+    if (env.MATRIX_HOMESERVER_URL && env.MATRIX_ACCESS_TOKEN) {
+        notifiers.set(NotifierKind.MATRIX, new MatrixNotifier(env.MATRIX_HOMESERVER_URL, env.MATRIX_ACCESS_TOKEN));
+    }
+    return notifiers;
+}
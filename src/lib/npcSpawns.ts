@@ -0,0 +1,31 @@
+// NPC group names of the ESI contain the kind of spawn, e.g. "Asteroid Guristas Officer", so the aliases match names
+// instead of listing the group IDs of every pirate faction
+export const NPC_SPAWN_ALIASES: { [alias: string]: RegExp } = {
+    officers: /Officer$/,
+    commanders: /Commander/,
+    capitals: /(Dreadnought|Carrier|Titan|Mothership)$/,
+};
+
+export interface NpcSpawnFilter {
+    patterns: RegExp[];
+    groupIds: number[];
+}
+
+// Comma separated aliases or NPC group IDs, null if an entry is neither
+export function parseNpcSpawnFilter(value: string): NpcSpawnFilter | null {
+    const filter: NpcSpawnFilter = {patterns: [], groupIds: []};
+    for (const part of value.split(',').map(part => part.trim().toLowerCase())) {
+        if (NPC_SPAWN_ALIASES[part]) {
+            filter.patterns.push(NPC_SPAWN_ALIASES[part]);
+        } else if (/^\d+$/.test(part)) {
+            filter.groupIds.push(Number(part));
+        } else {
+            return null;
+        }
+    }
+    return filter;
+}
+
+export function matchesNpcSpawn(filter: NpcSpawnFilter, groupId: number, groupName: string): boolean {
+    return filter.groupIds.includes(groupId) || filter.patterns.some(pattern => pattern.test(groupName));
+}
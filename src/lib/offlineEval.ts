@@ -0,0 +1,75 @@
+import * as fs from 'fs';
+import {Client, Intents} from 'discord.js';
+import {LimitType, Subscription, ZkData, ZKillSubscriber} from '../zKillSubscriber';
+import {formatTrace} from '../commands/whyCommand';
+import {logger} from './logger';
+
+export enum EvalExitCode {
+    MATCHED = 0,
+    // None of the subscriptions would post the kill
+    NOT_MATCHED = 1,
+    INVALID_INPUT = 2,
+}
+
+// The subscriptions of a file, either a guild config with all its channels or a single subscription as stored there
+export function readEvalSubscriptions(parsed: any): { ident: string, subscription: Subscription }[] {
+    const stored: { ident: string, object: any }[] = [];
+    if (parsed != null && typeof parsed.channels === 'object') {
+        for (const channelId of Object.keys(parsed.channels)) {
+            const subscriptions = parsed.channels[channelId]?.subscriptions ?? {};
+            Object.keys(subscriptions).forEach(ident => stored.push({ident: `${channelId}/${ident}`, object: subscriptions[ident]}));
+        }
+    } else if (parsed != null && parsed.subType) {
+        stored.push({ident: `${parsed.subType}${parsed.id ?? ''}`, object: parsed});
+    }
+    return stored.map(({ident, object}) => ({
+        ident,
+        subscription: {
+            ...object,
+            id: object.id != null ? String(object.id) : undefined,
+            limitTypes: new Map(Object.entries(object.limitTypes ?? {}).map(([key, value]): [LimitType, string] => [<LimitType>key, String(value)])),
+        },
+    }));
+}
+
+// Evaluates the subscriptions of a file against a kill in the format of the zKillboard websocket and prints the
+// trace of each, without Discord or network. Types and systems are looked up in the caches of the config directory
+// and SDE_DIR. Returns MATCHED if any subscription would post the kill
+export async function runEval(subscriptionFile: string | undefined, killFile: string | undefined, base_dir = './config/'): Promise<EvalExitCode> {
+    if (!subscriptionFile || !killFile) {
+        logger.error('usage: --eval <subscription or guild config file> <kill file> [config directory]');
+        return EvalExitCode.INVALID_INPUT;
+    }
+    let subscriptions: { ident: string, subscription: Subscription }[];
+    let data: ZkData;
+    try {
+        subscriptions = readEvalSubscriptions(JSON.parse(fs.readFileSync(subscriptionFile, 'utf8')));
+        data = JSON.parse(fs.readFileSync(killFile, 'utf8'));
+    } catch (e) {
+        logger.error(`failed to read the input: ${e}`);
+        return EvalExitCode.INVALID_INPUT;
+    }
+    if (subscriptions.length === 0) {
+        logger.error(`${subscriptionFile} contains no subscriptions`);
+        return EvalExitCode.INVALID_INPUT;
+    }
+    const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false)
+        .withOfflineLookups()
+        .withSystems(base_dir)
+        .withShips(base_dir)
+        .withCategories(base_dir)
+        .withNames(base_dir)
+        .withSde()
+        .withStarMap(base_dir);
+    let matched = false;
+    for (const {ident, subscription} of subscriptions) {
+        try {
+            const evaluation = await sub.evaluateSubscription(subscription, data);
+            matched = matched || evaluation.matched;
+            logger.info(formatTrace(data.killmail_id, ident, evaluation));
+        } catch (e) {
+            logger.error(`Kill ${data.killmail_id} against ${ident}: evaluation failed, the caches do not cover the kill: ${e}`);
+        }
+    }
+    return matched ? EvalExitCode.MATCHED : EvalExitCode.NOT_MATCHED;
+}
@@ -0,0 +1,33 @@
+// Steps of the setup wizard posted when the bot joins a server, each step is a message component or modal
+export enum OnboardingStep {
+    // Select menu of the channels to post the kills in
+    CHANNEL = 'channel',
+    // Modal asking for the alliance or corporation name
+    ENTITY = 'entity',
+}
+
+const ONBOARDING_PREFIX = 'onboard';
+
+// The wizard can also be sent to the owner in a DM, so the custom IDs carry the guild
+export interface OnboardingState {
+    step: OnboardingStep;
+    guildId: string;
+    channelId?: string;
+}
+
+export function onboardingCustomId(state: OnboardingState): string {
+    return [ONBOARDING_PREFIX, state.step, state.guildId, state.channelId ?? ''].join('_');
+}
+
+// Null if the custom ID does not belong to the setup wizard
+export function parseOnboardingCustomId(customId: string): OnboardingState | null {
+    const match = /^onboard_(channel|entity)_(\d+)_(\d*)$/.exec(customId);
+    if (!match) {
+        return null;
+    }
+    return {
+        step: <OnboardingStep>match[1],
+        guildId: match[2],
+        channelId: match[3] !== '' ? match[3] : undefined,
+    };
+}
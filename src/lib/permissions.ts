@@ -0,0 +1,38 @@
+import {Interaction, PermissionString, Permissions} from 'discord.js';
+import {GuildSettings} from '../zKillSubscriber';
+
+// Role IDs of the member who used the command, the member is not always cached
+export function memberRoleIds(interaction: Interaction): string[] {
+    const roles = interaction.member?.roles;
+    if (!roles) {
+        return [];
+    }
+    return Array.isArray(roles) ? roles : Array.from(roles.cache.keys());
+}
+
+// Members who can manage the channel, or who have the bot admin role or a subscriber role of the guild, may manage
+// its subscriptions
+export function isSubscriptionManager(
+    canManageChannels: boolean,
+    roleIds: string[],
+    adminRoleId?: string,
+    subscriberRoleIds: string[] = [],
+): boolean {
+    return canManageChannels ||
+        (adminRoleId != null && roleIds.includes(adminRoleId)) ||
+        subscriberRoleIds.some(roleId => roleIds.includes(roleId));
+}
+
+export function canManageSubscriptions(interaction: Interaction, settings: GuildSettings): boolean {
+    // Includes the permission overwrites of the channel the command was used in
+    const canManageChannels = interaction.memberPermissions?.has(Permissions.FLAGS.MANAGE_CHANNELS) ?? false;
+    return isSubscriptionManager(canManageChannels, memberRoleIds(interaction), settings.adminRoleId, settings.subscriberRoleIds);
+}
+
+// What the bot needs in a subscribed channel to post the kill embeds
+export const REQUIRED_CHANNEL_PERMISSIONS: PermissionString[] = ['VIEW_CHANNEL', 'SEND_MESSAGES', 'EMBED_LINKS'];
+
+// Names of the required permissions the bot lacks, all of them if its permissions are unknown
+export function missingChannelPermissions(permissions: Readonly<Permissions> | null): PermissionString[] {
+    return REQUIRED_CHANNEL_PERMISSIONS.filter(permission => !permissions?.has(permission));
+}
@@ -0,0 +1,32 @@
+import {ZkData} from '../zKillSubscriber';
+
+// Which side of a kill the own entities of the guild have to be on
+export enum Perspective {
+    // An own entity is an attacker and the victim is not an own entity
+    KILLS = 'kills',
+    // The victim is an own entity
+    LOSSES = 'losses',
+    // An own entity is on either side
+    BOTH = 'both',
+}
+
+export function parsePerspective(value: string): Perspective | null {
+    const perspective = value.trim().toLowerCase();
+    return (Object.values(Perspective) as string[]).includes(perspective) ? perspective as Perspective : null;
+}
+
+// The side of the kill the own entities are on, LOSSES if the victim is one, KILLS if only an attacker is one, null if
+// neither. Own entities are character, corporation or alliance IDs
+export function ownSide(ownEntityIds: number[], data: ZkData): Perspective.KILLS | Perspective.LOSSES | null {
+    const isOwn = (entity: { character_id?: number, corporation_id?: number, alliance_id?: number }) =>
+        [entity.character_id, entity.corporation_id, entity.alliance_id].some(id => id != null && ownEntityIds.includes(id));
+    if (isOwn(data.victim)) {
+        return Perspective.LOSSES;
+    }
+    return data.attackers.some(isOwn) ? Perspective.KILLS : null;
+}
+
+export function matchesPerspective(perspective: Perspective, ownEntityIds: number[], data: ZkData): boolean {
+    const side = ownSide(ownEntityIds, data);
+    return perspective === Perspective.BOTH ? side != null : side === perspective;
+}
@@ -0,0 +1,49 @@
+import {SolarSystem, Subscription, ZkData} from '../zKillSubscriber';
+import {indexedRequirement, involvedEntityIds} from './subscriptionIndex';
+
+// Union of what the subscriptions require, to drop kills no subscription can match before evaluating each of them.
+// Any subscription without an indexed requirement makes the pre-filter open and every kill above the minimum value passes.
+export class KillPreFilter {
+    private minValue = Number.POSITIVE_INFINITY;
+    private open = false;
+    private systemIds = new Set<number>();
+    private constellationIds = new Set<number>();
+    private regionIds = new Set<number>();
+    private entityIds = new Set<number>();
+
+    constructor(subscriptions: Subscription[]) {
+        subscriptions.forEach(subscription => this.add(subscription));
+    }
+
+    private add(subscription: Subscription) {
+        this.minValue = Math.min(this.minValue, subscription.minValue);
+        const requirement = indexedRequirement(subscription);
+        if (requirement == null) {
+            this.open = true;
+            return;
+        }
+        requirement.systemIds.forEach(id => this.systemIds.add(id));
+        requirement.constellationIds.forEach(id => this.constellationIds.add(id));
+        requirement.regionIds.forEach(id => this.regionIds.add(id));
+        requirement.entityIds.forEach(id => this.entityIds.add(id));
+    }
+
+    // False if no subscription can match the kill. Regions and constellations are only compared if the system is
+    // cached, so the pre-filter never waits for ESI.
+    mayMatch(data: ZkData, cachedSystem?: SolarSystem): boolean {
+        if (data.zkb.totalValue < this.minValue) {
+            return false;
+        }
+        if (this.open || this.systemIds.has(data.solar_system_id)) {
+            return true;
+        }
+        if (this.constellationIds.size > 0 || this.regionIds.size > 0) {
+            if (cachedSystem == null ||
+                this.constellationIds.has(cachedSystem.constellationId) ||
+                this.regionIds.has(cachedSystem.regionId)) {
+                return true;
+            }
+        }
+        return involvedEntityIds(data).some(id => this.entityIds.has(id));
+    }
+}
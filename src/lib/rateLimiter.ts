@@ -0,0 +1,24 @@
+// Allows at most `limit` events per key within any window of `windowMs`
+export class SlidingWindowRateLimiter {
+    readonly limit: number;
+    readonly windowMs: number;
+    // Mapping of key to the times of the events within the current window, oldest first
+    private events = new Map<string, number[]>();
+
+    constructor(limit: number, windowMs: number) {
+        this.limit = limit;
+        this.windowMs = windowMs;
+    }
+
+    // Counts an event for the key, returns false if the limit is reached
+    tryConsume(key: string, now = Date.now()): boolean {
+        const times = (this.events.get(key) || []).filter(time => now - time < this.windowMs);
+        if (times.length >= this.limit) {
+            this.events.set(key, times);
+            return false;
+        }
+        times.push(now);
+        this.events.set(key, times);
+        return true;
+    }
+}
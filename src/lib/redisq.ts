@@ -0,0 +1,115 @@
+import {Axios} from 'axios';
+import {randomBytes} from 'crypto';
+import * as fs from 'fs';
+import {ZkData} from '../zKillSubscriber';
+import {logger} from './logger';
+
+export enum KillSourceType {
+    WEBSOCKET = 'websocket',
+    REDISQ = 'redisq',
+}
+
+// Where killmails are received from, configured through the environment
+export class KillSource {
+    readonly type: KillSourceType;
+    readonly websocketUrl: string;
+    readonly redisqUrl: string;
+    // Seconds RedisQ holds a request open while waiting for a kill, between 1 and 10
+    readonly ttw: number;
+    // Reused across restarts so zKillboard keeps buffering kills for this bot while it is down
+    readonly queueId: string;
+
+    constructor(type: KillSourceType, websocketUrl: string, redisqUrl: string, ttw: number, queueId: string) {
+        this.type = type;
+        this.websocketUrl = websocketUrl;
+        this.redisqUrl = redisqUrl;
+        this.ttw = ttw;
+        this.queueId = queueId;
+    }
+
+    static fromEnv(env: NodeJS.ProcessEnv = process.env, base_dir = './config/'): KillSource {
+        const type = env.ZKILL_SOURCE === KillSourceType.REDISQ ? KillSourceType.REDISQ : KillSourceType.WEBSOCKET;
+        return new KillSource(
+            type,
+            env.ZKILL_WEBSOCKET_URL || 'wss://zkillboard.com/websocket/',
+            env.REDISQ_URL || 'https://zkillredisq.stream/listen.php',
+            Math.min(10, Math.max(1, Number(env.REDISQ_TTW || 10))),
+            env.REDISQ_QUEUE_ID || (type === KillSourceType.REDISQ ? loadOrCreateQueueId(base_dir) : ''),
+        );
+    }
+}
+
+// Stored in a file not matching the guild config pattern, so it is not loaded as subscriptions
+export function loadOrCreateQueueId(base_dir = './config/'): string {
+    const file = base_dir + 'redisq.json';
+    if (fs.existsSync(file)) {
+        try {
+            const queueId = JSON.parse(fs.readFileSync(file, 'utf8')).queueId;
+            if (typeof queueId === 'string' && queueId !== '') {
+                return queueId;
+            }
+        } catch (e) {
+            logger.warn(`failed to parse ${file}, generating a new queue ID`);
+        }
+    }
+    const queueId = 'zk-activity-' + randomBytes(8).toString('hex');
+    fs.writeFileSync(file, JSON.stringify({queueId}), 'utf8');
+    return queueId;
+}
+
+// RedisQ delivers the ESI killmail and the zKillboard metadata separately, the websocket merges them
+export function redisqPackageToZkData(redisqPackage: any): ZkData {
+    return {
+        ...redisqPackage.killmail,
+        zkb: {
+            ...redisqPackage.zkb,
+            url: redisqPackage.zkb.url || `https://zkillboard.com/kill/${redisqPackage.killID}/`,
+        },
+    };
+}
+
+// Long polls RedisQ for kills, one request at a time as required by zKillboard
+export class RedisQListener {
+    private source: KillSource;
+    private onKill: (data: ZkData) => Promise<void> | void;
+    private axios: Axios;
+    private running = false;
+
+    constructor(source: KillSource, onKill: (data: ZkData) => Promise<void> | void) {
+        this.source = source;
+        this.onKill = onKill;
+        this.axios = new Axios({
+            responseType: 'json',
+            timeout: (source.ttw + 20) * 1000,
+            transformResponse: data => JSON.parse(data),
+        });
+    }
+
+    start() {
+        this.running = true;
+        this.poll().catch(e => logger.error(`RedisQ listener stopped: ${e}`));
+    }
+
+    stop() {
+        this.running = false;
+    }
+
+    private async poll() {
+        while (this.running) {
+            try {
+                const response = await this.axios.get(this.source.redisqUrl, {
+                    params: {queueID: this.source.queueId, ttw: this.source.ttw},
+                });
+                if (response.status !== 200) {
+                    throw new Error(`status ${response.status}`);
+                }
+                if (response.data?.package) {
+                    await this.onKill(redisqPackageToZkData(response.data.package));
+                }
+            } catch (e) {
+                logger.warn(`RedisQ request failed, retrying in 5 seconds: ${e}`);
+                await new Promise(resolve => setTimeout(resolve, 5000));
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+export enum ReportSchedule {
+    DAILY = 'daily',
+    WEEKLY = 'weekly',
+}
+
+// Periodic summary of the kills posted in a channel
+export interface Report {
+    schedule: ReportSchedule;
+    // UTC hour the report is posted at
+    hour: number;
+    // Day of the week of weekly reports, 0 is Sunday
+    weekday?: number;
+    // ISO timestamp of the last report, reports are only posted for scheduled times after it
+    lastRun: string;
+}
+
+export const WEEKDAYS = ['sunday', 'monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday'];
+
+export function reportPeriodDays(report: Report): number {
+    return report.schedule === ReportSchedule.WEEKLY ? 7 : 1;
+}
+
+// Latest scheduled time of the report at or before now
+export function lastScheduledTime(report: Report, now: Date): Date {
+    const scheduled = new Date(Date.UTC(now.getUTCFullYear(), now.getUTCMonth(), now.getUTCDate(), report.hour));
+    if (scheduled.getTime() > now.getTime()) {
+        scheduled.setUTCDate(scheduled.getUTCDate() - 1);
+    }
+    if (report.schedule === ReportSchedule.WEEKLY) {
+        scheduled.setUTCDate(scheduled.getUTCDate() - (scheduled.getUTCDay() - (report.weekday ?? 1) + 7) % 7);
+    }
+    return scheduled;
+}
+
+// A report missed while the bot was offline is posted once, not once per missed time
+export function isReportDue(report: Report, now: Date): boolean {
+    return new Date(report.lastRun).getTime() < lastScheduledTime(report, now).getTime();
+}
+
+// Human readable schedule, e.g. "weekly on monday at 08:00 UTC"
+export function describeSchedule(report: Report): string {
+    const time = `${('0' + report.hour).slice(-2)}:00 UTC`;
+    return report.schedule === ReportSchedule.WEEKLY ?
+        `weekly on ${WEEKDAYS[report.weekday ?? 1]} at ${time}` : `daily at ${time}`;
+}
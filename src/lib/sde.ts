@@ -0,0 +1,134 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {SolarSystem} from '../zKillSubscriber';
+
+// Files of the fuzzwork CSV export of the static data export, only invTypes.csv is required
+export const SDE_TYPES_FILE = 'invTypes.csv';
+export const SDE_GROUPS_FILE = 'invGroups.csv';
+export const SDE_META_TYPES_FILE = 'invMetaTypes.csv';
+// Map files of the export, the systems cache is preloaded from them
+export const SDE_SYSTEMS_FILE = 'mapSolarSystems.csv';
+export const SDE_CONSTELLATIONS_FILE = 'mapConstellations.csv';
+export const SDE_REGIONS_FILE = 'mapRegions.csv';
+
+export interface SdeData {
+    // Mapping of type ID to group ID
+    typeGroups: Map<number, number>;
+    typeNames: Map<number, string>;
+    // Mapping of group ID to category ID
+    groupCategories: Map<number, number>;
+    groupNames: Map<number, string>;
+    // Mapping of type ID to meta group ID, only types of another meta group than Tech I are listed
+    typeMetaGroups: Map<number, number>;
+}
+
+// Parses CSV with quoted fields, which may contain commas, escaped quotes and line breaks like the type descriptions
+export function parseCsv(content: string): string[][] {
+    const rows: string[][] = [];
+    let row: string[] = [];
+    let field = '';
+    let quoted = false;
+    for (let i = 0; i < content.length; i++) {
+        const char = content[i];
+        if (quoted) {
+            if (char === '"' && content[i + 1] === '"') {
+                field += '"';
+                i++;
+            } else if (char === '"') {
+                quoted = false;
+            } else {
+                field += char;
+            }
+        } else if (char === '"') {
+            quoted = true;
+        } else if (char === ',') {
+            row.push(field);
+            field = '';
+        } else if (char === '\n') {
+            row.push(field.replace(/\r$/, ''));
+            rows.push(row);
+            row = [];
+            field = '';
+        } else {
+            field += char;
+        }
+    }
+    if (field !== '' || row.length > 0) {
+        row.push(field);
+        rows.push(row);
+    }
+    return rows;
+}
+
+// Rows as objects keyed by the header, empty if the file does not exist
+function readCsv(file: string): { [column: string]: string }[] {
+    if (!fs.existsSync(file)) {
+        return [];
+    }
+    const [header, ...rows] = parseCsv(fs.readFileSync(file, 'utf8'));
+    return rows.map(row => {
+        const record: { [column: string]: string } = {};
+        header.forEach((column, index) => record[column] = row[index]);
+        return record;
+    });
+}
+
+export function loadSde(dir: string): SdeData {
+    const sde: SdeData = {
+        typeGroups: new Map<number, number>(),
+        typeNames: new Map<number, string>(),
+        groupCategories: new Map<number, number>(),
+        groupNames: new Map<number, string>(),
+        typeMetaGroups: new Map<number, number>(),
+    };
+    for (const type of readCsv(path.join(dir, SDE_TYPES_FILE))) {
+        const typeId = Number(type.typeID);
+        if (Number.isInteger(typeId)) {
+            sde.typeGroups.set(typeId, Number(type.groupID));
+            sde.typeNames.set(typeId, type.typeName);
+        }
+    }
+    for (const group of readCsv(path.join(dir, SDE_GROUPS_FILE))) {
+        const groupId = Number(group.groupID);
+        if (Number.isInteger(groupId)) {
+            sde.groupCategories.set(groupId, Number(group.categoryID));
+            sde.groupNames.set(groupId, group.groupName);
+        }
+    }
+    for (const metaType of readCsv(path.join(dir, SDE_META_TYPES_FILE))) {
+        const typeId = Number(metaType.typeID);
+        if (Number.isInteger(typeId) && metaType.metaGroupID) {
+            sde.typeMetaGroups.set(typeId, Number(metaType.metaGroupID));
+        }
+    }
+    return sde;
+}
+
+// Systems with the names of their constellation and region, as the ESI lookups store them. Empty without the map files
+export function loadSdeSystems(dir: string): Map<number, SolarSystem> {
+    const regionNames = new Map<number, string>();
+    for (const region of readCsv(path.join(dir, SDE_REGIONS_FILE))) {
+        regionNames.set(Number(region.regionID), region.regionName);
+    }
+    const constellationNames = new Map<number, string>();
+    for (const constellation of readCsv(path.join(dir, SDE_CONSTELLATIONS_FILE))) {
+        constellationNames.set(Number(constellation.constellationID), constellation.constellationName);
+    }
+    const systems = new Map<number, SolarSystem>();
+    for (const system of readCsv(path.join(dir, SDE_SYSTEMS_FILE))) {
+        const id = Number(system.solarSystemID);
+        if (!Number.isInteger(id)) {
+            continue;
+        }
+        systems.set(id, {
+            id,
+            systemName: system.solarSystemName,
+            regionId: Number(system.regionID),
+            regionName: regionNames.get(Number(system.regionID)) ?? String(system.regionID),
+            constellationId: Number(system.constellationID),
+            constellationName: constellationNames.get(Number(system.constellationID)) ?? String(system.constellationID),
+            securityStatus: Number(system.security),
+        });
+    }
+    return systems;
+}
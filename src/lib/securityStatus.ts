@@ -0,0 +1,20 @@
+import {Attacker} from '../zKillSubscriber';
+import {isInValueRange, ValueRange} from './marketPrices';
+
+// Value format `min:max` of pilot security statuses from -10 to 10, either side may be left empty, e.g. `:-5` for outlaws
+export function parseSecStatusRange(value: string): ValueRange | null {
+    const parts = value.split(':');
+    if (parts.length !== 2 || parts.some(part => part.trim() !== '' && !/^-?\d+(\.\d+)?$/.test(part.trim()))) {
+        return null;
+    }
+    const [min, max] = parts.map(part => part.trim() === '' ? null : Number(part));
+    if ((min == null && max == null) || [min, max].some(bound => bound != null && Math.abs(bound) > 10)) {
+        return null;
+    }
+    return {min, max};
+}
+
+// Attacking pilots with a security status in the range, NPCs have none
+export function pilotsInSecStatusRange(attackers: Attacker[], range: ValueRange): Attacker[] {
+    return attackers.filter(attacker => attacker.character_id != null && isInValueRange(range, attacker.security_status));
+}
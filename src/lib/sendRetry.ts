@@ -0,0 +1,92 @@
+export enum SendErrorKind {
+    // Rate limits, Discord server errors and network failures, the send is retried
+    RETRYABLE = 'retryable',
+    // The channel is gone or the bot may not write in it
+    CHANNEL_UNAVAILABLE = 'channelUnavailable',
+    // The message was rejected, sending it again fails the same way
+    REJECTED = 'rejected',
+}
+
+// Takes the HTTP status of discord.js errors, errors without one never reached Discord
+export function classifySendError(e: unknown): SendErrorKind {
+    const status = (<{ httpStatus?: number }>e)?.httpStatus;
+    if (status == null || status === 429 || status >= 500) {
+        return SendErrorKind.RETRYABLE;
+    }
+    if (status === 403 || status === 404) {
+        return SendErrorKind.CHANNEL_UNAVAILABLE;
+    }
+    return SendErrorKind.REJECTED;
+}
+
+// Retries failed sends with exponential backoff instead of dropping the kill
+export class SendRetryQueue {
+    readonly maxAttempts: number;
+    readonly baseDelayMs: number;
+    private pending = 0;
+
+    constructor(maxAttempts: number, baseDelayMs: number) {
+        this.maxAttempts = maxAttempts;
+        this.baseDelayMs = baseDelayMs;
+    }
+
+    static fromEnv(env: NodeJS.ProcessEnv = process.env): SendRetryQueue {
+        return new SendRetryQueue(Number(env.SEND_RETRY_ATTEMPTS || 5), Number(env.SEND_RETRY_DELAY_SECONDS || 5) * 1000);
+    }
+
+    // Delay before the given retry, doubling with every attempt
+    delayMs(attempt: number): number {
+        return this.baseDelayMs * Math.pow(2, attempt - 1);
+    }
+
+    // Time from the first failure until the last retry, the kill has to be kept from being sent twice meanwhile
+    totalDelayMs(): number {
+        return this.baseDelayMs * (Math.pow(2, this.maxAttempts) - 1);
+    }
+
+    size(): number {
+        return this.pending;
+    }
+
+    // Resolves with the error kind the last attempt failed with, or null once sent
+    async retry(send: () => Promise<void>, sleep = (ms: number) => new Promise(resolve => setTimeout(resolve, ms))): Promise<SendErrorKind | null> {
+        this.pending++;
+        try {
+            for (let attempt = 1; ; attempt++) {
+                await sleep(this.delayMs(attempt));
+                try {
+                    await send();
+                    return null;
+                } catch (e) {
+                    const kind = classifySendError(e);
+                    if (kind !== SendErrorKind.RETRYABLE || attempt >= this.maxAttempts) {
+                        return kind;
+                    }
+                }
+            }
+        } finally {
+            this.pending--;
+        }
+    }
+}
+
+// Consecutive failures per channel, so a single failed send does not unsubscribe a channel
+export class ChannelFailures {
+    readonly threshold: number;
+    private failures = new Map<string, number>();
+
+    constructor(threshold: number) {
+        this.threshold = threshold;
+    }
+
+    // True once the channel failed threshold times in a row
+    fail(channelId: string): boolean {
+        const count = (this.failures.get(channelId) ?? 0) + 1;
+        this.failures.set(channelId, count);
+        return count >= this.threshold;
+    }
+
+    succeed(channelId: string) {
+        this.failures.delete(channelId);
+    }
+}
@@ -0,0 +1,51 @@
+// Ship filters take type IDs and match their groups, so every alias lists one type ID of each group it stands for
+export const SHIP_GROUP_ALIASES: { [alias: string]: { [groupName: string]: number } } = {
+    capitals: {
+        'Carrier': 23757,
+        'Dreadnought': 19720,
+        'Force Auxiliary': 37604,
+        'Capital Industrial Ship': 28352,
+        'Supercarrier': 23913,
+        'Titan': 11567,
+    },
+    supers: {
+        'Supercarrier': 23913,
+        'Titan': 11567,
+    },
+    structures: {
+        'Citadel': 35832,
+        'Engineering Complex': 35825,
+        'Refinery': 35835,
+        'Upwell Jump Gate': 35841,
+        'Control Tower': 12235,
+    },
+    industrials: {
+        'Industrial': 648,
+        'Deep Space Transport': 12753,
+        'Blockade Runner': 12735,
+        'Freighter': 20185,
+        'Jump Freighter': 28844,
+        'Mining Barge': 17480,
+        'Exhumer': 22544,
+        'Industrial Command Ship': 28606,
+    },
+    blops: {
+        'Black Ops': 22430,
+    },
+};
+
+// Replaces aliases like `capitals` in a comma separated list of type IDs, null if an entry is neither
+export function expandShipAliases(value: string): string | null {
+    const typeIds: number[] = [];
+    for (const part of value.split(',').map(part => part.trim().toLowerCase())) {
+        const alias = SHIP_GROUP_ALIASES[part];
+        if (alias) {
+            typeIds.push(...Object.values(alias));
+        } else if (/^\d+$/.test(part)) {
+            typeIds.push(Number(part));
+        } else {
+            return null;
+        }
+    }
+    return typeIds.filter((typeId, index) => typeIds.indexOf(typeId) === index).join(',');
+}
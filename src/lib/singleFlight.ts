@@ -0,0 +1,21 @@
+// Coalesces concurrent lookups of the same key, e.g. 30 attackers of the same unknown alliance cause one ESI request.
+// Only in-flight lookups are shared, the callers cache the results themselves.
+export class SingleFlight<T> {
+    private inFlight = new Map<string, Promise<T>>();
+
+    do(key: string, lookup: () => Promise<T>): Promise<T> {
+        let pending = this.inFlight.get(key);
+        if (!pending) {
+            pending = lookup();
+            this.inFlight.set(key, pending);
+            const forget = () => this.inFlight.delete(key);
+            pending.then(forget, forget);
+        }
+        return pending;
+    }
+
+    // Number of lookups currently in flight
+    size(): number {
+        return this.inFlight.size;
+    }
+}
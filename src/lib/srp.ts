@@ -0,0 +1,105 @@
+import * as fs from 'fs';
+import {logger} from './logger';
+
+export enum SrpStatus {
+    PENDING = 'pending',
+    APPROVED = 'approved',
+    DENIED = 'denied',
+}
+
+export interface SrpRequest {
+    killmailId: number;
+    url: string;
+    characterId: number;
+    pilotName: string;
+    shipTypeId?: number;
+    shipName: string;
+    totalValue: number;
+    // User ID of the member who clicked the button
+    requestedBy: string;
+    // ISO timestamp of the request
+    requestedAt: string;
+    status: SrpStatus;
+    reviewedBy?: string;
+}
+
+const SRP_BUTTON_PREFIX = 'srp_';
+
+// Custom ID of the "Request SRP" button on a loss embed
+export function srpButtonId(killmailId: number): string {
+    return SRP_BUTTON_PREFIX + killmailId;
+}
+
+// Null if the custom ID does not belong to a "Request SRP" button
+export function parseSrpButtonId(customId: string): number | null {
+    const match = /^srp_(\d+)$/.exec(customId);
+    return match ? Number(match[1]) : null;
+}
+
+// Ship replacement requests per guild, persisted in one file per guild
+export class SrpQueue {
+    private base_dir: string;
+    private guilds = new Map<string, SrpRequest[]>();
+
+    constructor(base_dir = './config/srp/') {
+        this.base_dir = base_dir;
+    }
+
+    private load(guildId: string): SrpRequest[] {
+        let requests = this.guilds.get(guildId);
+        if (requests) {
+            return requests;
+        }
+        requests = [];
+        const file = this.base_dir + guildId + '.json';
+        if (fs.existsSync(file)) {
+            try {
+                requests = JSON.parse(fs.readFileSync(file, 'utf8'));
+            } catch (e) {
+                logger.error(`failed to parse ${file}`);
+            }
+        }
+        this.guilds.set(guildId, requests);
+        return requests;
+    }
+
+    private save(guildId: string) {
+        if (!fs.existsSync(this.base_dir)) {
+            fs.mkdirSync(this.base_dir, {recursive: true});
+        }
+        fs.writeFileSync(this.base_dir + guildId + '.json', JSON.stringify(this.load(guildId)), 'utf8');
+    }
+
+    has(guildId: string, killmailId: number): boolean {
+        return this.load(guildId).some(request => request.killmailId === killmailId);
+    }
+
+    // False if the kill was already requested, a loss is replaced at most once
+    add(guildId: string, request: SrpRequest): boolean {
+        if (this.has(guildId, request.killmailId)) {
+            return false;
+        }
+        this.load(guildId).push(request);
+        this.save(guildId);
+        return true;
+    }
+
+    // Oldest first
+    pending(guildId: string): SrpRequest[] {
+        return this.load(guildId)
+            .filter(request => request.status === SrpStatus.PENDING)
+            .sort((a, b) => new Date(a.requestedAt).getTime() - new Date(b.requestedAt).getTime());
+    }
+
+    // Null if there is no pending request for the kill
+    review(guildId: string, killmailId: number, status: SrpStatus, reviewedBy: string): SrpRequest | null {
+        const request = this.load(guildId).find(existing => existing.killmailId === killmailId && existing.status === SrpStatus.PENDING);
+        if (!request) {
+            return null;
+        }
+        request.status = status;
+        request.reviewedBy = reviewedBy;
+        this.save(guildId);
+        return request;
+    }
+}
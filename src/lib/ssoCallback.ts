@@ -0,0 +1,73 @@
+import * as http from 'http';
+
+// Outcome of a request to the callback path, null if it was not the expected redirect
+export function parseSsoCallback(url: string, expectedState: string): { code: string } | { error: string } | null {
+    const parsed = new URL(url, 'http://localhost');
+    const code = parsed.searchParams.get('code');
+    const state = parsed.searchParams.get('state');
+    const error = parsed.searchParams.get('error');
+    if (state !== expectedState) {
+        return null;
+    }
+    if (error) {
+        return {error: parsed.searchParams.get('error_description') || error};
+    }
+    return code ? {code} : null;
+}
+
+// Receives the EVE SSO redirect after the login, so the grant code does not have to be copied out of the browser.
+// The redirect URI has to be the callback URL of the EVE application
+export class SsoCallbackServer {
+    readonly port: number;
+    readonly redirectUri: string;
+
+    constructor(port: number, redirectUri: string) {
+        this.port = port;
+        this.redirectUri = redirectUri;
+    }
+
+    // Null if SSO_CALLBACK_PORT is not set
+    static fromEnv(env: NodeJS.ProcessEnv = process.env): SsoCallbackServer | null {
+        if (!env.SSO_CALLBACK_PORT) {
+            return null;
+        }
+        const port = Number(env.SSO_CALLBACK_PORT);
+        return new SsoCallbackServer(port, env.SSO_CALLBACK_URL || `http://localhost:${port}/callback`);
+    }
+
+    // Resolves with the grant code of the first redirect with the given state, the server only runs until then
+    waitForCode(state: string, timeoutMs = 5 * 60 * 1000): Promise<string> {
+        const callbackPath = new URL(this.redirectUri).pathname;
+        return new Promise((resolve, reject) => {
+            const server = http.createServer((request, response) => {
+                const url = request.url || '';
+                const result = request.method === 'GET' && url.split('?')[0] === callbackPath ? parseSsoCallback(url, state) : null;
+                if (result == null) {
+                    response.writeHead(404, {'Content-Type': 'text/plain'});
+                    response.end('Not found');
+                    return;
+                }
+                response.writeHead(200, {'Content-Type': 'text/plain'});
+                response.end('error' in result ? 'Login failed: ' + result.error : 'Logged in, you can close this tab');
+                clearTimeout(timer);
+                server.close();
+                if ('error' in result) {
+                    reject(new Error('EVE SSO login failed: ' + result.error));
+                } else {
+                    resolve(result.code);
+                }
+            });
+            const timer = setTimeout(() => {
+                server.close();
+                reject(new Error(`No EVE SSO redirect within ${Math.round(timeoutMs / 1000)} seconds`));
+            }, timeoutMs);
+            // E.g. the port is in use, the login fails instead of the bot
+            server.on('error', e => {
+                clearTimeout(timer);
+                server.close();
+                reject(new Error(`EVE SSO callback server failed on port ${this.port}: ${e.message}`));
+            });
+            server.listen(this.port);
+        });
+    }
+}
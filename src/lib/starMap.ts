@@ -0,0 +1,170 @@
+import * as fs from 'fs';
+import {getSpaceType, SpaceType} from '../zKillSubscriber';
+import {LIGHT_YEAR_IN_METERS} from './jumpRange';
+
+export interface StarMapSystem {
+    id: number;
+    regionId: number;
+    securityStatus: number;
+    name?: string;
+    // Position in meters, used for jump drive distances
+    x?: number;
+    y?: number;
+    z?: number;
+}
+
+// Parses a CSV file with a header line into one object per row, keyed by the header names
+function readCsv(file: string): { [key: string]: string }[] {
+    const lines = fs.readFileSync(file, 'utf8').split(/\r?\n/).filter(line => line.trim() !== '');
+    if (lines.length === 0) {
+        return [];
+    }
+    const header = lines[0].split(',');
+    return lines.slice(1).map(line => {
+        const values = line.split(',');
+        const row: { [key: string]: string } = {};
+        header.forEach((key, index) => row[key] = values[index]);
+        return row;
+    });
+}
+
+// Stargate graph of New Eden, loaded from the SDE dumps mapSolarSystems.csv and mapSolarSystemJumps.csv
+// as published on https://www.fuzzwork.co.uk/dump/latest/
+export class StarMap {
+    private systems = new Map<number, StarMapSystem>();
+    private jumps = new Map<number, number[]>();
+    // Mapping of lower case system name to system ID
+    private systemIds = new Map<string, number>();
+    // Mapping of a BFS cache key to the jump distances it produced
+    private distanceCache = new Map<string, Map<number, number>>();
+
+    static fromSde(base_dir = './config/'): StarMap | null {
+        const systemsFile = base_dir + 'mapSolarSystems.csv';
+        const jumpsFile = base_dir + 'mapSolarSystemJumps.csv';
+        if (!fs.existsSync(systemsFile) || !fs.existsSync(jumpsFile)) {
+            return null;
+        }
+        const starMap = new StarMap();
+        for (const row of readCsv(systemsFile)) {
+            starMap.addSystem({
+                id: Number(row.solarSystemID),
+                regionId: Number(row.regionID),
+                securityStatus: Number(row.security),
+                name: row.solarSystemName,
+                x: Number(row.x),
+                y: Number(row.y),
+                z: Number(row.z),
+            });
+        }
+        for (const row of readCsv(jumpsFile)) {
+            starMap.addJump(Number(row.fromSolarSystemID), Number(row.toSolarSystemID));
+        }
+        return starMap;
+    }
+
+    addSystem(system: StarMapSystem) {
+        this.systems.set(system.id, system);
+        if (system.name) {
+            this.systemIds.set(system.name.toLowerCase(), system.id);
+        }
+        this.distanceCache.clear();
+    }
+
+    addJump(from: number, to: number) {
+        const neighbours = this.jumps.get(from) || [];
+        if (!neighbours.includes(to)) {
+            neighbours.push(to);
+        }
+        this.jumps.set(from, neighbours);
+        this.distanceCache.clear();
+    }
+
+    getSystem(systemId: number): StarMapSystem | undefined {
+        return this.systems.get(systemId);
+    }
+
+    findSystemByName(name: string): StarMapSystem | undefined {
+        const systemId = this.systemIds.get(name.trim().toLowerCase());
+        return systemId != null ? this.systems.get(systemId) : undefined;
+    }
+
+    // Straight line distance as used by jump drives, null if a position is unknown
+    lightYearsBetween(a: number, b: number): number | null {
+        const from = this.systems.get(a);
+        const to = this.systems.get(b);
+        if (from?.x == null || from.y == null || from.z == null || to?.x == null || to.y == null || to.z == null) {
+            return null;
+        }
+        const meters = Math.sqrt((from.x - to.x) ** 2 + (from.y - to.y) ** 2 + (from.z - to.z) ** 2);
+        return meters / LIGHT_YEAR_IN_METERS;
+    }
+
+    // Jump distances of all systems within maxJumps of any of the sources, computed once per key
+    distancesFrom(cacheKey: string, sources: number[], maxJumps: number): Map<number, number> {
+        const key = `${cacheKey}:${maxJumps}`;
+        const cached = this.distanceCache.get(key);
+        if (cached) {
+            return cached;
+        }
+        const distances = new Map<number, number>();
+        let frontier: number[] = [];
+        for (const source of sources) {
+            if (!distances.has(source)) {
+                distances.set(source, 0);
+                frontier.push(source);
+            }
+        }
+        for (let jumps = 1; jumps <= maxJumps && frontier.length > 0; jumps++) {
+            const next: number[] = [];
+            for (const systemId of frontier) {
+                for (const neighbour of this.jumps.get(systemId) || []) {
+                    if (!distances.has(neighbour)) {
+                        distances.set(neighbour, jumps);
+                        next.push(neighbour);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        this.distanceCache.set(key, distances);
+        return distances;
+    }
+
+    // Systems of the region with a stargate into another region
+    regionBorderSystems(regionId: number): number[] {
+        const border: number[] = [];
+        this.systems.forEach((system) => {
+            if (system.regionId !== regionId) {
+                return;
+            }
+            const neighbours = this.jumps.get(system.id) || [];
+            if (neighbours.some(neighbour => this.systems.get(neighbour)?.regionId !== regionId)) {
+                border.push(system.id);
+            }
+        });
+        return border;
+    }
+
+    jumpsFromRegionBorder(systemId: number, regionId: number, maxJumps: number): number | null {
+        const distances = this.distancesFrom(`border${regionId}`, this.regionBorderSystems(regionId), maxJumps);
+        return distances.get(systemId) ?? null;
+    }
+
+    jumpsFromSystem(systemId: number, fromSystemId: number, maxJumps: number): number | null {
+        const distances = this.distancesFrom(`system${fromSystemId}`, [fromSystemId], maxJumps);
+        return distances.get(systemId) ?? null;
+    }
+
+    // Number of jumps to the closest system of the space type, or null if it is further than maxJumps
+    jumpsToSpaceType(systemId: number, spaceType: SpaceType, maxJumps: number): number | null {
+        const distances = this.distancesFrom(`system${systemId}`, [systemId], maxJumps);
+        let closest: number | null = null;
+        distances.forEach((jumps, id) => {
+            const system = this.systems.get(id);
+            if (system && getSpaceType(system) === spaceType && (closest == null || jumps < closest)) {
+                closest = jumps;
+            }
+        });
+        return closest;
+    }
+}
@@ -0,0 +1,76 @@
+export enum StructureSize {
+    MEDIUM = 'M',
+    LARGE = 'L',
+    EXTRA_LARGE = 'XL',
+}
+
+export enum StructureKind {
+    CITADEL = 'citadel',
+    ENGINEERING_COMPLEX = 'engineeringComplex',
+    REFINERY = 'refinery',
+    FLEX = 'flex',
+    MOON_DRILL = 'moonDrill',
+}
+
+export enum QuantumCoreStatus {
+    DROPPED = 'dropped',
+    DESTROYED = 'destroyed',
+    NONE = 'none',
+}
+
+export interface StructureClass {
+    kind: StructureKind;
+    size: StructureSize | null;
+    // Type ID of the quantum core fitted to this hull, null if it takes none
+    coreTypeId: number | null;
+}
+
+const ASTRAHUS_CORE = 56201;
+const FORTIZAR_CORE = 56202;
+const KEEPSTAR_CORE = 56203;
+const RAITARU_CORE = 56204;
+const AZBEL_CORE = 56205;
+const SOTIYO_CORE = 56206;
+const ATHANOR_CORE = 56207;
+const TATARA_CORE = 56208;
+
+// Upwell structures by type ID, faction hulls share the core of the hull they are based on
+const UPWELL_STRUCTURES = new Map<number, StructureClass>([
+    [35832, {kind: StructureKind.CITADEL, size: StructureSize.MEDIUM, coreTypeId: ASTRAHUS_CORE}],
+    [35833, {kind: StructureKind.CITADEL, size: StructureSize.LARGE, coreTypeId: FORTIZAR_CORE}],
+    [47512, {kind: StructureKind.CITADEL, size: StructureSize.LARGE, coreTypeId: FORTIZAR_CORE}],
+    [47513, {kind: StructureKind.CITADEL, size: StructureSize.LARGE, coreTypeId: FORTIZAR_CORE}],
+    [47514, {kind: StructureKind.CITADEL, size: StructureSize.LARGE, coreTypeId: FORTIZAR_CORE}],
+    [47515, {kind: StructureKind.CITADEL, size: StructureSize.LARGE, coreTypeId: FORTIZAR_CORE}],
+    [47516, {kind: StructureKind.CITADEL, size: StructureSize.LARGE, coreTypeId: FORTIZAR_CORE}],
+    [35834, {kind: StructureKind.CITADEL, size: StructureSize.EXTRA_LARGE, coreTypeId: KEEPSTAR_CORE}],
+    [40340, {kind: StructureKind.CITADEL, size: StructureSize.EXTRA_LARGE, coreTypeId: KEEPSTAR_CORE}],
+    [35825, {kind: StructureKind.ENGINEERING_COMPLEX, size: StructureSize.MEDIUM, coreTypeId: RAITARU_CORE}],
+    [35826, {kind: StructureKind.ENGINEERING_COMPLEX, size: StructureSize.LARGE, coreTypeId: AZBEL_CORE}],
+    [35827, {kind: StructureKind.ENGINEERING_COMPLEX, size: StructureSize.EXTRA_LARGE, coreTypeId: SOTIYO_CORE}],
+    [35835, {kind: StructureKind.REFINERY, size: StructureSize.MEDIUM, coreTypeId: ATHANOR_CORE}],
+    [35836, {kind: StructureKind.REFINERY, size: StructureSize.LARGE, coreTypeId: TATARA_CORE}],
+    [35840, {kind: StructureKind.FLEX, size: null, coreTypeId: null}],
+    [35841, {kind: StructureKind.FLEX, size: null, coreTypeId: null}],
+    [37534, {kind: StructureKind.FLEX, size: null, coreTypeId: null}],
+    [81826, {kind: StructureKind.MOON_DRILL, size: StructureSize.MEDIUM, coreTypeId: null}],
+]);
+
+// Null if the type is not a known Upwell structure
+export function classifyStructure(typeId?: number): StructureClass | null {
+    if (typeId == null) {
+        return null;
+    }
+    return UPWELL_STRUCTURES.get(typeId) ?? null;
+}
+
+export function quantumCoreStatus(
+    structure: StructureClass,
+    items: { item_type_id: number; quantity_dropped?: number; quantity_destroyed?: number }[],
+): QuantumCoreStatus {
+    const core = items.find(item => item.item_type_id === structure.coreTypeId);
+    if (core == null) {
+        return QuantumCoreStatus.NONE;
+    }
+    return (core.quantity_dropped ?? 0) > 0 ? QuantumCoreStatus.DROPPED : QuantumCoreStatus.DESTROYED;
+}
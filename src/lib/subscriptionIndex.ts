@@ -0,0 +1,112 @@
+import {getLimitType, hasLimitType, LimitType, SolarSystem, Subscription, ZkData} from '../zKillSubscriber';
+
+// IDs of which at least one has to be on a kill for the subscription to match
+export interface IndexedRequirement {
+    systemIds: number[];
+    constellationIds: number[];
+    regionIds: number[];
+    // Character, corporation and alliance IDs of the victim or an attacker
+    entityIds: number[];
+}
+
+// Only requirements that reject a kill regardless of the other filters are indexed, null if the subscription has none
+export function indexedRequirement(subscription: Subscription): IndexedRequirement | null {
+    const ids = (limitType: LimitType) => (getLimitType(subscription, limitType) || '').split(',')
+        .filter(id => id.trim() !== '').map(Number);
+    // The location filters reject every kill outside the locations
+    if (hasLimitType(subscription, LimitType.SYSTEM) ||
+        hasLimitType(subscription, LimitType.CONSTELLATION) ||
+        hasLimitType(subscription, LimitType.REGION)) {
+        return {
+            systemIds: ids(LimitType.SYSTEM),
+            constellationIds: ids(LimitType.CONSTELLATION),
+            regionIds: ids(LimitType.REGION),
+            entityIds: [],
+        };
+    }
+    // The victim entity filter rejects every kill of someone else
+    if (hasLimitType(subscription, LimitType.VICTIM_ENTITY)) {
+        return {systemIds: [], constellationIds: [], regionIds: [], entityIds: ids(LimitType.VICTIM_ENTITY)};
+    }
+    // The entity filters need the victim or an attacker to match, unless a ship or faction filter matched before
+    if ((hasLimitType(subscription, LimitType.CHARACTER) ||
+        hasLimitType(subscription, LimitType.CORPORATION) ||
+        hasLimitType(subscription, LimitType.ALLIANCE)) &&
+        !hasLimitType(subscription, LimitType.SHIP_INCLUSION_TYPE_ID) &&
+        !hasLimitType(subscription, LimitType.FACTION)) {
+        return {
+            systemIds: [],
+            constellationIds: [],
+            regionIds: [],
+            entityIds: [...ids(LimitType.CHARACTER), ...ids(LimitType.CORPORATION), ...ids(LimitType.ALLIANCE)],
+        };
+    }
+    return null;
+}
+
+// Character, corporation and alliance IDs of the victim and the attackers
+export function involvedEntityIds(data: ZkData): number[] {
+    const ids: number[] = [];
+    for (const entity of [data.victim, ...data.attackers]) {
+        for (const id of [entity.character_id, entity.corporation_id, entity.alliance_id]) {
+            if (id != null) {
+                ids.push(id);
+            }
+        }
+    }
+    return ids;
+}
+
+// Inverted index from the IDs on a kill to the subscriptions requiring them, so a kill is only evaluated against
+// subscriptions that can match it. Subscriptions without an indexed requirement are evaluated for every kill.
+export class SubscriptionIndex<T> {
+    private unindexed: T[] = [];
+    private bySystem = new Map<number, T[]>();
+    private byConstellation = new Map<number, T[]>();
+    private byRegion = new Map<number, T[]>();
+    private byEntity = new Map<number, T[]>();
+
+    add(subscription: Subscription, target: T) {
+        const requirement = indexedRequirement(subscription);
+        if (requirement == null) {
+            this.unindexed.push(target);
+            return;
+        }
+        const insert = (index: Map<number, T[]>, ids: number[]) => ids.forEach(id => {
+            const targets = index.get(id);
+            if (targets) {
+                targets.push(target);
+            } else {
+                index.set(id, [target]);
+            }
+        });
+        insert(this.bySystem, requirement.systemIds);
+        insert(this.byConstellation, requirement.constellationIds);
+        insert(this.byRegion, requirement.regionIds);
+        insert(this.byEntity, requirement.entityIds);
+    }
+
+    // Targets to evaluate the kill for, each once. Without the system every constellation and region subscription is
+    // a candidate.
+    candidates(data: ZkData, system?: SolarSystem): T[] {
+        const candidates = new Set<T>(this.unindexed);
+        const collect = (targets?: T[]) => targets?.forEach(target => candidates.add(target));
+        collect(this.bySystem.get(data.solar_system_id));
+        if (system) {
+            collect(this.byConstellation.get(system.constellationId));
+            collect(this.byRegion.get(system.regionId));
+        } else {
+            this.byConstellation.forEach(collect);
+            this.byRegion.forEach(collect);
+        }
+        involvedEntityIds(data).forEach(id => collect(this.byEntity.get(id)));
+        return Array.from(candidates);
+    }
+
+    size(): number {
+        const targets = new Set<T>(this.unindexed);
+        [this.bySystem, this.byConstellation, this.byRegion, this.byEntity]
+            .forEach(index => index.forEach(indexed => indexed.forEach(target => targets.add(target))));
+        return targets.size;
+    }
+}
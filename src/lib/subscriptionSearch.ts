@@ -0,0 +1,46 @@
+import {Subscription, SubscriptionChannel} from '../zKillSubscriber';
+
+export interface SubscriptionSearchResult {
+    channelId: string;
+    ident: string;
+    subscription: Subscription;
+    // What contained the text, e.g. `description` or `alliance 99000001`
+    matchedOn: string;
+}
+
+// Subscriptions of the channels whose ID, description, campaign, filter name or filter value contains the text,
+// ignoring the case
+export function searchSubscriptions(channels: Map<string, SubscriptionChannel>, text: string): SubscriptionSearchResult[] {
+    const needle = text.trim().toLowerCase();
+    const results: SubscriptionSearchResult[] = [];
+    if (needle === '') {
+        return results;
+    }
+    const contains = (value?: string | null) => value != null && value.toLowerCase().includes(needle);
+    channels.forEach((channel, channelId) => channel.subscriptions.forEach((subscription, ident) => {
+        let matchedOn: string | null = null;
+        if (contains(ident)) {
+            matchedOn = 'ID';
+        } else if (contains(subscription.description)) {
+            matchedOn = 'description';
+        } else if (contains(subscription.campaign)) {
+            matchedOn = 'campaign';
+        } else {
+            subscription.limitTypes.forEach((value, limitType) => {
+                if (matchedOn == null && (contains(limitType) || contains(value))) {
+                    matchedOn = `${limitType} ${value}`;
+                }
+            });
+        }
+        if (matchedOn != null) {
+            results.push({channelId, ident, subscription, matchedOn});
+        }
+    }));
+    return results;
+}
+
+// Comma or space separated subscription IDs, null if one is not a number
+export function parseSubscriptionIds(value: string): string[] | null {
+    const ids = value.split(/[\s,]+/).filter(id => id !== '');
+    return ids.length > 0 && ids.every(id => /^\d+$/.test(id)) ? ids.filter((id, index) => ids.indexOf(id) === index) : null;
+}
@@ -0,0 +1,79 @@
+// How a subscription handled the kills since the counters were created, to tell a broken subscription from a quiet one
+export interface SubscriptionCounters {
+    // ISO timestamp the counting started
+    since: string;
+    // Kills the filters ran on, kills the pre-filter or the index ruled out are not counted
+    evaluated: number;
+    matched: number;
+    posted: number;
+    pinged: number;
+    // Mapping of the limit type or setting that rejected a kill to the number of kills it rejected
+    rejectedBy: { [filter: string]: number };
+    // ISO timestamp of the last matched kill
+    lastMatchAt?: string;
+}
+
+// Counters per subscription, keyed by guild, channel and subscription ident
+export class SubscriptionStats {
+    private counters = new Map<string, SubscriptionCounters>();
+
+    static key(guildId: string, channelId: string, ident: string): string {
+        return `${guildId}/${channelId}/${ident}`;
+    }
+
+    static fromJson(json: string): SubscriptionStats {
+        const stats = new SubscriptionStats();
+        const data = JSON.parse(json);
+        Object.keys(data).forEach(key => stats.counters.set(key, data[key]));
+        return stats;
+    }
+
+    toJson(): string {
+        return JSON.stringify(Object.fromEntries(this.counters));
+    }
+
+    get(key: string): SubscriptionCounters | undefined {
+        return this.counters.get(key);
+    }
+
+    private getOrCreate(key: string, now: Date): SubscriptionCounters {
+        let counters = this.counters.get(key);
+        if (!counters) {
+            counters = {since: now.toISOString(), evaluated: 0, matched: 0, posted: 0, pinged: 0, rejectedBy: {}};
+            this.counters.set(key, counters);
+        }
+        return counters;
+    }
+
+    recordEvaluation(key: string, matched: boolean, rejectedBy?: string, now = new Date()) {
+        const counters = this.getOrCreate(key, now);
+        counters.evaluated++;
+        if (matched) {
+            counters.matched++;
+            counters.lastMatchAt = now.toISOString();
+        } else if (rejectedBy) {
+            counters.rejectedBy[rejectedBy] = (counters.rejectedBy[rejectedBy] ?? 0) + 1;
+        }
+    }
+
+    recordPost(key: string, pinged: boolean, now = new Date()) {
+        const counters = this.getOrCreate(key, now);
+        counters.posted++;
+        if (pinged) {
+            counters.pinged++;
+        }
+    }
+
+    // Drops the counters of subscriptions that no longer exist
+    retain(keys: Set<string>) {
+        Array.from(this.counters.keys()).filter(key => !keys.has(key)).forEach(key => this.counters.delete(key));
+    }
+}
+
+// Filters that rejected the most kills, most first
+export function topRejections(counters: SubscriptionCounters, count: number): [string, number][] {
+    return Object.keys(counters.rejectedBy)
+        .map((filter): [string, number] => [filter, counters.rejectedBy[filter]])
+        .sort((a, b) => b[1] - a[1])
+        .slice(0, count);
+}
@@ -0,0 +1,95 @@
+import {LimitType} from '../zKillSubscriber';
+import {limitTypeProblem} from './configLint';
+import {parseLyRange} from './jumpRange';
+import {parseValueRange, ValueRange} from './marketPrices';
+import {parseSecStatusRange} from './securityStatus';
+
+// ID ranges of the static data, including wormhole space, Pochven and Zarzakh
+const LOCATION_ID_PATTERNS: [LimitType, RegExp, string][] = [
+    [LimitType.REGION, /^1[01]\d{6}$/, 'region IDs start with 10 or 11'],
+    [LimitType.CONSTELLATION, /^2[01]\d{6}$/, 'constellation IDs start with 20 or 21'],
+    [LimitType.SYSTEM, /^3[0-2]\d{6}$/, 'system IDs start with 30, 31 or 32'],
+];
+
+// Problems that make a subscription never match, reported when subscribing instead of saving filters silently.
+// `isKnownSystemName` returns null if no star map is loaded
+export function validateLimitTypes(limitTypes: Map<LimitType, string>, isKnownSystemName: (name: string) => boolean | null): string[] {
+    const problems: string[] = [];
+    limitTypes.forEach((value, key) => {
+        const problem = limitTypeProblem(key, value);
+        if (problem != null) {
+            problems.push(`${key} ${problem}`);
+        }
+    });
+    if (problems.length > 0) {
+        return problems;
+    }
+
+    for (const [limitType, pattern, hint] of LOCATION_ID_PATTERNS) {
+        const invalid = (limitTypes.get(limitType) ?? '').split(',').map(id => id.trim()).filter(id => id !== '' && !pattern.test(id));
+        if (invalid.length > 0) {
+            problems.push(`${limitType} ${invalid.join(',')} are no ${limitType} IDs, ${hint}`);
+        }
+    }
+
+    // A kill matches a lower bound and an upper bound only if the lower one is below the upper one
+    const bound = (limitType: LimitType) => limitTypes.has(limitType) ? Number(limitTypes.get(limitType)) : null;
+    const lowers = [bound(LimitType.SECURITY_MIN_INCLUSIVE), bound(LimitType.SECURITY_MIN_EXCLUSIVE)];
+    const uppers = [bound(LimitType.SECURITY_MAX_INCLUSIVE), bound(LimitType.SECURITY_MAX_EXCLUSIVE)];
+    lowers.forEach((lower, lowerIndex) => uppers.forEach((upper, upperIndex) => {
+        // The second bound of each is exclusive
+        if (lower != null && upper != null && (lower > upper || lower === upper && (lowerIndex === 1 || upperIndex === 1))) {
+            problems.push(`the minimum security ${lower} is not below the maximum security ${upper}`);
+        }
+    }));
+    [...lowers, ...uppers].filter(value => value != null && (value < -1 || value > 1))
+        .forEach(value => problems.push(`security ${value} is outside of -1.0 to 1.0`));
+
+    const included = (limitTypes.get(LimitType.SHIP_INCLUSION_TYPE_ID) ?? '').split(',');
+    const both = (limitTypes.get(LimitType.SHIP_EXCLUSION_TYPE_ID) ?? '').split(',').filter(id => id !== '' && included.includes(id));
+    if (both.length > 0) {
+        problems.push(`ship types ${both.join(',')} are both included and excluded`);
+    }
+
+    // The excluded attackers do not count for the entity filters and the excluded victims are not posted
+    for (const [limitType, excludedType] of [
+        [LimitType.ALLIANCE, LimitType.EXCLUDED_ALLIANCE],
+        [LimitType.CORPORATION, LimitType.EXCLUDED_CORPORATION],
+    ]) {
+        const excluded = (limitTypes.get(excludedType) ?? '').split(',').map(id => id.trim());
+        const entityIds = (limitTypes.get(limitType) ?? '').split(',').map(id => id.trim()).filter(id => id !== '');
+        if (entityIds.length > 0 && entityIds.every(id => excluded.includes(id))) {
+            problems.push(`all ${limitType} IDs ${entityIds.join(',')} are excluded as well`);
+        }
+    }
+
+    for (const [limitType, parse] of [
+        [LimitType.HULL_VALUE, parseValueRange],
+        [LimitType.VICTIM_CORP_SIZE, parseValueRange],
+        [LimitType.ATTACKER_SEC_STATUS, parseSecStatusRange],
+    ] as [LimitType, (value: string) => ValueRange | null][]) {
+        const range = limitTypes.has(limitType) ? parse(<string>limitTypes.get(limitType)) : null;
+        if (range?.min != null && range.max != null && range.min > range.max) {
+            problems.push(`the minimum ${limitType} ${range.min} is above the maximum ${range.max}`);
+        }
+    }
+
+    for (const limitType of [LimitType.TIME_RANGE_START, LimitType.TIME_RANGE_END]) {
+        const hour = bound(limitType);
+        if (hour != null && (!Number.isInteger(hour) || hour < 0 || hour > 23)) {
+            problems.push(`${limitType} ${hour} is not an hour from 0 to 23`);
+        }
+    }
+
+    const lyRange = limitTypes.has(LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME) ?
+        parseLyRange(<string>limitTypes.get(LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME)) : null;
+    if (lyRange != null) {
+        const known = isKnownSystemName(lyRange.systemName);
+        if (known == null) {
+            problems.push('no star map is loaded on this instance, jump ranges never match');
+        } else if (!known) {
+            problems.push(`unknown system ${lyRange.systemName} for the jump range`);
+        }
+    }
+    return problems;
+}
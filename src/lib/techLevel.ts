@@ -0,0 +1,37 @@
+export enum TechLevel {
+    T1 = 't1',
+    T2 = 't2',
+    T3 = 't3',
+    // Navy, pirate and storyline hulls
+    FACTION = 'faction',
+    DEADSPACE = 'deadspace',
+    OFFICER = 'officer',
+}
+
+// Dogma attribute of the type holding its meta group, for types not in the SDE
+export const META_GROUP_ATTRIBUTE_ID = 1692;
+
+// Mapping of the meta group IDs of the static data to the tech levels, types without a meta group are T1
+const META_GROUP_TECH_LEVELS = new Map<number, TechLevel>([
+    [1, TechLevel.T1],
+    [2, TechLevel.T2],
+    [3, TechLevel.FACTION],
+    [4, TechLevel.FACTION],
+    [5, TechLevel.OFFICER],
+    [6, TechLevel.DEADSPACE],
+    [14, TechLevel.T3],
+    [52, TechLevel.FACTION],
+    [53, TechLevel.T2],
+    [54, TechLevel.T1],
+]);
+
+export function techLevelOf(metaGroupId: number | null | undefined): TechLevel {
+    return (metaGroupId != null ? META_GROUP_TECH_LEVELS.get(metaGroupId) : undefined) ?? TechLevel.T1;
+}
+
+// Comma separated tech levels, null if an entry is not a tech level
+export function parseTechLevels(value: string): TechLevel[] | null {
+    const levels = value.split(',').map(part => part.trim().toLowerCase());
+    const known = Object.values(TechLevel) as string[];
+    return levels.every(part => known.includes(part)) ? levels as TechLevel[] : null;
+}
@@ -0,0 +1,54 @@
+import {LimitType} from '../zKillSubscriber';
+import {expandShipAliases} from './shipGroups';
+import {parseLyRange} from './jumpRange';
+
+// Filters and minimum value a quick subscription expands to
+export interface SubscriptionTemplate {
+    limitTypes: Map<LimitType, string>;
+    minValue: number;
+}
+
+export const BIG_KILL_VALUE = 10e9;
+
+// Losses of the alliances, kills by them are not posted
+export function allianceLossesTemplate(allianceIds: string, minValue = 0): SubscriptionTemplate {
+    return {
+        limitTypes: new Map<LimitType, string>([
+            [LimitType.VICTIM_ENTITY, allianceIds],
+        ]),
+        minValue,
+    };
+}
+
+// Kills and losses of an alliance or corporation, the starter subscription of the setup wizard
+export function entityActivityTemplate(limitType: LimitType.ALLIANCE | LimitType.CORPORATION, entityId: number, minValue = 0): SubscriptionTemplate {
+    return {limitTypes: new Map<LimitType, string>([[limitType, String(entityId)]]), minValue};
+}
+
+// Capitals killing or dying within jump range of a system, e.g. hostile capitals near the staging. Null if the range
+// is neither a distance nor a ship class.
+export function capitalsInRangeTemplate(systemName: string, range: string, minValue = 0): SubscriptionTemplate | null {
+    const lyRange = `${systemName}:${range}`;
+    if (parseLyRange(lyRange) == null) {
+        return null;
+    }
+    return {
+        limitTypes: new Map<LimitType, string>([
+            [LimitType.SHIP_INCLUSION_TYPE_ID, <string>expandShipAliases('capitals')],
+            [LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME, lyRange],
+        ]),
+        minValue,
+    };
+}
+
+// NPC kills by officer and faction commander spawns, e.g. to find them in the anomalies nearby
+export function npcSpawnsTemplate(minValue = 0): SubscriptionTemplate {
+    return {
+        limitTypes: new Map<LimitType, string>([[LimitType.NPC_SPAWN, 'officers,commanders']]),
+        minValue,
+    };
+}
+
+export function bigKillsTemplate(minValue = BIG_KILL_VALUE): SubscriptionTemplate {
+    return {limitTypes: new Map<LimitType, string>(), minValue};
+}
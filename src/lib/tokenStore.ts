@@ -0,0 +1,94 @@
+import * as fs from 'fs';
+import {createCipheriv, createDecipheriv, randomBytes} from 'crypto';
+
+const ALGORITHM = 'aes-256-gcm';
+const FORMAT_VERSION = 1;
+
+// The file content of an encrypted token, all binary fields base64 encoded
+interface EncryptedToken {
+    encrypted: number;
+    nonce: string;
+    tag: string;
+    data: string;
+}
+
+// 32 bytes, base64 or hex encoded, e.g. generated with `openssl rand -base64 32`. Null if unset, throws if malformed
+export function parseEncryptionKey(value?: string): Buffer | null {
+    if (!value) {
+        return null;
+    }
+    const key = /^[0-9a-fA-F]{64}$/.test(value) ? Buffer.from(value, 'hex') : Buffer.from(value, 'base64');
+    if (key.length !== 32) {
+        throw new Error('TOKEN_ENCRYPTION_KEY has to be 32 bytes, base64 or hex encoded');
+    }
+    return key;
+}
+
+export function encryptToken(json: string, key: Buffer): string {
+    const nonce = randomBytes(12);
+    const cipher = createCipheriv(ALGORITHM, key, nonce);
+    const data = Buffer.concat([cipher.update(json, 'utf8'), cipher.final()]);
+    const encrypted: EncryptedToken = {
+        encrypted: FORMAT_VERSION,
+        nonce: nonce.toString('base64'),
+        tag: cipher.getAuthTag().toString('base64'),
+        data: data.toString('base64'),
+    };
+    return JSON.stringify(encrypted);
+}
+
+export function isEncryptedToken(content: string): boolean {
+    try {
+        return JSON.parse(content).encrypted === FORMAT_VERSION;
+    } catch (e) {
+        return false;
+    }
+}
+
+// Throws if the key is wrong or the file was modified
+export function decryptToken(content: string, key: Buffer): string {
+    const encrypted: EncryptedToken = JSON.parse(content);
+    const decipher = createDecipheriv(ALGORITHM, key, Buffer.from(encrypted.nonce, 'base64'));
+    decipher.setAuthTag(Buffer.from(encrypted.tag, 'base64'));
+    return Buffer.concat([decipher.update(Buffer.from(encrypted.data, 'base64')), decipher.final()]).toString('utf8');
+}
+
+// Keeps the EVE SSO access and refresh tokens in a file, encrypted if TOKEN_ENCRYPTION_KEY is set. Plaintext files
+// written before the key was set are encrypted on the first load
+export class TokenStore {
+    private file: string;
+    private key: Buffer | null;
+
+    constructor(file: string, key: Buffer | null) {
+        this.file = file;
+        this.key = key;
+    }
+
+    static fromEnv(env: NodeJS.ProcessEnv = process.env, file = 'accessToken.json'): TokenStore {
+        return new TokenStore(file, parseEncryptionKey(env.TOKEN_ENCRYPTION_KEY));
+    }
+
+    // The token as JSON, null if none was stored yet
+    load(): string | null {
+        if (!fs.existsSync(this.file)) {
+            return null;
+        }
+        const content = fs.readFileSync(this.file, 'utf8');
+        if (!isEncryptedToken(content)) {
+            if (this.key) {
+                this.save(content);
+            }
+            return content;
+        }
+        if (!this.key) {
+            throw new Error(`${this.file} is encrypted, set TOKEN_ENCRYPTION_KEY to read it`);
+        }
+        return decryptToken(content, this.key);
+    }
+
+    save(json: string) {
+        // Only the bot's user may read the tokens, even if they are encrypted. The mode only applies to new files
+        fs.writeFileSync(this.file, this.key ? encryptToken(json, this.key) : json, {encoding: 'utf8', mode: 0o600});
+        fs.chmodSync(this.file, 0o600);
+    }
+}
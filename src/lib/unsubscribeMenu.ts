@@ -0,0 +1,101 @@
+import {randomBytes} from 'crypto';
+import {Subscription} from '../zKillSubscriber';
+import {BoundedCache} from './boundedCache';
+
+// Select menus have up to 25 options, labels and descriptions up to 100 characters
+export const MAX_MENU_OPTIONS = 25;
+const MAX_OPTION_TEXT = 100;
+
+export enum UnsubscribeAction {
+    // Select menu of the subscriptions of the channel
+    SELECT = 'select',
+    CONFIRM = 'confirm',
+    CANCEL = 'cancel',
+}
+
+const UNSUBSCRIBE_PREFIX = 'unsub';
+
+export function unsubscribeCustomId(action: UnsubscribeAction, sessionId: string): string {
+    return [UNSUBSCRIBE_PREFIX, action, sessionId].join('_');
+}
+
+// Null if the custom ID does not belong to the unsubscribe menu
+export function parseUnsubscribeCustomId(customId: string): { action: UnsubscribeAction, sessionId: string } | null {
+    const match = /^unsub_(select|confirm|cancel)_([0-9a-f]+)$/.exec(customId);
+    if (!match) {
+        return null;
+    }
+    return {action: <UnsubscribeAction>match[1], sessionId: match[2]};
+}
+
+function shorten(text: string): string {
+    return text.length > MAX_OPTION_TEXT ? text.substring(0, MAX_OPTION_TEXT - 1) + '…' : text;
+}
+
+// The description, or the filters if the subscription has none, so subscriptions can be told apart without their ID
+export function describeSubscription(subscription: Subscription): string {
+    let text = subscription.description ?? '';
+    if (text === '') {
+        const filters: string[] = [];
+        subscription.limitTypes.forEach((value, limitType) => filters.push(`${limitType} ${value}`));
+        if (subscription.minValue > 0) {
+            filters.push(`minValue ${subscription.minValue}`);
+        }
+        text = filters.length > 0 ? filters.join(', ') : 'every kill';
+    }
+    return subscription.disabledAt ? `disabled, ${text}` : text;
+}
+
+// Options of the select menu, the subscription IDs sorted like /zkill-find lists them, at most MAX_MENU_OPTIONS
+export function unsubscribeOptions(subscriptions: Map<string, Subscription>): { label: string, value: string, description: string }[] {
+    const options: { label: string, value: string, description: string }[] = [];
+    subscriptions.forEach(subscription => {
+        const id = subscription.id ?? '';
+        options.push({label: shorten(id !== '' ? id : subscription.subType), value: id, description: shorten(describeSubscription(subscription))});
+    });
+    return options
+        .sort((a, b) => a.value.localeCompare(b.value, undefined, {numeric: true}))
+        .slice(0, MAX_MENU_OPTIONS);
+}
+
+// Subscription IDs picked in the menu, removed only once the member confirms
+export interface UnsubscribeSelection {
+    guildId: string;
+    channelId: string;
+    userId: string;
+    ids: string[];
+}
+
+// Selections stop being usable once their ephemeral message expires after 15 minutes
+const SESSION_TTL_MS = 15 * 60 * 1000;
+const MAX_SESSIONS = 1000;
+
+// Selections of the open menus, kept in memory only
+export class UnsubscribeSessions {
+    private selections = new BoundedCache<string, UnsubscribeSelection>(MAX_SESSIONS, SESSION_TTL_MS);
+
+    create(guildId: string, channelId: string, userId: string, now = Date.now()): string {
+        const sessionId = randomBytes(6).toString('hex');
+        this.selections.set(sessionId, {guildId, channelId, userId, ids: []}, now);
+        return sessionId;
+    }
+
+    get(sessionId: string, now = Date.now()): UnsubscribeSelection | undefined {
+        return this.selections.get(sessionId, now);
+    }
+
+    // Replaces the picked IDs, the session lives another 15 minutes
+    select(sessionId: string, ids: string[], now = Date.now()): UnsubscribeSelection | undefined {
+        const selection = this.selections.get(sessionId, now);
+        if (!selection) {
+            return undefined;
+        }
+        const updated = {...selection, ids};
+        this.selections.set(sessionId, updated, now);
+        return updated;
+    }
+
+    delete(sessionId: string) {
+        this.selections.delete(sessionId);
+    }
+}
@@ -0,0 +1,45 @@
+import {Locale} from './i18n';
+
+export enum ValueFormat {
+    // 1.5bil
+    SHORT = 'short',
+    // 1,512,345,678 with the thousands separator of the guild's language, e.g. for exact SRP payouts
+    FULL = 'full',
+}
+
+// Value settings of a guild, applied to the ISK values of the kill embeds and the SRP queue
+export interface ValueFormatSettings {
+    valueFormat?: ValueFormat;
+    // Adds the value in PLEX at the current average market price
+    showPlexValue?: boolean;
+}
+
+export const PLEX_TYPE_ID = 44992;
+
+const NUMBER_LOCALES: { [locale in Locale]: string } = {
+    [Locale.EN]: 'en-US',
+    [Locale.DE]: 'de-DE',
+    [Locale.RU]: 'ru-RU',
+};
+
+export function abbreviateValue(n: number): string {
+    if (n < 1e3) return String(n);
+    if (n < 1e6) return +(n / 1e3).toFixed(1) + 'K';
+    if (n < 1e9) return +(n / 1e6).toFixed(1) + 'mil';
+    if (n < 1e12) return +(n / 1e9).toFixed(1) + 'bil';
+    return +(n / 1e12).toFixed(1) + 'tril';
+}
+
+// The value in ISK as configured, the PLEX equivalent is left out while the PLEX price is unknown
+export function formatValue(n: number, settings: ValueFormatSettings, locale = Locale.EN, plexPrice: number | null = null): string {
+    let text = settings.valueFormat === ValueFormat.FULL ?
+        Math.round(n).toLocaleString(NUMBER_LOCALES[locale]) :
+        abbreviateValue(n);
+    if (settings.showPlexValue && plexPrice) {
+        const plex = n / plexPrice;
+        // Small amounts keep a decimal, a 0.4 PLEX kill would otherwise show as 0
+        const rounded = plex < 10 ? Math.round(plex * 10) / 10 : Math.round(plex);
+        text += ` (≈${rounded.toLocaleString(NUMBER_LOCALES[locale])} PLEX)`;
+    }
+    return text;
+}
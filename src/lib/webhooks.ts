@@ -0,0 +1,70 @@
+import {Axios} from 'axios';
+import {createHmac} from 'crypto';
+import {ZkData} from '../zKillSubscriber';
+
+export enum WebhookKind {
+    // Receives the embed of the kill, like a channel
+    DISCORD = 'discord',
+    // Receives the killmail as JSON, e.g. a Slack or Matrix bridge or a SIEM
+    JSON = 'json',
+}
+
+export interface Webhook {
+    url: string;
+    kind: WebhookKind;
+    // Key of the HMAC-SHA256 signature of generic webhooks, unsigned if unset
+    secret?: string;
+}
+
+export const WEBHOOK_SIGNATURE_HEADER = 'X-Signature-256';
+
+const DISCORD_WEBHOOK_PATTERN = /^https:\/\/(ptb\.|canary\.)?discord(app)?\.com\/api\/webhooks\/\d+\/[\w-]+$/;
+
+// Discord webhook URLs are recognized, any other HTTPS URL is a generic webhook. Null if the URL is not HTTPS
+export function webhookKind(url: string): WebhookKind | null {
+    if (DISCORD_WEBHOOK_PATTERN.test(url)) {
+        return WebhookKind.DISCORD;
+    }
+    return /^https:\/\/[^\s/]+(\/\S*)?$/.test(url) ? WebhookKind.JSON : null;
+}
+
+// Value of the signature header, the hex HMAC-SHA256 of the body prefixed with the algorithm like GitHub does
+export function signWebhookBody(secret: string, body: string): string {
+    return 'sha256=' + createHmac('sha256', secret).update(body).digest('hex');
+}
+
+// Body posted to generic webhooks
+export function webhookKillPayload(data: ZkData, guildId: string, channelId: string, subscriptionId?: string): object {
+    return {
+        killmail_id: data.killmail_id,
+        killmail_time: data.killmail_time,
+        solar_system_id: data.solar_system_id,
+        url: data.zkb.url,
+        total_value: data.zkb.totalValue,
+        victim: data.victim,
+        attackers: data.attackers,
+        guild_id: guildId,
+        channel_id: channelId,
+        subscription_id: subscriptionId,
+    };
+}
+
+// Hides the token of the URL, e.g. in command replies and logs
+export function redactWebhookUrl(url: string): string {
+    const match = /^(https:\/\/[^/]+)/.exec(url);
+    return match ? match[1] + '/...' : url;
+}
+
+const axios = new Axios({timeout: 10000});
+
+// Throws if the webhook does not answer with a 2xx status
+export async function postWebhook(webhook: Webhook, body: string) {
+    const headers: { [name: string]: string } = {'Content-Type': 'application/json'};
+    if (webhook.kind === WebhookKind.JSON && webhook.secret) {
+        headers[WEBHOOK_SIGNATURE_HEADER] = signWebhookBody(webhook.secret, body);
+    }
+    const response = await axios.post(webhook.url, body, {headers});
+    if (response.status < 200 || response.status >= 300) {
+        throw new Error(`webhook ${redactWebhookUrl(webhook.url)} answered ${response.status}`);
+    }
+}
@@ -0,0 +1,102 @@
+import {Axios} from 'axios';
+import {Zkb} from '../zKillSubscriber';
+
+const ZKB_API_URL = 'https://zkillboard.com/api/';
+
+export enum IntelEntityType {
+    CHARACTER = 'character',
+    CORPORATION = 'corporation',
+    ALLIANCE = 'alliance',
+}
+
+// Name of the ID in the zKillboard URLs of each entity type
+const ZKB_ID_NAMES: { [type in IntelEntityType]: string } = {
+    [IntelEntityType.CHARACTER]: 'characterID',
+    [IntelEntityType.CORPORATION]: 'corporationID',
+    [IntelEntityType.ALLIANCE]: 'allianceID',
+};
+
+export interface FavoriteShip {
+    shipTypeId: number;
+    shipName: string;
+    kills: number;
+}
+
+// What the lookup command shows of the zKillboard statistics of an entity
+export interface EntityIntel {
+    kills: number;
+    losses: number;
+    iskDestroyed: number;
+    iskLost: number;
+    soloKills: number;
+    // Percentages of zKillboard, null if it has not computed them for the entity
+    dangerRatio: number | null;
+    gangRatio: number | null;
+    favoriteShips: FavoriteShip[];
+    // EVE time hour with the most kills, null without any recent activity
+    primeTimeHour: number | null;
+}
+
+export function zkbEntityUrl(type: IntelEntityType, id: number): string {
+    return `https://zkillboard.com/${type}/${id}/`;
+}
+
+// The activity of the stats is a kill count per weekday and hour, keyed '0' to '6' and '0' to '23'
+export function primeTimeHour(activity?: { [day: string]: unknown }): number | null {
+    const perHour: number[] = [];
+    for (let hour = 0; hour < 24; hour++) {
+        let count = 0;
+        for (let day = 0; day < 7; day++) {
+            const hours = activity?.[String(day)] as { [hour: string]: number } | undefined;
+            count += Number(hours?.[String(hour)] ?? 0);
+        }
+        perHour.push(count);
+    }
+    const max = Math.max(...perHour);
+    return max > 0 ? perHour.indexOf(max) : null;
+}
+
+export function parseEntityIntel(stats: any, maxShips = 3): EntityIntel {
+    const shipList = (stats?.topLists ?? []).find((list: any) => list.type === 'shipType');
+    return {
+        kills: Number(stats?.shipsDestroyed ?? 0),
+        losses: Number(stats?.shipsLost ?? 0),
+        iskDestroyed: Number(stats?.iskDestroyed ?? 0),
+        iskLost: Number(stats?.iskLost ?? 0),
+        soloKills: Number(stats?.soloKills ?? 0),
+        dangerRatio: stats?.dangerRatio != null ? Number(stats.dangerRatio) : null,
+        gangRatio: stats?.gangRatio != null ? Number(stats.gangRatio) : null,
+        favoriteShips: (shipList?.values ?? []).slice(0, maxShips).map((ship: any) => ({
+            shipTypeId: Number(ship.shipTypeID),
+            shipName: ship.shipName,
+            kills: Number(ship.kills),
+        })),
+        primeTimeHour: primeTimeHour(stats?.activity),
+    };
+}
+
+// Client of the zKillboard API, apart from the RedisQ feed
+export class ZkbApiClient {
+    private axios: Axios;
+
+    constructor() {
+        this.axios = new Axios({baseURL: ZKB_API_URL, responseType: 'json', transformResponse: data => JSON.parse(data)});
+    }
+
+    // zKillboard metadata of a kill, including the hash needed to fetch the killmail from ESI
+    async getZkb(killmailId: number): Promise<Zkb> {
+        const zkbData = await this.axios.get(`killID/${killmailId}/`);
+        if (!Array.isArray(zkbData.data) || zkbData.data.length === 0) {
+            throw new Error('ZKB_FETCH_ERROR');
+        }
+        return {...zkbData.data[0].zkb, url: `https://zkillboard.com/kill/${killmailId}/`};
+    }
+
+    async getEntityIntel(type: IntelEntityType, id: number): Promise<EntityIntel> {
+        const statsData = await this.axios.get(`stats/${ZKB_ID_NAMES[type]}/${id}/`);
+        if (statsData.status !== 200 || statsData.data == null || statsData.data.error) {
+            throw new Error('ZKB_STATS_FETCH_ERROR');
+        }
+        return parseEntityIntel(statsData.data);
+    }
+}
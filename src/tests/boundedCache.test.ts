@@ -0,0 +1,27 @@
+import {BoundedCache, hitRate} from '../lib/boundedCache';
+
+describe('BoundedCache', () => {
+    it('should evict the least recently used entries', () => {
+        const cache = new BoundedCache<number, string>(2);
+        cache.set(1, 'Jita');
+        cache.set(2, 'Amarr');
+        expect(cache.get(1)).toBe('Jita');
+        cache.set(3, 'Dodixie');
+        expect(cache.get(2)).toBeUndefined();
+        expect(cache.toArray()).toEqual([[1, 'Jita'], [3, 'Dodixie']]);
+        expect(cache.toJson()).toBe('{"1":"Jita","3":"Dodixie"}');
+        expect(cache.stats()).toEqual({size: 2, hits: 1, misses: 1, evictions: 1});
+        expect(cache.delete(1)).toBe(true);
+        expect(cache.delete(1)).toBe(false);
+    });
+
+    it('should expire entries older than the TTL', () => {
+        const cache = new BoundedCache<number, string>(10, 1000);
+        cache.set(1, 'Old name', 0);
+        expect(cache.get(1, 1000)).toBe('Old name');
+        expect(cache.get(1, 1001)).toBeUndefined();
+        expect(cache.size).toBe(0);
+        expect(hitRate(cache.stats())).toBe(0.5);
+        expect(hitRate(new BoundedCache<number, string>(1).stats())).toBeNull();
+    });
+});
@@ -0,0 +1,31 @@
+import {BoundedQueue} from '../lib/boundedQueue';
+
+describe('Bounded Queue', () => {
+    it('should reject items when full', () => {
+        const queue = new BoundedQueue<number>(2);
+        expect(queue.tryPush(1)).toBe(true);
+        expect(queue.tryPush(2)).toBe(true);
+        expect(queue.tryPush(3)).toBe(false);
+        expect(queue.size).toBe(2);
+    });
+
+    it('should let producers wait for space', async () => {
+        const queue = new BoundedQueue<number>(1);
+        await queue.push(1);
+        let pushed = false;
+        const pending = queue.push(2).then(() => pushed = true);
+        await Promise.resolve();
+        expect(pushed).toBe(false);
+        expect(await queue.take()).toBe(1);
+        await pending;
+        expect(pushed).toBe(true);
+        expect(await queue.take()).toBe(2);
+    });
+
+    it('should hand items to waiting consumers', async () => {
+        const queue = new BoundedQueue<number>(1);
+        const taken = queue.take();
+        queue.tryPush(7);
+        expect(await taken).toBe(7);
+    });
+});
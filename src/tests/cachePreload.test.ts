@@ -0,0 +1,44 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {Axios} from 'axios';
+import {downloadSdeDumps, FUZZWORK_CSV_URL, preloadCaches} from '../lib/cachePreload';
+
+describe('CachePreload', () => {
+    let dir: string;
+
+    beforeEach(() => {
+        dir = fs.mkdtempSync(path.join(os.tmpdir(), 'preload-'));
+    });
+
+    afterEach(() => fs.rmSync(dir, {recursive: true}));
+
+    const readJson = (file: string) => JSON.parse(fs.readFileSync(path.join(dir, file), 'utf8'));
+
+    it('should fill the cache files and keep the names the dumps do not cover', () => {
+        fs.writeFileSync(path.join(dir, 'invTypes.csv'), 'typeID,groupID,typeName\n587,25,Rifter\n');
+        fs.writeFileSync(path.join(dir, 'invGroups.csv'), 'groupID,categoryID,groupName\n25,6,Frigate\n');
+        fs.writeFileSync(path.join(dir, 'mapSolarSystems.csv'), 'regionID,constellationID,solarSystemID,solarSystemName,security\n10000002,20000020,30000142,Jita,0.9\n');
+        fs.writeFileSync(path.join(dir, 'names.json'), '{"2112625428":"CCP Zoetrope"}');
+
+        expect(preloadCaches(dir, dir)).toEqual({systems: 1, ships: 1, categories: 1, names: 1});
+        expect(readJson('names.json')).toEqual({2112625428: 'CCP Zoetrope', 587: 'Rifter'});
+        expect(readJson('ships.json')).toEqual({587: 25});
+        expect(readJson('categories.json')).toEqual({25: 6});
+        expect(readJson('systems.json')[30000142].regionName).toBe('10000002');
+    });
+
+    it('should only download the missing dumps', async () => {
+        fs.writeFileSync(path.join(dir, 'invTypes.csv'), 'typeID,groupID,typeName\n');
+        const urls: string[] = [];
+        const axios = <Axios><unknown>{
+            get: async (url: string) => {
+                urls.push(url);
+                return {status: 200, data: 'header\n'};
+            },
+        };
+        expect(await downloadSdeDumps(dir, axios)).toEqual(['invGroups.csv', 'mapSolarSystems.csv', 'mapConstellations.csv', 'mapRegions.csv']);
+        expect(urls[0]).toBe(FUZZWORK_CSV_URL + 'invGroups.csv');
+        expect(fs.readFileSync(path.join(dir, 'mapRegions.csv'), 'utf8')).toBe('header\n');
+    });
+});
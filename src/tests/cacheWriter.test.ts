@@ -0,0 +1,43 @@
+import {CacheWriter} from '../lib/cacheWriter';
+
+describe('Cache Writer', () => {
+    afterEach(() => jest.useRealTimers());
+
+    it('should write each dirty file once per interval with the latest content', () => {
+        jest.useFakeTimers();
+        const writes: [string, string][] = [];
+        const writer = new CacheWriter(30000, (file, content) => writes.push([file, content]));
+        const names = new Map<number, string>();
+        for (const [id, name] of [[1, 'a'], [2, 'b'], [3, 'c']] as [number, string][]) {
+            names.set(id, name);
+            writer.markDirty('names.json', () => JSON.stringify(Object.fromEntries(names)));
+        }
+        expect(writes).toEqual([]);
+        expect(writer.isDirty('names.json')).toBe(true);
+
+        jest.advanceTimersByTime(30000);
+        expect(writes).toEqual([['names.json', '{"1":"a","2":"b","3":"c"}']]);
+        expect(writer.isDirty('names.json')).toBe(false);
+        jest.advanceTimersByTime(30000);
+        expect(writes.length).toBe(1);
+    });
+
+    it('should write pending changes on flush', () => {
+        const writes: string[] = [];
+        const writer = new CacheWriter(30000, file => writes.push(file));
+        writer.markDirty('ships.json', () => '{}');
+        writer.markDirty('systems.json', () => '{}');
+        writer.flush();
+        expect(writes).toEqual(['ships.json', 'systems.json']);
+    });
+
+    it('should not write discarded files', () => {
+        const writes: string[] = [];
+        const writer = new CacheWriter(30000, file => writes.push(file));
+        writer.markDirty('1.json', () => '{}');
+        writer.markDirty('2.json', () => '{}');
+        writer.discard('1.json');
+        writer.flush();
+        expect(writes).toEqual(['2.json']);
+    });
+});
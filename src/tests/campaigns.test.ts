@@ -0,0 +1,27 @@
+import {iskEfficiency, newCampaign, recordCampaignKill, topParticipants} from '../lib/campaigns';
+import {ZkData} from '../zKillSubscriber';
+
+describe('Campaigns', () => {
+    const kill = (victimAllianceId: number, attackers: { character_id?: number, alliance_id?: number }[], totalValue: number) =>
+        ({victim: {alliance_id: victimAllianceId}, attackers, zkb: {totalValue}} as unknown as ZkData);
+
+    it('should count kills, losses and participants of the own side', () => {
+        const campaign = newCampaign('1', 'deployment', [99000001], new Date('2024-01-01T00:00:00Z'));
+        expect(campaign.subscriptionId).toBe('campaign-deployment');
+        recordCampaignKill(campaign, kill(99000002, [{character_id: 10, alliance_id: 99000001}, {character_id: 20, alliance_id: 99000002}], 300));
+        recordCampaignKill(campaign, kill(99000002, [{character_id: 10, alliance_id: 99000001}, {character_id: 11, alliance_id: 99000001}], 500));
+        recordCampaignKill(campaign, kill(99000001, [{character_id: 20, alliance_id: 99000002}], 200));
+        expect(campaign.kills).toBe(2);
+        expect(campaign.losses).toBe(1);
+        expect(iskEfficiency(campaign)).toBe(80);
+        expect(topParticipants(campaign, 1)).toEqual([{characterId: 10, kills: 2}]);
+    });
+
+    it('should freeze the totals once stopped', () => {
+        const campaign = newCampaign('1', 'war', [99000001]);
+        expect(iskEfficiency(campaign)).toBeNull();
+        campaign.stoppedAt = new Date().toISOString();
+        recordCampaignKill(campaign, kill(99000002, [], 100));
+        expect(campaign.kills).toBe(0);
+    });
+});
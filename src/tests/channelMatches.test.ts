@@ -0,0 +1,36 @@
+import {groupByChannel, mergedMention} from '../lib/channelMatches';
+
+describe('ChannelMatches', () => {
+    const matches = [
+        {guildId: '1', channelId: '10', ident: 'region10000002'},
+        {guildId: '1', channelId: '11', ident: 'region10000043'},
+        {guildId: '1', channelId: '10', ident: 'alliance99000001'},
+        {guildId: '2', channelId: '10', ident: 'public'},
+    ];
+
+    it('should share one message per channel', () => {
+        expect(groupByChannel(matches, () => false).map(group => group.map(match => match.ident))).toEqual([
+            ['region10000002', 'alliance99000001'],
+            ['region10000043'],
+            ['public'],
+        ]);
+    });
+
+    it('should post every match of guilds that opted out', () => {
+        expect(groupByChannel(matches, guildId => guildId === '1').map(group => group.map(match => match.ident))).toEqual([
+            ['region10000002'],
+            ['region10000043'],
+            ['alliance99000001'],
+            ['public'],
+        ]);
+    });
+
+    it('should merge the pings of the matching subscriptions', () => {
+        const capitals = {pingRoleId: '123', pingRoleValue: 1e9};
+        const home = {pingRoleId: '456', pingRoleValue: 1e8};
+        expect(mergedMention([capitals, home, {}], 5e8)).toBe('<@&456>');
+        expect(mergedMention([capitals, home, {...capitals}], 2e9)).toBe('<@&123> <@&456>');
+        expect(mergedMention([capitals, {pingHereValue: 1e9}], 2e9)).toBe('@here');
+        expect(mergedMention([{}], 2e9)).toBeNull();
+    });
+});
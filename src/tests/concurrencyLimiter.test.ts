@@ -0,0 +1,34 @@
+import {ConcurrencyLimiter} from '../lib/concurrencyLimiter';
+
+describe('Concurrency Limiter', () => {
+    it('should not run more tasks than the limit at once', async () => {
+        const limiter = new ConcurrencyLimiter(2);
+        let running = 0;
+        let maxRunning = 0;
+        const task = async (n: number) => {
+            running++;
+            maxRunning = Math.max(maxRunning, running);
+            await new Promise(resolve => setTimeout(resolve, 10));
+            running--;
+            return n;
+        };
+        const results = await Promise.all([1, 2, 3, 4, 5].map(n => limiter.run(() => task(n))));
+        expect(results).toEqual([1, 2, 3, 4, 5]);
+        expect(maxRunning).toBe(2);
+    });
+
+    it('should start the waiting tasks by priority', async () => {
+        const limiter = new ConcurrencyLimiter(1);
+        const started: string[] = [];
+        let release = () => {};
+        const blocking = limiter.run(() => new Promise<void>(resolve => release = resolve));
+        const waiting = [['normal', 0], ['ping', 2], ['super', 1], ['second normal', 0], ['second ping', 2]].map(([name, priority]) =>
+            limiter.run(async () => {
+                started.push(<string>name);
+            }, <number>priority));
+        expect(limiter.pending).toBe(5);
+        release();
+        await Promise.all([blocking, ...waiting]);
+        expect(started).toEqual(['ping', 'second ping', 'super', 'normal', 'second normal']);
+    });
+});
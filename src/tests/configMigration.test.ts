@@ -0,0 +1,60 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {ConfigLintExitCode} from '../lib/configLint';
+import {guildIdOfFile, migrateGuildConfig, runMigration} from '../lib/configMigration';
+
+describe('ConfigMigration', () => {
+    const legacy = {
+        channels: {
+            '10': {
+                subscriptions: {
+                    public1329845725: {subType: 'public', id: 1, minValue: 0, limitTypes: {region: 10000002}},
+                    public1: {subType: 'public', id: 122, minValue: 1000000000, minNumInvolved: 5, limitTypes: {npcOnly: 'true'}},
+                    public7: {subType: 'public', id: 7, minValue: 0, limitTypes: {wormholeClass: 'c5'}},
+                },
+            },
+            '11': {},
+        },
+    };
+
+    it('should convert IDs and idents of older configs', () => {
+        const migration = migrateGuildConfig(legacy);
+        const subscriptions = migration.config.channels['10'].subscriptions;
+        expect(Object.keys(subscriptions)).toEqual(['public1329845725', 'public122']);
+        expect(subscriptions.public1329845725).toEqual({subType: 'public', id: '1', minValue: 0, limitTypes: {region: '10000002'}});
+        expect(subscriptions.public122.limitTypes).toEqual({npcOnly: 'true', minNumInvolved: '5'});
+        expect(subscriptions.public122.minNumInvolved).toBeUndefined();
+        expect(migration.config.channels['11']).toBeUndefined();
+        expect(migration.notes).toEqual([
+            'subscription public1329845725 in channel 10 has the ID of public1, it keeps its ident and cannot be unsubscribed by ID',
+            'subscription public1: minNumInvolved was moved to the limit types',
+            'subscription public1 in channel 10 was renamed to public122',
+            'subscription public7 was dropped, wormholeClass is not a known limit type',
+            'channel 11 has no subscriptions and was dropped',
+        ]);
+    });
+
+    it('should reject files that are no guild config', () => {
+        expect(migrateGuildConfig({subscriptions: {}}).config).toBeNull();
+        expect(guildIdOfFile('backups/888224317991706685-radar-backup-20240524.json')).toBe('888224317991706685');
+        expect(guildIdOfFile('888224317991706685.json')).toBe('888224317991706685');
+        expect(guildIdOfFile('names.json')).toBeNull();
+    });
+
+    it('should write migrated files without overwriting existing configs', () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'migrate-'));
+        const input = path.join(dir, 'legacy');
+        const output = path.join(dir, 'config');
+        fs.mkdirSync(input);
+        fs.mkdirSync(output);
+        fs.writeFileSync(path.join(input, '888224317991706685-backup.json'), JSON.stringify(legacy));
+        fs.writeFileSync(path.join(input, '888224317991706686.json'), JSON.stringify(legacy));
+        fs.writeFileSync(path.join(output, '888224317991706686.json'), '{"channels":{}}');
+
+        expect(runMigration(input, output)).toBe(ConfigLintExitCode.INVALID_SUBSCRIPTIONS);
+        expect(Object.keys(JSON.parse(fs.readFileSync(path.join(output, '888224317991706685.json'), 'utf8')).channels)).toEqual(['10']);
+        expect(fs.readFileSync(path.join(output, '888224317991706686.json'), 'utf8')).toBe('{"channels":{}}');
+        fs.rmSync(dir, {recursive: true});
+    });
+});
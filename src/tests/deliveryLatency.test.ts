@@ -0,0 +1,29 @@
+import {DeliveryLatency, killAgeMs, percentile, RollingLatency} from '../lib/deliveryLatency';
+
+describe('Delivery Latency', () => {
+    it('should compute nearest rank percentiles', () => {
+        const sorted = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        expect(percentile(sorted, 50)).toBe(5);
+        expect(percentile(sorted, 90)).toBe(9);
+        expect(percentile(sorted, 99)).toBe(10);
+        expect(percentile([7], 50)).toBe(7);
+    });
+    it('should only keep the last samples', () => {
+        const latency = new RollingLatency(3);
+        expect(latency.summary()).toBeNull();
+        [100, 1, 2, 3].forEach(ms => latency.record(ms));
+        expect(latency.summary()).toEqual({count: 3, p50: 2, p90: 3, p99: 3, max: 3});
+    });
+    it('should measure from the killmail time', () => {
+        const now = Date.parse('2024-03-18T09:48:12Z');
+        expect(killAgeMs('2024-03-18T09:47:12Z', now)).toBe(60000);
+        expect(killAgeMs('2024-03-18T09:49:12Z', now)).toBe(0);
+        expect(killAgeMs('not a time', now)).toBeNull();
+
+        const delivery = new DeliveryLatency();
+        delivery.recordReceived('2024-03-18T09:47:12Z', now);
+        delivery.recordPosted('2024-03-18T09:47:12Z', now + 5000);
+        expect(delivery.received.summary()?.max).toBe(60000);
+        expect(delivery.posted.summary()?.max).toBe(65000);
+    });
+});
@@ -0,0 +1,11 @@
+import {DeliveryPriority, deliveryPriority} from '../lib/deliveryPriority';
+
+describe('DeliveryPriority', () => {
+    it('should send pings first, then supercapital kills', () => {
+        expect(deliveryPriority(true, 25)).toBe(DeliveryPriority.PING);
+        expect(deliveryPriority(false, 30)).toBe(DeliveryPriority.SUPERCAPITAL);
+        expect(deliveryPriority(false, 659)).toBe(DeliveryPriority.SUPERCAPITAL);
+        expect(deliveryPriority(false, 485)).toBe(DeliveryPriority.NORMAL);
+        expect(deliveryPriority(false, null)).toBe(DeliveryPriority.NORMAL);
+    });
+});
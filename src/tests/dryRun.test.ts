@@ -0,0 +1,19 @@
+import {ZkData} from '../zKillSubscriber';
+import {DRY_RUN_OFF, DryRunMode, dryRunText, parseDryRunMode} from '../lib/dryRun';
+
+describe('DryRun', () => {
+    it('should parse the option values', () => {
+        expect(parseDryRunMode(DryRunMode.LOG)).toBe(DryRunMode.LOG);
+        expect(parseDryRunMode(DryRunMode.DM)).toBe(DryRunMode.DM);
+        expect(parseDryRunMode(DRY_RUN_OFF)).toBeUndefined();
+        expect(parseDryRunMode('on')).toBeNull();
+    });
+
+    it('should name the subscription, channel and kill', () => {
+        const data = <ZkData><unknown>{zkb: {url: 'https://zkillboard.com/kill/115787551/'}};
+        expect(dryRunText('public7', '<#949761682165620767>', data, '2.35b'))
+            .toBe('Dry run of public7 in <#949761682165620767>: would have posted https://zkillboard.com/kill/115787551/ (2.35b ISK)');
+        expect(dryRunText('public3', 'direct messages', data, '2.35b'))
+            .toBe('Dry run of public3 in direct messages: would have posted https://zkillboard.com/kill/115787551/ (2.35b ISK)');
+    });
+});
@@ -0,0 +1,37 @@
+import {applyDisplaySettings, COLOR_BLIND_COLORS, EmbedMode, killOutcome, KillOutcome, parseEmbedMode} from '../lib/embedDisplay';
+
+describe('Embed Display', () => {
+    const embed = {
+        title: 'Destroyed a Rifter',
+        url: 'https://zkillboard.com/kill/1/',
+        color: <const>'GREEN',
+        author: {name: 'Vexor attacking in Jita (The Forge)'},
+        thumbnail: {url: 'https://images.evetech.net/types/587/render'},
+        fields: [{name: 'Victim', value: 'Pilot', inline: true}],
+        footer: {text: 'Value: 10m'},
+    };
+
+    it('should tell kills from losses by the filter color', () => {
+        expect(killOutcome('GREEN')).toBe(KillOutcome.KILL);
+        expect(killOutcome('RED')).toBe(KillOutcome.LOSS);
+        expect(killOutcome('GREY')).toBeNull();
+        expect(parseEmbedMode(' Compact')).toBe(EmbedMode.COMPACT);
+        expect(parseEmbedMode('tiny')).toBeNull();
+    });
+    it('should keep the embed without display settings', () => {
+        expect(applyDisplaySettings(embed, {}, KillOutcome.KILL, 'KILL')).toEqual(embed);
+    });
+    it('should prefix the outcome and replace the colors for color blind users', () => {
+        const result = applyDisplaySettings(embed, {colorBlind: true, hideThumbnails: true}, KillOutcome.KILL, 'KILL');
+        expect(result.title).toBe('KILL: Destroyed a Rifter');
+        expect(result.color).toBe(COLOR_BLIND_COLORS[KillOutcome.KILL]);
+        expect(result.thumbnail).toBeUndefined();
+        expect(applyDisplaySettings(embed, {colorBlind: true}, KillOutcome.KILL, 'KILL', true).color).toBe('GREEN');
+    });
+    it('should collapse compact embeds into one line', () => {
+        expect(applyDisplaySettings(embed, {embedMode: EmbedMode.COMPACT}, KillOutcome.KILL, 'KILL')).toEqual({
+            description: '**[Destroyed a Rifter](https://zkillboard.com/kill/1/)** • Vexor attacking in Jita (The Forge) • Value: 10m',
+            color: 'GREEN',
+        });
+    });
+});
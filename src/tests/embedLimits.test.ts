@@ -0,0 +1,40 @@
+import {EmbedFieldData, MessageEmbedOptions} from 'discord.js';
+import {EMBED_LIMITS, embedLength, fitEmbed, truncateText} from '../lib/embedLimits';
+
+describe('EmbedLimits', () => {
+    const url = 'https://zkillboard.com/kill/115787551/';
+    const lines = (count: number, line: string) => Array.from({length: count}, (_, index) => `${line} ${index}`).join('\n');
+
+    it('should cut texts at a line break and link the kill', () => {
+        expect(truncateText('short', 10, url)).toBe('short');
+        expect(truncateText('a'.repeat(300), 256)).toBe('a'.repeat(255) + '…');
+        const text = truncateText(lines(100, '[Goonswarm Federation](https://zkillboard.com/alliance/1354830081/)'), 1024, url);
+        expect(text.length).toBeLessThanOrEqual(1024);
+        expect(text.endsWith(`)\n[…](${url})`)).toBe(true);
+        const codeBlock = truncateText('```\n' + lines(100, 'Goonswarm Federation   x') + '\n```', 1024, url);
+        expect(codeBlock.length).toBeLessThanOrEqual(1024);
+        expect(codeBlock.endsWith(`\n\`\`\`\n[…](${url})`)).toBe(true);
+    });
+
+    it('should keep the embed under the limits of Discord', () => {
+        const embed: MessageEmbedOptions = {
+            title: 'x'.repeat(300),
+            description: lines(300, 'Attacker'),
+            fields: Array.from({length: 30}, (_, index) => ({name: `Field ${index}`, value: lines(100, 'Pilot'), inline: true})),
+            footer: {text: 'Matched by public1'},
+        };
+        const fitted = fitEmbed(embed, url);
+        expect(<string>fitted.title).toHaveLength(EMBED_LIMITS.title);
+        const fields = <EmbedFieldData[]>fitted.fields;
+        expect(fields.length).toBeLessThanOrEqual(EMBED_LIMITS.fields);
+        expect(fields.every(field => field.value.length <= EMBED_LIMITS.fieldValue)).toBe(true);
+        expect(embedLength(fitted)).toBeLessThanOrEqual(EMBED_LIMITS.total);
+        expect(fitted.footer?.text).toBe('Matched by public1');
+        expect(embed.fields).toHaveLength(30);
+    });
+
+    it('should leave embeds within the limits unchanged', () => {
+        const embed: MessageEmbedOptions = {title: 'Rifter', description: 'Jita', fields: [{name: 'Attackers', value: '1'}]};
+        expect(fitEmbed(embed, url)).toEqual(embed);
+    });
+});
@@ -0,0 +1,43 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {LimitType, ZkData} from '../zKillSubscriber';
+import {filterListNames, isOnEntityList, listAgeText, mergeEntityLists, normalizeListName, normalizeListNames, parseEntityIds} from '../lib/entityLists';
+
+describe('Entity Lists', () => {
+    // Victim corporation 98597379, first attacker alliance 99012162
+    const data: ZkData = JSON.parse(fs.readFileSync(path.join(__dirname, 'resources', '115769073_ostingele.json'), 'utf-8'));
+
+    it('should parse IDs separated by commas or whitespace', () => {
+        expect(parseEntityIds('99012162, 98597379 1354830081')).toEqual([99012162, 98597379, 1354830081]);
+        expect(parseEntityIds('99012162,goons')).toBeNull();
+        expect(parseEntityIds(' , ')).toBeNull();
+    });
+
+    it('should normalize list names', () => {
+        expect(normalizeListName(' Blues ')).toBe('blues');
+        expect(normalizeListName('blue list')).toBeNull();
+        expect(normalizeListNames('Blues, coalition,blues')).toEqual(['blues', 'coalition']);
+        expect(normalizeListNames('blues,')).toBeNull();
+    });
+
+    it('should merge the named lists', () => {
+        const lists = {blues: [1, 2], coalition: [3]};
+        expect(mergeEntityLists(lists, ['blues', 'coalition', 'unknown'])).toEqual([1, 2, 3]);
+        expect(isOnEntityList(mergeEntityLists(lists, ['coalition']), data)).toBe(false);
+    });
+
+    it('should match the victim and the attackers', () => {
+        expect(isOnEntityList([98597379], data)).toBe(true);
+        expect(isOnEntityList([99012162], data)).toBe(true);
+        expect(isOnEntityList([1354830081], data)).toBe(false);
+        expect(isOnEntityList([], data)).toBe(false);
+    });
+
+    it('should flag lists unchanged for a long time', () => {
+        const now = new Date('2024-03-01T00:00:00Z');
+        expect(filterListNames(new Map([[LimitType.ENTITY_LIST_EXCLUDE, 'blues,coalition']]))).toEqual(['blues', 'coalition']);
+        expect(listAgeText('blues', '2024-02-29T12:00:00Z', now)).toBe('blues changed today');
+        expect(listAgeText('blues', '2024-01-01T00:00:00Z', now)).toBe('blues changed 60 days ago, may be outdated');
+        expect(listAgeText('blues', undefined, now)).toBe('blues changed at an unknown time');
+    });
+});
@@ -0,0 +1,46 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {LimitType, ZkData} from '../zKillSubscriber';
+import {IntelEntityType} from '../lib/zkbApi';
+import {
+    isTrackedLoss,
+    matchesTrackedEntity,
+    newTracking,
+    trackingLimitType,
+    trackingSubscriptionId,
+    withTrackedKill,
+} from '../lib/entityTracking';
+
+describe('EntityTracking', () => {
+    const data = <ZkData>JSON.parse(fs.readFileSync(path.join(__dirname, 'resources', '115787551_astrahus.json'), 'utf8'));
+    const since = new Date('2024-03-18T12:00:00Z');
+
+    it('should count the kill as lost for the victim and destroyed otherwise', () => {
+        const victim = newTracking(IntelEntityType.ALLIANCE, 386292982, 'Victim Alliance', since);
+        expect(isTrackedLoss(victim, data)).toBe(true);
+        const lost = withTrackedKill(victim, data);
+        expect(lost.iskLost).toBe(data.zkb.totalValue);
+        expect(lost.iskDestroyed).toBe(0);
+        expect(victim.iskLost).toBe(0);
+
+        const attacker = newTracking(IntelEntityType.CORPORATION, 98000001, 'Attacker Corp', since);
+        expect(isTrackedLoss(attacker, data)).toBe(false);
+        const destroyed = withTrackedKill(withTrackedKill(attacker, data), data);
+        expect(destroyed.iskDestroyed).toBe(2 * data.zkb.totalValue);
+        expect(destroyed.since).toBe('2024-03-18T12:00:00.000Z');
+    });
+
+    it('should filter on the limit type of the entity', () => {
+        expect(trackingLimitType(IntelEntityType.ALLIANCE)).toBe(LimitType.ALLIANCE);
+        expect(trackingLimitType(IntelEntityType.CORPORATION)).toBe(LimitType.CORPORATION);
+        expect(trackingLimitType(IntelEntityType.CHARACTER)).toBe(LimitType.CHARACTER);
+        expect(trackingSubscriptionId(386292982)).toBe('track-386292982');
+    });
+
+    it('should match the ID or the name ignoring case', () => {
+        const tracking = newTracking(IntelEntityType.ALLIANCE, 1354830081, 'Goonswarm Federation', since);
+        expect(matchesTrackedEntity(tracking, '1354830081')).toBe(true);
+        expect(matchesTrackedEntity(tracking, ' goonswarm federation ')).toBe(true);
+        expect(matchesTrackedEntity(tracking, 'Goonswarm')).toBe(false);
+    });
+});
@@ -0,0 +1,88 @@
+import * as net from 'net';
+import {EventPublisher, eventTopic, natsPublish, PublisherKind, redisCommand} from '../lib/eventPublisher';
+
+describe('EventPublisher', () => {
+    const event = {guild_id: '1', channel_id: '2', subscription: 'region10000002', killmail_id: 123};
+
+    it('should name the topics after the server and subscription', () => {
+        expect(eventTopic(PublisherKind.REDIS, 'zk-activity:kills', event)).toBe('zk-activity:kills:1:region10000002');
+        expect(eventTopic(PublisherKind.NATS, 'zk-activity.kills', {...event, subscription: 'a.b c'})).toBe('zk-activity.kills.1.a_b_c');
+    });
+
+    it('should encode the commands', () => {
+        expect(redisCommand('PUBLISH', 'kills', 'ä').toString()).toBe('*3\r\n$7\r\nPUBLISH\r\n$5\r\nkills\r\n$2\r\nä\r\n');
+        expect(natsPublish('kills', '{}').toString()).toBe('PUB kills 2\r\n{}\r\n');
+    });
+
+    it('should only be enabled with a Redis or NATS URL', () => {
+        expect(EventPublisher.fromEnv({})).toBeNull();
+        expect(EventPublisher.fromEnv({PUBLISH_URL: 'http://localhost'})).toBeNull();
+        expect(EventPublisher.fromEnv({PUBLISH_URL: 'nats://localhost:4222'})?.prefix).toBe('zk-activity.kills');
+        expect(EventPublisher.fromEnv({PUBLISH_URL: 'tls://localhost:4222'})?.kind).toBe(PublisherKind.NATS);
+        expect(EventPublisher.fromEnv({PUBLISH_URL: 'rediss://localhost:6380'})?.kind).toBe(PublisherKind.REDIS);
+    });
+
+    it('should authenticate and publish to Redis', async () => {
+        let received = '';
+        const server = net.createServer(socket => socket.on('data', data => {
+            received += data.toString();
+            socket.write(data.toString().includes('AUTH') ? '+OK\r\n' : ':1\r\n');
+        }));
+        await new Promise<void>(resolve => server.listen(0, '127.0.0.1', () => resolve()));
+        const port = (<net.AddressInfo>server.address()).port;
+        const publisher = new EventPublisher(`redis://:secret@127.0.0.1:${port}`, 'kills');
+        publisher.publish(event);
+        const payload = JSON.stringify(event);
+        const expected = redisCommand('AUTH', 'secret').toString() + redisCommand('PUBLISH', 'kills:1:region10000002', payload).toString();
+        for (let attempt = 0; attempt < 50 && received.length < expected.length; attempt++) {
+            await new Promise(resolve => setTimeout(resolve, 20));
+        }
+        expect(received).toBe(expected);
+        publisher.close();
+        await new Promise(resolve => server.close(resolve));
+    });
+
+    it('should not publish when Redis rejects the password', async () => {
+        let received = '';
+        const server = net.createServer(socket => socket.on('data', data => {
+            received += data.toString();
+            socket.write('-WRONGPASS invalid username-password pair\r\n');
+        }));
+        await new Promise<void>(resolve => server.listen(0, '127.0.0.1', () => resolve()));
+        const port = (<net.AddressInfo>server.address()).port;
+        const publisher = new EventPublisher(`redis://:wrong@127.0.0.1:${port}`, 'kills');
+        publisher.publish(event);
+        for (let attempt = 0; attempt < 50 && received.length === 0; attempt++) {
+            await new Promise(resolve => setTimeout(resolve, 20));
+        }
+        await new Promise(resolve => setTimeout(resolve, 100));
+        expect(received).toBe(redisCommand('AUTH', 'wrong').toString());
+        publisher.close();
+        await new Promise(resolve => server.close(resolve));
+    });
+
+    it('should publish to NATS once the connection was accepted', async () => {
+        let received = '';
+        const server = net.createServer(socket => {
+            socket.write('INFO {"server_id":"test"}\r\n');
+            socket.on('data', data => {
+                received += data.toString();
+                if (data.toString().includes('PING')) {
+                    socket.write('PONG\r\n');
+                }
+            });
+        });
+        await new Promise<void>(resolve => server.listen(0, '127.0.0.1', () => resolve()));
+        const port = (<net.AddressInfo>server.address()).port;
+        const publisher = new EventPublisher(`nats://127.0.0.1:${port}`, 'kills');
+        publisher.publish(event);
+        const publish = natsPublish('kills.1.region10000002', JSON.stringify(event)).toString();
+        for (let attempt = 0; attempt < 50 && !received.includes(publish); attempt++) {
+            await new Promise(resolve => setTimeout(resolve, 20));
+        }
+        expect(received.startsWith('CONNECT ')).toBe(true);
+        expect(received.endsWith('PING\r\n' + publish)).toBe(true);
+        publisher.close();
+        await new Promise(resolve => server.close(resolve));
+    });
+});
@@ -0,0 +1,53 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {ZkData} from '../zKillSubscriber';
+import {exchangeResult, ExchangeResult, ExchangeTracker, parseExchangeOutcome} from '../lib/exchangeOutcome';
+
+describe('ExchangeOutcome', () => {
+    // Victim corporation 98597379, first attacker alliance 99012162
+    const data: ZkData = JSON.parse(fs.readFileSync(path.join(__dirname, 'resources', '115769073_ostingele.json'), 'utf-8'));
+    const time = Date.parse(data.killmail_time);
+
+    const kill = (killmailId: number, minutes: number, totalValue: number, systemId = data.solar_system_id): ZkData => ({
+        ...data,
+        killmail_id: killmailId,
+        solar_system_id: systemId,
+        killmail_time: new Date(time + minutes * 60 * 1000).toISOString(),
+        zkb: {...data.zkb, totalValue},
+    });
+
+    it('should parse the outcome and window', () => {
+        expect(parseExchangeOutcome('Won')).toEqual({require: ExchangeResult.WON, windowMinutes: 30});
+        expect(parseExchangeOutcome('lost:60')).toEqual({require: ExchangeResult.LOST, windowMinutes: 60});
+        expect(parseExchangeOutcome('won:0')).toBeNull();
+        expect(parseExchangeOutcome('won:361')).toBeNull();
+        expect(parseExchangeOutcome('draw')).toBeNull();
+    });
+
+    it('should sum the kills and losses of the own entities in the system and window', () => {
+        const tracker = new ExchangeTracker();
+        // Losses of corporation 98597379, kills of alliance 99012162
+        expect(tracker.record('guild', [98597379], kill(1, 0, 100), time)).toBe(true);
+        tracker.record('guild', [98597379], kill(1, 0, 100), time);
+        tracker.record('guild', [98597379], kill(2, 10, 50), time);
+        tracker.record('guild', [98597379], kill(3, 10, 500, 30000142), time);
+        tracker.record('guild', [98597379], kill(4, 90, 500), time);
+        tracker.record('other', [99012162], kill(5, 0, 80), time);
+        expect(tracker.record('guild', [1], kill(6, 0, 80), time)).toBe(false);
+        expect(tracker.balance('guild', data.solar_system_id, time - 30 * 60 * 1000, time + 30 * 60 * 1000)).toEqual({destroyed: 0, lost: 150});
+        expect(tracker.balance('other', data.solar_system_id, time - 30 * 60 * 1000, time + 30 * 60 * 1000)).toEqual({destroyed: 80, lost: 0});
+    });
+
+    it('should forget kills after the retention', () => {
+        const tracker = new ExchangeTracker(60 * 1000);
+        tracker.record('guild', [98597379], kill(1, 0, 100), time);
+        tracker.record('guild', [98597379], kill(2, 2, 50), time + 2 * 60 * 1000);
+        expect(tracker.balance('guild', data.solar_system_id, time, time + 10 * 60 * 1000)).toEqual({destroyed: 0, lost: 50});
+    });
+
+    it('should tell the direction of the exchange', () => {
+        expect(exchangeResult({destroyed: 2, lost: 1})).toBe(ExchangeResult.WON);
+        expect(exchangeResult({destroyed: 1, lost: 2})).toBe(ExchangeResult.LOST);
+        expect(exchangeResult({destroyed: 0, lost: 0})).toBeNull();
+    });
+});
@@ -0,0 +1,26 @@
+import {LimitType, ZkData} from '../zKillSubscriber';
+import {applyExclusions, EntityExclusions} from '../lib/exclusions';
+
+describe('Exclusions', () => {
+    const kill = (victim: { alliance_id: number, corporation_id: number }, attackers: { alliance_id: number, corporation_id: number }[]) =>
+        <ZkData><unknown>{killmail_id: 1, victim, attackers};
+    const exclusions: EntityExclusions = {allianceIds: [99000001], corporationIds: [98000001], shipGroupIds: [541]};
+
+    it('should only remove the excluded attackers', () => {
+        const result = applyExclusions(kill({alliance_id: 99000009, corporation_id: 98000009}, [
+            {alliance_id: 99000001, corporation_id: 98000002},
+            {alliance_id: 99000002, corporation_id: 98000001},
+            {alliance_id: 99000002, corporation_id: 98000002},
+            {alliance_id: 99000003, corporation_id: 98000003},
+        ]), exclusions, undefined, [undefined, undefined, 541, 26]);
+        expect(result.rejectedBy).toBeNull();
+        expect(result.removedAttackers).toBe(3);
+        expect(result.data.attackers).toEqual([{alliance_id: 99000003, corporation_id: 98000003}]);
+    });
+    it('should reject the kill if the victim is excluded or no attacker remains', () => {
+        expect(applyExclusions(kill({alliance_id: 99000001, corporation_id: 98000009}, []), exclusions).rejectedBy)
+            .toBe(LimitType.EXCLUDED_ALLIANCE);
+        expect(applyExclusions(kill({alliance_id: 99000009, corporation_id: 98000009}, [{alliance_id: 99000002, corporation_id: 98000001}]), exclusions).rejectedBy)
+            .toBe(LimitType.EXCLUDED_CORPORATION);
+    });
+});
@@ -0,0 +1,24 @@
+import {generateFeedToken, renderAtomFeed} from '../lib/feedServer';
+
+describe('Atom Feed', () => {
+    it('should render escaped entries', () => {
+        const xml = renderAtomFeed({
+            title: 'zKill feed public 1',
+            selfLink: 'http://localhost:8080/feed/abc.atom',
+            entries: [{
+                id: 'https://zkillboard.com/kill/1/',
+                title: '1.5b ISK kill in <Jita> & co',
+                link: 'https://zkillboard.com/kill/1/',
+                updated: '2023-01-01T12:00:00.000Z',
+                summary: 'summary',
+            }],
+        });
+        expect(xml).toContain('<title>1.5b ISK kill in &lt;Jita&gt; &amp; co</title>');
+        expect(xml).toContain('<updated>2023-01-01T12:00:00.000Z</updated>');
+        expect(xml).toContain('<link rel="self" href="http://localhost:8080/feed/abc.atom"/>');
+    });
+
+    it('should generate url safe tokens', () => {
+        expect(generateFeedToken()).toMatch(/^[A-Za-z0-9_-]{24}$/);
+    });
+});
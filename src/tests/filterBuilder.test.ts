@@ -0,0 +1,65 @@
+import {LimitType} from '../zKillSubscriber';
+import {
+    addCondition,
+    addOrGroup,
+    BuilderAction,
+    builderCustomId,
+    BUILDER_CONDITIONS,
+    draftName,
+    draftSubscriptions,
+    FilterBuilderSessions,
+    FilterDraft,
+    MIN_VALUE_CONDITION,
+    parseBuilderCustomId,
+    removeLastCondition,
+} from '../lib/filterBuilder';
+
+describe('FilterBuilder', () => {
+    it('should fit the conditions into a select menu and modal', () => {
+        expect(BUILDER_CONDITIONS.length).toBeLessThanOrEqual(25);
+        BUILDER_CONDITIONS.forEach(condition => {
+            expect(condition.label.length).toBeLessThanOrEqual(45);
+            expect(condition.hint.length).toBeLessThanOrEqual(100);
+        });
+    });
+
+    it('should build Or groups of conditions', () => {
+        const sessions = new FilterBuilderSessions();
+        const draft = <FilterDraft>sessions.get(sessions.create('guild', 'channel', 'user'));
+        expect(draftName(draft)).toBe('every kill');
+        addOrGroup(draft);
+        expect(draft.groups.length).toBe(1);
+        addCondition(draft, LimitType.ALLIANCE, '99000001');
+        addCondition(draft, MIN_VALUE_CONDITION, '5');
+        addCondition(draft, MIN_VALUE_CONDITION, '1000000000');
+        addOrGroup(draft);
+        addCondition(draft, LimitType.REGION, '10000002');
+        expect(draftName(draft)).toBe('(alliance = 99000001 AND minValue = 1000000000) OR region = 10000002');
+
+        const subscriptions = draftSubscriptions(draft);
+        expect(subscriptions.length).toBe(2);
+        expect(subscriptions[0].minValue).toBe(1000000000);
+        expect(subscriptions[0].limitTypes.get(LimitType.ALLIANCE)).toBe('99000001');
+        expect(subscriptions[1].limitTypes.get(LimitType.REGION)).toBe('10000002');
+
+        removeLastCondition(draft);
+        removeLastCondition(draft);
+        expect(draft.groups.length).toBe(1);
+        expect(draftName(draft)).toBe('alliance = 99000001 AND minValue = 1000000000');
+    });
+
+    it('should round trip the custom IDs', () => {
+        expect(parseBuilderCustomId(builderCustomId(BuilderAction.VALUE, 'a1b2', LimitType.ALLIANCE)))
+            .toEqual({action: BuilderAction.VALUE, sessionId: 'a1b2', key: LimitType.ALLIANCE});
+        expect(parseBuilderCustomId(builderCustomId(BuilderAction.SAVE, 'a1b2'))).toEqual({action: BuilderAction.SAVE, sessionId: 'a1b2', key: undefined});
+        expect(parseBuilderCustomId('onboard_channel_1_')).toBeNull();
+    });
+
+    it('should expire unused drafts', () => {
+        const sessions = new FilterBuilderSessions();
+        const sessionId = sessions.create('guild', 'channel', 'user', '7', 0);
+        expect(sessions.get(sessionId, 10 * 60 * 1000)?.id).toBe('7');
+        expect(sessions.get(sessionId, 20 * 60 * 1000)).toBeDefined();
+        expect(sessions.get(sessionId, 36 * 60 * 1000)).toBeUndefined();
+    });
+});
@@ -0,0 +1,28 @@
+import {FittingSlot, formatEft, getFittingSlot} from '../lib/fitting';
+
+describe('Fitting', () => {
+    it('should map inventory flags to slots', () => {
+        expect(getFittingSlot(11)).toBe(FittingSlot.LOW);
+        expect(getFittingSlot(27)).toBe(FittingSlot.HIGH);
+        expect(getFittingSlot(92)).toBe(FittingSlot.RIG);
+        expect(getFittingSlot(89)).toBeNull();
+    });
+
+    it('should render the fit in EFT format', () => {
+        const names = new Map<number, string>([
+            [1, 'Damage Control II'], [2, '10MN Afterburner II'], [3, '125mm Railgun II'],
+            [4, 'Antimatter Charge S'], [5, 'Hobgoblin II'], [6, 'Small Hybrid Burst Aerator I'],
+        ]);
+        const eft = formatEft('Merlin', [
+            {item_type_id: 3, singleton: 0, flag: 27, quantity_destroyed: 1},
+            {item_type_id: 4, singleton: 0, flag: 27, quantity_dropped: 100},
+            {item_type_id: 1, singleton: 0, flag: 11, quantity_dropped: 1},
+            {item_type_id: 2, singleton: 0, flag: 19, quantity_destroyed: 1},
+            {item_type_id: 6, singleton: 0, flag: 92, quantity_destroyed: 1},
+            {item_type_id: 5, singleton: 0, flag: 87, quantity_destroyed: 2},
+            {item_type_id: 5, singleton: 0, flag: 87, quantity_dropped: 1},
+        ], names, new Set([4]));
+        expect(eft).toBe('[Merlin, Loss]\n\nDamage Control II\n\n10MN Afterburner II\n\n125mm Railgun II, Antimatter Charge S\n\n' +
+            'Small Hybrid Burst Aerator I\n\nHobgoblin II x3');
+    });
+});
@@ -0,0 +1,14 @@
+import {fleetComposition} from '../lib/fleetComposition';
+
+describe('FleetComposition', () => {
+    it('should count the attackers per ship class, largest first', () => {
+        expect(fleetComposition([25, 358, 485, 25, 358, 1283, 358, 485])).toBe('2 dreads, 3 HACs, 3 frigates');
+        expect(fleetComposition([419, 1201, 27])).toBe('1 battleship, 2 battlecruisers');
+    });
+
+    it('should sum up unknown groups and the smaller classes as others', () => {
+        expect(fleetComposition([30, 1657, null])).toBe('1 titan, 2 others');
+        expect(fleetComposition([30, 547, 420, 25], 2)).toBe('1 titan, 1 carrier, 2 others');
+        expect(fleetComposition([1657, null])).toBeNull();
+    });
+});
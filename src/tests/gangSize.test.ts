@@ -0,0 +1,23 @@
+import {attackingPilotCount, classifyGangSize, GangSizeClass, parseGangSizeClasses} from '../lib/gangSize';
+import {ZkData} from '../zKillSubscriber';
+
+describe('Gang Size', () => {
+    it('should classify the number of attacking pilots', () => {
+        expect(classifyGangSize(1)).toBe(GangSizeClass.SOLO);
+        expect(classifyGangSize(5)).toBe(GangSizeClass.SMALL);
+        expect(classifyGangSize(6)).toBe(GangSizeClass.MID);
+        expect(classifyGangSize(100)).toBe(GangSizeClass.FLEET);
+        expect(classifyGangSize(101)).toBe(GangSizeClass.BLOB);
+    });
+
+    it('should not count NPCs next to pilots', () => {
+        const data = {attackers: [{character_id: 1}, {ship_type_id: 2}, {ship_type_id: 3}]} as unknown as ZkData;
+        expect(attackingPilotCount(data)).toBe(1);
+        expect(attackingPilotCount({attackers: [{ship_type_id: 2}, {ship_type_id: 3}]} as unknown as ZkData)).toBe(2);
+    });
+
+    it('should parse comma separated classes', () => {
+        expect(parseGangSizeClasses('Solo, small')).toEqual([GangSizeClass.SOLO, GangSizeClass.SMALL]);
+        expect(parseGangSizeClasses('solo,huge')).toBeNull();
+    });
+});
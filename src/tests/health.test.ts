@@ -0,0 +1,42 @@
+import {buildHealthReport, HealthProbe, isKillStreamStalled, KillWatchdog} from '../lib/health';
+
+describe('Health', () => {
+    const probe = (lastKillAt: number | null, discordConnected = true, esiReachable = true): HealthProbe => ({
+        lastKillAt: () => lastKillAt,
+        discordConnected: () => discordConnected,
+        esiReachable: async () => esiReachable,
+    });
+
+    it('should count from the start until the first kill', () => {
+        expect(isKillStreamStalled(null, 0, 1000, 500)).toBe(false);
+        expect(isKillStreamStalled(null, 0, 1000, 1500)).toBe(true);
+        expect(isKillStreamStalled(1000, 0, 1000, 1500)).toBe(false);
+    });
+
+    it('should report unhealthy dependencies', async () => {
+        const healthy = await buildHealthReport(probe(60000), 0, 120000, 90000);
+        expect(healthy).toEqual({
+            ok: true,
+            lastKillAt: new Date(60000).toISOString(),
+            secondsSinceLastKill: 30,
+            discordConnected: true,
+            esiReachable: true,
+        });
+        expect((await buildHealthReport(probe(60000, false), 0, 120000, 90000)).ok).toBe(false);
+        expect((await buildHealthReport(probe(60000, true, false), 0, 120000, 90000)).ok).toBe(false);
+    });
+
+    it('should report a stall once', () => {
+        let lastKillAt: number | null = Date.now();
+        const stalls: number[] = [];
+        const watchdog = new KillWatchdog(60000, {...probe(null), lastKillAt: () => lastKillAt}, minutes => stalls.push(minutes));
+        watchdog.check(lastKillAt + 30000);
+        watchdog.check(lastKillAt + 120000);
+        watchdog.check(lastKillAt + 180000);
+        expect(stalls).toEqual([2]);
+        lastKillAt = lastKillAt + 200000;
+        watchdog.check(lastKillAt + 1000);
+        watchdog.check(lastKillAt + 120000);
+        expect(stalls).toEqual([2, 2]);
+    });
+});
@@ -0,0 +1,15 @@
+import {HostedMode} from '../lib/hostedMode';
+
+describe('Hosted Mode', () => {
+    it('should give failed posts back to the daily quota', () => {
+        const hostedMode = new HostedMode(true, 25, 2, []);
+        const now = new Date('2024-03-18T09:47:12Z');
+        expect(hostedMode.tryConsumePost('1', now)).toBe(true);
+        expect(hostedMode.tryConsumePost('1', now)).toBe(true);
+        expect(hostedMode.tryConsumePost('1', now)).toBe(false);
+        hostedMode.refundPost('1', now);
+        expect(hostedMode.tryConsumePost('1', now)).toBe(true);
+        hostedMode.refundPost('1', new Date('2024-03-17T09:47:12Z'));
+        expect(hostedMode.tryConsumePost('1', now)).toBe(false);
+    });
+});
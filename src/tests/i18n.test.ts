@@ -0,0 +1,28 @@
+import {Locale, parseLocale, relativeTimeText, translate} from '../lib/i18n';
+
+describe('i18n', () => {
+    it('should fill in placeholders', () => {
+        expect(translate(Locale.EN, 'embed.value', {total: '1.5bil'})).toBe('Value: 1.5bil');
+        expect(translate(Locale.DE, 'embed.value', {total: '1.5bil'})).toBe('Wert: 1.5bil');
+        expect(translate(Locale.EN, 'embed.corpSize', {count: 5})).toBe('(5-man corp)');
+    });
+
+    it('should translate command replies', () => {
+        expect(translate(Locale.EN, 'command.filter.set', {limit: 'faction', value: '500001', id: 3}))
+            .toBe('Set filter faction: 500001 on subscription ID: 3');
+        expect(translate(Locale.DE, 'command.noSubscription', {id: 3})).toBe('In diesem Kanal gibt es kein Abonnement mit der ID 3');
+        expect(translate(Locale.RU, 'command.configure.showFitting', {value: translate(Locale.RU, 'command.yes')})).toBe('Показывать фит: да');
+    });
+
+    it('should format relative times', () => {
+        expect(relativeTimeText(Locale.EN, 90 * 60 * 1000)).toBe('1 hour');
+        expect(relativeTimeText(Locale.EN, 3 * 24 * 60 * 60 * 1000)).toBe('3 days');
+        expect(relativeTimeText(Locale.RU, 5 * 60 * 1000)).toBe('5 мин');
+    });
+
+    it('should fall back to English', () => {
+        expect(parseLocale('fr')).toBe(Locale.EN);
+        expect(parseLocale(undefined)).toBe(Locale.EN);
+        expect(parseLocale('ru')).toBe(Locale.RU);
+    });
+});
@@ -0,0 +1,75 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {GENERIC_IMAGE_URL, imageContentType, ImageProxy, Images, ThumbnailSource} from '../lib/images';
+
+describe('Images', () => {
+    it('should fall back to the next working image', async () => {
+        const checked: string[] = [];
+        const images = new Images(async url => {
+            checked.push(url);
+            return url.endsWith('/icon');
+        });
+        const chain = images.typeImageChain(123);
+        expect(await images.firstAvailable(chain)).toBe('https://images.evetech.net/types/123/icon');
+        // Known bad images are not checked again
+        expect(await images.firstAvailable(chain)).toBe('https://images.evetech.net/types/123/icon');
+        expect(checked.length).toBe(2);
+        expect(images.isKnownBad('https://images.evetech.net/types/123/render?size=128')).toBe(true);
+    });
+
+    it('should use the generic image if nothing works', async () => {
+        const images = new Images(async () => false);
+        expect(await images.firstAvailable(images.typeImageChain(1, 2))).toBe(GENERIC_IMAGE_URL);
+    });
+
+    it('should start the chain with the chosen thumbnail source', () => {
+        const images = new Images(async () => true);
+        expect(images.typeImageChain(123, undefined, ThumbnailSource.ICON)).toEqual(['https://images.evetech.net/types/123/icon']);
+        expect(images.ownerImageChain({character_id: 9, corporation_id: 98, alliance_id: 99}, ThumbnailSource.PORTRAIT))
+            .toEqual(['https://images.evetech.net/characters/9/portrait?size=128']);
+        expect(images.ownerImageChain({character_id: 9, corporation_id: 98, alliance_id: null}, ThumbnailSource.LOGO))
+            .toEqual(['https://images.evetech.net/corporations/98/logo?size=128']);
+        expect(images.ownerImageChain({character_id: 9}, ThumbnailSource.RENDER)).toEqual([]);
+    });
+
+    it('should fall back from the alliance logo to the corporation logo and the ship icon', async () => {
+        const images = new Images(async url => !url.includes('/alliances/'));
+        const chain = images.affiliationImageChain({corporation_id: 98, alliance_id: 99}, 123);
+        expect(chain).toEqual([
+            'https://images.evetech.net/alliances/99/logo?size=64',
+            'https://images.evetech.net/corporations/98/logo?size=64',
+            'https://images.evetech.net/types/123/icon',
+        ]);
+        expect(await images.firstAvailable(chain)).toBe('https://images.evetech.net/corporations/98/logo?size=64');
+        expect(images.affiliationImageChain({}, 123)).toEqual(['https://images.evetech.net/types/123/icon']);
+    });
+
+    it('should stop checking images while the image server is down', async () => {
+        let checks = 0;
+        const images = new Images(async () => {
+            checks++;
+            return null;
+        });
+        expect(await images.firstAvailable(images.typeImageChain(123), 0)).toBe(GENERIC_IMAGE_URL);
+        expect(checks).toBe(1);
+        expect(images.isDown(60 * 1000)).toBe(true);
+        expect(await images.firstAvailable(images.typeImageChain(123), 60 * 1000)).toBe(GENERIC_IMAGE_URL);
+        expect(checks).toBe(1);
+        expect(images.isDown(6 * 60 * 1000)).toBe(false);
+    });
+
+    it('should use the proxied copies while the image server is down', async () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'images-'));
+        const proxy = new ImageProxy(dir, 'https://zk.example.com/');
+        const url = 'https://images.evetech.net/types/123/icon';
+        fs.writeFileSync(path.join(dir, ImageProxy.fileName(url)), Buffer.from([0x89, 0x50, 0x4e, 0x47]));
+        const images = new Images(async () => null, proxy);
+        expect(await images.firstAvailable(images.typeImageChain(123), 0))
+            .toBe(`https://zk.example.com/images/${ImageProxy.fileName(url)}`);
+        expect(proxy.read(ImageProxy.fileName(url))?.length).toBe(4);
+        expect(proxy.read('../secret.img')).toBeNull();
+        expect(imageContentType(Buffer.from([0xff, 0xd8, 0xff]))).toBe('image/jpeg');
+        fs.rmSync(dir, {recursive: true, force: true});
+    });
+});
@@ -0,0 +1,22 @@
+import {classesInJumpRange, JumpShipClass, jumpRangeLy, parseLyRange} from '../lib/jumpRange';
+
+describe('Jump Range', () => {
+    it('should apply jump drive calibration', () => {
+        expect(jumpRangeLy(JumpShipClass.DREADNOUGHT)).toBeCloseTo(7.0);
+        expect(jumpRangeLy(JumpShipClass.TITAN, 4)).toBeCloseTo(5.4);
+        expect(jumpRangeLy(JumpShipClass.BLACK_OPS, 0)).toBeCloseTo(4.0);
+    });
+
+    it('should parse distances and ship classes', () => {
+        expect(parseLyRange('1DQ1-A:6.5')).toEqual({systemName: '1DQ1-A', maxLy: 6.5});
+        expect(parseLyRange('Jita:titan')?.maxLy).toBeCloseTo(6.0);
+        expect(parseLyRange('Jita:titan')?.shipClass).toBe(JumpShipClass.TITAN);
+        expect(parseLyRange('Jita')).toBeNull();
+        expect(parseLyRange('Jita:far')).toBeNull();
+    });
+
+    it('should list the classes that can reach a distance', () => {
+        expect(classesInJumpRange(6.5)).toEqual([JumpShipClass.BLACK_OPS, JumpShipClass.CARRIER, JumpShipClass.DREADNOUGHT, JumpShipClass.FORCE_AUXILIARY]);
+        expect(classesInJumpRange(9)).toEqual([]);
+    });
+});
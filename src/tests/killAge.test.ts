@@ -0,0 +1,18 @@
+import {isStaleKill, maxKillAgeMinutes} from '../lib/killAge';
+
+describe('KillAge', () => {
+    const now = Date.parse('2024-01-01T12:00:00Z');
+
+    it('should prefer the maximum age of the subscription', () => {
+        expect(maxKillAgeMinutes(30, 120)).toBe(30);
+        expect(maxKillAgeMinutes(undefined, 120)).toBe(120);
+        expect(maxKillAgeMinutes(0, 120)).toBeNull();
+        expect(maxKillAgeMinutes(undefined, null)).toBeNull();
+    });
+
+    it('should tell stale kills', () => {
+        expect(isStaleKill('2024-01-01T11:00:00Z', 30, now)).toBe(true);
+        expect(isStaleKill('2024-01-01T11:45:00Z', 30, now)).toBe(false);
+        expect(isStaleKill('2023-12-01T11:00:00Z', null, now)).toBe(false);
+    });
+});
@@ -0,0 +1,81 @@
+import * as fs from 'fs';
+import * as http from 'http';
+import * as net from 'net';
+import * as os from 'os';
+import * as path from 'path';
+import {ZkData} from '../zKillSubscriber';
+import {ArchiveMode, KillArchive, readArchiveDay, signS3Put} from '../lib/killArchive';
+
+describe('KillArchive', () => {
+    const kill = (killmailId: number) => <ZkData><unknown>{killmail_id: killmailId, zkb: {totalValue: 1}};
+
+    it('should archive each kill once per day and rotate daily', () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'archive-'));
+        const archive = new KillArchive(dir, ArchiveMode.MATCHED, null);
+        const day1 = Date.parse('2024-01-01T12:00:00Z');
+        archive.record(kill(1), ArchiveMode.MATCHED, day1);
+        archive.record(kill(1), ArchiveMode.MATCHED, day1);
+        archive.record(kill(2), ArchiveMode.ALL, day1);
+        archive.flush();
+        archive.record(kill(3), ArchiveMode.MATCHED, day1);
+        archive.record(kill(4), ArchiveMode.MATCHED, Date.parse('2024-01-02T00:01:00Z'));
+        archive.flush();
+
+        expect(readArchiveDay(dir, '2024-01-01')?.map(data => data.killmail_id)).toEqual([1, 3]);
+        expect(readArchiveDay(dir, '2024-01-02')?.map(data => data.killmail_id)).toEqual([4]);
+        expect(readArchiveDay(dir, '2024-01-03')).toBeNull();
+        fs.rmSync(dir, {recursive: true});
+    });
+
+    it('should upload the finished days and retry failed uploads with the next flush', async () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'archive-'));
+        fs.writeFileSync(path.join(dir, 'kills-2024-01-01.ndjson.gz'), '');
+        const uploads: string[] = [];
+        const server = http.createServer((request, response) => {
+            uploads.push(<string>request.url);
+            request.resume();
+            response.statusCode = uploads.length === 1 ? 500 : 200;
+            response.end();
+        });
+        await new Promise<void>(resolve => server.listen(0, '127.0.0.1', () => resolve()));
+        const port = (<net.AddressInfo>server.address()).port;
+        const uploaded = path.join(dir, 'kills-2024-01-01.ndjson.gz.uploaded');
+        const waitFor = async (condition: () => boolean) => {
+            for (let attempt = 0; attempt < 50 && !condition(); attempt++) {
+                await new Promise(resolve => setTimeout(resolve, 20));
+            }
+        };
+        const archive = new KillArchive(dir, ArchiveMode.MATCHED, {
+            endpoint: `http://127.0.0.1:${port}`,
+            bucket: 'kills',
+            region: 'us-east-1',
+            accessKeyId: 'AKID',
+            secretAccessKey: 'secret',
+            prefix: '',
+        });
+        await waitFor(() => uploads.length === 1);
+        await new Promise(resolve => setTimeout(resolve, 50));
+        expect(fs.existsSync(uploaded)).toBe(false);
+
+        archive.flush();
+        await waitFor(() => fs.existsSync(uploaded));
+        expect(uploads).toEqual(['/kills/kills-2024-01-01.ndjson.gz', '/kills/kills-2024-01-01.ndjson.gz']);
+        await new Promise(resolve => server.close(resolve));
+        fs.rmSync(dir, {recursive: true});
+    });
+
+    it('should sign the upload with Signature Version 4', () => {
+        const request = signS3Put({
+            endpoint: 'https://s3.example.com/',
+            bucket: 'kills',
+            region: 'eu-central-1',
+            accessKeyId: 'AKID',
+            secretAccessKey: 'secret',
+            prefix: 'zk/',
+        }, 'zk/kills-2024-01-01.ndjson.gz', Buffer.from(''), new Date('2024-01-02T03:04:05Z'));
+        expect(request.url).toBe('https://s3.example.com/kills/zk/kills-2024-01-01.ndjson.gz');
+        expect(request.headers['x-amz-date']).toBe('20240102T030405Z');
+        expect(request.headers['Authorization']).toMatch(
+            /^AWS4-HMAC-SHA256 Credential=AKID\/20240102\/eu-central-1\/s3\/aws4_request, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature=[0-9a-f]{64}$/);
+    });
+});
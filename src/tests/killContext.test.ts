@@ -0,0 +1,29 @@
+import {KillContext} from '../lib/killContext';
+import {ClosestCelestial, SolarSystem, ZkData} from '../zKillSubscriber';
+
+describe('Kill Context', () => {
+    it('should resolve every lookup once per kill', async () => {
+        let systemLookups = 0;
+        let groupLookups = 0;
+        const context = new KillContext(<ZkData><unknown>{solar_system_id: 30000142}, {
+            system: async systemId => {
+                systemLookups++;
+                return <SolarSystem><unknown>{id: systemId};
+            },
+            groupId: async () => {
+                groupLookups++;
+                return 25;
+            },
+            categoryId: async () => 6,
+            name: async () => 'Rifter',
+            celestial: async () => <ClosestCelestial>{distance: 0, itemId: 0, typeId: 0, itemName: ''},
+        });
+        const systems = await Promise.all([context.system(), context.system()]);
+        expect(systems[0].id).toBe(30000142);
+        expect(systemLookups).toBe(1);
+        await context.groupId(587);
+        await context.groupId(587);
+        await context.groupId(588);
+        expect(groupLookups).toBe(2);
+    });
+});
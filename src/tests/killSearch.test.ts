@@ -0,0 +1,50 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {ZkData} from '../zKillSubscriber';
+import {ArchiveMode, KillArchive} from '../lib/killArchive';
+import {daysBetween, pageCount, pageOf, searchArchive} from '../lib/killSearch';
+
+describe('KillSearch', () => {
+    const kill = (killmailId: number, time: string, systemId: number, value: number, shipTypeId: number) => <ZkData><unknown>{
+        killmail_id: killmailId,
+        killmail_time: time,
+        solar_system_id: systemId,
+        victim: {character_id: 90000001, corporation_id: 98000001, ship_type_id: shipTypeId},
+        attackers: [{character_id: 90000002, corporation_id: 98000002, alliance_id: 99000002}],
+        zkb: {totalValue: value, url: `https://zkillboard.com/kill/${killmailId}/`},
+    };
+
+    it('should list the days of the range', () => {
+        expect(daysBetween(Date.parse('2024-01-30T23:00:00Z'), Date.parse('2024-02-01T01:00:00Z')))
+            .toEqual(['2024-01-30', '2024-01-31', '2024-02-01']);
+    });
+
+    it('should search the archived kills newest first', async () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'search-'));
+        const archive = new KillArchive(dir, ArchiveMode.ALL, null);
+        archive.record(kill(1, '2024-01-01T10:00:00Z', 30002813, 5e6, 587), ArchiveMode.ALL, Date.parse('2024-01-01T10:00:05Z'));
+        archive.record(kill(2, '2024-01-01T23:59:50Z', 30002813, 9e7, 24690), ArchiveMode.ALL, Date.parse('2024-01-02T00:00:05Z'));
+        archive.record(kill(3, '2024-01-02T12:00:00Z', 30000142, 2e8, 24690), ArchiveMode.ALL, Date.parse('2024-01-02T12:00:05Z'));
+        archive.flush();
+        const range = {from: Date.parse('2024-01-01T00:00:00Z'), to: Date.parse('2024-01-01T23:59:59Z')};
+        const groupOf = async (typeId: number) => typeId === 24690 ? 419 : 25;
+
+        const all = await searchArchive(dir, range, groupOf);
+        expect(all.kills.map(data => data.killmail_id)).toEqual([2, 1]);
+        expect(all.totalValue).toBe(9.5e7);
+        expect((await searchArchive(dir, {...range, shipGroupId: 419}, groupOf)).kills.length).toBe(1);
+        expect((await searchArchive(dir, {...range, minValue: 1e8}, groupOf)).kills.length).toBe(0);
+        expect((await searchArchive(dir, {...range, to: Date.parse('2024-01-03T00:00:00Z'), entityId: 99000002}, groupOf))
+            .kills.map(data => data.killmail_id)).toEqual([3, 2, 1]);
+        fs.rmSync(dir, {recursive: true});
+    });
+
+    it('should page the results', () => {
+        const items = Array.from(Array(25).keys());
+        expect(pageCount(items.length)).toBe(3);
+        expect(pageOf(items, 3)).toEqual([20, 21, 22, 23, 24]);
+        expect(pageOf(items, 9)).toEqual([20, 21, 22, 23, 24]);
+        expect(pageCount(0)).toBe(1);
+    });
+});
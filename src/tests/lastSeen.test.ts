@@ -0,0 +1,43 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {LimitType, ZkData} from '../zKillSubscriber';
+import {LastSeen, sightingsOf, watchedEntities} from '../lib/lastSeen';
+import {IntelEntityType} from '../lib/zkbApi';
+
+describe('LastSeen', () => {
+    const kill = (killmailId: number, time: string, systemId: number) => <ZkData><unknown>{
+        killmail_id: killmailId,
+        killmail_time: time,
+        solar_system_id: systemId,
+        victim: {character_id: 90000001, corporation_id: 98000001, alliance_id: 99000001, ship_type_id: 670},
+        attackers: [{character_id: 90000002, corporation_id: 98000002, alliance_id: 99000002, ship_type_id: 587}],
+        zkb: {url: `https://zkillboard.com/kill/${killmailId}/`},
+    };
+    const watched = watchedEntities(new Map([[LimitType.ALLIANCE, '99000002'], [LimitType.CHARACTER, '90000001,x']]));
+
+    it('should find the watched entities on the kill', () => {
+        expect(Array.from(watched.entries())).toEqual([[90000001, IntelEntityType.CHARACTER], [99000002, IntelEntityType.ALLIANCE]]);
+        const sightings = sightingsOf(kill(1, '2024-01-01T00:00:00Z', 30002813), watched);
+        expect(sightings.get(90000001)).toMatchObject({shipTypeId: 670, loss: true, systemId: 30002813});
+        expect(sightings.get(99000002)).toMatchObject({shipTypeId: 587, loss: false});
+    });
+
+    it('should keep the newest sighting and the one before', () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'last-seen-')) + '/';
+        const lastSeen = new LastSeen(dir);
+        expect(lastSeen.record('guild', sightingsOf(kill(1, '2024-01-01T00:00:00Z', 30002813), watched))).toBe(true);
+        expect(lastSeen.record('guild', sightingsOf(kill(2, '2024-01-02T00:00:00Z', 30000142), watched))).toBe(true);
+        expect(lastSeen.record('guild', sightingsOf(kill(2, '2024-01-02T00:00:00Z', 30000142), watched))).toBe(false);
+        expect(lastSeen.record('guild', sightingsOf(kill(0, '2023-12-31T00:00:00Z', 30000001), watched))).toBe(false);
+
+        expect(lastSeen.get('guild', 99000002)?.killmailId).toBe(2);
+        expect(lastSeen.before('guild', 99000002, 2)?.systemId).toBe(30002813);
+        expect(lastSeen.before('guild', 99000002, 3)?.systemId).toBe(30000142);
+        expect(lastSeen.get('other', 99000002)).toBeUndefined();
+
+        fs.writeFileSync(lastSeen.file('guild'), lastSeen.toJson('guild'));
+        expect(new LastSeen(dir).get('guild', 90000001)?.killmailId).toBe(2);
+        fs.rmSync(dir, {recursive: true});
+    });
+});
@@ -0,0 +1,54 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {LeaderLease, readLease} from '../lib/leaderLease';
+
+describe('LeaderLease', () => {
+    const now = Date.parse('2024-01-01T12:00:00Z');
+    let dir: string;
+    let file: string;
+
+    beforeEach(() => {
+        dir = fs.mkdtempSync(path.join(os.tmpdir(), 'lease-'));
+        file = path.join(dir, 'leader.json');
+    });
+
+    afterEach(() => fs.rmSync(dir, {recursive: true}));
+
+    it('should let one replica lead until its lease expires', () => {
+        const first = new LeaderLease(file, 'first', 30000);
+        const second = new LeaderLease(file, 'second', 30000);
+        expect(first.tryAcquire(now)).toBe(true);
+        expect(second.tryAcquire(now + 10000)).toBe(false);
+        expect(first.tryAcquire(now + 20000)).toBe(true);
+        expect(second.tryAcquire(now + 40000)).toBe(false);
+        expect(second.tryAcquire(now + 50001)).toBe(true);
+        expect(readLease(file)).toEqual({holder: 'second', expiresAt: now + 80001});
+    });
+
+    it('should hand over the released lease right away', () => {
+        const first = new LeaderLease(file, 'first', 30000);
+        const second = new LeaderLease(file, 'second', 30000);
+        expect(first.tryAcquire()).toBe(true);
+        second.release();
+        expect(second.tryAcquire()).toBe(false);
+        first.release();
+        expect(second.tryAcquire()).toBe(true);
+    });
+
+    it('should wait for the lock of another replica unless it was left behind', () => {
+        const first = new LeaderLease(file, 'first', 30000);
+        fs.writeFileSync(`${file}.lock`, '');
+        expect(() => first.tryAcquire(Date.now())).toThrow();
+        expect(readLease(file)).toBeNull();
+        expect(first.tryAcquire(Date.now() + 31000)).toBe(true);
+        expect(fs.existsSync(`${file}.lock`)).toBe(false);
+    });
+
+    it('should only be enabled with a lease file', () => {
+        expect(LeaderLease.fromEnv({})).toBeNull();
+        const lease = LeaderLease.fromEnv({HA_LEASE_FILE: file, HA_INSTANCE_ID: 'bot-a', HA_LEASE_SECONDS: '60'});
+        expect(lease?.instanceId).toBe('bot-a');
+        expect(lease?.ttlMs).toBe(60000);
+    });
+});
@@ -0,0 +1,18 @@
+import {MapProviderKind, mapProvider, parseMapProvider, providerRegionUrl, providerSystemUrl} from '../lib/mapLinks';
+import {systemMapUrl} from '../zKillSubscriber';
+
+describe('MapLinks', () => {
+    it('should link the system on the chosen site', () => {
+        expect(providerSystemUrl(MapProviderKind.DOTLAN, 30002813, 'Tama')).toBe('https://evemaps.dotlan.net/system/Tama');
+        expect(providerSystemUrl(MapProviderKind.GATECHECK, 30002813, 'Tama')).toBe('https://eve-gatecheck.space/eve/#Tama');
+        expect(providerSystemUrl(MapProviderKind.EVE_SCOUT, 30002813, 'Tama')).toBe('https://www.eve-scout.com/#/thera');
+        expect(mapProvider(MapProviderKind.GATECHECK).label).toBe('Gatecheck');
+    });
+
+    it('should fall back to dotlan', () => {
+        expect(parseMapProvider('unknown')).toBe(MapProviderKind.DOTLAN);
+        expect(providerSystemUrl(MapProviderKind.GATECHECK, 30002813)).toBe('https://evemaps.dotlan.net/system/30002813');
+        expect(providerRegionUrl(MapProviderKind.EVE_SCOUT, 10000002, 'The Forge')).toBe('https://evemaps.dotlan.net/region/The_Forge');
+        expect(systemMapUrl(32000001, 'AD001', MapProviderKind.GATECHECK)).toBe('https://zkillboard.com/system/32000001/');
+    });
+});
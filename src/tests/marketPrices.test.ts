@@ -0,0 +1,29 @@
+import {isInValueRange, MarketPriceCache, parseValueRange} from '../lib/marketPrices';
+
+describe('Market Prices', () => {
+    it('should parse value ranges', () => {
+        expect(parseValueRange('1000000000:')).toEqual({min: 1000000000, max: null});
+        expect(parseValueRange(':5e8')).toBeNull();
+        expect(parseValueRange('100:200')).toEqual({min: 100, max: 200});
+        expect(parseValueRange(':')).toBeNull();
+        expect(parseValueRange('100')).toBeNull();
+    });
+
+    it('should compare values with open ranges', () => {
+        expect(isInValueRange({min: 100, max: null}, 150)).toBe(true);
+        expect(isInValueRange({min: null, max: 100}, 150)).toBe(false);
+    });
+
+    it('should fall back to the adjusted price', async () => {
+        const cache = new MarketPriceCache(60000, async () => [
+            {type_id: 670, average_price: 10000, adjusted_price: 9000},
+            {type_id: 23913, adjusted_price: 2e10},
+        ]);
+        expect(cache.price(670)).toBeNull();
+        await cache.refresh(1000);
+        expect(cache.price(670)).toBe(10000);
+        expect(cache.price(23913)).toBe(2e10);
+        expect(cache.price(11567)).toBeNull();
+        expect(cache.lastRefresh()).toBe(1000);
+    });
+});
@@ -0,0 +1,18 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {ZkData} from '../zKillSubscriber';
+import {summaryAttacker} from '../lib/matchSummary';
+
+describe('MatchSummary', () => {
+    const data: ZkData = JSON.parse(fs.readFileSync(path.join(__dirname, 'resources', '115769073_ostingele.json'), 'utf-8'));
+
+    it('should name the first attacker of the filtered entities', () => {
+        expect(summaryAttacker(data, [99009845])?.character_id).toBe(2112947546);
+        expect(summaryAttacker(data, [98063794])?.character_id).toBe(95235405);
+    });
+
+    it('should fall back to the final blow', () => {
+        expect(summaryAttacker(data, [])?.character_id).toBe(2120686890);
+        expect(summaryAttacker(data, [1])?.final_blow).toBe(true);
+    });
+});
@@ -0,0 +1,19 @@
+import {containsNameFragment, hasEntityNameScope, NameScope, parseNameScopes} from '../lib/nameFragment';
+
+describe('Name Fragment', () => {
+    it('should parse the name scopes', () => {
+        expect(parseNameScopes('Pilot, corporation,pilot')).toEqual([NameScope.PILOT, NameScope.CORPORATION]);
+        expect(parseNameScopes('ship')).toEqual([NameScope.SHIP]);
+        expect(parseNameScopes('region')).toBeNull();
+        expect(parseNameScopes('')).toBeNull();
+    });
+    it('should only leave the ship type filter for entity scopes', () => {
+        expect(hasEntityNameScope([NameScope.SHIP])).toBe(false);
+        expect(hasEntityNameScope([NameScope.SHIP, NameScope.ALLIANCE])).toBe(true);
+    });
+    it('should match the fragment ignoring the case', () => {
+        expect(containsNameFragment('Goonswarm Federation', 'swarm')).toBe(true);
+        expect(containsNameFragment('Goonswarm Federation', 'SWARM')).toBe(true);
+        expect(containsNameFragment('Pandemic Horde', 'swarm')).toBe(false);
+    });
+});
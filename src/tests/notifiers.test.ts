@@ -0,0 +1,28 @@
+import {markdownToHtml, markdownToText, NotifierKind, notifiersFromEnv, renderEmbeds} from '../lib/notifiers';
+
+describe('Notifiers', () => {
+    it('should convert the embed markdown', () => {
+        expect(markdownToHtml('__Victim__ [Jita](https://evemaps.dotlan.net/system/Jita) `Rifter` <b>'))
+            .toBe('<b>Victim</b> <a href="https://evemaps.dotlan.net/system/Jita">Jita</a> <code>Rifter</code> &lt;b&gt;');
+        expect(markdownToText('__Victim__ [Jita](https://evemaps.dotlan.net/system/Jita)'))
+            .toBe('Victim Jita (https://evemaps.dotlan.net/system/Jita)');
+    });
+
+    it('should render the embeds as text', () => {
+        const text = renderEmbeds([{
+            author: {name: 'Rifter killed in Jita (The Forge)'},
+            title: 'Died',
+            url: 'https://zkillboard.com/kill/1/',
+            fields: [{name: '__Victim__', value: 'Pilot', inline: true}],
+            footer: {text: 'Value: 10m'},
+        }], false);
+        expect(text).toBe('Rifter killed in Jita (The Forge)\nDied (https://zkillboard.com/kill/1/)\nVictim\nPilot\nValue: 10m');
+    });
+
+    it('should only enable the configured backends', () => {
+        expect(notifiersFromEnv({}).size).toBe(0);
+        const notifiers = notifiersFromEnv({TELEGRAM_BOT_TOKEN: 'token', MATRIX_HOMESERVER_URL: 'https://matrix.org'});
+        expect(notifiers.has(NotifierKind.TELEGRAM)).toBe(true);
+        expect(notifiers.has(NotifierKind.MATRIX)).toBe(false);
+    });
+});
@@ -0,0 +1,21 @@
+import {matchesNpcSpawn, parseNpcSpawnFilter} from '../lib/npcSpawns';
+
+describe('NPC Spawns', () => {
+    it('should parse aliases and group IDs', () => {
+        const filter = parseNpcSpawnFilter('Officers, 1234');
+        expect(filter?.groupIds).toEqual([1234]);
+        expect(filter?.patterns.length).toBe(1);
+        expect(parseNpcSpawnFilter('officers,rats')).toBeNull();
+    });
+
+    it('should match the group names of the aliases', () => {
+        const filter = parseNpcSpawnFilter('officers,capitals');
+        if (!filter) {
+            throw new Error('filter not parsed');
+        }
+        expect(matchesNpcSpawn(filter, 564, 'Asteroid Guristas Officer')).toBe(true);
+        expect(matchesNpcSpawn(filter, 1, 'Asteroid Guristas Dreadnought')).toBe(true);
+        expect(matchesNpcSpawn(filter, 2, 'Asteroid Guristas Battleship')).toBe(false);
+        expect(matchesNpcSpawn({patterns: [], groupIds: [2]}, 2, 'Asteroid Guristas Battleship')).toBe(true);
+    });
+});
@@ -0,0 +1,56 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {LimitType} from '../zKillSubscriber';
+import {EvalExitCode, readEvalSubscriptions, runEval} from '../lib/offlineEval';
+
+describe('OfflineEval', () => {
+    const killFile = path.join(__dirname, 'resources', '115787551_astrahus.json');
+    let dir: string;
+
+    beforeEach(() => {
+        dir = fs.mkdtempSync(path.join(os.tmpdir(), 'eval-'));
+        fs.writeFileSync(path.join(dir, 'systems.json'), JSON.stringify({
+            30000186: {
+                id: 30000186,
+                systemName: 'Uitra',
+                regionId: 10000033,
+                regionName: 'The Citadel',
+                constellationId: 20000027,
+                constellationName: 'Ihilakken',
+                securityStatus: 0.64,
+            },
+        }));
+    });
+
+    afterEach(() => fs.rmSync(dir, {recursive: true}));
+
+    const writeJson = (name: string, content: unknown): string => {
+        fs.writeFileSync(path.join(dir, name), JSON.stringify(content));
+        return path.join(dir, name);
+    };
+
+    it('should read single subscriptions and guild configs', () => {
+        const single = readEvalSubscriptions({subType: 'public', id: 3, minValue: 0, limitTypes: {region: 10000002}});
+        expect(single.map(({ident}) => ident)).toEqual(['public3']);
+        expect(single[0].subscription.id).toBe('3');
+        expect(single[0].subscription.limitTypes.get(LimitType.REGION)).toBe('10000002');
+
+        const guild = readEvalSubscriptions({channels: {'10': {subscriptions: {public1: {subType: 'public', id: '1', minValue: 0}}}}});
+        expect(guild.map(({ident}) => ident)).toEqual(['10/public1']);
+        expect(guild[0].subscription.limitTypes.size).toBe(0);
+        expect(readEvalSubscriptions({})).toEqual([]);
+    });
+
+    it('should evaluate against the cached systems without network', async () => {
+        const matching = writeJson('matching.json', {subType: 'public', id: 1, minValue: 1000000000, limitTypes: {region: '10000033'}});
+        const otherRegion = writeJson('other.json', {subType: 'public', id: 2, minValue: 0, limitTypes: {region: '10000002'}});
+        expect(await runEval(matching, killFile, dir + '/')).toBe(EvalExitCode.MATCHED);
+        expect(await runEval(otherRegion, killFile, dir + '/')).toBe(EvalExitCode.NOT_MATCHED);
+    });
+
+    it('should reject missing input', async () => {
+        expect(await runEval(undefined, killFile, dir + '/')).toBe(EvalExitCode.INVALID_INPUT);
+        expect(await runEval(writeJson('empty.json', {}), killFile, dir + '/')).toBe(EvalExitCode.INVALID_INPUT);
+    });
+});
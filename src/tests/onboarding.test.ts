@@ -0,0 +1,15 @@
+import {onboardingCustomId, OnboardingStep, parseOnboardingCustomId} from '../lib/onboarding';
+
+describe('Onboarding', () => {
+    it('should round trip the custom IDs of the wizard steps', () => {
+        const channelStep = {step: OnboardingStep.CHANNEL, guildId: '949761682165620766'};
+        expect(parseOnboardingCustomId(onboardingCustomId(channelStep))).toEqual({...channelStep, channelId: undefined});
+
+        const entityStep = {step: OnboardingStep.ENTITY, guildId: '949761682165620766', channelId: '949761682165620767'};
+        expect(parseOnboardingCustomId(onboardingCustomId(entityStep))).toEqual(entityStep);
+    });
+    it('should ignore the custom IDs of other components', () => {
+        expect(parseOnboardingCustomId('srp_123')).toBeNull();
+        expect(parseOnboardingCustomId('onboard_other_1_')).toBeNull();
+    });
+});
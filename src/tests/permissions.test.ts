@@ -0,0 +1,22 @@
+import {Permissions} from 'discord.js';
+import {isSubscriptionManager, missingChannelPermissions} from '../lib/permissions';
+
+describe('Permissions', () => {
+    it('should allow channel managers and the bot admin role', () => {
+        expect(isSubscriptionManager(true, [])).toBe(true);
+        expect(isSubscriptionManager(false, ['1', '2'], '2')).toBe(true);
+        expect(isSubscriptionManager(false, ['1'], '2')).toBe(false);
+        expect(isSubscriptionManager(false, ['1'])).toBe(false);
+    });
+
+    it('should allow the subscriber roles', () => {
+        expect(isSubscriptionManager(false, ['1', '3'], '2', ['3', '4'])).toBe(true);
+        expect(isSubscriptionManager(false, ['1'], '2', ['3', '4'])).toBe(false);
+    });
+
+    it('should list the missing channel permissions', () => {
+        expect(missingChannelPermissions(new Permissions(['VIEW_CHANNEL', 'SEND_MESSAGES', 'EMBED_LINKS']))).toEqual([]);
+        expect(missingChannelPermissions(new Permissions(['VIEW_CHANNEL', 'SEND_MESSAGES']))).toEqual(['EMBED_LINKS']);
+        expect(missingChannelPermissions(null)).toEqual(['VIEW_CHANNEL', 'SEND_MESSAGES', 'EMBED_LINKS']);
+    });
+});
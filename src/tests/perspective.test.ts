@@ -0,0 +1,23 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {ZkData} from '../zKillSubscriber';
+import {matchesPerspective, parsePerspective, Perspective} from '../lib/perspective';
+
+describe('Perspective', () => {
+    // Victim corporation 98597379, first attacker alliance 99012162
+    const data: ZkData = JSON.parse(fs.readFileSync(path.join(__dirname, 'resources', '115769073_ostingele.json'), 'utf-8'));
+
+    it('should parse the perspectives', () => {
+        expect(parsePerspective(' Losses')).toBe(Perspective.LOSSES);
+        expect(parsePerspective('victims')).toBeNull();
+    });
+
+    it('should match the side of the own entities', () => {
+        expect(matchesPerspective(Perspective.LOSSES, [98597379], data)).toBe(true);
+        expect(matchesPerspective(Perspective.KILLS, [98597379], data)).toBe(false);
+        expect(matchesPerspective(Perspective.KILLS, [99012162], data)).toBe(true);
+        expect(matchesPerspective(Perspective.LOSSES, [99012162], data)).toBe(false);
+        expect(matchesPerspective(Perspective.BOTH, [99012162], data)).toBe(true);
+        expect(matchesPerspective(Perspective.BOTH, [1], data)).toBe(false);
+    });
+});
@@ -0,0 +1,46 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {LimitType, SolarSystem, Subscription, SubscriptionType, ZkData} from '../zKillSubscriber';
+import {KillPreFilter} from '../lib/preFilter';
+
+describe('Kill Pre-Filter', () => {
+    // Kill in 30003792, victim corporation 98597379, first attacker alliance 99012162, worth 7.2 billion
+    const data: ZkData = JSON.parse(fs.readFileSync(path.join(__dirname, 'resources', '115769073_ostingele.json'), 'utf-8'));
+    const subscription = (limitTypes: [LimitType, string][], minValue = 0): Subscription => ({
+        subType: SubscriptionType.PUBLIC,
+        minValue,
+        limitTypes: new Map(limitTypes),
+        inclusionLimitAlsoComparesAttacker: true,
+        inclusionLimitAlsoComparesAttackerWeapons: true,
+        exclusionLimitAlsoComparesAttacker: true,
+        exclusionLimitAlsoComparesAttackerWeapons: true,
+    });
+    const system = (regionId: number): SolarSystem => ({
+        id: 30003792, systemName: 'Ostingele', regionId, regionName: '', constellationId: 20000554, constellationName: '', securityStatus: 0.5,
+    });
+
+    it('should drop kills below every minimum value', () => {
+        expect(new KillPreFilter([subscription([], 1e10)]).mayMatch(data)).toBe(false);
+        expect(new KillPreFilter([subscription([], 1e10), subscription([], 1e9)]).mayMatch(data)).toBe(true);
+    });
+
+    it('should drop kills outside the subscribed locations and entities', () => {
+        const filter = new KillPreFilter([
+            subscription([[LimitType.REGION, '10000002']]),
+            subscription([[LimitType.ALLIANCE, '1354830081']]),
+        ]);
+        expect(filter.mayMatch(data, system(10000048))).toBe(false);
+        expect(filter.mayMatch(data, system(10000002))).toBe(true);
+        // Without the cached system the region cannot be compared, so the kill is kept
+        expect(filter.mayMatch(data)).toBe(true);
+        expect(new KillPreFilter([subscription([[LimitType.SYSTEM, '30003792']])]).mayMatch(data)).toBe(true);
+        expect(new KillPreFilter([subscription([[LimitType.ALLIANCE, '99012162']])]).mayMatch(data, system(10000048))).toBe(true);
+    });
+
+    it('should keep every kill for subscriptions without an indexed requirement', () => {
+        expect(new KillPreFilter([
+            subscription([[LimitType.ALLIANCE, '1354830081'], [LimitType.SHIP_INCLUSION_TYPE_ID, '670']]),
+        ]).mayMatch(data, system(10000048))).toBe(true);
+        expect(new KillPreFilter([subscription([[LimitType.SPACE_TYPE, 'nullsec']])]).mayMatch(data, system(10000048))).toBe(true);
+    });
+});
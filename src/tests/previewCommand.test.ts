@@ -0,0 +1,10 @@
+import {parseKillId} from '../commands/previewCommand';
+
+describe('Preview Command', () => {
+    it('should parse kill IDs and links', () => {
+        expect(parseKillId('123456789')).toBe(123456789);
+        expect(parseKillId('https://zkillboard.com/kill/123456789/')).toBe(123456789);
+        expect(parseKillId(' https://zkillboard.com/kill/123456789 ')).toBe(123456789);
+        expect(parseKillId('https://zkillboard.com/character/123/')).toBeNull();
+    });
+});
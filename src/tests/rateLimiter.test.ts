@@ -0,0 +1,13 @@
+import {SlidingWindowRateLimiter} from '../lib/rateLimiter';
+
+describe('Rate Limiter', () => {
+    it('should limit events per key within the window', () => {
+        const limiter = new SlidingWindowRateLimiter(2, 1000);
+        expect(limiter.tryConsume('a', 0)).toBe(true);
+        expect(limiter.tryConsume('a', 100)).toBe(true);
+        expect(limiter.tryConsume('a', 200)).toBe(false);
+        expect(limiter.tryConsume('b', 200)).toBe(true);
+        expect(limiter.tryConsume('a', 1000)).toBe(true);
+        expect(limiter.tryConsume('a', 1050)).toBe(false);
+    });
+});
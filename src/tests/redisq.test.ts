@@ -0,0 +1,15 @@
+import {redisqPackageToZkData} from '../lib/redisq';
+
+describe('RedisQ', () => {
+    it('should merge the killmail and the zkb metadata', () => {
+        const data = redisqPackageToZkData({
+            killID: 123,
+            killmail: {killmail_id: 123, solar_system_id: 30000142, attackers: []},
+            zkb: {totalValue: 1000, hash: 'abc'},
+        });
+        expect(data.killmail_id).toBe(123);
+        expect(data.solar_system_id).toBe(30000142);
+        expect(data.zkb.totalValue).toBe(1000);
+        expect(data.zkb.url).toBe('https://zkillboard.com/kill/123/');
+    });
+});
@@ -0,0 +1,23 @@
+import {isReportDue, lastScheduledTime, Report, ReportSchedule} from '../lib/reports';
+
+describe('Reports', () => {
+    const report = (schedule: ReportSchedule, lastRun: string, weekday?: number): Report => ({schedule, hour: 8, weekday, lastRun});
+
+    it('should find the last scheduled time', () => {
+        // 2024-03-20 is a wednesday
+        const now = new Date('2024-03-20T07:30:00Z');
+        expect(lastScheduledTime(report(ReportSchedule.DAILY, ''), now).toISOString()).toBe('2024-03-19T08:00:00.000Z');
+        expect(lastScheduledTime(report(ReportSchedule.WEEKLY, '', 1), now).toISOString()).toBe('2024-03-18T08:00:00.000Z');
+        expect(lastScheduledTime(report(ReportSchedule.WEEKLY, '', 3), now).toISOString()).toBe('2024-03-13T08:00:00.000Z');
+    });
+
+    it('should be due once after the scheduled time passed', () => {
+        const daily = report(ReportSchedule.DAILY, '2024-03-19T09:00:00Z');
+        expect(isReportDue(daily, new Date('2024-03-20T07:59:00Z'))).toBe(false);
+        expect(isReportDue(daily, new Date('2024-03-20T08:00:00Z'))).toBe(true);
+        // Missed reports are posted once
+        expect(isReportDue(daily, new Date('2024-03-25T12:00:00Z'))).toBe(true);
+        daily.lastRun = '2024-03-25T12:00:00Z';
+        expect(isReportDue(daily, new Date('2024-03-25T12:01:00Z'))).toBe(false);
+    });
+});
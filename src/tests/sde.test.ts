@@ -0,0 +1,47 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {loadSde, loadSdeSystems, parseCsv} from '../lib/sde';
+
+describe('SDE', () => {
+    it('should parse quoted CSV fields', () => {
+        expect(parseCsv('a,b\r\n1,"x, ""y""\nz"\n2,\n')).toEqual([['a', 'b'], ['1', 'x, "y"\nz'], ['2', '']]);
+    });
+
+    it('should load types and groups', () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'sde-'));
+        fs.writeFileSync(path.join(dir, 'invTypes.csv'),
+            'typeID,groupID,typeName,description\n587,25,Rifter,"The Rifter is a very powerful combat frigate, ..."\n');
+        fs.writeFileSync(path.join(dir, 'invGroups.csv'), 'groupID,categoryID,groupName\n25,6,Frigate\n');
+        const sde = loadSde(dir);
+        expect(sde.typeGroups.get(587)).toBe(25);
+        expect(sde.typeNames.get(587)).toBe('Rifter');
+        expect(sde.groupCategories.get(25)).toBe(6);
+        expect(sde.groupNames.get(25)).toBe('Frigate');
+    });
+
+    it('should load the meta groups if the meta types are given', () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'sde-'));
+        fs.writeFileSync(path.join(dir, 'invTypes.csv'), 'typeID,groupID,typeName\n587,25,Rifter\n17703,25,Imperial Navy Slicer\n');
+        expect(loadSde(dir).typeMetaGroups.size).toBe(0);
+        fs.writeFileSync(path.join(dir, 'invMetaTypes.csv'), 'typeID,parentTypeID,metaGroupID\n17703,,4\n');
+        expect(loadSde(dir).typeMetaGroups.get(17703)).toBe(4);
+    });
+
+    it('should load the systems with their constellation and region names', () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'sde-'));
+        fs.writeFileSync(path.join(dir, 'mapSolarSystems.csv'),
+            'regionID,constellationID,solarSystemID,solarSystemName,security\n10000002,20000020,30000142,Jita,0.945913116664839\n');
+        fs.writeFileSync(path.join(dir, 'mapConstellations.csv'), 'regionID,constellationID,constellationName\n10000002,20000020,Kimotoro\n');
+        fs.writeFileSync(path.join(dir, 'mapRegions.csv'), 'regionID,regionName\n10000002,The Forge\n');
+        expect(loadSdeSystems(dir).get(30000142)).toEqual({
+            id: 30000142,
+            systemName: 'Jita',
+            regionId: 10000002,
+            regionName: 'The Forge',
+            constellationId: 20000020,
+            constellationName: 'Kimotoro',
+            securityStatus: 0.945913116664839,
+        });
+    });
+});
@@ -0,0 +1,21 @@
+import {Attacker} from '../zKillSubscriber';
+import {parseSecStatusRange, pilotsInSecStatusRange} from '../lib/securityStatus';
+
+describe('Security Status', () => {
+    const attacker = (character_id: number | null, security_status: number) => <Attacker><unknown>{character_id, security_status};
+
+    it('should parse signed ranges', () => {
+        expect(parseSecStatusRange(':-5')).toEqual({min: null, max: -5});
+        expect(parseSecStatusRange('-2.5:0')).toEqual({min: -2.5, max: 0});
+        expect(parseSecStatusRange(':')).toBeNull();
+        expect(parseSecStatusRange('-11:')).toBeNull();
+        expect(parseSecStatusRange('outlaw')).toBeNull();
+    });
+
+    it('should only count pilots in the range', () => {
+        const attackers = [attacker(1, -9.8), attacker(2, 2.1), attacker(null, -10)];
+        expect(pilotsInSecStatusRange(attackers, {min: null, max: -5}).length).toBe(1);
+        expect(pilotsInSecStatusRange(attackers, {min: 0, max: null}).length).toBe(1);
+        expect(pilotsInSecStatusRange(attackers, {min: 5, max: null}).length).toBe(0);
+    });
+});
@@ -0,0 +1,48 @@
+import {ChannelFailures, classifySendError, SendErrorKind, SendRetryQueue} from '../lib/sendRetry';
+
+describe('Send Retry', () => {
+    it('should classify send errors', () => {
+        expect(classifySendError({httpStatus: 429})).toBe(SendErrorKind.RETRYABLE);
+        expect(classifySendError({httpStatus: 502})).toBe(SendErrorKind.RETRYABLE);
+        expect(classifySendError(new Error('ECONNRESET'))).toBe(SendErrorKind.RETRYABLE);
+        expect(classifySendError({httpStatus: 403})).toBe(SendErrorKind.CHANNEL_UNAVAILABLE);
+        expect(classifySendError({httpStatus: 404})).toBe(SendErrorKind.CHANNEL_UNAVAILABLE);
+        expect(classifySendError({httpStatus: 400})).toBe(SendErrorKind.REJECTED);
+    });
+
+    it('should back off exponentially until sent', async () => {
+        const queue = new SendRetryQueue(4, 1000);
+        const delays: number[] = [];
+        let failures = 2;
+        const result = await queue.retry(async () => {
+            if (failures-- > 0) {
+                throw {httpStatus: 503};
+            }
+        }, async ms => {
+            delays.push(ms);
+        });
+        expect(result).toBeNull();
+        expect(delays).toEqual([1000, 2000, 4000]);
+        expect(queue.totalDelayMs()).toBe(15000);
+    });
+
+    it('should give up on errors that are not retryable and after the last attempt', async () => {
+        const queue = new SendRetryQueue(2, 1);
+        const noSleep = async () => undefined;
+        expect(await queue.retry(async () => {
+            throw {httpStatus: 403};
+        }, noSleep)).toBe(SendErrorKind.CHANNEL_UNAVAILABLE);
+        expect(await queue.retry(async () => {
+            throw {httpStatus: 500};
+        }, noSleep)).toBe(SendErrorKind.RETRYABLE);
+        expect(queue.size()).toBe(0);
+    });
+
+    it('should clean up channels only after consecutive failures', () => {
+        const failures = new ChannelFailures(2);
+        expect(failures.fail('1')).toBe(false);
+        failures.succeed('1');
+        expect(failures.fail('1')).toBe(false);
+        expect(failures.fail('1')).toBe(true);
+    });
+});
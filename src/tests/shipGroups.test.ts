@@ -0,0 +1,10 @@
+import {expandShipAliases} from '../lib/shipGroups';
+
+describe('Ship Groups', () => {
+    it('should expand aliases to type IDs', () => {
+        expect(expandShipAliases('supers')).toBe('23913,11567');
+        expect(expandShipAliases('Blops, 670')).toBe('22430,670');
+        expect(expandShipAliases('capitals,supers')).toBe('23757,19720,37604,28352,23913,11567');
+        expect(expandShipAliases('battleships')).toBeNull();
+    });
+});
@@ -0,0 +1,26 @@
+import {SingleFlight} from '../lib/singleFlight';
+
+describe('Single Flight', () => {
+    it('should share a lookup while it is in flight', async () => {
+        const flight = new SingleFlight<string>();
+        let calls = 0;
+        const lookup = async () => {
+            calls++;
+            return 'Goonswarm Federation';
+        };
+        const names = await Promise.all([flight.do('1354830081', lookup), flight.do('1354830081', lookup), flight.do('99003581', lookup)]);
+        expect(names).toEqual(['Goonswarm Federation', 'Goonswarm Federation', 'Goonswarm Federation']);
+        expect(calls).toBe(2);
+        expect(flight.size()).toBe(0);
+        await flight.do('1354830081', lookup);
+        expect(calls).toBe(3);
+    });
+
+    it('should forget failed lookups', async () => {
+        const flight = new SingleFlight<string>();
+        await expect(flight.do('1', async () => {
+            throw new Error('ESI is down');
+        })).rejects.toThrow('ESI is down');
+        expect(await flight.do('1', async () => 'retried')).toBe('retried');
+    });
+});
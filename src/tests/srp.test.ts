@@ -0,0 +1,38 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {parseSrpButtonId, SrpQueue, SrpRequest, SrpStatus, srpButtonId} from '../lib/srp';
+
+describe('SRP', () => {
+    const request = (killmailId: number, requestedAt: string): SrpRequest => ({
+        killmailId,
+        url: `https://zkillboard.com/kill/${killmailId}/`,
+        characterId: 90000001,
+        pilotName: 'Pilot',
+        shipTypeId: 670,
+        shipName: 'Capsule',
+        totalValue: 10000,
+        requestedBy: '1',
+        requestedAt,
+        status: SrpStatus.PENDING,
+    });
+
+    it('should round trip the button ID', () => {
+        expect(parseSrpButtonId(srpButtonId(123456789))).toBe(123456789);
+        expect(parseSrpButtonId('other_123')).toBeNull();
+    });
+
+    it('should queue each loss once and persist reviews', () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'srp-')) + '/';
+        const queue = new SrpQueue(dir);
+        expect(queue.add('guild', request(2, '2024-01-02T00:00:00Z'))).toBe(true);
+        expect(queue.add('guild', request(1, '2024-01-01T00:00:00Z'))).toBe(true);
+        expect(queue.add('guild', request(1, '2024-01-03T00:00:00Z'))).toBe(false);
+        expect(queue.pending('guild').map(pending => pending.killmailId)).toEqual([1, 2]);
+
+        expect(queue.review('guild', 1, SrpStatus.APPROVED, '2')?.reviewedBy).toBe('2');
+        expect(queue.review('guild', 1, SrpStatus.DENIED, '2')).toBeNull();
+        expect(new SrpQueue(dir).pending('guild').map(pending => pending.killmailId)).toEqual([2]);
+        fs.rmSync(dir, {recursive: true});
+    });
+});
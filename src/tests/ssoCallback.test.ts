@@ -0,0 +1,33 @@
+import * as http from 'http';
+import {parseSsoCallback, SsoCallbackServer} from '../lib/ssoCallback';
+
+describe('SSO Callback', () => {
+    it('should only accept the redirect with the expected state', () => {
+        expect(parseSsoCallback('/callback?code=abc&state=s1', 's1')).toEqual({code: 'abc'});
+        expect(parseSsoCallback('/callback?code=abc&state=other', 's1')).toBeNull();
+        expect(parseSsoCallback('/callback?error=access_denied&state=s1', 's1')).toEqual({error: 'access_denied'});
+        expect(parseSsoCallback('/favicon.ico', 's1')).toBeNull();
+    });
+
+    it('should resolve the grant code of the redirect', async () => {
+        const server = new SsoCallbackServer(18741, 'http://localhost:18741/callback');
+        const code = server.waitForCode('s1', 5000);
+        const body = await new Promise<string>((resolve, reject) => {
+            http.get('http://localhost:18741/callback?code=abc&state=s1', response => {
+                let data = '';
+                response.on('data', chunk => data += chunk);
+                response.on('end', () => resolve(data));
+            }).on('error', reject);
+        });
+        expect(body).toBe('Logged in, you can close this tab');
+        expect(await code).toBe('abc');
+    });
+
+    it('should fail the login if the port is in use', async () => {
+        const blocker = http.createServer();
+        await new Promise<void>(resolve => blocker.listen(18742, () => resolve()));
+        const server = new SsoCallbackServer(18742, 'http://localhost:18742/callback');
+        await expect(server.waitForCode('s1', 5000)).rejects.toThrow('port 18742');
+        await new Promise(resolve => blocker.close(resolve));
+    });
+});
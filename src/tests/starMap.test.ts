@@ -0,0 +1,48 @@
+import {StarMap} from '../lib/starMap';
+import {SpaceType} from '../zKillSubscriber';
+
+describe('Star Map', () => {
+    // 1 - 2 - 3 | 4 - 5, where | is the border between region 100 and region 200
+    const starMap = new StarMap();
+    starMap.addSystem({id: 1, regionId: 100, securityStatus: 0.9});
+    starMap.addSystem({id: 2, regionId: 100, securityStatus: 0.5});
+    starMap.addSystem({id: 3, regionId: 100, securityStatus: 0.4});
+    starMap.addSystem({id: 4, regionId: 200, securityStatus: -0.2});
+    starMap.addSystem({id: 5, regionId: 200, securityStatus: -0.5});
+    [[1, 2], [2, 3], [3, 4], [4, 5]].forEach(([a, b]) => {
+        starMap.addJump(a, b);
+        starMap.addJump(b, a);
+    });
+
+    it('should find region border systems', () => {
+        expect(starMap.regionBorderSystems(100)).toEqual([3]);
+        expect(starMap.regionBorderSystems(200)).toEqual([4]);
+    });
+
+    it('should count jumps from a region border', () => {
+        expect(starMap.jumpsFromRegionBorder(1, 100, 5)).toBe(2);
+        expect(starMap.jumpsFromRegionBorder(5, 100, 5)).toBe(2);
+        expect(starMap.jumpsFromRegionBorder(1, 100, 1)).toBeNull();
+    });
+
+    it('should count jumps from a system', () => {
+        expect(starMap.jumpsFromSystem(5, 1, 5)).toBe(4);
+        expect(starMap.jumpsFromSystem(1, 1, 0)).toBe(0);
+        expect(starMap.jumpsFromSystem(5, 1, 3)).toBeNull();
+    });
+
+    it('should measure light-years between systems', () => {
+        const positions = new StarMap();
+        positions.addSystem({id: 1, regionId: 100, securityStatus: 0.9, name: 'Jita', x: 0, y: 0, z: 0});
+        positions.addSystem({id: 2, regionId: 100, securityStatus: 0.9, name: 'Perimeter', x: 9460730472580800 * 3, y: 0, z: 9460730472580800 * 4});
+        expect(positions.findSystemByName('jita')?.id).toBe(1);
+        expect(positions.lightYearsBetween(1, 2)).toBeCloseTo(5);
+        expect(starMap.lightYearsBetween(1, 2)).toBeNull();
+    });
+
+    it('should count jumps to another space type', () => {
+        expect(starMap.jumpsToSpaceType(1, SpaceType.LOWSEC, 5)).toBe(2);
+        expect(starMap.jumpsToSpaceType(3, SpaceType.HIGHSEC, 5)).toBe(1);
+        expect(starMap.jumpsToSpaceType(1, SpaceType.NULLSEC, 2)).toBeNull();
+    });
+});
@@ -0,0 +1,18 @@
+import {classifyStructure, QuantumCoreStatus, quantumCoreStatus, StructureKind, StructureSize} from '../lib/structures';
+
+describe('Structures', () => {
+    it('should classify Upwell structures', () => {
+        expect(classifyStructure(35833)).toEqual({kind: StructureKind.CITADEL, size: StructureSize.LARGE, coreTypeId: 56202});
+        expect(classifyStructure(35841)?.kind).toBe(StructureKind.FLEX);
+        expect(classifyStructure(670)).toBeNull();
+        expect(classifyStructure(undefined)).toBeNull();
+    });
+
+    it('should tell whether the quantum core dropped', () => {
+        const fortizar = <NonNullable<ReturnType<typeof classifyStructure>>>classifyStructure(35833);
+        const core = {item_type_id: 56202};
+        expect(quantumCoreStatus(fortizar, [{...core, quantity_dropped: 1}])).toBe(QuantumCoreStatus.DROPPED);
+        expect(quantumCoreStatus(fortizar, [{...core, quantity_destroyed: 1}])).toBe(QuantumCoreStatus.DESTROYED);
+        expect(quantumCoreStatus(fortizar, [{item_type_id: 56201, quantity_dropped: 1}])).toBe(QuantumCoreStatus.NONE);
+    });
+});
@@ -0,0 +1,41 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {LimitType, SolarSystem, Subscription, SubscriptionType, ZkData} from '../zKillSubscriber';
+import {SubscriptionIndex} from '../lib/subscriptionIndex';
+
+describe('Subscription Index', () => {
+    // Kill in 30003792, victim corporation 98597379, first attacker alliance 99012162
+    const data: ZkData = JSON.parse(fs.readFileSync(path.join(__dirname, 'resources', '115769073_ostingele.json'), 'utf-8'));
+    const subscription = (limitTypes: [LimitType, string][]): Subscription => ({
+        subType: SubscriptionType.PUBLIC,
+        minValue: 0,
+        limitTypes: new Map(limitTypes),
+        inclusionLimitAlsoComparesAttacker: true,
+        inclusionLimitAlsoComparesAttackerWeapons: true,
+        exclusionLimitAlsoComparesAttacker: true,
+        exclusionLimitAlsoComparesAttackerWeapons: true,
+    });
+    const system: SolarSystem = {
+        id: 30003792, systemName: 'Ostingele', regionId: 10000048, regionName: 'Placid', constellationId: 20000554, constellationName: '', securityStatus: 0.5,
+    };
+
+    const index = new SubscriptionIndex<string>();
+    index.add(subscription([[LimitType.SYSTEM, '30003792,30000142']]), 'system');
+    index.add(subscription([[LimitType.REGION, '10000048']]), 'region');
+    index.add(subscription([[LimitType.REGION, '10000002']]), 'other region');
+    index.add(subscription([[LimitType.CORPORATION, '98597379'], [LimitType.ALLIANCE, '99012162']]), 'entities');
+    index.add(subscription([[LimitType.ALLIANCE, '1354830081']]), 'other alliance');
+    index.add(subscription([[LimitType.ALLIANCE, '1354830081'], [LimitType.SHIP_INCLUSION_TYPE_ID, '670']]), 'ships');
+    index.add(subscription([[LimitType.VICTIM_ENTITY, '98597379']]), 'victim');
+    index.add(subscription([[LimitType.VICTIM_ENTITY, '1354830081']]), 'other victim');
+    index.add(subscription([]), 'everything');
+
+    it('should only return subscriptions whose IDs are on the kill, each once', () => {
+        expect(index.candidates(data, system).sort()).toEqual(['entities', 'everything', 'region', 'ships', 'system', 'victim']);
+        expect(index.size()).toBe(9);
+    });
+
+    it('should return every region subscription without the system', () => {
+        expect(index.candidates(data).sort()).toEqual(['entities', 'everything', 'other region', 'region', 'ships', 'system', 'victim']);
+    });
+});
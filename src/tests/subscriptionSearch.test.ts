@@ -0,0 +1,41 @@
+import {LimitType, Subscription, SubscriptionChannel, SubscriptionType} from '../zKillSubscriber';
+import {parseSubscriptionIds, searchSubscriptions} from '../lib/subscriptionSearch';
+
+describe('SubscriptionSearch', () => {
+    const subscription = (id: string, limitTypes: [LimitType, string][], settings: Partial<Subscription> = {}): Subscription => ({
+        subType: SubscriptionType.PUBLIC,
+        id,
+        minValue: 0,
+        limitTypes: new Map(limitTypes),
+        inclusionLimitAlsoComparesAttacker: true,
+        inclusionLimitAlsoComparesAttackerWeapons: true,
+        exclusionLimitAlsoComparesAttacker: true,
+        exclusionLimitAlsoComparesAttackerWeapons: true,
+        ...settings,
+    });
+    const channels = new Map<string, SubscriptionChannel>([
+        ['10', {subscriptions: new Map([
+            ['public1', subscription('1', [[LimitType.ALLIANCE, '99000001']], {description: 'Home defense'})],
+            ['public2', subscription('2', [[LimitType.REGION, '10000002']])],
+        ])}],
+        ['11', {subscriptions: new Map([
+            ['public12', subscription('12', [[LimitType.PERSPECTIVE, 'losses']], {campaign: 'Defense of 1DQ'})],
+        ])}],
+    ]);
+
+    it('should find subscriptions by ID, description, campaign and filter', () => {
+        expect(searchSubscriptions(channels, '99000001').map(result => [result.channelId, result.ident, result.matchedOn]))
+            .toEqual([['10', 'public1', 'alliance 99000001']]);
+        expect(searchSubscriptions(channels, 'DEFENSE').map(result => [result.ident, result.matchedOn]))
+            .toEqual([['public1', 'description'], ['public12', 'campaign']]);
+        expect(searchSubscriptions(channels, 'public1').map(result => result.ident)).toEqual(['public1', 'public12']);
+        expect(searchSubscriptions(channels, 'perspective').map(result => result.matchedOn)).toEqual(['perspective losses']);
+        expect(searchSubscriptions(channels, ' ')).toEqual([]);
+    });
+
+    it('should parse lists of IDs', () => {
+        expect(parseSubscriptionIds('1, 4 7,4')).toEqual(['1', '4', '7']);
+        expect(parseSubscriptionIds('1,a')).toBeNull();
+        expect(parseSubscriptionIds(' , ')).toBeNull();
+    });
+});
@@ -0,0 +1,28 @@
+import {SubscriptionStats, topRejections} from '../lib/subscriptionStats';
+
+describe('Subscription Stats', () => {
+    const now = new Date('2024-01-01T12:00:00Z');
+
+    it('should count evaluations, rejections and posts', () => {
+        const stats = new SubscriptionStats();
+        const key = SubscriptionStats.key('1', '2', 'public3');
+        stats.recordEvaluation(key, false, 'region', now);
+        stats.recordEvaluation(key, false, 'region', now);
+        stats.recordEvaluation(key, false, 'minValue', now);
+        stats.recordEvaluation(key, true, undefined, now);
+        stats.recordPost(key, true, now);
+        const counters = stats.get(key);
+        expect(counters).toMatchObject({evaluated: 4, matched: 1, posted: 1, pinged: 1, lastMatchAt: now.toISOString()});
+        expect(counters && topRejections(counters, 1)).toEqual([['region', 2]]);
+    });
+
+    it('should persist and drop the counters of deleted subscriptions', () => {
+        const stats = new SubscriptionStats();
+        stats.recordEvaluation('1/2/public3', true, undefined, now);
+        stats.recordEvaluation('1/2/public4', true, undefined, now);
+        const loaded = SubscriptionStats.fromJson(stats.toJson());
+        loaded.retain(new Set(['1/2/public3']));
+        expect(loaded.get('1/2/public3')?.matched).toBe(1);
+        expect(loaded.get('1/2/public4')).toBeUndefined();
+    });
+});
@@ -0,0 +1,34 @@
+import {LimitType} from '../zKillSubscriber';
+import {validateLimitTypes} from '../lib/subscriptionValidation';
+
+describe('Subscription Validation', () => {
+    const validate = (limitTypes: [LimitType, string][], starMap = true) =>
+        validateLimitTypes(new Map(limitTypes), name => starMap ? name === 'Jita' : null);
+
+    it('should accept filters that can match', () => {
+        expect(validate([
+            [LimitType.REGION, '10000002,11000001'],
+            [LimitType.SECURITY_MIN_INCLUSIVE, '0.5'],
+            [LimitType.SECURITY_MAX_INCLUSIVE, '0.5'],
+            [LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME, 'Jita:7'],
+        ])).toEqual([]);
+    });
+
+    it('should report malformed and contradicting filters', () => {
+        expect(validate([[LimitType.ALLIANCE, ' , ']])).toEqual(['alliance is an empty ID list']);
+        expect(validate([[LimitType.REGION, '30000142']])).toHaveLength(1);
+        expect(validate([[LimitType.SECURITY_MIN_EXCLUSIVE, '0.5'], [LimitType.SECURITY_MAX_INCLUSIVE, '0.5']])).toHaveLength(1);
+        expect(validate([[LimitType.SECURITY_MAX_INCLUSIVE, '5']])).toEqual(['security 5 is outside of -1.0 to 1.0']);
+        expect(validate([[LimitType.SHIP_INCLUSION_TYPE_ID, '587,588'], [LimitType.SHIP_EXCLUSION_TYPE_ID, '588']])).toHaveLength(1);
+        expect(validate([[LimitType.HULL_VALUE, '2000:1000']])).toHaveLength(1);
+        expect(validate([[LimitType.VICTIM_CORP_SIZE, '10:5']])).toEqual(['the minimum victimCorpSize 10 is above the maximum 5']);
+        expect(validate([[LimitType.ALLIANCE, '99000001'], [LimitType.EXCLUDED_ALLIANCE, '99000001,99000002']]))
+            .toEqual(['all alliance IDs 99000001 are excluded as well']);
+        expect(validate([[LimitType.CORPORATION, '98000001,98000002'], [LimitType.EXCLUDED_CORPORATION, '98000001']])).toEqual([]);
+    });
+
+    it('should report jump ranges from unknown systems', () => {
+        expect(validate([[LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME, 'Jitta:7']])).toEqual(['unknown system Jitta for the jump range']);
+        expect(validate([[LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME, 'Jita:7']], false)).toHaveLength(1);
+    });
+});
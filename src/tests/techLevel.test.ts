@@ -0,0 +1,17 @@
+import {parseTechLevels, TechLevel, techLevelOf} from '../lib/techLevel';
+
+describe('TechLevel', () => {
+    it('should map the meta groups', () => {
+        expect(techLevelOf(null)).toBe(TechLevel.T1);
+        expect(techLevelOf(2)).toBe(TechLevel.T2);
+        expect(techLevelOf(14)).toBe(TechLevel.T3);
+        expect(techLevelOf(4)).toBe(TechLevel.FACTION);
+        expect(techLevelOf(5)).toBe(TechLevel.OFFICER);
+        expect(techLevelOf(999)).toBe(TechLevel.T1);
+    });
+
+    it('should parse the filter value', () => {
+        expect(parseTechLevels('Faction, officer')).toEqual([TechLevel.FACTION, TechLevel.OFFICER]);
+        expect(parseTechLevels('t4')).toBeNull();
+    });
+});
@@ -0,0 +1,24 @@
+import {LimitType} from '../zKillSubscriber';
+import {
+    allianceLossesTemplate,
+    bigKillsTemplate,
+    capitalsInRangeTemplate,
+    entityActivityTemplate,
+    npcSpawnsTemplate,
+} from '../lib/templates';
+
+describe('Subscription Templates', () => {
+    it('should expand the presets into filters', () => {
+        const losses = allianceLossesTemplate('1354830081');
+        expect(losses.limitTypes).toEqual(new Map([[LimitType.VICTIM_ENTITY, '1354830081']]));
+
+        const capitals = capitalsInRangeTemplate('1DQ1-A', 'dread');
+        expect(capitals?.limitTypes.get(LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME)).toBe('1DQ1-A:dread');
+        expect(capitals?.limitTypes.get(LimitType.SHIP_INCLUSION_TYPE_ID)).toBe('23757,19720,37604,28352,23913,11567');
+        expect(capitalsInRangeTemplate('1DQ1-A', 'far')).toBeNull();
+
+        expect(npcSpawnsTemplate().limitTypes.get(LimitType.NPC_SPAWN)).toBe('officers,commanders');
+        expect(bigKillsTemplate()).toEqual({limitTypes: new Map(), minValue: 10e9});
+        expect(entityActivityTemplate(LimitType.CORPORATION, 98000001).limitTypes).toEqual(new Map([[LimitType.CORPORATION, '98000001']]));
+    });
+});
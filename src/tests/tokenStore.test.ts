@@ -0,0 +1,44 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {randomBytes} from 'crypto';
+import {decryptToken, encryptToken, isEncryptedToken, parseEncryptionKey, TokenStore} from '../lib/tokenStore';
+
+describe('TokenStore', () => {
+    const token = JSON.stringify({access_token: 'access', refresh_token: 'refresh'});
+
+    it('should parse base64 and hex keys', () => {
+        const key = randomBytes(32);
+        expect(parseEncryptionKey(key.toString('base64'))).toEqual(key);
+        expect(parseEncryptionKey(key.toString('hex'))).toEqual(key);
+        expect(parseEncryptionKey(undefined)).toBeNull();
+        expect(() => parseEncryptionKey('c2hvcnQ=')).toThrow();
+    });
+
+    it('should reject a wrong key', () => {
+        const encrypted = encryptToken(token, randomBytes(32));
+        expect(isEncryptedToken(encrypted)).toBe(true);
+        expect(encrypted).not.toContain('refresh');
+        expect(() => decryptToken(encrypted, randomBytes(32))).toThrow();
+    });
+
+    it('should encrypt plaintext files on load', () => {
+        const file = path.join(fs.mkdtempSync(path.join(os.tmpdir(), 'token-')), 'accessToken.json');
+        const key = randomBytes(32);
+        expect(new TokenStore(file, key).load()).toBeNull();
+        new TokenStore(file, null).save(token);
+        expect(new TokenStore(file, key).load()).toBe(token);
+        expect(isEncryptedToken(fs.readFileSync(file, 'utf8'))).toBe(true);
+        expect(new TokenStore(file, key).load()).toBe(token);
+        expect(() => new TokenStore(file, null).load()).toThrow();
+        fs.rmSync(path.dirname(file), {recursive: true});
+    });
+
+    it('should restrict the permissions of existing files', () => {
+        const file = path.join(fs.mkdtempSync(path.join(os.tmpdir(), 'token-')), 'accessToken.json');
+        fs.writeFileSync(file, '', {mode: 0o644});
+        new TokenStore(file, null).save(token);
+        expect(fs.statSync(file).mode & 0o777).toBe(0o600);
+        fs.rmSync(path.dirname(file), {recursive: true});
+    });
+});
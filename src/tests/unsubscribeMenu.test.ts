@@ -0,0 +1,60 @@
+import {LimitType, Subscription, SubscriptionType} from '../zKillSubscriber';
+import {
+    describeSubscription,
+    MAX_MENU_OPTIONS,
+    parseUnsubscribeCustomId,
+    UnsubscribeAction,
+    unsubscribeCustomId,
+    unsubscribeOptions,
+    UnsubscribeSessions,
+} from '../lib/unsubscribeMenu';
+
+describe('UnsubscribeMenu', () => {
+    const subscription = (id: string, limitTypes: [LimitType, string][], settings: Partial<Subscription> = {}): Subscription => ({
+        subType: SubscriptionType.PUBLIC,
+        id,
+        minValue: 0,
+        limitTypes: new Map(limitTypes),
+        inclusionLimitAlsoComparesAttacker: true,
+        inclusionLimitAlsoComparesAttackerWeapons: true,
+        exclusionLimitAlsoComparesAttacker: true,
+        exclusionLimitAlsoComparesAttackerWeapons: true,
+        ...settings,
+    });
+
+    it('should describe subscriptions without a description by their filters', () => {
+        expect(describeSubscription(subscription('1', [], {description: 'Home defense'}))).toBe('Home defense');
+        expect(describeSubscription(subscription('2', [[LimitType.REGION, '10000002']], {minValue: 1000000})))
+            .toBe('region 10000002, minValue 1000000');
+        expect(describeSubscription(subscription('3', [], {disabledAt: '2024-03-18T12:00:00.000Z'}))).toBe('disabled, every kill');
+    });
+
+    it('should list the subscriptions sorted by ID within the limits of a select menu', () => {
+        const subscriptions = new Map<string, Subscription>();
+        for (let id = 30; id > 0; id--) {
+            subscriptions.set(`public${id}`, subscription(String(id), [[LimitType.ALLIANCE, '99000001,'.repeat(20)]]));
+        }
+        subscriptions.set('publictrack-1354830081', subscription('track-1354830081', [[LimitType.ALLIANCE, '1354830081']]));
+        const options = unsubscribeOptions(subscriptions);
+        expect(options).toHaveLength(MAX_MENU_OPTIONS);
+        expect(options.slice(0, 3).map(option => option.value)).toEqual(['1', '2', '3']);
+        expect(options.every(option => option.description.length <= 100)).toBe(true);
+    });
+
+    it('should round trip the custom IDs', () => {
+        expect(parseUnsubscribeCustomId(unsubscribeCustomId(UnsubscribeAction.CONFIRM, 'a1b2')))
+            .toEqual({action: UnsubscribeAction.CONFIRM, sessionId: 'a1b2'});
+        expect(parseUnsubscribeCustomId('fbuild_save_a1b2_')).toBeNull();
+    });
+
+    it('should keep the selection until it expires or is removed', () => {
+        const sessions = new UnsubscribeSessions();
+        const sessionId = sessions.create('guild', 'channel', 'user', 0);
+        expect(sessions.select(sessionId, ['1', '4'], 1000)).toEqual({guildId: 'guild', channelId: 'channel', userId: 'user', ids: ['1', '4']});
+        expect(sessions.get(sessionId, 1000 + 15 * 60 * 1000)?.ids).toEqual(['1', '4']);
+        sessions.delete(sessionId);
+        expect(sessions.get(sessionId, 2000)).toBeUndefined();
+        const expired = sessions.create('guild', 'channel', 'user', 0);
+        expect(sessions.select(expired, ['1'], 15 * 60 * 1000 + 1)).toBeUndefined();
+    });
+});
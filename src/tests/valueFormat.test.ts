@@ -0,0 +1,21 @@
+import {Locale} from '../lib/i18n';
+import {abbreviateValue, formatValue, ValueFormat} from '../lib/valueFormat';
+
+describe('ValueFormat', () => {
+    it('should abbreviate the values by default', () => {
+        expect(abbreviateValue(950)).toBe('950');
+        expect(abbreviateValue(1512345678)).toBe('1.5bil');
+        expect(formatValue(25300000, {})).toBe('25.3mil');
+    });
+
+    it('should show the full values with the separators of the language', () => {
+        expect(formatValue(1512345678.4, {valueFormat: ValueFormat.FULL})).toBe('1,512,345,678');
+        expect(formatValue(1512345678.4, {valueFormat: ValueFormat.FULL}, Locale.DE)).toBe('1.512.345.678');
+    });
+
+    it('should add the PLEX equivalent once the price is known', () => {
+        expect(formatValue(1.5e9, {showPlexValue: true}, Locale.EN, 5e6)).toBe('1.5bil (≈300 PLEX)');
+        expect(formatValue(2e6, {showPlexValue: true}, Locale.EN, 5e6)).toBe('2mil (≈0.4 PLEX)');
+        expect(formatValue(1.5e9, {showPlexValue: true}, Locale.EN, null)).toBe('1.5bil');
+    });
+});
@@ -0,0 +1,20 @@
+import {createHmac} from 'crypto';
+import {redactWebhookUrl, signWebhookBody, webhookKind, WebhookKind} from '../lib/webhooks';
+
+describe('Webhooks', () => {
+    it('should recognize Discord webhooks', () => {
+        expect(webhookKind('https://discord.com/api/webhooks/123/abc-DEF_1')).toBe(WebhookKind.DISCORD);
+        expect(webhookKind('https://hooks.slack.com/services/T0/B0/x')).toBe(WebhookKind.JSON);
+        expect(webhookKind('http://example.com/hook')).toBeNull();
+        expect(webhookKind('not a url')).toBeNull();
+    });
+
+    it('should sign the body with HMAC-SHA256', () => {
+        const expected = createHmac('sha256', 'secret').update('{"killmail_id":1}').digest('hex');
+        expect(signWebhookBody('secret', '{"killmail_id":1}')).toBe('sha256=' + expected);
+    });
+
+    it('should hide the token of the URL', () => {
+        expect(redactWebhookUrl('https://discord.com/api/webhooks/123/abc')).toBe('https://discord.com/...');
+    });
+});
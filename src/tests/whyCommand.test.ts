@@ -0,0 +1,22 @@
+import {formatTrace} from '../commands/whyCommand';
+
+describe('Why Command', () => {
+    it('should render the trace up to the rejecting filter', () => {
+        const text = formatTrace(123, 'public1', {
+            matched: false,
+            rejectedBy: 'securityMaxInclusive',
+            trace: [
+                {filter: 'minValue', passed: true, detail: 'value 1.5b, minimum 100m'},
+                {filter: 'securityMaxInclusive', passed: false, detail: 'Jita security status 0.95, maximum 0.5'},
+            ],
+            matchedShip: null,
+            minNumInvolved: null,
+            color: 'GREEN',
+            matchedAttackerCount: null,
+        });
+        expect(text).toBe('Kill 123 against public1:\n' +
+            '✅ minValue: value 1.5b, minimum 100m\n' +
+            '❌ securityMaxInclusive: Jita security status 0.95, maximum 0.5\n' +
+            'Rejected by securityMaxInclusive');
+    });
+});
@@ -1,13 +1,35 @@
 import {Client, Intents} from 'discord.js';
 import {
+    CelestialKind,
+    ClosestCelestial,
+    cloneSubscription,
+    escalationMention,
+    getCelestialKind,
+    getSpaceType,
+    hasSelectingLimitType,
+    isAbyssalSystemId,
     LimitType,
+    mirrorSubscription,
+    parseHexColor,
+    PrepareEmbedFields,
+    regionMapUrl,
+    resolveFactionIds,
+    SolarSystem,
+    SpaceType,
     Subscription,
     SubscriptionFlags,
     SubscriptionType,
+    systemMapUrl,
+    usesFullEmbed,
     ZkData,
     ZKillSubscriber
 } from '../zKillSubscriber';
+import {strRelatedKills} from '../lib/battleDetector';
+import {KillContext} from '../lib/killContext';
+import {Locale} from '../lib/i18n';
+import {ThumbnailSource} from '../lib/images';
 import * as fs from 'fs';
+import * as os from 'os';
 import * as path from 'path';
 
 jest.setTimeout(30000);
@@ -18,6 +40,47 @@ const readTestData = (filePath: string): ZkData => {
     return JSON.parse(fileContent);
 };
 
+// A Rifter killed solo in UHKL-N by a pilot with -2 security status
+const soloNullsecKill = (): ZkData => <ZkData><unknown>{
+    killmail_id: 1,
+    killmail_time: '2024-03-18T09:47:12Z',
+    solar_system_id: 30004706,
+    victim: {character_id: 1, corporation_id: 98000001, ship_type_id: 587, damage_taken: 0, items: [], position: {x: 0, y: 0, z: 0}},
+    attackers: [{character_id: 2, corporation_id: 98000002, ship_type_id: 587, security_status: -2, final_blow: true}],
+    zkb: {totalValue: 1e7, npc: false, url: ''},
+};
+
+const soloNullsecContext = (data: ZkData): KillContext => new KillContext(data, {
+    system: async systemId => <SolarSystem><unknown>{id: systemId, systemName: 'UHKL-N', regionId: 10000039, securityStatus: -0.0052409493},
+    groupId: async () => 25,
+    categoryId: async () => 6,
+    name: async () => 'Rifter',
+    celestial: async () => <ClosestCelestial>{distance: 0, itemId: 0, typeId: 0, itemName: ''},
+});
+
+const filterSubscription = (limitTypes: Map<LimitType, string>): Subscription => ({
+    subType: SubscriptionType.PUBLIC,
+    minValue: 0,
+    limitTypes,
+    inclusionLimitAlsoComparesAttacker: true,
+    inclusionLimitAlsoComparesAttackerWeapons: true,
+    exclusionLimitAlsoComparesAttacker: true,
+    exclusionLimitAlsoComparesAttackerWeapons: true,
+});
+
+// Message parameters of a kill matched without a ship, involved count or attacker count
+const embedParams = (subscription: Subscription, data: ZkData, matchedAttackerCount: number | null = null): PrepareEmbedFields => ({
+    guildId: '1',
+    channelId: '2',
+    subscription,
+    embedding: null,
+    data,
+    matchedShip: null,
+    minNumInvolved: null,
+    messageColor: 'GREY',
+    matchedAttackerCount,
+});
+
 describe('ZK Subscriber', () => {
     it('should send message to discord', async () => {
         const client = new Client({intents: [Intents.FLAGS.GUILDS]});
@@ -357,4 +420,276 @@ describe('ZK Subscriber', () => {
             expect(result).toBe(!system['ls']);
         }
     });
+    it('should classify space types', () => {
+        const system = (regionId: number, securityStatus: number): SolarSystem => ({
+            id: 0,
+            systemName: '',
+            regionId,
+            regionName: '',
+            constellationId: 0,
+            constellationName: '',
+            securityStatus,
+        });
+        expect(getSpaceType(system(10000002, 0.9459131))).toBe(SpaceType.HIGHSEC);
+        expect(getSpaceType(system(10000064, 0.4608890986))).toBe(SpaceType.HIGHSEC);
+        expect(getSpaceType(system(10000064, 0.4405891678))).toBe(SpaceType.LOWSEC);
+        expect(getSpaceType(system(10000036, 0.0291474894))).toBe(SpaceType.LOWSEC);
+        expect(getSpaceType(system(10000039, -0.0052409493))).toBe(SpaceType.NULLSEC);
+        expect(getSpaceType(system(11000001, -0.99))).toBe(SpaceType.WORMHOLE);
+        expect(getSpaceType(system(10000070, -1.0))).toBe(SpaceType.POCHVEN);
+        expect(getSpaceType(system(12000001, -1.0))).toBe(SpaceType.ABYSSAL);
+    });
+    it('should link abyssal pockets to zKillboard and other systems to dotlan by name', () => {
+        expect(isAbyssalSystemId(32000001)).toBe(true);
+        expect(isAbyssalSystemId(30000142)).toBe(false);
+        expect(systemMapUrl(32000001, 'AD001')).toBe('https://zkillboard.com/system/32000001/');
+        expect(systemMapUrl(30002079, 'Arvasaras')).toBe('https://evemaps.dotlan.net/system/Arvasaras');
+        expect(systemMapUrl(30000142)).toBe('https://evemaps.dotlan.net/system/30000142');
+        expect(regionMapUrl(10000002, 'The Forge')).toBe('https://evemaps.dotlan.net/region/The_Forge');
+        expect(regionMapUrl(12000001, 'ADR01')).toBe('https://zkillboard.com/region/12000001/');
+    });
+    it('should classify celestials', () => {
+        expect(getCelestialKind(10)).toBe(CelestialKind.STARGATE);
+        expect(getCelestialKind(15)).toBe(CelestialKind.STATION);
+        expect(getCelestialKind(6)).toBe(CelestialKind.SUN);
+        expect(getCelestialKind(25)).toBeNull();
+    });
+    it('should escalate mentions by kill value', () => {
+        const settings = {pingRoleId: '123', pingRoleValue: 1e9, pingHereValue: 50e9};
+        expect(escalationMention(settings, 5e8)).toBeNull();
+        expect(escalationMention(settings, 2e9)).toBe('<@&123>');
+        expect(escalationMention(settings, 60e9)).toBe('@here');
+        expect(escalationMention({pingHereValue: 0}, 60e9)).toBeNull();
+    });
+    it('should replace the mentions of mirror channels', () => {
+        const subscription: Subscription = {
+            subType: SubscriptionType.PUBLIC,
+            id: '1',
+            minValue: 0,
+            limitTypes: new Map(),
+            inclusionLimitAlsoComparesAttacker: true,
+            inclusionLimitAlsoComparesAttackerWeapons: true,
+            exclusionLimitAlsoComparesAttacker: true,
+            exclusionLimitAlsoComparesAttackerWeapons: true,
+            pingRoleId: '123',
+            pingRoleValue: 1e9,
+        };
+        expect(escalationMention(mirrorSubscription(subscription, {channelId: '2'}), 2e9)).toBe('<@&123>');
+        expect(escalationMention(mirrorSubscription(subscription, {channelId: '2', pingRoleValue: 0}), 2e9)).toBeNull();
+        expect(escalationMention(mirrorSubscription(subscription, {channelId: '2', pingRoleId: '456'}), 2e9)).toBe('<@&456>');
+    });
+    it('should clone the filters of a subscription and replace the entity filters', () => {
+        const subscription: Subscription = {
+            subType: SubscriptionType.PUBLIC,
+            id: '1',
+            createdBy: '42',
+            minValue: 1e6,
+            limitTypes: new Map([[LimitType.ALLIANCE, '99000001'], [LimitType.REGION, '10000002']]),
+            inclusionLimitAlsoComparesAttacker: true,
+            inclusionLimitAlsoComparesAttackerWeapons: true,
+            exclusionLimitAlsoComparesAttacker: true,
+            exclusionLimitAlsoComparesAttackerWeapons: true,
+            pingRoleId: '123',
+            mirrorChannels: [{channelId: '2'}],
+            disabledReason: 'missing permissions',
+        };
+        const clone = cloneSubscription(subscription, '2', new Map([[LimitType.ALLIANCE, '99000002']]), '43');
+        expect(clone.id).toBe('2');
+        expect(clone.createdBy).toBe('43');
+        expect(clone.minValue).toBe(1e6);
+        expect(clone.pingRoleId).toBe('123');
+        expect(clone.limitTypes.get(LimitType.ALLIANCE)).toBe('99000002');
+        expect(clone.limitTypes.get(LimitType.REGION)).toBe('10000002');
+        expect(clone.mirrorChannels).toBeUndefined();
+        expect(clone.disabledReason).toBeUndefined();
+        expect(subscription.limitTypes.get(LimitType.ALLIANCE)).toBe('99000001');
+    });
+    it('should link to the related kills from the hour of the kill', () => {
+        expect(strRelatedKills(30000142, new Date('2024-03-18T09:47:12Z')))
+            .toBe('https://br.evetools.org/related/30000142/202403180900');
+    });
+    it('should resolve militia names to faction IDs', () => {
+        expect(resolveFactionIds('caldari, Gallente')).toBe('500001,500004');
+        expect(resolveFactionIds('militia,500001')).toBe('500001,500002,500003,500004');
+        expect(resolveFactionIds('500010')).toBe('500010');
+        expect(resolveFactionIds('pirates')).toBeNull();
+    });
+    it('should match a subscription with only a space type filter', async () => {
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false);
+        const data = soloNullsecKill();
+        const context = soloNullsecContext(data);
+        const nullsec = filterSubscription(new Map([[LimitType.SPACE_TYPE, SpaceType.NULLSEC]]));
+        expect(hasSelectingLimitType(nullsec)).toBe(false);
+        expect((await sub.evaluateSubscription(nullsec, data, context)).matched).toBe(true);
+        const highsec = await sub.evaluateSubscription(filterSubscription(new Map([[LimitType.SPACE_TYPE, SpaceType.HIGHSEC]])), data, context);
+        expect(highsec.matched).toBe(false);
+        expect(highsec.rejectedBy).toBe(LimitType.SPACE_TYPE);
+        const withRegion = filterSubscription(new Map([[LimitType.SPACE_TYPE, SpaceType.NULLSEC], [LimitType.REGION, '10000002']]));
+        expect(hasSelectingLimitType(withRegion)).toBe(true);
+        expect((await sub.evaluateSubscription(withRegion, data, context)).matched).toBe(false);
+    });
+    it('should match subscriptions with only narrowing filters', async () => {
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false);
+        const data = soloNullsecKill();
+        const context = soloNullsecContext(data);
+        for (const limitType of [
+            LimitType.NPC_SPAWN,
+            LimitType.GANG_SIZE,
+            LimitType.VICTIM_CATEGORY,
+            LimitType.VICTIM_TECH_LEVEL,
+            LimitType.HULL_VALUE,
+            LimitType.VICTIM_CORP_SIZE,
+            LimitType.NEAR_CELESTIAL,
+            LimitType.ENTITY_LIST_INCLUDE,
+            LimitType.PERSPECTIVE,
+            LimitType.ATTACKER_AVG_SEC_STATUS_MAX,
+            LimitType.ATTACKER_SEC_STATUS,
+            LimitType.JUMPS_FROM_REGION_BORDER,
+        ]) {
+            expect(hasSelectingLimitType(filterSubscription(new Map([[limitType, '']])))).toBe(false);
+        }
+        const matches = async (limitTypes: [LimitType, string][]) =>
+            (await sub.evaluateSubscription(filterSubscription(new Map(limitTypes)), data, context)).matched;
+        expect(await matches([[LimitType.GANG_SIZE, 'solo']])).toBe(true);
+        expect(await matches([[LimitType.GANG_SIZE, 'fleet']])).toBe(false);
+        expect(await matches([[LimitType.ATTACKER_AVG_SEC_STATUS_MAX, '-1']])).toBe(true);
+        expect(await matches([[LimitType.ATTACKER_SEC_STATUS, ':-5']])).toBe(false);
+        expect(await matches([[LimitType.ATTACKER_SEC_STATUS, ':-1'], [LimitType.GANG_SIZE, 'solo,small']])).toBe(true);
+    });
+    it('should only match the losses of the victim entities', async () => {
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false);
+        const data = soloNullsecKill();
+        const context = soloNullsecContext(data);
+        const loss = await sub.evaluateSubscription(filterSubscription(new Map([[LimitType.VICTIM_ENTITY, '98000001']])), data, context);
+        expect(loss.matched).toBe(true);
+        expect(loss.color).toBe('RED');
+        const kill = await sub.evaluateSubscription(filterSubscription(new Map([[LimitType.VICTIM_ENTITY, '98000002']])), data, context);
+        expect(kill.rejectedBy).toBe(LimitType.VICTIM_ENTITY);
+    });
+    it('should require the minimum of matching attackers when a ship matched', async () => {
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false);
+        const data = soloNullsecKill();
+        const context = soloNullsecContext(data);
+        const rifters = (limitTypes: [LimitType, string][]): Subscription => ({
+            ...filterSubscription(new Map<LimitType, string>([[LimitType.SHIP_INCLUSION_TYPE_ID, '587'], ...limitTypes])),
+            shipInclusionGroups: {typeIds: '587', groupIds: '25'},
+        });
+        expect((await sub.evaluateSubscription(rifters([[LimitType.CORPORATION, '98000003']]), data, context)).matched).toBe(true);
+        const gang = await sub.evaluateSubscription(rifters([[LimitType.CORPORATION, '98000002'], [LimitType.MIN_ENTITY_ATTACKERS, '2']]), data, context);
+        expect(gang.rejectedBy).toBe(LimitType.CORPORATION);
+        const solo = await sub.evaluateSubscription(rifters([[LimitType.CORPORATION, '98000002'], [LimitType.MIN_ENTITY_ATTACKERS, '1']]), data, context);
+        expect(solo.matched).toBe(true);
+        const loss = await sub.evaluateSubscription(rifters([[LimitType.CORPORATION, '98000001'], [LimitType.MIN_ENTITY_ATTACKERS, '2']]), data, context);
+        expect(loss.matched).toBe(true);
+    });
+    it('should keep a ship match when no faction matched', async () => {
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false);
+        const data = soloNullsecKill();
+        const context = soloNullsecContext(data);
+        const rifters: Subscription = {
+            ...filterSubscription(new Map([[LimitType.SHIP_INCLUSION_TYPE_ID, '587'], [LimitType.FACTION, '500001']])),
+            shipInclusionGroups: {typeIds: '587', groupIds: '25'},
+        };
+        expect((await sub.evaluateSubscription(rifters, data, context)).matched).toBe(true);
+        const factionOnly = await sub.evaluateSubscription(filterSubscription(new Map([[LimitType.FACTION, '500001']])), data, context);
+        expect(factionOnly.rejectedBy).toBe(LimitType.FACTION);
+    });
+    it('should keep the preview for entity subscriptions without a minimum of attackers', async () => {
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false);
+        const data = soloNullsecKill();
+        data.attackers[0].alliance_id = 99000002;
+        const context = soloNullsecContext(data);
+        const subscription = filterSubscription(new Map([[LimitType.ALLIANCE, '99000002']]));
+        const evaluation = await sub.evaluateSubscription(subscription, data, context);
+        expect(evaluation.matched).toBe(true);
+        expect(evaluation.matchedAttackerCount).toBeNull();
+        expect(usesFullEmbed(embedParams(subscription, data, evaluation.matchedAttackerCount))).toBe(false);
+        subscription.limitTypes.set(LimitType.MIN_ENTITY_ATTACKERS, '1');
+        expect((await sub.evaluateSubscription(subscription, data, context)).matchedAttackerCount).toBe(1);
+    });
+    it('should post the value breakdown with the full embed', () => {
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false);
+        const data = soloNullsecKill();
+        data.zkb.fittedValue = 3.9e9;
+        data.zkb.totalValue = 5.1e9;
+        const subscription = filterSubscription(new Map([[LimitType.REGION, '10000039']]));
+        expect(usesFullEmbed(embedParams(subscription, data))).toBe(false);
+        subscription.showValueBreakdown = true;
+        expect(usesFullEmbed(embedParams(subscription, data))).toBe(true);
+        expect(sub.valueFooterText(subscription, data.zkb)).toBe('Fit 3.9bil / Total 5.1bil');
+    });
+    it('should show the tickers below the zKillboard preview', async () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'tickers-'));
+        fs.writeFileSync(path.join(dir, 'tickers.json'), JSON.stringify({98000001: 'VICT', 98000002: 'ATTK'}));
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false).withTickers(dir + '/');
+        const data = soloNullsecKill();
+        const subscription = filterSubscription(new Map([[LimitType.REGION, '10000039']]));
+        expect(usesFullEmbed(embedParams(subscription, data))).toBe(false);
+        expect(await sub.previewDetails(embedParams(subscription, data))).toEqual(['Victim: [VICT] • Final blow: [ATTK]']);
+        fs.rmSync(dir, {recursive: true});
+    });
+    it('should show the structure details below the zKillboard preview', async () => {
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false);
+        const data = soloNullsecKill();
+        data.victim.ship_type_id = 35832;
+        const subscription = filterSubscription(new Map([[LimitType.REGION, '10000039']]));
+        expect(usesFullEmbed(embedParams(subscription, data))).toBe(false);
+        expect(await sub.previewDetails(embedParams(subscription, data))).toContain(<string>sub.structureDetails(data, Locale.EN, '1'));
+    });
+    it('should post the fitting with the full embed', () => {
+        const subscription = filterSubscription(new Map([[LimitType.REGION, '10000039']]));
+        subscription.showFitting = true;
+        expect(usesFullEmbed(embedParams(subscription, soloNullsecKill()))).toBe(true);
+    });
+    it('should post kills filtered by the victim corporation size with the full embed', () => {
+        const subscription = filterSubscription(new Map([[LimitType.REGION, '10000039'], [LimitType.VICTIM_CORP_SIZE, ':10']]));
+        expect(usesFullEmbed(embedParams(subscription, soloNullsecKill()))).toBe(true);
+    });
+    it('should post kills filtered by the gang size with the full embed', () => {
+        const subscription = filterSubscription(new Map([[LimitType.REGION, '10000039'], [LimitType.GANG_SIZE, 'solo']]));
+        expect(usesFullEmbed(embedParams(subscription, soloNullsecKill()))).toBe(true);
+    });
+    it('should show the last sighting below the zKillboard preview', async () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'last-seen-'));
+        fs.writeFileSync(path.join(dir, 'names.json'), JSON.stringify({98000002: 'Hunters'}));
+        fs.writeFileSync(path.join(dir, 'systems.json'), JSON.stringify({
+            30000142: {id: 30000142, systemName: 'Jita', regionId: 10000002, regionName: 'The Forge', securityStatus: 0.9459},
+        }));
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false)
+            .withNames(dir + '/')
+            .withSystems(dir + '/');
+        // Read from the working directory like the sightings of the running bot
+        const guildId = 'last-seen-preview-test';
+        const lastSeenFile = `./config/last_seen/${guildId}.json`;
+        fs.mkdirSync(path.dirname(lastSeenFile), {recursive: true});
+        fs.writeFileSync(lastSeenFile, JSON.stringify({
+            98000002: {type: 'corporation', systemId: 30000142, time: '2024-03-18T07:47:12Z', killmailId: 0, url: 'https://zkillboard.com/kill/0/', loss: false},
+        }));
+        const subscription = filterSubscription(new Map([[LimitType.CORPORATION, '98000002']]));
+        const details = await sub.previewDetails({...embedParams(subscription, soloNullsecKill()), guildId});
+        expect(details).toContain('Hunters last seen 2 hours ago in [Jita](https://zkillboard.com/kill/0/)');
+        fs.unlinkSync(lastSeenFile);
+        fs.rmSync(dir, {recursive: true});
+    });
+    it('should post kills with a chosen thumbnail with the full embed', () => {
+        const subscription = filterSubscription(new Map([[LimitType.REGION, '10000039']]));
+        subscription.thumbnailSource = ThumbnailSource.PORTRAIT;
+        expect(usesFullEmbed(embedParams(subscription, soloNullsecKill()))).toBe(true);
+    });
+    it('should show the fleet composition below the zKillboard preview', async () => {
+        const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'ships-'));
+        fs.writeFileSync(path.join(dir, 'ships.json'), JSON.stringify({587: 25, 12003: 358}));
+        const sub = ZKillSubscriber.getInstance(new Client({intents: [Intents.FLAGS.GUILDS]}), false).withShips(dir + '/');
+        const data = soloNullsecKill();
+        data.attackers.push({...data.attackers[0], character_id: 3, ship_type_id: 12003, final_blow: false});
+        const subscription = filterSubscription(new Map([[LimitType.REGION, '10000039']]));
+        expect(usesFullEmbed(embedParams(subscription, data))).toBe(false);
+        expect(await sub.previewDetails(embedParams(subscription, data))).toContain('**Fleet:** 1 HAC, 1 frigate');
+        fs.rmSync(dir, {recursive: true});
+    });
+    it('should parse hex colors', () => {
+        expect(parseHexColor('#ff8800')).toBe('#FF8800');
+        expect(parseHexColor('f80')).toBe('#FF8800');
+        expect(parseHexColor('orange')).toBeNull();
+    });
 });
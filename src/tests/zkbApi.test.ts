@@ -0,0 +1,34 @@
+import {parseEntityIntel, primeTimeHour} from '../lib/zkbApi';
+
+describe('zKillboard API', () => {
+    it('should find the hour with the most kills over all weekdays', () => {
+        expect(primeTimeHour({'0': {'18': 3, '19': 1}, '3': {'19': 4}, max: 4})).toBe(19);
+        expect(primeTimeHour({})).toBeNull();
+        expect(primeTimeHour(undefined)).toBeNull();
+    });
+
+    it('should parse the entity statistics', () => {
+        const intel = parseEntityIntel({
+            shipsDestroyed: 120,
+            shipsLost: 30,
+            iskDestroyed: 5e10,
+            iskLost: 1e9,
+            soloKills: 12,
+            dangerRatio: 80,
+            topLists: [
+                {type: 'character', values: []},
+                {type: 'shipType', values: [
+                    {shipTypeID: 17738, shipName: 'Machariel', kills: 50},
+                    {shipTypeID: 11987, shipName: 'Guardian', kills: 20},
+                ]},
+            ],
+            activity: {'1': {'20': 5}},
+        }, 1);
+        expect(intel.kills).toBe(120);
+        expect(intel.losses).toBe(30);
+        expect(intel.dangerRatio).toBe(80);
+        expect(intel.gangRatio).toBeNull();
+        expect(intel.favoriteShips).toEqual([{shipTypeId: 17738, shipName: 'Machariel', kills: 50}]);
+        expect(intel.primeTimeHour).toBe(20);
+    });
+});
@@ -1,11 +1,15 @@
 import {
     Client,
     ColorResolvable,
+    Constants,
     DiscordAPIError,
+    MessageActionRow,
+    MessageButton,
     MessageEmbed,
     MessageEmbedOptions,
     MessageOptions,
-    TextChannel
+    TextChannel,
+    ThreadChannel
 } from 'discord.js';
 import {MessageEvent, WebSocket} from 'ws';
 import {REST} from '@discordjs/rest';
@@ -16,6 +20,71 @@ import {APIEmbed} from 'discord-api-types/v10';
 import * as fs from 'fs';
 import * as util from 'util';
 import {EsiClient} from './lib/esiClient';
+import {EntityIntel, IntelEntityType, ZkbApiClient} from './lib/zkbApi';
+import {HostedMode} from './lib/hostedMode';
+import {KillStats} from './lib/killStats';
+import {StarMap} from './lib/starMap';
+import {Battle, BattleDetector, strBattleReport, strRelatedKills} from './lib/battleDetector';
+import {AtomEntry, AtomFeed, FeedServer} from './lib/feedServer';
+import {ImageProxy, Images, ThumbnailOwner, ThumbnailSource} from './lib/images';
+import {KillContext} from './lib/killContext';
+import {Locale, parseLocale, relativeTimeText, translate} from './lib/i18n';
+import {KillSource, KillSourceType, RedisQListener} from './lib/redisq';
+import {DeliveryLatency, LatencySummary} from './lib/deliveryLatency';
+import {applyExclusions, EntityExclusions, ExclusionResult} from './lib/exclusions';
+import {applyDisplaySettings, DisplaySettings, killOutcome} from './lib/embedDisplay';
+import {containsNameFragment, hasEntityNameScope, NameScope, parseNameScopes} from './lib/nameFragment';
+import {LastSeen, Sighting, sightingsOf, watchedEntities} from './lib/lastSeen';
+import {mapProvider, MapProviderKind, providerRegionUrl, providerSystemUrl} from './lib/mapLinks';
+import {ArchiveMode, KillArchive} from './lib/killArchive';
+import {KillQuery, KillSearchResult, pageCount, pageOf, searchArchive} from './lib/killSearch';
+import {ConcurrencyLimiter} from './lib/concurrencyLimiter';
+import {BoundedQueue} from './lib/boundedQueue';
+import {LogContext, logger, withLogContext} from './lib/logger';
+import {groupByChannel, mergedMention} from './lib/channelMatches';
+import {fleetComposition} from './lib/fleetComposition';
+import {abbreviateValue, formatValue, PLEX_TYPE_ID, ValueFormatSettings} from './lib/valueFormat';
+import {isStaleKill, maxKillAgeMinutes, StaleKillAction} from './lib/killAge';
+import {EventPublisher, MatchEvent} from './lib/eventPublisher';
+import {deliveryPriority} from './lib/deliveryPriority';
+import {exchangeResult, ExchangeTracker, parseExchangeOutcome} from './lib/exchangeOutcome';
+import {summaryAttacker} from './lib/matchSummary';
+import {searchSubscriptions, SubscriptionSearchResult} from './lib/subscriptionSearch';
+import {SlidingWindowRateLimiter} from './lib/rateLimiter';
+import {classesInJumpRange, parseLyRange} from './lib/jumpRange';
+import {HealthProbe, HealthServer, KillWatchdog} from './lib/health';
+import {classifyStructure, quantumCoreStatus} from './lib/structures';
+import {isInValueRange, MarketPriceCache, parseValueRange} from './lib/marketPrices';
+import {parseSecStatusRange, pilotsInSecStatusRange} from './lib/securityStatus';
+import {parseTechLevels, TechLevel, techLevelOf} from './lib/techLevel';
+import {EntityLists, isOnEntityList, mergeEntityLists} from './lib/entityLists';
+import {Campaign, Campaigns, iskEfficiency, newCampaign, recordCampaignKill, topParticipants} from './lib/campaigns';
+import {matchesNpcSpawn, parseNpcSpawnFilter} from './lib/npcSpawns';
+import {attackingPilotCount, classifyGangSize, parseGangSizeClasses} from './lib/gangSize';
+import {matchesPerspective, parsePerspective} from './lib/perspective';
+import {missingChannelPermissions} from './lib/permissions';
+import {SubscriptionCounters, SubscriptionStats} from './lib/subscriptionStats';
+import {postWebhook, redactWebhookUrl, Webhook, webhookKillPayload, WebhookKind} from './lib/webhooks';
+import {Notifier, NotifierKind, notifiersFromEnv, NotifierTarget} from './lib/notifiers';
+import {loadSde} from './lib/sde';
+import {ChannelFailures, classifySendError, SendErrorKind, SendRetryQueue} from './lib/sendRetry';
+import {describeSchedule, isReportDue, Report, reportPeriodDays} from './lib/reports';
+import {SrpQueue, SrpRequest, SrpStatus, srpButtonId} from './lib/srp';
+import {CHARGE_CATEGORY_ID, formatEft, getFittingSlot} from './lib/fitting';
+import {SingleFlight} from './lib/singleFlight';
+import {CacheWriter} from './lib/cacheWriter';
+import {BoundedCache, CacheStats} from './lib/boundedCache';
+import {fitEmbed} from './lib/embedLimits';
+import {
+    EntityTracking,
+    matchesTrackedEntity,
+    trackingLimitType,
+    trackingSubscriptionId,
+    withTrackedKill,
+} from './lib/entityTracking';
+import {KillPreFilter} from './lib/preFilter';
+import {SubscriptionIndex} from './lib/subscriptionIndex';
+import {DryRunMode, dryRunText} from './lib/dryRun';
 
 export enum SubscriptionType {
     PUBLIC = 'public',
@@ -36,31 +105,356 @@ export enum LimitType {
     CHARACTER = 'character',
     // A partial name of the entity type to require for sending
     NAME_FRAGMENT = 'nameFragment',
+    // Comma separated NameScope values the name fragment is looked for in, the matched ship types if unset
+    NAME_FRAGMENT_SCOPE = 'nameFragmentScope',
     MIN_NUM_INVOLVED = 'minNumInvolved',
     TIME_RANGE_START = 'startingTime',
     TIME_RANGE_END = 'endingTime',
     NPC_ONLY = 'npcOnly',
+    // Maximum jump drive distance from a system, as `systemName:maxLy` or `systemName:shipClass` with a JumpShipClass
     LY_RANGE_TO_SYSTEM_WITH_NAME = 'lyRangeToSystemWithName',
+    // Comma separated list of SpaceType values
+    SPACE_TYPE = 'spaceType',
+    // Maximum average security status of the attacking pilots, to find gank fleets
+    ATTACKER_AVG_SEC_STATUS_MAX = 'attackerAvgSecStatusMax',
+    // Security status range an attacking pilot has to be in, as `min:max`, either side may be empty, e.g. `:-5`
+    ATTACKER_SEC_STATUS = 'attackerSecStatus',
+    // Minimum number of distinct attackers that have to match the character, corporation or alliance filter
+    MIN_ENTITY_ATTACKERS = 'minEntityAttackers',
+    // Maximum stargate jumps from the border of a region, as `regionId:maxJumps`
+    JUMPS_FROM_REGION_BORDER = 'jumpsFromRegionBorder',
+    // Comma separated list of ShipCategory values the victim has to be in
+    VICTIM_CATEGORY = 'victimCategory',
+    // Comma separated tech levels of the victim's ship: t1, t2, t3, faction, deadspace or officer
+    VICTIM_TECH_LEVEL = 'victimTechLevel',
+    NEAR_CELESTIAL = 'nearCelestial',
+    // Maximum stargate jumps from a system, as `systemId:maxJumps`, e.g. from a staging system
+    JUMPS_FROM_SYSTEM = 'jumpsFromSystem',
+    // Comma separated faction IDs the victim or an attacker has to belong to, e.g. a faction warfare militia
+    FACTION = 'faction',
+    // Comma separated character, corporation or alliance IDs the victim has to be or belong to, kills by them do not match
+    VICTIM_ENTITY = 'victimEntity',
+    // Market price range of the victim's hull alone, as `min:max` in ISK, either side may be empty
+    HULL_VALUE = 'hullValue',
+    // Member count range of the victim's corporation, as `min:max`, either side may be empty
+    VICTIM_CORP_SIZE = 'victimCorpSize',
+    // Comma separated NPC group IDs or aliases of the attacking NPCs on NPC kills, e.g. officer spawns
+    NPC_SPAWN = 'npcSpawn',
+    // Comma separated GangSizeClass values of the attacking pilots, e.g. solo,small
+    GANG_SIZE = 'gangSize',
+    // Perspective of the own entities of the guild, kills, losses or both
+    PERSPECTIVE = 'perspective',
+    // Net ISK exchange of the own entities in the system around the kill, as `won|lost:windowMinutes`. The kill is only
+    // posted once the window has passed
+    EXCHANGE_OUTCOME = 'exchangeOutcome',
+    // Name of an entity list of the guild the victim or an attacker has to be on
+    ENTITY_LIST_INCLUDE = 'entityListInclude',
+    // Name of an entity list of the guild neither the victim nor an attacker may be on
+    ENTITY_LIST_EXCLUDE = 'entityListExclude',
+    // Comma separated alliance, corporation or ship group IDs of attackers the other filters ignore, the kill is only
+    // dropped if the victim matches or no attacker remains
+    EXCLUDED_ALLIANCE = 'excludedAlliance',
+    EXCLUDED_CORPORATION = 'excludedCorporation',
+    EXCLUDED_SHIP_GROUP = 'excludedShipGroup',
+}
+
+// Mapping of the name of a faction warfare militia to its faction ID
+export const MILITIA_FACTION_IDS: { [name: string]: number } = {
+    caldari: 500001,
+    minmatar: 500002,
+    amarr: 500003,
+    gallente: 500004,
+};
+
+// Replaces militia names with faction IDs, `militia` stands for all four. Null if a value is neither
+export function resolveFactionIds(value: string): string | null {
+    const ids: number[] = [];
+    for (const part of value.split(',').map(part => part.trim().toLowerCase())) {
+        if (part === 'militia') {
+            ids.push(...Object.values(MILITIA_FACTION_IDS));
+        } else if (MILITIA_FACTION_IDS[part] != null) {
+            ids.push(MILITIA_FACTION_IDS[part]);
+        } else if (/^\d+$/.test(part)) {
+            ids.push(Number(part));
+        } else {
+            return null;
+        }
+    }
+    return ids.filter((id, index) => ids.indexOf(id) === index).join(',');
+}
+
+export enum SpaceType {
+    HIGHSEC = 'highsec',
+    LOWSEC = 'lowsec',
+    NULLSEC = 'nullsec',
+    WORMHOLE = 'wormhole',
+    POCHVEN = 'pochven',
+    // Filaments pockets of the Abyssal Deadspace, without celestials, stargates or cynosural fields
+    ABYSSAL = 'abyssal',
+}
+
+export enum ShipCategory {
+    CAPSULE = 'capsule',
+    SHIP = 'ship',
+    STRUCTURE = 'structure',
+    STARBASE = 'starbase',
+    DEPLOYABLE = 'deployable',
+    FIGHTER = 'fighter',
+}
+
+export const CAPSULE_GROUP_ID = 29;
+
+// Mapping of ShipCategory to the inventory category ID, capsules are matched by their group instead
+const SHIP_CATEGORY_IDS = new Map<ShipCategory, number>([
+    [ShipCategory.SHIP, 6],
+    [ShipCategory.STRUCTURE, 65],
+    [ShipCategory.STARBASE, 23],
+    [ShipCategory.DEPLOYABLE, 22],
+    [ShipCategory.FIGHTER, 87],
+]);
+
+export function getShipCategory(groupId: number, categoryId: number): ShipCategory | null {
+    if (groupId === CAPSULE_GROUP_ID) {
+        return ShipCategory.CAPSULE;
+    }
+    let shipCategory: ShipCategory | null = null;
+    SHIP_CATEGORY_IDS.forEach((id, category) => {
+        if (id === categoryId) {
+            shipCategory = category;
+        }
+    });
+    return shipCategory;
+}
+
+export enum CelestialKind {
+    SUN = 'sun',
+    PLANET = 'planet',
+    MOON = 'moon',
+    BELT = 'belt',
+    STARGATE = 'stargate',
+    STATION = 'station',
+}
+
+// Mapping of CelestialKind to the inventory group ID of the celestial type
+const CELESTIAL_GROUP_IDS = new Map<CelestialKind, number>([
+    [CelestialKind.SUN, 6],
+    [CelestialKind.PLANET, 7],
+    [CelestialKind.MOON, 8],
+    [CelestialKind.BELT, 9],
+    [CelestialKind.STARGATE, 10],
+    [CelestialKind.STATION, 15],
+]);
+
+export function getCelestialKind(groupId: number): CelestialKind | null {
+    let celestialKind: CelestialKind | null = null;
+    CELESTIAL_GROUP_IDS.forEach((id, kind) => {
+        if (id === groupId) {
+            celestialKind = kind;
+        }
+    });
+    return celestialKind;
+}
+
+// How long the nearest celestial of a position is cached, the embed and the filters look it up for the same kill
+const CELESTIAL_CACHE_MS = 60 * 60 * 1000;
+
+// Member counts change slowly, a day old count is good enough for the corporation size
+const MEMBER_COUNT_CACHE_MS = 24 * 60 * 60 * 1000;
+
+// How long a posted loss suppresses the pod kill of the same pilot in the same channel
+const POD_SUPPRESSION_MS = 5 * 60 * 1000;
+
+// Region ID ranges and IDs that do not follow the regular security status rules
+const WORMHOLE_REGION_MIN_ID = 11000000;
+const WORMHOLE_REGION_MAX_ID = 11999999;
+const POCHVEN_REGION_ID = 10000070;
+const ABYSSAL_REGION_MIN_ID = 12000000;
+const ABYSSAL_REGION_MAX_ID = 12999999;
+const ABYSSAL_SYSTEM_MIN_ID = 32000000;
+const ABYSSAL_SYSTEM_MAX_ID = 32999999;
+
+export interface FilterTraceEntry {
+    filter: string;
+    passed: boolean;
+    // Values the filter compared
+    detail: string;
+}
+
+// Records the outcome of a filter and logs rejections, returns whether the filter passed
+function traceFilter(trace: FilterTraceEntry[] | undefined, filter: string, passed: boolean, detail: string): boolean {
+    trace?.push({filter, passed, detail});
+    if (!passed) {
+        logger.info(`limiting kill due to ${filter} filter: ${detail}`);
+    }
+    return passed;
+}
+
+// Result of running the filters of a subscription over a kill, the send parameters are only set if it matched
+export interface SubscriptionEvaluation {
+    matched: boolean;
+    // Limit type or setting that rejected the kill
+    rejectedBy?: string;
+    // Every filter evaluated, in order, up to the one that rejected the kill
+    trace: FilterTraceEntry[];
+    matchedShip: FilterShipMatch | null;
+    minNumInvolved: number | null;
+    color: ColorResolvable;
+    matchedAttackerCount: number | null;
+}
+
+// Per-guild settings, changed with the settings command
+export interface GuildSettings extends DisplaySettings, ValueFormatSettings {
+    // Language of the kill messages and command replies, English if unset
+    locale?: string;
+    // Members with this role may manage subscriptions without the Manage Channels permission
+    adminRoleId?: string;
+    // Further roles whose members may manage subscriptions, e.g. the FC roles
+    subscriberRoleIds?: string[];
+    // Channel notified about disabled subscriptions, the server owner is notified if unset
+    alertChannelId?: string;
+    // Character, corporation and alliance IDs of the guild's own side, used by the perspective filter
+    ownEntityIds?: number[];
+    // Ping role and value new subscriptions start with
+    defaultPingRoleId?: string;
+    defaultPingRoleValue?: number;
+    // Site of the system and region links, dotlan if unset
+    mapProvider?: MapProviderKind;
+    // If true, every subscription of a channel that matches a kill posts it, instead of one message naming all of them
+    postDuplicates?: boolean;
 }
 
-export interface SubscriptionGuild {
+export interface SubscriptionGuild extends GuildSettings {
     channels: Map<string, SubscriptionChannel>;
+    // Named ID lists for the entity list filters, managed with the bluelist command
+    entityLists?: EntityLists;
+    // ISO timestamp of the last change per entity list
+    entityListsUpdatedAt?: { [name: string]: string };
+    // Scheduled summaries per channel ID
+    reports?: { [channelId: string]: Report };
+    // Campaigns by name, stopped campaigns are kept for their scoreboard
+    campaigns?: Campaigns;
 }
 
 export interface SubscriptionChannel {
     subscriptions: Map<string, Subscription>;
 }
 
-export interface Subscription {
+// Per-subscription display settings, changed after subscribing with the configure command
+export interface SubscriptionSettings {
+    // If true, the footer shows the fitted and dropped value next to the total value
+    showValueBreakdown?: boolean,
+    // If true, pod kills are not posted if the pilot's ship loss was posted in the channel shortly before
+    suppressPodsAfterLoss?: boolean,
+    // Number of kills in one system within 15 minutes after which a battle summary is posted, 0 to disable
+    battleReportThreshold?: number,
+    // If true, kills of a reported battle are posted into a thread below the battle summary
+    battleReportThreads?: boolean,
+    // Secret part of the Atom feed URL of this subscription, no feed is served if unset
+    feedToken?: string,
+    // Role mentioned for kills worth at least pingRoleValue ISK
+    pingRoleId?: string,
+    pingRoleValue?: number,
+    // Kills worth at least this many ISK mention @here instead of the role, 0 to disable
+    pingHereValue?: number,
+    // Embed color as `#RRGGBB`, replaces the colors marking losses and kills
+    embedColor?: string,
+    // If true, losses of pilots get a button to request ship replacement, collected in the SRP queue
+    srpButton?: boolean,
+    // If true, the victim's fit is attached in EFT format
+    showFitting?: boolean,
+    // If true, buttons below the embed link to zKillboard, the guild's map site, the related kills and EveWho
+    linkButtons?: boolean,
+    // If true, a plain text line above the embed names the matched entity and ship, shown in push notifications
+    matchSummary?: boolean,
+    // What the subscription is for, found by /zkill-find
+    description?: string,
+    // Thumbnail of the kill embeds, the ship render if unset
+    thumbnailSource?: ThumbnailSource,
+    // Kills older than this many minutes are handled as staleKills says, MAX_KILL_AGE_MINUTES if unset, 0 to disable
+    maxKillAgeMinutes?: number,
+    // Skipped if unset
+    staleKills?: StaleKillAction,
+    // If set, matched kills are logged instead of posted anywhere, e.g. to watch the volume of a new filter for a day
+    dryRun?: DryRunMode,
+    // Further channels the kills are posted to, e.g. a public and a leadership channel sharing one filter
+    mirrorChannels?: MirrorChannel[],
+    // Webhooks the kills are posted to as well, e.g. a Slack or Matrix bridge or a SIEM
+    webhooks?: Webhook[],
+    // If true, the kills are only posted to the webhooks and mirror channels, not the subscription's channel
+    webhookOnly?: boolean,
+    // Matrix rooms and Telegram chats the kills are posted to as well
+    notifierTargets?: NotifierTarget[],
+}
+
+// Another channel of the guild a subscription posts to, the ping settings replace the subscription's if set
+export interface MirrorChannel {
+    channelId: string,
+    pingRoleId?: string,
+    pingRoleValue?: number,
+    pingHereValue?: number,
+}
+
+// Group IDs of the ship inclusion type IDs, resolved when subscribing so evaluation does not hit ESI
+export interface ResolvedShipGroups {
+    // The SHIP_INCLUSION_TYPE_ID value the group IDs were resolved from
+    typeIds: string,
+    // Comma separated, deduplicated group IDs
+    groupIds: string,
+}
+
+export interface Subscription extends SubscriptionSettings {
     subType: SubscriptionType
     id?: string,
+    // User ID of the member who created the subscription, unset for subscriptions created before it was recorded
+    createdBy?: string,
     minValue: number,
     // Mapping of LimitType to the value(s) to compare against
     limitTypes: Map<LimitType, string>,
+    shipInclusionGroups?: ResolvedShipGroups,
     inclusionLimitAlsoComparesAttacker: boolean,
     inclusionLimitAlsoComparesAttackerWeapons: boolean,
     exclusionLimitAlsoComparesAttacker: boolean,
-    exclusionLimitAlsoComparesAttackerWeapons: boolean
+    exclusionLimitAlsoComparesAttackerWeapons: boolean,
+    // Set while the bot cannot post in the channel, the subscription is deleted after DISABLED_PURGE_DAYS
+    disabledReason?: string,
+    // ISO timestamp of when the subscription was disabled
+    disabledAt?: string,
+    // Name of the campaign the posted kills count towards
+    campaign?: string,
+    // Set for the subscriptions of /zkill-track, their footer shows the running ISK totals
+    tracking?: EntityTracking,
+}
+
+// A subscription with the channel or the user its kills are sent to
+export interface SubscriptionTarget {
+    subscription: Subscription;
+    guildId?: string;
+    channelId?: string;
+    // Set for direct message subscriptions instead of the guild and channel
+    userId?: string;
+}
+
+// A guild subscription that matched a kill, with the evaluation its message is built from
+export interface SubscriptionMatch {
+    subscription: Subscription;
+    guildId: string;
+    channelId: string;
+    evaluation: SubscriptionEvaluation;
+    statsKey: string;
+}
+
+// How fast the kills arrive and are posted, shown by /zkill-diag
+export interface DeliveryDiagnostics {
+    received: LatencySummary | null;
+    posted: LatencySummary | null;
+    // Set when the kills come from RedisQ
+    queueId?: string;
+    // Received kills waiting for the delivery workers
+    queuedKills: number;
+    // Number of entries per cache
+    cacheSizes: { [cache: string]: number };
+    // Lookups of the bounded caches since the start
+    cacheStats: { [cache: string]: CacheStats };
 }
 
 export interface SubscriptionFlags {
@@ -91,8 +485,25 @@ export type PrepareEmbedFields = {
     matchedShip: FilterShipMatch | null,
     minNumInvolved: number | null,
     messageColor: ColorResolvable,
+    matchedAttackerCount: number | null,
+    // Idents of the subscriptions sharing the message, set if more than one matched in the channel
+    matchedBy?: string[],
 };
 
+// The full embed lists what made the subscription match, other kills get the zKillboard preview. Display settings
+// only the full embed shows select it as well
+export function usesFullEmbed(params: PrepareEmbedFields): boolean {
+    return params.matchedShip != null ||
+        params.minNumInvolved != null ||
+        params.matchedAttackerCount != null ||
+        hasLimitType(params.subscription, LimitType.ATTACKER_AVG_SEC_STATUS_MAX) ||
+        params.subscription.showValueBreakdown === true ||
+        params.subscription.showFitting === true ||
+        hasLimitType(params.subscription, LimitType.VICTIM_CORP_SIZE) ||
+        hasLimitType(params.subscription, LimitType.GANG_SIZE) ||
+        params.subscription.thumbnailSource != null;
+}
+
 export type FilterShipMatch = {
     shipName: string | null,
     typeId: number | null,
@@ -109,6 +520,7 @@ export class Attacker {
     ship_type_id?: number;
     weapon_type_id?: number;
     character_id?: number;
+    faction_id?: number;
 
     constructor(
         alliance_id: number,
@@ -174,6 +586,7 @@ export type Victim = {
     position: Position;
     ship_type_id?: number; // ship_type_id is now optional
     character_id?: number; // character_id is optional and may be present instead of ship_type_id
+    faction_id?: number;
 };
 
 export type VictimItem = {
@@ -208,21 +621,46 @@ export type ZkData = {
     zkb: Zkb;
 };
 
-function hasLimitType(subscription: Subscription, limitType: LimitType): boolean {
+// The matched ship types if unset or invalid
+export function getNameFragmentScopes(subscription: Subscription): NameScope[] {
+    return parseNameScopes(getLimitType(subscription, LimitType.NAME_FRAGMENT_SCOPE) ?? '') ?? [NameScope.SHIP];
+}
+
+export function hasLimitType(subscription: Subscription, limitType: LimitType): boolean {
     return subscription.limitTypes.has(limitType);
 }
 
-function getLimitType(subscription: Subscription, limitType: LimitType): string | undefined {
+export function getLimitType(subscription: Subscription, limitType: LimitType): string | undefined {
     if (subscription.limitTypes instanceof Map) {
         return subscription.limitTypes.get(limitType) as string | undefined;
     } else {
-        console.log('subscription is not of type Map, exiting');
-        console.log(`subscription.limitTypes: ${subscription.limitTypes}`);
-        console.log(`subscription.limitTypes type: ${typeof subscription.limitTypes}`);
+        logger.info('subscription is not of type Map, exiting');
+        logger.info(`subscription.limitTypes: ${subscription.limitTypes}`);
+        logger.info(`subscription.limitTypes type: ${typeof subscription.limitTypes}`);
         process.exit(2);
     }
 }
 
+// The ship, entity and location filters pick the kills of a subscription, the other filters only narrow them down
+export const SELECTING_LIMIT_TYPES: LimitType[] = [
+    LimitType.SHIP_INCLUSION_TYPE_ID,
+    LimitType.CHARACTER,
+    LimitType.CORPORATION,
+    LimitType.ALLIANCE,
+    LimitType.FACTION,
+    LimitType.VICTIM_ENTITY,
+    LimitType.REGION,
+    LimitType.CONSTELLATION,
+    LimitType.SYSTEM,
+    LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME,
+    LimitType.JUMPS_FROM_SYSTEM,
+];
+
+// Without a selecting filter every kill the other filters let through matches, e.g. of a space type only subscription
+export function hasSelectingLimitType(subscription: Subscription): boolean {
+    return SELECTING_LIMIT_TYPES.some(limitType => hasLimitType(subscription, limitType));
+}
+
 export interface ClosestCelestial {
     distance: number;
     itemId: number;
@@ -240,38 +678,289 @@ export interface SolarSystem {
     securityStatus: number;
 }
 
+export function isAbyssalSystemId(systemId: number): boolean {
+    return systemId >= ABYSSAL_SYSTEM_MIN_ID && systemId <= ABYSSAL_SYSTEM_MAX_ID;
+}
+
+// The map sites have no pages for abyssal pockets, zKillboard lists their kills instead
+export function systemMapUrl(systemId: number, systemName?: string, provider?: MapProviderKind): string {
+    if (isAbyssalSystemId(systemId)) {
+        return `https://zkillboard.com/system/${systemId}/`;
+    }
+    return providerSystemUrl(provider, systemId, systemName);
+}
+
+export function regionMapUrl(regionId: number, regionName?: string, provider?: MapProviderKind): string {
+    if (regionId >= ABYSSAL_REGION_MIN_ID && regionId <= ABYSSAL_REGION_MAX_ID) {
+        return `https://zkillboard.com/region/${regionId}/`;
+    }
+    return providerRegionUrl(provider, regionId, regionName);
+}
+
+export function getSpaceType(system: Pick<SolarSystem, 'regionId' | 'securityStatus'>): SpaceType {
+    if (system.regionId === POCHVEN_REGION_ID) {
+        return SpaceType.POCHVEN;
+    }
+    if (system.regionId >= WORMHOLE_REGION_MIN_ID && system.regionId <= WORMHOLE_REGION_MAX_ID) {
+        return SpaceType.WORMHOLE;
+    }
+    if (system.regionId >= ABYSSAL_REGION_MIN_ID && system.regionId <= ABYSSAL_REGION_MAX_ID) {
+        return SpaceType.ABYSSAL;
+    }
+    // EVE displays anything from 0.45 upwards as 0.5, and anything above 0.0 as at least 0.1
+    if (system.securityStatus >= 0.45) {
+        return SpaceType.HIGHSEC;
+    }
+    if (system.securityStatus > 0.0) {
+        return SpaceType.LOWSEC;
+    }
+    return SpaceType.NULLSEC;
+}
+
+// Normalizes `#abc`, `abc`, `#aabbcc` or `aabbcc` to `#AABBCC`, null if the value is not a hex color
+export function parseHexColor(value: string): string | null {
+    let hex = value.trim().replace(/^#/, '');
+    if (/^[0-9a-fA-F]{3}$/.test(hex)) {
+        hex = hex.split('').map(digit => digit + digit).join('');
+    }
+    return /^[0-9a-fA-F]{6}$/.test(hex) ? '#' + hex.toUpperCase() : null;
+}
+
+// Mention for a kill of the given value, the @here threshold takes precedence over the role threshold
+export function escalationMention(settings: SubscriptionSettings, totalValue: number): string | null {
+    if (settings.pingHereValue && totalValue >= settings.pingHereValue) {
+        return '@here';
+    }
+    if (settings.pingRoleId && settings.pingRoleValue && totalValue >= settings.pingRoleValue) {
+        return `<@&${settings.pingRoleId}>`;
+    }
+    return null;
+}
+
+// Key of the subscription in its channel, e.g. `region10000002`
+export function subscriptionIdent(subscription: Subscription): string {
+    return `${subscription.subType}${subscription.id ? subscription.id : ''}`;
+}
+
+// Log fields of the channel or the user the kill is sent to
+function targetLogContext(target: SubscriptionTarget): LogContext {
+    return {
+        guild_id: target.guildId,
+        channel_id: target.channelId,
+        user_id: target.userId,
+        subscription_id: target.subscription.id != null ? String(target.subscription.id) : undefined,
+    };
+}
+
+// The subscription as posted to a mirror channel, with the mirror's ping settings
+export function mirrorSubscription(subscription: Subscription, mirror: MirrorChannel): Subscription {
+    return {
+        ...subscription,
+        // The kill counts towards the campaign once, in the subscription's own channel
+        campaign: undefined,
+        pingRoleId: mirror.pingRoleId ?? subscription.pingRoleId,
+        pingRoleValue: mirror.pingRoleValue ?? subscription.pingRoleValue,
+        pingHereValue: mirror.pingHereValue ?? subscription.pingHereValue,
+    };
+}
+
+// Deep copy of the filters and display settings for another channel, the limit types in `overrides` replace the
+// copied ones. Where else the subscription posts, its feed and its state are not copied
+export function cloneSubscription(
+    subscription: Subscription,
+    id: string | undefined,
+    overrides: Map<LimitType, string>,
+    createdBy?: string,
+): Subscription {
+    const clone: Subscription = JSON.parse(JSON.stringify({...subscription, limitTypes: undefined}));
+    clone.id = id;
+    clone.createdBy = createdBy;
+    clone.limitTypes = new Map(subscription.limitTypes);
+    overrides.forEach((value, limitType) => clone.limitTypes.set(limitType, value));
+    delete clone.mirrorChannels;
+    delete clone.webhooks;
+    delete clone.webhookOnly;
+    delete clone.notifierTargets;
+    delete clone.feedToken;
+    delete clone.campaign;
+    delete clone.tracking;
+    delete clone.disabledReason;
+    delete clone.disabledAt;
+    if (overrides.has(LimitType.SHIP_INCLUSION_TYPE_ID)) {
+        delete clone.shipInclusionGroups;
+    }
+    return clone;
+}
+
+// Average security status of the attacking pilots, NPCs are not taken into account
+export function getAttackerAverageSecurityStatus(attackers: Attacker[]): number | null {
+    const pilots = attackers.filter(attacker => attacker.character_id != null);
+    if (pilots.length === 0) {
+        return null;
+    }
+    return pilots.reduce((total, attacker) => total + attacker.security_status, 0) / pilots.length;
+}
+
+// Number of distinct attackers whose ID, as returned by getId, is in the list of IDs
+export function countMatchingAttackers(
+    attackers: Attacker[],
+    getId: (attacker: Attacker) => number | null | undefined,
+    ids: string[],
+): number {
+    const matched = new Set<string>();
+    attackers.forEach((attacker, index) => {
+        const id = getId(attacker);
+        if (id != null && ids.includes(id.toString())) {
+            // NPCs have no character ID, count every one of them
+            matched.add(attacker.character_id != null ? `c${attacker.character_id}` : `i${index}`);
+        }
+    });
+    return matched.size;
+}
+
 export class ZKillSubscriber {
     protected static instance: ZKillSubscriber;
     protected doClient: Client;
 
     protected subscriptions: Map<string, SubscriptionGuild>;
+    // Mapping of user ID to the subscriptions delivered as direct messages to that user
+    protected dmSubscriptions: Map<string, SubscriptionChannel>;
     // Mapping of a solar system type ID to a description
-    protected systems: Map<number, SolarSystem>;
+    protected systems: BoundedCache<number, SolarSystem>;
     // Mapping of ship type ID to group ID
-    protected ships: Map<number, number>;
+    protected ships: BoundedCache<number, number>;
     // Mapping of ship type ID to name
-    protected names: Map<number, string>;
+    protected names: BoundedCache<number, string>;
+    // Mapping of group ID to category ID
+    protected categories: Map<number, number>;
+    // Mapping of corporation or alliance ID to ticker, the IDs never overlap
+    protected tickers: Map<number, string>;
     protected rest: REST;
 
     protected asyncLock: AsyncLock;
+    // Concurrent lookups of the same uncached ID share one ESI request
+    protected systemLookups = new SingleFlight<SolarSystem>();
+    protected groupLookups = new SingleFlight<number>();
+    protected categoryLookups = new SingleFlight<number>();
+    // Meta group IDs of the types, from the SDE or ESI, null for Tech I types without one
+    protected metaGroups = new Map<number, number | null>();
+    protected metaGroupLookups = new SingleFlight<number | null>();
+    protected nameLookups = new SingleFlight<string>();
+    protected tickerLookups = new SingleFlight<string>();
+    // Mapping of group ID to name, only looked up for NPC attackers
+    protected groupNames = new Map<number, string>();
+    protected groupNameLookups = new SingleFlight<string>();
+    protected memberCountLookups = new SingleFlight<number>();
+    // Flushes the system, ship, category, name and ticker caches to disk every CACHE_FLUSH_SECONDS
+    protected cacheWriter = new CacheWriter(Number(process.env.CACHE_FLUSH_SECONDS || 30) * 1000);
     protected esiClient: EsiClient;
+    protected zkbApi: ZkbApiClient;
+    // Evaluated, matched and posted kills per subscription, flushed to disk with the caches
+    protected subscriptionStats = new SubscriptionStats();
+    // Matrix and Telegram backends enabled by the operator
+    protected notifiers: Map<NotifierKind, Notifier> = notifiersFromEnv();
+    protected hostedMode: HostedMode;
+    protected killSource: KillSource;
+    // Bounds how many subscriptions are evaluated and sent at the same time
+    protected dispatchLimiter: ConcurrencyLimiter;
+    // Received kills waiting for the delivery workers, so slow sends never stall the kill source
+    protected killQueue: BoundedQueue<ZkData>;
+    // Rebuilt on the next kill after the subscriptions changed
+    protected preFilter: KillPreFilter | null = null;
+    protected subscriptionIndex: SubscriptionIndex<SubscriptionTarget> | null = null;
+    protected killStats: KillStats;
+    protected lastSeen = new LastSeen();
+    protected srpQueue = new SrpQueue();
+    protected starMap: StarMap | null = null;
+    // Jump Drive Calibration level assumed for the pilots of jump capable ships
+    protected jumpDriveCalibrationLevel = Number(process.env.JUMP_DRIVE_CALIBRATION_LEVEL || 5);
+    protected battleDetector = new BattleDetector();
+    // Kills and losses of the own entities of the guilds, for the exchange outcome filter
+    protected exchangeTracker = new ExchangeTracker();
+    protected feedServer: FeedServer | null = null;
+    protected healthServer: HealthServer | null = null;
+    protected killWatchdog: KillWatchdog | null = null;
+    // Time the last kill was received from the kill source
+    protected lastKillReceivedAt: number | null = null;
+    protected deliveryLatency = new DeliveryLatency();
+    // Raw kills kept for later analysis, disabled unless ARCHIVE_DIR is set
+    protected killArchive = KillArchive.fromEnv();
+    // Matched kills for downstream tools, disabled unless PUBLISH_URL is set
+    protected eventPublisher = EventPublisher.fromEnv();
+    // Last ESI status check, repeated at most once a minute
+    protected esiStatus: { reachable: boolean, time: number } | null = null;
+    // Copies of the images served by the feed server, disabled unless IMAGE_PROXY_DIR is set
+    protected imageProxy = ImageProxy.fromEnv();
+    protected images = new Images(undefined, this.imageProxy);
+    // Current market prices, refreshed from ESI once withMarketPrices is called
+    protected marketPrices: MarketPriceCache;
+    // Sends failing with rate limits or Discord server errors are retried instead of dropped
+    protected sendRetries = SendRetryQueue.fromEnv();
+    protected channelFailures = new ChannelFailures(Number(process.env.CHANNEL_CLEANUP_FAILURES || 3));
+    protected disabledPurgeDays = Number(process.env.DISABLED_PURGE_DAYS || 14);
+    // Default maximum age of posted kills, unlimited if unset
+    protected maxKillAgeMinutes = process.env.MAX_KILL_AGE_MINUTES ? Number(process.env.MAX_KILL_AGE_MINUTES) : null;
+    // Bounds the direct messages sent to a single user
+    protected dmRateLimiter: SlidingWindowRateLimiter;
 
     protected constructor(client: Client, connect = true) {
         this.asyncLock = new AsyncLock();
         this.esiClient = new EsiClient();
+        this.zkbApi = new ZkbApiClient();
+        this.hostedMode = HostedMode.fromEnv();
+        this.killSource = KillSource.fromEnv();
+        this.dispatchLimiter = new ConcurrencyLimiter(Number(process.env.DISPATCH_CONCURRENCY || 8));
+        this.killQueue = new BoundedQueue<ZkData>(Number(process.env.KILL_QUEUE_SIZE || 1000));
+        this.killStats = new KillStats();
         this.subscriptions = new Map<string, SubscriptionGuild>();
-        this.systems = new Map<number, SolarSystem>();
-        this.ships = new Map<number, number>();
-        this.names = new Map<number, string>();
+        this.dmSubscriptions = new Map<string, SubscriptionChannel>();
+        this.dmRateLimiter = new SlidingWindowRateLimiter(Number(process.env.DM_MAX_PER_HOUR || 20), 60 * 60 * 1000);
+        this.systems = new BoundedCache<number, SolarSystem>(Number(process.env.CACHE_MAX_SYSTEMS || 10000));
+        this.ships = new BoundedCache<number, number>(Number(process.env.CACHE_MAX_SHIPS || 100000));
+        // Characters, corporations and alliances get renamed, so names are looked up again after the TTL
+        this.names = new BoundedCache<number, string>(
+            Number(process.env.CACHE_MAX_NAMES || 200000),
+            Number(process.env.CACHE_NAME_TTL_DAYS || 30) * 24 * 60 * 60 * 1000,
+        );
+        this.categories = new Map<number, number>();
+        this.tickers = new Map<number, string>();
+        this.marketPrices = MarketPriceCache.fromEnv(() => this.esiClient.getMarketPrices());
         this.doClient = client;
         this.rest = new REST({version: '9'}).setToken(process.env.DISCORD_BOT_TOKEN || '');
         if (connect) {
+            this.startKillWorkers(Number(process.env.KILL_WORKERS || 4));
             ZKillSubscriber.connect(this);
         }
     }
 
+    protected startKillWorkers(count: number) {
+        for (let i = 0; i < count; i++) {
+            (async () => {
+                // eslint-disable-next-line no-constant-condition
+                while (true) {
+                    const data = await this.killQueue.take();
+                    try {
+                        await this.processKill(data);
+                    } catch (e) {
+                        logger.error(e);
+                    }
+                }
+            })();
+        }
+    }
+
     protected static connect(sub: ZKillSubscriber) {
-        const websocket = new WebSocket('wss://zkillboard.com/websocket/');
+        if (sub.killSource.type === KillSourceType.REDISQ) {
+            logger.info(`listening to RedisQ with queue ID ${sub.killSource.queueId}`);
+            // Waits for space in the queue, RedisQ keeps buffering the kills meanwhile
+            new RedisQListener(sub.killSource, data => {
+                sub.lastKillReceivedAt = Date.now();
+                sub.deliveryLatency.recordReceived(data.killmail_time, sub.lastKillReceivedAt);
+                return sub.killQueue.push(data);
+            }).start();
+            return;
+        }
+        const websocket = new WebSocket(sub.killSource.websocketUrl);
         websocket.onmessage = sub.onMessage.bind(sub);
         websocket.onopen = () => {
             websocket.send(JSON.stringify({
@@ -280,34 +969,147 @@ export class ZKillSubscriber {
             }));
         };
         websocket.onclose = (e) => {
-            console.log('Socket is closed. Reconnect will be attempted in 1 second.', e.reason);
+            logger.info('Socket is closed. Reconnect will be attempted in 1 second.', e.reason);
             setTimeout(function () {
                 ZKillSubscriber.connect(sub);
             }, 1000);
         };
         websocket.onerror = (error) => {
-            console.error('Socket encountered error: ', error.message, 'Closing socket');
+            logger.error('Socket encountered error: ', error.message, 'Closing socket');
             websocket.close();
         };
     }
 
     protected async onMessage(event: MessageEvent) {
-        const data: ZkData = JSON.parse(event.data.toString());
-        this.subscriptions.forEach((guild, guildId) => {
-            const log_prefix = `["${data.killmail_id}"][${new Date()}] `;
-            console.log(log_prefix);
-            guild.channels.forEach((channel, channelId) => {
-                channel.subscriptions.forEach(async (subscription) => {
+        this.onKill(JSON.parse(event.data.toString()));
+    }
+
+    // The websocket cannot be paused, kills are dropped if the workers fall too far behind
+    protected onKill(data: ZkData) {
+        this.lastKillReceivedAt = Date.now();
+        this.deliveryLatency.recordReceived(data.killmail_time, this.lastKillReceivedAt);
+        if (!this.killQueue.tryPush(data)) {
+            logger.error(`kill queue is full, dropping kill ${data.killmail_id}`);
+        }
+    }
+
+    protected processKill(data: ZkData): Promise<void> {
+        return withLogContext({kill_id: data.killmail_id}, async () => {
+            logger.info('received killmail');
+            this.killArchive?.record(data, ArchiveMode.ALL);
+            this.recordExchanges(data);
+            if (!this.getPreFilter().mayMatch(data, this.systems.get(data.solar_system_id))) {
+                logger.info('no subscription can match the killmail, skipping');
+                return;
+            }
+            const killContext = this.createKillContext(data);
+            // The region and constellation subscriptions are only looked up with the system, without it all are candidates
+            const system = await killContext.system().catch(() => undefined);
+            const evaluations = this.getSubscriptionIndex().candidates(data, system).map(target =>
+                withLogContext(targetLogContext(target), async (): Promise<SubscriptionMatch | null> => {
                     try {
-                        await this.process_subscription(subscription, data, guildId, channelId);
+                        if (target.userId != null) {
+                            await this.dispatchLimiter.run(() =>
+                                this.process_dm_subscription(target.subscription, data, <string>target.userId, killContext));
+                            return null;
+                        }
+                        return await this.dispatchLimiter.run(() =>
+                            this.evaluate_subscription(target.subscription, data, <string>target.guildId, <string>target.channelId, killContext));
                     } catch (e) {
-                        console.log(e);
+                        logger.error(e);
+                        return null;
                     }
-                });
-            });
+                }));
+            const evaluated = (await Promise.all(evaluations)).filter((match): match is SubscriptionMatch => match != null);
+            // Subscriptions waiting for the outcome of the exchange post on their own once it is known
+            const matches = evaluated.filter(match => !hasLimitType(match.subscription, LimitType.EXCHANGE_OUTCOME));
+            evaluated.filter(match => hasLimitType(match.subscription, LimitType.EXCHANGE_OUTCOME))
+                .forEach(match => this.deferUntilExchangeKnown(match, data));
+            // Overlapping subscriptions of a channel post the kill once, unless the guild opted out
+            const groups = groupByChannel(matches, guildId => this.subscriptions.get(guildId)?.postDuplicates === true);
+            const victimGroupId = groups.length > 0 ? await killContext.groupId(data.victim.ship_type_id).catch(() => null) : null;
+            await Promise.all(groups.map(group => withLogContext(targetLogContext(group[0]), async () => {
+                // Pings and supercapital kills skip the queue of waiting messages
+                const pings = mergedMention(group.map(match => match.subscription), data.zkb.totalValue) != null;
+                try {
+                    await this.dispatchLimiter.run(() => this.deliver_matches(group, data), deliveryPriority(pings, victimGroupId));
+                } catch (e) {
+                    logger.error(e);
+                }
+            })));
+        });
+    }
+
+    // Remembers the kill for every guild whose own entities are on either side of it
+    protected recordExchanges(data: ZkData) {
+        this.subscriptions.forEach((guild, guildId) => {
+            if ((guild.ownEntityIds ?? []).length > 0) {
+                this.exchangeTracker.record(guildId, <number[]>guild.ownEntityIds, data);
+            }
         });
     }
 
+    // Posts the match once the window of the exchange after the kill has passed, if the own entities won or lost it
+    // as required. Waiting posts are lost on a restart
+    protected deferUntilExchangeKnown(match: SubscriptionMatch, data: ZkData) {
+        const filter = parseExchangeOutcome(<string>getLimitType(match.subscription, LimitType.EXCHANGE_OUTCOME));
+        if (filter == null) {
+            return;
+        }
+        const killTime = Date.parse(data.killmail_time);
+        const windowMs = filter.windowMinutes * 60 * 1000;
+        const timer = setTimeout(() => withLogContext(targetLogContext(match), async () => {
+            const balance = this.exchangeTracker.balance(match.guildId, data.solar_system_id, killTime - windowMs, killTime + windowMs);
+            const result = exchangeResult(balance);
+            if (result !== filter.require) {
+                logger.info(`exchange ${result ?? 'even'} with ${balance.destroyed} ISK destroyed and ${balance.lost} ISK lost, expected ${filter.require}, not posting`);
+                return;
+            }
+            try {
+                await this.dispatchLimiter.run(() => this.deliver_matches([match], data));
+            } catch (e) {
+                logger.error(e);
+            }
+        }), Math.max(0, killTime + windowMs - Date.now()));
+        timer.unref?.();
+    }
+
+    // Every subscription of the guilds and direct messages with where to send its kills
+    protected subscriptionTargets(): SubscriptionTarget[] {
+        const targets: SubscriptionTarget[] = [];
+        this.subscriptions.forEach((guild, guildId) => guild.channels.forEach((channel, channelId) => {
+            channel.subscriptions.forEach(subscription => {
+                if (!subscription.disabledAt) {
+                    targets.push({subscription, guildId, channelId});
+                }
+            });
+        }));
+        this.dmSubscriptions.forEach((dm, userId) => dm.subscriptions.forEach(subscription => targets.push({subscription, userId})));
+        return targets;
+    }
+
+    protected getPreFilter(): KillPreFilter {
+        if (this.preFilter == null) {
+            this.preFilter = new KillPreFilter(this.subscriptionTargets().map(target => target.subscription));
+        }
+        return this.preFilter;
+    }
+
+    protected getSubscriptionIndex(): SubscriptionIndex<SubscriptionTarget> {
+        if (this.subscriptionIndex == null) {
+            const index = new SubscriptionIndex<SubscriptionTarget>();
+            this.subscriptionTargets().forEach(target => index.add(target.subscription, target));
+            this.subscriptionIndex = index;
+        }
+        return this.subscriptionIndex;
+    }
+
+    // Rebuilds the pre-filter and the subscription index on the next kill
+    protected invalidateSubscriptionIndex() {
+        this.preFilter = null;
+        this.subscriptionIndex = null;
+    }
+
     private init_subscription_flags(): SubscriptionFlags {
         return {
             inclusionLimitAlsoComparesAttacker: true,
@@ -317,60 +1119,326 @@ export class ZKillSubscriber {
         };
     }
 
-    private async process_subscription(
+    // Lookups for one killmail, shared by all subscriptions it is evaluated against
+    public createKillContext(data: ZkData): KillContext {
+        return new KillContext(data, {
+            system: systemId => this.getSystemData(systemId),
+            groupId: typeId => this.getGroupIdForEntityId(typeId),
+            categoryId: groupId => this.getCategoryIdForGroupId(groupId),
+            name: typeId => this.getNameForEntityId(typeId),
+            celestial: kill => this.getClosestCelestial(kill.solar_system_id, kill.victim.position.x, kill.victim.position.y, kill.victim.position.z),
+        });
+    }
+
+    private async evaluate_subscription(
+        subscription: Subscription,
+        data: ZkData,
+        guildId: string,
+        channelId: string,
+        context: KillContext = this.createKillContext(data),
+    ): Promise<SubscriptionMatch | null> {
+        const evaluation = await this.evaluateSubscription(subscription, data, context, guildId);
+        const statsKey = SubscriptionStats.key(guildId, channelId, subscriptionIdent(subscription));
+        this.subscriptionStats.recordEvaluation(statsKey, evaluation.matched, evaluation.rejectedBy);
+        this.cacheWriter.markDirty('./config/subscription_stats.json', () => this.subscriptionStats.toJson());
+        return evaluation.matched ? {subscription, guildId, channelId, evaluation, statsKey} : null;
+    }
+
+    // Posts the kill once to the channel of the matches, then to the mirror channels, webhooks and notifiers of each
+    private async deliver_matches(allMatches: SubscriptionMatch[], data: ZkData) {
+        for (const match of allMatches) {
+            if (match.subscription.dryRun) {
+                await this.reportDryRun(match.subscription, data, `<#${match.channelId}>`, match.subscription.createdBy);
+            }
+        }
+        const matches = allMatches.filter(match => !match.subscription.dryRun);
+        if (matches.length === 0) {
+            return;
+        }
+        logger.info('sending filtered kill');
+        for (const match of matches) {
+            this.recordSightings(match.guildId, match.subscription, data);
+            this.killArchive?.record(data, ArchiveMode.MATCHED);
+            this.eventPublisher?.publish(<MatchEvent>{
+                ...webhookKillPayload(data, match.guildId, match.channelId, match.subscription.id),
+                subscription: subscriptionIdent(match.subscription),
+            });
+        }
+        const posting = matches.filter(match => !match.subscription.webhookOnly);
+        if (posting.length > 0) {
+            const [first, ...others] = posting;
+            await this.sendMessageToDiscord(
+                first.guildId,
+                first.channelId,
+                first.subscription,
+                data,
+                first.evaluation.matchedShip,
+                first.evaluation.minNumInvolved,
+                first.evaluation.color,
+                first.evaluation.matchedAttackerCount,
+                first.statsKey,
+                others.map((match): [Subscription, string] => [match.subscription, match.statsKey]),
+            );
+        }
+        for (const {subscription, guildId, channelId, evaluation} of matches) {
+            for (const mirror of subscription.mirrorChannels ?? []) {
+                await this.sendMessageToDiscord(
+                    guildId,
+                    mirror.channelId,
+                    mirrorSubscription(subscription, mirror),
+                    data,
+                    evaluation.matchedShip,
+                    evaluation.minNumInvolved,
+                    evaluation.color,
+                    evaluation.matchedAttackerCount,
+                );
+            }
+            for (const webhook of subscription.webhooks ?? []) {
+                await this.sendWebhook(guildId, channelId, subscription, data, evaluation, webhook);
+            }
+            for (const target of subscription.notifierTargets ?? []) {
+                await this.sendNotification(guildId, channelId, subscription, data, evaluation, target);
+            }
+        }
+    }
+
+    private recordSightings(guildId: string, subscription: Subscription, data: ZkData) {
+        if (this.lastSeen.record(guildId, sightingsOf(data, watchedEntities(subscription.limitTypes)))) {
+            this.cacheWriter.markDirty(this.lastSeen.file(guildId), () => this.lastSeen.toJson(guildId));
+        }
+    }
+
+    // Undefined if the entity was not on a kill posted in the guild
+    public getLastSeen(guildId: string, entityId: number): Sighting | undefined {
+        return this.lastSeen.get(guildId, entityId);
+    }
+
+    // Where a watched entity on the kill was seen before, e.g. `Goonswarm Federation last seen 2 hours ago in Tama`
+    private async lastSeenText(guildId: string, subscription: Subscription, data: ZkData, locale: Locale): Promise<string | null> {
+        const entityIds = Array.from(sightingsOf(data, watchedEntities(subscription.limitTypes)).keys());
+        for (const entityId of entityIds) {
+            const sighting = this.lastSeen.before(guildId, entityId, data.killmail_id);
+            if (!sighting) {
+                continue;
+            }
+            const name = await this.getNameForIntelEntity(sighting.type, entityId);
+            const system = await this.getSystemData(sighting.systemId);
+            return translate(locale, 'embed.lastSeen', {
+                name,
+                time: relativeTimeText(locale, Date.parse(data.killmail_time) - Date.parse(sighting.time)),
+                system: `[${system.systemName}](${sighting.url})`,
+            });
+        }
+        return null;
+    }
+
+    public getNameForIntelEntity(type: IntelEntityType, id: number): Promise<string> {
+        if (type === IntelEntityType.CHARACTER) {
+            return this.getNameForCharacter(id);
+        }
+        return type === IntelEntityType.CORPORATION ? this.getNameForCorporation(id) : this.getNameForAlliance(id);
+    }
+
+    private async sendNotification(
+        guildId: string,
+        channelId: string,
         subscription: Subscription,
         data: ZkData,
+        evaluation: SubscriptionEvaluation,
+        target: NotifierTarget,
+    ) {
+        const notifier = this.notifiers.get(target.kind);
+        const cacheKey = `${target.kind}_${target.target}_${data.killmail_id}`;
+        if (!notifier || MemoryCache.get(cacheKey)) {
+            return;
+        }
+        MemoryCache.put(cacheKey, 'send', 60000);
+        try {
+            const content = await this.previewMessage(guildId, channelId, subscription, data, evaluation);
+            await notifier.send(<MessageEmbedOptions[]>(content.embeds ?? []), target.target);
+        } catch (e) {
+            logger.info(`failed to post kill ${data.killmail_id} to ${target.kind} ${target.target}: ${e}`);
+        }
+    }
+
+    public hasNotifier(kind: NotifierKind): boolean {
+        return this.notifiers.has(kind);
+    }
+
+    private async sendWebhook(
         guildId: string,
         channelId: string,
+        subscription: Subscription,
+        data: ZkData,
+        evaluation: SubscriptionEvaluation,
+        webhook: Webhook,
     ) {
+        // Several subscriptions may post to the same webhook
+        const cacheKey = `webhook_${webhook.url}_${data.killmail_id}`;
+        if (MemoryCache.get(cacheKey)) {
+            return;
+        }
+        MemoryCache.put(cacheKey, 'send', 60000);
+        try {
+            let body: string;
+            if (webhook.kind === WebhookKind.DISCORD) {
+                const content = await this.previewMessage(guildId, channelId, subscription, data, evaluation);
+                // Webhooks take the raw API embeds, which MessageEmbed resolves the colors for
+                body = JSON.stringify({
+                    embeds: (content.embeds ?? []).map(embed => new MessageEmbed(<MessageEmbedOptions>embed).toJSON()),
+                    allowed_mentions: {parse: []},
+                });
+            } else {
+                body = JSON.stringify(webhookKillPayload(data, guildId, channelId, subscription.id));
+            }
+            await postWebhook(webhook, body);
+        } catch (e) {
+            logger.info(`failed to post kill ${data.killmail_id} to webhook ${redactWebhookUrl(webhook.url)}: ${e}`);
+        }
+    }
+
+    private async process_dm_subscription(subscription: Subscription, data: ZkData, userId: string, context: KillContext) {
+        const evaluation = await this.evaluateSubscription(subscription, data, context);
+        if (evaluation.matched && subscription.dryRun) {
+            await this.reportDryRun(subscription, data, 'direct messages', userId);
+        } else if (evaluation.matched) {
+            logger.info('sending filtered kill as direct message');
+            await this.sendDirectMessage(userId, subscription, data, evaluation);
+        }
+    }
+
+    // Runs the filters of the subscription over the kill without sending anything, the entity lists are the guild's
+    public async evaluateSubscription(
+        subscription: Subscription,
+        data: ZkData,
+        context: KillContext = this.createKillContext(data),
+        guildId?: string,
+    ): Promise<SubscriptionEvaluation> {
         let color: ColorResolvable = 'GREEN';
         let requireSend = false;
         let matchedShip: FilterShipMatch | null = null;
+        const trace: FilterTraceEntry[] = [];
+        const rejected = (rejectedBy: string): SubscriptionEvaluation => ({
+            matched: false,
+            rejectedBy,
+            trace,
+            matchedShip: null,
+            minNumInvolved: null,
+            color,
+            matchedAttackerCount: null,
+        });
 
-        if (subscription.minValue > data.zkb.totalValue) {
-            return; // Do not send if below the min value
+        if (!traceFilter(trace, 'minValue', subscription.minValue <= data.zkb.totalValue,
+            `value ${this.abbreviateNumber(data.zkb.totalValue)}, minimum ${this.abbreviateNumber(subscription.minValue)}`)) {
+            return rejected('minValue'); // Do not send if below the min value
         }
 
         if (subscription.limitTypes.size === 0) {
-            await this.sendMessageToDiscord(guildId, channelId, subscription, data);
-            return;
+            return {matched: true, trace, matchedShip: null, minNumInvolved: null, color: 'GREY', matchedAttackerCount: null};
         }
-        if (hasLimitType(subscription, LimitType.NPC_ONLY) && data.zkb.npc) {
+        const exclusion = await this.checkExclusions(subscription, data, context, trace);
+        if (exclusion.rejectedBy != null) {
+            return rejected(exclusion.rejectedBy);
+        }
+        // The remaining filters see the kill without the excluded attackers
+        data = exclusion.data;
+        if (hasLimitType(subscription, LimitType.NPC_ONLY)) {
             const val = getLimitType(subscription, LimitType.NPC_ONLY) ?? 'false';
-            if (val === 'true') {
-                console.log('limiting kill due to NPC only filter');
-                return;
+            if (!traceFilter(trace, LimitType.NPC_ONLY, !(val === 'true' && data.zkb.npc), `npc kill: ${data.zkb.npc}, filter: ${val}`)) {
+                return rejected(LimitType.NPC_ONLY);
             }
         }
         if (hasLimitType(subscription, LimitType.SHIP_INCLUSION_TYPE_ID)) {
             let nameFragment = '';
-            if (hasLimitType(subscription, LimitType.NAME_FRAGMENT)) {
+            // With pilot, corporation or alliance scopes checkNameFragment looks for the fragment instead
+            if (hasLimitType(subscription, LimitType.NAME_FRAGMENT) && !hasEntityNameScope(getNameFragmentScopes(subscription))) {
                 nameFragment = <string>getLimitType(subscription, LimitType.NAME_FRAGMENT);
             }
+            const permittedGroupIds = await this.getShipInclusionGroupIds(subscription);
             const __ret = await this.sendIfAnyShipsMatchLimitFilter(
                 data,
-                <string>getLimitType(subscription, LimitType.SHIP_INCLUSION_TYPE_ID),
+                permittedGroupIds,
                 nameFragment,
                 subscription.inclusionLimitAlsoComparesAttacker,
                 subscription.inclusionLimitAlsoComparesAttackerWeapons,
+                context,
             );
             requireSend = __ret.requireSend;
             color = __ret.color;
             matchedShip = __ret.matchedShip;
-            if (!requireSend) return;
+            const shipDetail = matchedShip ? `matched ${matchedShip.shipName}` : `no ship in groups ${permittedGroupIds.join(',')}`;
+            if (!traceFilter(trace, LimitType.SHIP_INCLUSION_TYPE_ID, requireSend, shipDetail + (nameFragment ? ` named *${nameFragment}*` : ''))) {
+                return rejected(LimitType.SHIP_INCLUSION_TYPE_ID);
+            }
         }
-        if (!await this.checkSecurityMaxExclusive(subscription, data)) {
-            return;
+        if (!await this.checkNameFragment(subscription, data, context, trace)) {
+            return rejected(LimitType.NAME_FRAGMENT);
         }
-        if (!await this.checkSecurityMinExclusive(subscription, data)) {
-            return;
+        if (!await this.checkSecurityMaxExclusive(subscription, data, context, trace)) {
+            return rejected(LimitType.SECURITY_MAX_EXCLUSIVE);
         }
-        if (!await this.checkSecurityMaxInclusive(subscription, data)) {
-            return;
+        if (!await this.checkSecurityMinExclusive(subscription, data, context, trace)) {
+            return rejected(LimitType.SECURITY_MIN_EXCLUSIVE);
         }
-        if (!await this.checkSecurityMinInclusive(subscription, data)) {
-            return;
+        if (!await this.checkSecurityMaxInclusive(subscription, data, context, trace)) {
+            return rejected(LimitType.SECURITY_MAX_INCLUSIVE);
+        }
+        if (!await this.checkSecurityMinInclusive(subscription, data, context, trace)) {
+            return rejected(LimitType.SECURITY_MIN_INCLUSIVE);
         }
+        if (!await this.checkSpaceType(subscription, data, context, trace)) {
+            return rejected(LimitType.SPACE_TYPE);
+        }
+        if (!this.checkAttackerAvgSecStatus(subscription, data, trace)) {
+            return rejected(LimitType.ATTACKER_AVG_SEC_STATUS_MAX);
+        }
+        if (!this.checkAttackerSecStatus(subscription, data, trace)) {
+            return rejected(LimitType.ATTACKER_SEC_STATUS);
+        }
+        if (!this.checkJumpsFromRegionBorder(subscription, data, trace)) {
+            return rejected(LimitType.JUMPS_FROM_REGION_BORDER);
+        }
+        if (!await this.checkVictimCategory(subscription, data, context, trace)) {
+            return rejected(LimitType.VICTIM_CATEGORY);
+        }
+        if (!await this.checkVictimTechLevel(subscription, data, trace)) {
+            return rejected(LimitType.VICTIM_TECH_LEVEL);
+        }
+        if (!await this.checkNearCelestial(subscription, data, context, trace)) {
+            return rejected(LimitType.NEAR_CELESTIAL);
+        }
+        if (!this.checkHullValue(subscription, data, trace)) {
+            return rejected(LimitType.HULL_VALUE);
+        }
+        if (!await this.checkVictimCorpSize(subscription, data, trace)) {
+            return rejected(LimitType.VICTIM_CORP_SIZE);
+        }
+        if (!await this.checkNpcSpawn(subscription, data, context, trace)) {
+            return rejected(LimitType.NPC_SPAWN);
+        }
+        if (!this.checkGangSize(subscription, data, trace)) {
+            return rejected(LimitType.GANG_SIZE);
+        }
+        const entityLists = guildId ? this.getEntityLists(guildId) : {};
+        if (!this.checkEntityListInclude(subscription, data, entityLists, trace)) {
+            return rejected(LimitType.ENTITY_LIST_INCLUDE);
+        }
+        if (!this.checkEntityListExclude(subscription, data, entityLists, trace)) {
+            return rejected(LimitType.ENTITY_LIST_EXCLUDE);
+        }
+        const ownEntityIds = guildId ? this.subscriptions.get(guildId)?.ownEntityIds ?? [] : [];
+        if (!this.checkPerspective(subscription, data, ownEntityIds, trace)) {
+            return rejected(LimitType.PERSPECTIVE);
+        }
+        if (!this.checkExchangeOutcome(subscription, ownEntityIds, trace)) {
+            return rejected(LimitType.EXCHANGE_OUTCOME);
+        }
+        let matchedAttackerCount: number | null = null;
+        const minEntityAttackers = hasLimitType(subscription, LimitType.MIN_ENTITY_ATTACKERS) ?
+            Number(<string>getLimitType(subscription, LimitType.MIN_ENTITY_ATTACKERS)) : 1;
+        // A matched ship makes the entity filters optional, unless a minimum of matching attackers is set
+        let entityMatched = false;
+        const countAttackers = () => hasLimitType(subscription, LimitType.MIN_ENTITY_ATTACKERS) && !entityMatched;
         if (hasLimitType(subscription, LimitType.CHARACTER)) {
             const characterIdsStr = <string>getLimitType(subscription, LimitType.CHARACTER);
 
@@ -396,72 +1464,130 @@ export class ZKillSubscriber {
                 }
             } else {
                 // just match based on matching character_id
+                let victimMatched = false;
                 for (const characterId of characterIdsStr.split(',')) {
                     if (data.victim.character_id === Number(characterId)) {
-                        requireSend = true;
+                        victimMatched = true;
                         color = 'RED';
                     }
-                    if (!requireSend) {
-                        for (const attacker of data.attackers) {
-                            if (attacker.character_id === Number(characterId)) {
-                                requireSend = true;
-                                break;
-                            }
-                        }
-                    }
                 }
+                if (victimMatched) {
+                    requireSend = true;
+                } else if (countAttackers()) {
+                    matchedAttackerCount = countMatchingAttackers(data.attackers, attacker => attacker.character_id, characterIdsStr.split(','));
+                    requireSend = matchedAttackerCount >= minEntityAttackers;
+                } else if (!requireSend) {
+                    requireSend = data.attackers.some(attacker => attacker.character_id != null && characterIdsStr.split(',').includes(attacker.character_id.toString()));
+                }
+            }
+            if (!traceFilter(trace, LimitType.CHARACTER, requireSend, matchedAttackerCount != null ?
+                `${matchedAttackerCount} matching attackers, minimum ${minEntityAttackers}` : `victim character matched: ${requireSend}`)) {
+                return rejected(LimitType.CHARACTER);
             }
-            if (!requireSend) return;
+            entityMatched = true;
         }
         if (hasLimitType(subscription, LimitType.CORPORATION)) {
             const corporationIds = <string>getLimitType(subscription, LimitType.CORPORATION);
+            let victimMatched = false;
             for (const corporationId of corporationIds.split(',')) {
                 if (data.victim.corporation_id === Number(corporationId)) {
-                    requireSend = true;
+                    victimMatched = true;
                     color = 'RED';
                 }
-                if (!requireSend) {
-                    for (const attacker of data.attackers) {
-                        if (attacker.corporation_id === Number(corporationId)) {
-                            requireSend = true;
-                            break;
-                        }
-                    }
-                }
             }
-            if (!requireSend) return;
+            if (victimMatched) {
+                requireSend = true;
+            } else if (countAttackers()) {
+                matchedAttackerCount = countMatchingAttackers(data.attackers, attacker => attacker.corporation_id, corporationIds.split(','));
+                requireSend = matchedAttackerCount >= minEntityAttackers;
+            } else if (!requireSend) {
+                requireSend = data.attackers.some(attacker => attacker.corporation_id != null && corporationIds.split(',').includes(attacker.corporation_id.toString()));
+            }
+            if (!traceFilter(trace, LimitType.CORPORATION, requireSend, matchedAttackerCount != null ?
+                `${matchedAttackerCount} matching attackers, minimum ${minEntityAttackers}` : `victim corporation matched: ${requireSend}`)) {
+                return rejected(LimitType.CORPORATION);
+            }
+            entityMatched = true;
         }
         if (hasLimitType(subscription, LimitType.ALLIANCE)) {
             const allianceIds = <string>getLimitType(subscription, LimitType.ALLIANCE);
+            let victimMatched = false;
             for (const allianceId of allianceIds.split(',')) {
                 if (data.victim.alliance_id === Number(allianceId)) {
-                    requireSend = true;
+                    victimMatched = true;
                     color = 'RED';
                 }
-                if (!requireSend) {
-                    for (const attacker of data.attackers) {
-                        if (attacker.alliance_id === Number(allianceId)) {
-                            requireSend = true;
-                            break;
-                        }
-                    }
-                }
             }
-            if (!requireSend) return;
+            if (victimMatched) {
+                requireSend = true;
+            } else if (countAttackers()) {
+                matchedAttackerCount = countMatchingAttackers(data.attackers, attacker => attacker.alliance_id, allianceIds.split(','));
+                requireSend = matchedAttackerCount >= minEntityAttackers;
+            } else if (!requireSend) {
+                requireSend = data.attackers.some(attacker => attacker.alliance_id != null && allianceIds.split(',').includes(attacker.alliance_id.toString()));
+            }
+            if (!traceFilter(trace, LimitType.ALLIANCE, requireSend, matchedAttackerCount != null ?
+                `${matchedAttackerCount} matching attackers, minimum ${minEntityAttackers}` : `victim alliance matched: ${requireSend}`)) {
+                return rejected(LimitType.ALLIANCE);
+            }
+            entityMatched = true;
+        }
+        if (hasLimitType(subscription, LimitType.FACTION)) {
+            const factionIds = (<string>getLimitType(subscription, LimitType.FACTION)).split(',');
+            const victimMatches = data.victim.faction_id != null && factionIds.includes(data.victim.faction_id.toString());
+            if (victimMatches) {
+                color = 'RED';
+                requireSend = true;
+            } else if (countAttackers()) {
+                matchedAttackerCount = countMatchingAttackers(data.attackers, attacker => attacker.faction_id, factionIds);
+                requireSend = matchedAttackerCount >= minEntityAttackers;
+            } else if (!requireSend) {
+                requireSend = data.attackers.some(attacker => attacker.faction_id != null && factionIds.includes(attacker.faction_id.toString()));
+            }
+            if (!traceFilter(trace, LimitType.FACTION, requireSend, matchedAttackerCount != null ?
+                `${matchedAttackerCount} matching attackers, minimum ${minEntityAttackers}` : `victim faction ${data.victim.faction_id}, matched: ${requireSend}, expected ${factionIds}`)) {
+                return rejected(LimitType.FACTION);
+            }
+            entityMatched = true;
+        }
+        if (hasLimitType(subscription, LimitType.VICTIM_ENTITY)) {
+            const entityIds = (<string>getLimitType(subscription, LimitType.VICTIM_ENTITY)).split(',').map(id => id.trim());
+            const victimIds = [data.victim.character_id, data.victim.corporation_id, data.victim.alliance_id];
+            requireSend = victimIds.some(id => id != null && entityIds.includes(id.toString()));
+            color = 'RED';
+            if (!traceFilter(trace, LimitType.VICTIM_ENTITY, requireSend, `victim ${victimIds.join(',')}, expected ${entityIds}`)) {
+                return rejected(LimitType.VICTIM_ENTITY);
+            }
         }
         if (hasLimitType(subscription, LimitType.REGION) ||
             hasLimitType(subscription, LimitType.CONSTELLATION) ||
             hasLimitType(subscription, LimitType.SYSTEM)) {
-            requireSend = await this.isInLocationLimit(subscription, data.solar_system_id);
-            if (!requireSend) return;
+            requireSend = await this.isInLocationLimit(subscription, context);
+            const systemData = await context.system();
+            if (!traceFilter(trace, 'location', requireSend,
+                `${systemData.systemName} (${systemData.id}), ${systemData.constellationName} (${systemData.constellationId}), ${systemData.regionName} (${systemData.regionId})`)) {
+                return rejected('location');
+            }
+        }
+        if (hasLimitType(subscription, LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME)) {
+            requireSend = await this.checkLyRange(subscription, data, context, trace);
+            if (!requireSend) {
+                return rejected(LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME);
+            }
+        }
+        if (hasLimitType(subscription, LimitType.JUMPS_FROM_SYSTEM)) {
+            // Like the location filters, a kill within range is sent without an entity or ship filter
+            requireSend = this.checkJumpsFromSystem(subscription, data, trace);
+            if (!requireSend) {
+                return rejected(LimitType.JUMPS_FROM_SYSTEM);
+            }
         }
         let minNumInvolved: number | null = null;
         if (hasLimitType(subscription, LimitType.MIN_NUM_INVOLVED)) {
             minNumInvolved = Number(<string>getLimitType(subscription, LimitType.MIN_NUM_INVOLVED));
             const numInvolved = data.attackers.length + 1;
-            if (numInvolved < minNumInvolved) {
-                console.log(`limiting kill due to minimum number of involved parties filter: ${numInvolved} < ${minNumInvolved}`);
-                return;
+            if (!traceFilter(trace, LimitType.MIN_NUM_INVOLVED, numInvolved >= minNumInvolved, `${numInvolved} involved, minimum ${minNumInvolved}`)) {
+                return rejected(LimitType.MIN_NUM_INVOLVED);
             }
         }
         if (hasLimitType(subscription, LimitType.TIME_RANGE_START) && hasLimitType(subscription, LimitType.TIME_RANGE_END)) {
@@ -470,92 +1596,561 @@ export class ZKillSubscriber {
             const killmailTime = new Date(data.killmail_time);
             const killmailHour = killmailTime.getUTCHours();
 
-            if (startTime < endTime) {
-                if (killmailHour < startTime || killmailHour > endTime) {
-                    console.log(`limiting kill due to time range filter: ${killmailHour} not in range ${startTime} - ${endTime}`);
-                    return;
-                }
-            } else {
-                if (killmailHour < startTime && killmailHour > endTime) {
-                    console.log(`limiting kill due to time range filter: ${killmailHour} not in range ${startTime} - ${endTime}`);
-                    return;
-                }
+            const inRange = startTime < endTime ?
+                killmailHour >= startTime && killmailHour <= endTime :
+                killmailHour >= startTime || killmailHour <= endTime;
+            if (!traceFilter(trace, 'timeRange', inRange, `hour ${killmailHour}, range ${startTime} - ${endTime}`)) {
+                return rejected('timeRange');
             }
         }
-        if (requireSend) {
-            console.log('sending filtered kill');
-            await this.sendMessageToDiscord(
-                guildId,
-                channelId,
-                subscription,
-                data,
-                matchedShip,
-                minNumInvolved,
-                color
-            );
+        if (!hasSelectingLimitType(subscription)) {
+            return {matched: true, trace, matchedShip, minNumInvolved, color: 'GREY', matchedAttackerCount};
         }
+        if (!requireSend) {
+            return rejected('no ship, entity or location filter matched');
+        }
+        return {matched: true, trace, matchedShip, minNumInvolved, color, matchedAttackerCount};
     }
 
-    public async checkSecurityMaxInclusive(subscription: Subscription, data: ZkData): Promise<boolean> {
+    public async checkSecurityMaxInclusive(
+        subscription: Subscription,
+        data: ZkData,
+        context: KillContext = this.createKillContext(data),
+        trace?: FilterTraceEntry[],
+    ): Promise<boolean> {
         if (hasLimitType(subscription, LimitType.SECURITY_MAX_INCLUSIVE)) {
-            const systemData = await this.getSystemData(data.solar_system_id);
+            const systemData = await context.system();
             const maximumSecurityStatus = Number(<string>getLimitType(subscription, LimitType.SECURITY_MAX_INCLUSIVE));
-            if (maximumSecurityStatus < systemData.securityStatus) {
-                console.log(`limiting kill in ${systemData.systemName} due to inclusive maximum security status filter: ${systemData.securityStatus} > ${maximumSecurityStatus}`);
-                return false;
-            }
+            return traceFilter(trace, LimitType.SECURITY_MAX_INCLUSIVE, !(maximumSecurityStatus < systemData.securityStatus),
+                `${systemData.systemName} security status ${systemData.securityStatus}, maximum ${maximumSecurityStatus}`);
         }
         return true;
     }
 
-    public async checkSecurityMaxExclusive(subscription: Subscription, data: ZkData): Promise<boolean> {
+    public async checkSecurityMaxExclusive(
+        subscription: Subscription,
+        data: ZkData,
+        context: KillContext = this.createKillContext(data),
+        trace?: FilterTraceEntry[],
+    ): Promise<boolean> {
         if (hasLimitType(subscription, LimitType.SECURITY_MAX_EXCLUSIVE)) {
-            const systemData = await this.getSystemData(data.solar_system_id);
+            const systemData = await context.system();
             const maximumSecurityStatus = Number(<string>getLimitType(subscription, LimitType.SECURITY_MAX_EXCLUSIVE));
-            if (maximumSecurityStatus <= systemData.securityStatus) {
-                console.log(`limiting kill in ${systemData.systemName} due to exclusive maximum security status filter: ${systemData.securityStatus} >= ${maximumSecurityStatus}`);
-                return false;
-            }
+            return traceFilter(trace, LimitType.SECURITY_MAX_EXCLUSIVE, !(maximumSecurityStatus <= systemData.securityStatus),
+                `${systemData.systemName} security status ${systemData.securityStatus}, maximum ${maximumSecurityStatus}`);
         }
         return true;
     }
 
-    public async checkSecurityMinInclusive(subscription: Subscription, data: ZkData): Promise<boolean> {
+    public async checkSecurityMinInclusive(
+        subscription: Subscription,
+        data: ZkData,
+        context: KillContext = this.createKillContext(data),
+        trace?: FilterTraceEntry[],
+    ): Promise<boolean> {
         if (hasLimitType(subscription, LimitType.SECURITY_MIN_INCLUSIVE)) {
-            const systemData = await this.getSystemData(data.solar_system_id);
+            const systemData = await context.system();
             const minimumSecurityStatus = Number(<string>getLimitType(subscription, LimitType.SECURITY_MIN_INCLUSIVE));
-            if (minimumSecurityStatus > systemData.securityStatus) {
-                console.log(`limiting kill in ${systemData.systemName} due to inclusive minimum security status filter: ${systemData.securityStatus} < ${minimumSecurityStatus}`);
-                return false;
-            }
+            return traceFilter(trace, LimitType.SECURITY_MIN_INCLUSIVE, !(minimumSecurityStatus > systemData.securityStatus),
+                `${systemData.systemName} security status ${systemData.securityStatus}, minimum ${minimumSecurityStatus}`);
         }
         return true;
     }
 
-    public async checkSecurityMinExclusive(subscription: Subscription, data: ZkData): Promise<boolean> {
+    public async checkSecurityMinExclusive(
+        subscription: Subscription,
+        data: ZkData,
+        context: KillContext = this.createKillContext(data),
+        trace?: FilterTraceEntry[],
+    ): Promise<boolean> {
         if (hasLimitType(subscription, LimitType.SECURITY_MIN_EXCLUSIVE)) {
-            const systemData = await this.getSystemData(data.solar_system_id);
+            const systemData = await context.system();
             const minimumSecurityStatus = Number(<string>getLimitType(subscription, LimitType.SECURITY_MIN_EXCLUSIVE));
-            if (minimumSecurityStatus >= systemData.securityStatus) {
-                console.log(`limiting kill in ${systemData.systemName} due to exclusive minimum security status filter: ${systemData.securityStatus} <= ${minimumSecurityStatus}`);
-                return false;
+            return traceFilter(trace, LimitType.SECURITY_MIN_EXCLUSIVE, !(minimumSecurityStatus >= systemData.securityStatus),
+                `${systemData.systemName} security status ${systemData.securityStatus}, minimum ${minimumSecurityStatus}`);
+        }
+        return true;
+    }
+
+    public async checkSpaceType(
+        subscription: Subscription,
+        data: ZkData,
+        context: KillContext = this.createKillContext(data),
+        trace?: FilterTraceEntry[],
+    ): Promise<boolean> {
+        if (hasLimitType(subscription, LimitType.SPACE_TYPE)) {
+            const systemData = await context.system();
+            const spaceTypes = getLimitType(subscription, LimitType.SPACE_TYPE)?.split(',') || [];
+            const spaceType = getSpaceType(systemData);
+            return traceFilter(trace, LimitType.SPACE_TYPE, spaceTypes.indexOf(spaceType) !== -1,
+                `${systemData.systemName} is ${spaceType}, expected ${spaceTypes}`);
+        }
+        return true;
+    }
+
+    public checkAttackerAvgSecStatus(subscription: Subscription, data: ZkData, trace?: FilterTraceEntry[]): boolean {
+        if (hasLimitType(subscription, LimitType.ATTACKER_AVG_SEC_STATUS_MAX)) {
+            const maximumAverage = Number(<string>getLimitType(subscription, LimitType.ATTACKER_AVG_SEC_STATUS_MAX));
+            const average = getAttackerAverageSecurityStatus(data.attackers);
+            return traceFilter(trace, LimitType.ATTACKER_AVG_SEC_STATUS_MAX, average != null && average <= maximumAverage,
+                `average ${average}, maximum ${maximumAverage}`);
+        }
+        return true;
+    }
+
+    // Unlike the average, a single outlaw among the attackers is enough, e.g. for gate camps in lowsec
+    public checkAttackerSecStatus(subscription: Subscription, data: ZkData, trace?: FilterTraceEntry[]): boolean {
+        if (hasLimitType(subscription, LimitType.ATTACKER_SEC_STATUS)) {
+            const value = <string>getLimitType(subscription, LimitType.ATTACKER_SEC_STATUS);
+            const range = parseSecStatusRange(value);
+            if (range == null) {
+                return traceFilter(trace, LimitType.ATTACKER_SEC_STATUS, false, `invalid range ${value}`);
+            }
+            const pilots = pilotsInSecStatusRange(data.attackers, range);
+            return traceFilter(trace, LimitType.ATTACKER_SEC_STATUS, pilots.length > 0,
+                `${pilots.length} attacking pilots with a security status from ${range.min ?? -10} to ${range.max ?? 10}`);
+        }
+        return true;
+    }
+
+    public async checkVictimCategory(
+        subscription: Subscription,
+        data: ZkData,
+        context: KillContext = this.createKillContext(data),
+        trace?: FilterTraceEntry[],
+    ): Promise<boolean> {
+        if (hasLimitType(subscription, LimitType.VICTIM_CATEGORY)) {
+            const categories = getLimitType(subscription, LimitType.VICTIM_CATEGORY)?.split(',') || [];
+            const category = await this.getShipCategoryForTypeId(data.victim.ship_type_id, context);
+            return traceFilter(trace, LimitType.VICTIM_CATEGORY, category != null && categories.indexOf(category) !== -1,
+                `victim is ${category}, expected ${categories}`);
+        }
+        return true;
+    }
+
+    // Tells faction, officer or T2 losses apart from T1 hulls of the same value
+    public async checkVictimTechLevel(subscription: Subscription, data: ZkData, trace?: FilterTraceEntry[]): Promise<boolean> {
+        if (hasLimitType(subscription, LimitType.VICTIM_TECH_LEVEL)) {
+            const value = <string>getLimitType(subscription, LimitType.VICTIM_TECH_LEVEL);
+            const levels = parseTechLevels(value);
+            if (levels == null) {
+                return traceFilter(trace, LimitType.VICTIM_TECH_LEVEL, false, `invalid tech levels ${value}`);
+            }
+            let level: TechLevel;
+            try {
+                level = techLevelOf(await this.getMetaGroupIdForTypeId(data.victim.ship_type_id));
+            } catch (e) {
+                return traceFilter(trace, LimitType.VICTIM_TECH_LEVEL, false, `failed to get the meta group: ${e}`);
+            }
+            return traceFilter(trace, LimitType.VICTIM_TECH_LEVEL, levels.includes(level), `victim ship is ${level}, expected ${levels}`);
+        }
+        return true;
+    }
+
+    // Value format `kinds:maxDistanceKm`, e.g. `stargate,station:100`
+    public async checkNearCelestial(
+        subscription: Subscription,
+        data: ZkData,
+        context: KillContext = this.createKillContext(data),
+        trace?: FilterTraceEntry[],
+    ): Promise<boolean> {
+        if (hasLimitType(subscription, LimitType.NEAR_CELESTIAL)) {
+            const [kindsStr, maxDistanceStr] = (<string>getLimitType(subscription, LimitType.NEAR_CELESTIAL)).split(':');
+            const kinds = kindsStr.split(',');
+            const maxDistanceKm = Number(maxDistanceStr);
+            let celestial: ClosestCelestial;
+            try {
+                celestial = await context.celestial();
+            } catch (e) {
+                return traceFilter(trace, LimitType.NEAR_CELESTIAL, false, `failed to get the nearest celestial: ${e}`);
+            }
+            const kind = getCelestialKind(await context.groupId(celestial.typeId));
+            const distanceKm = celestial.distance / 1000;
+            return traceFilter(trace, LimitType.NEAR_CELESTIAL, kind != null && kinds.indexOf(kind) !== -1 && distanceKm <= maxDistanceKm,
+                `${kind} ${celestial.itemName} ${Math.round(distanceKm)} km away, expected ${kinds} within ${maxDistanceKm} km`);
+        }
+        return true;
+    }
+
+    // Compares the current market price of the victim's hull, independent of the fit and cargo zKillboard includes
+    public checkHullValue(subscription: Subscription, data: ZkData, trace?: FilterTraceEntry[]): boolean {
+        if (hasLimitType(subscription, LimitType.HULL_VALUE)) {
+            const range = parseValueRange(<string>getLimitType(subscription, LimitType.HULL_VALUE));
+            const hullValue = this.marketPrices.price(data.victim.ship_type_id);
+            if (range == null || hullValue == null) {
+                return traceFilter(trace, LimitType.HULL_VALUE, false, `no market price for ${data.victim.ship_type_id}`);
+            }
+            return traceFilter(trace, LimitType.HULL_VALUE, isInValueRange(range, hullValue),
+                `hull worth ${this.abbreviateNumber(hullValue)}, expected ${range.min ?? 0} to ${range.max ?? 'any'}`);
+        }
+        return true;
+    }
+
+    public async checkVictimCorpSize(subscription: Subscription, data: ZkData, trace?: FilterTraceEntry[]): Promise<boolean> {
+        if (hasLimitType(subscription, LimitType.VICTIM_CORP_SIZE)) {
+            const value = <string>getLimitType(subscription, LimitType.VICTIM_CORP_SIZE);
+            const range = parseValueRange(value);
+            if (range == null || data.victim.corporation_id == null) {
+                return traceFilter(trace, LimitType.VICTIM_CORP_SIZE, false, `victim corporation ${data.victim.corporation_id}, expected ${value}`);
+            }
+            let members: number;
+            try {
+                members = await this.getCorporationMemberCount(data.victim.corporation_id);
+            } catch (e) {
+                return traceFilter(trace, LimitType.VICTIM_CORP_SIZE, false, `failed to get the member count: ${e}`);
+            }
+            return traceFilter(trace, LimitType.VICTIM_CORP_SIZE, isInValueRange(range, members),
+                `${members} members, expected ${range.min ?? 0} to ${range.max ?? 'any'}`);
+        }
+        return true;
+    }
+
+    public checkGangSize(subscription: Subscription, data: ZkData, trace?: FilterTraceEntry[]): boolean {
+        if (hasLimitType(subscription, LimitType.GANG_SIZE)) {
+            const value = <string>getLimitType(subscription, LimitType.GANG_SIZE);
+            const pilots = attackingPilotCount(data);
+            const gangSize = classifyGangSize(pilots);
+            const classes = parseGangSizeClasses(value) || [];
+            return traceFilter(trace, LimitType.GANG_SIZE, classes.includes(gangSize), `${pilots} attackers (${gangSize}), expected ${value}`);
+        }
+        return true;
+    }
+
+    // Only NPC kills match, with an attacking NPC of the given groups
+    public async checkNpcSpawn(
+        subscription: Subscription,
+        data: ZkData,
+        context: KillContext = this.createKillContext(data),
+        trace?: FilterTraceEntry[],
+    ): Promise<boolean> {
+        if (hasLimitType(subscription, LimitType.NPC_SPAWN)) {
+            const value = <string>getLimitType(subscription, LimitType.NPC_SPAWN);
+            const filter = parseNpcSpawnFilter(value);
+            if (filter == null || !data.zkb.npc) {
+                return traceFilter(trace, LimitType.NPC_SPAWN, false, `npc kill: ${data.zkb.npc}, filter: ${value}`);
+            }
+            const groups: string[] = [];
+            for (const attacker of data.attackers) {
+                if (attacker.character_id != null || attacker.ship_type_id == null) {
+                    continue;
+                }
+                try {
+                    const groupId = await context.groupId(attacker.ship_type_id);
+                    const groupName = await this.getGroupName(groupId);
+                    if (matchesNpcSpawn(filter, groupId, groupName)) {
+                        return traceFilter(trace, LimitType.NPC_SPAWN, true, `attacked by ${groupName} (${groupId})`);
+                    }
+                    groups.push(groupName);
+                } catch (e) {
+                    logger.info(e);
+                }
+            }
+            return traceFilter(trace, LimitType.NPC_SPAWN, false, `attacking NPC groups ${groups.join(', ')}, expected ${value}`);
+        }
+        return true;
+    }
+
+    // A list missing from the guild is empty, so nothing is on it
+    public checkEntityListInclude(subscription: Subscription, data: ZkData, entityLists: EntityLists, trace?: FilterTraceEntry[]): boolean {
+        if (hasLimitType(subscription, LimitType.ENTITY_LIST_INCLUDE)) {
+            const names = <string>getLimitType(subscription, LimitType.ENTITY_LIST_INCLUDE);
+            const onList = isOnEntityList(mergeEntityLists(entityLists, names.split(',')), data);
+            return traceFilter(trace, LimitType.ENTITY_LIST_INCLUDE, onList, `victim or attacker on lists ${names}: ${onList}`);
+        }
+        return true;
+    }
+
+    public async checkExclusions(subscription: Subscription, data: ZkData, context: KillContext, trace?: FilterTraceEntry[]): Promise<ExclusionResult> {
+        const ids = (limitType: LimitType) => (getLimitType(subscription, limitType) ?? '').split(',').filter(id => id !== '').map(Number);
+        const exclusions: EntityExclusions = {
+            allianceIds: ids(LimitType.EXCLUDED_ALLIANCE),
+            corporationIds: ids(LimitType.EXCLUDED_CORPORATION),
+            shipGroupIds: ids(LimitType.EXCLUDED_SHIP_GROUP),
+        };
+        if (exclusions.allianceIds.length + exclusions.corporationIds.length + exclusions.shipGroupIds.length === 0) {
+            return {data, removedAttackers: 0, rejectedBy: null};
+        }
+        // The ship groups are only looked up if they are excluded
+        const groupId = (typeId?: number) => typeId != null && exclusions.shipGroupIds.length > 0 ?
+            context.groupId(typeId).catch(() => undefined) : Promise.resolve(undefined);
+        const result = applyExclusions(
+            data,
+            exclusions,
+            await groupId(data.victim.ship_type_id),
+            await Promise.all(data.attackers.map(attacker => groupId(attacker.ship_type_id))),
+        );
+        if (result.rejectedBy != null) {
+            traceFilter(trace, result.rejectedBy, false,
+                result.removedAttackers > 0 ? `all ${result.removedAttackers} attackers excluded` : 'victim excluded');
+        } else if (result.removedAttackers > 0) {
+            traceFilter(trace, 'exclusions', true, `${result.removedAttackers} attackers ignored`);
+        }
+        return result;
+    }
+
+    // Only with pilot, corporation or alliance scopes, the ship type filter checks the fragment otherwise
+    public async checkNameFragment(subscription: Subscription, data: ZkData, context: KillContext, trace?: FilterTraceEntry[]): Promise<boolean> {
+        const scopes = getNameFragmentScopes(subscription);
+        if (!hasLimitType(subscription, LimitType.NAME_FRAGMENT) || !hasEntityNameScope(scopes)) {
+            return true;
+        }
+        const fragment = <string>getLimitType(subscription, LimitType.NAME_FRAGMENT);
+        // The victim first, then the attackers, stopping at the first match to save name lookups
+        for (const entity of [data.victim, ...data.attackers]) {
+            for (const scope of scopes) {
+                const name = await this.getNameInScope(scope, entity, context).catch(() => null);
+                if (name != null && containsNameFragment(name, fragment)) {
+                    return traceFilter(trace, LimitType.NAME_FRAGMENT, true, `${scope} ${name} contains *${fragment}*`);
+                }
+            }
+        }
+        return traceFilter(trace, LimitType.NAME_FRAGMENT, false, `no ${scopes.join(', ')} name contains *${fragment}*`);
+    }
+
+    private async getNameInScope(
+        scope: NameScope,
+        entity: { ship_type_id?: number, character_id?: number, corporation_id?: number | null, alliance_id?: number | null },
+        context: KillContext,
+    ): Promise<string | null> {
+        if (scope === NameScope.SHIP) {
+            return entity.ship_type_id != null ? context.name(entity.ship_type_id) : null;
+        } else if (scope === NameScope.PILOT) {
+            return entity.character_id != null ? this.getNameForCharacter(entity.character_id) : null;
+        } else if (scope === NameScope.CORPORATION) {
+            return entity.corporation_id != null ? this.getNameForCorporation(entity.corporation_id) : null;
+        }
+        return entity.alliance_id != null ? this.getNameForAlliance(entity.alliance_id) : null;
+    }
+
+    public checkPerspective(subscription: Subscription, data: ZkData, ownEntityIds: number[], trace?: FilterTraceEntry[]): boolean {
+        if (hasLimitType(subscription, LimitType.PERSPECTIVE)) {
+            const perspective = parsePerspective(<string>getLimitType(subscription, LimitType.PERSPECTIVE));
+            if (perspective == null || ownEntityIds.length === 0) {
+                return traceFilter(trace, LimitType.PERSPECTIVE, false, `perspective ${perspective}, ${ownEntityIds.length} own entities set`);
+            }
+            return traceFilter(trace, LimitType.PERSPECTIVE, matchesPerspective(perspective, ownEntityIds, data),
+                `victim ${data.victim.character_id}/${data.victim.corporation_id}/${data.victim.alliance_id}, expected ${perspective} of the own entities`);
+        }
+        return true;
+    }
+
+    // Only checks the filter can be applied, the outcome is checked once the window has passed
+    public checkExchangeOutcome(subscription: Subscription, ownEntityIds: number[], trace?: FilterTraceEntry[]): boolean {
+        if (hasLimitType(subscription, LimitType.EXCHANGE_OUTCOME)) {
+            const value = <string>getLimitType(subscription, LimitType.EXCHANGE_OUTCOME);
+            const filter = parseExchangeOutcome(value);
+            if (filter == null || ownEntityIds.length === 0) {
+                return traceFilter(trace, LimitType.EXCHANGE_OUTCOME, false, `exchange outcome ${value}, ${ownEntityIds.length} own entities set`);
+            }
+            return traceFilter(trace, LimitType.EXCHANGE_OUTCOME, true,
+                `posted ${filter.windowMinutes} minutes after the kill if the own entities ${filter.require} the exchange in the system`);
+        }
+        return true;
+    }
+
+    public checkEntityListExclude(subscription: Subscription, data: ZkData, entityLists: EntityLists, trace?: FilterTraceEntry[]): boolean {
+        if (hasLimitType(subscription, LimitType.ENTITY_LIST_EXCLUDE)) {
+            const names = <string>getLimitType(subscription, LimitType.ENTITY_LIST_EXCLUDE);
+            const onList = isOnEntityList(mergeEntityLists(entityLists, names.split(',')), data);
+            return traceFilter(trace, LimitType.ENTITY_LIST_EXCLUDE, !onList, `victim or attacker on lists ${names}: ${onList}`);
+        }
+        return true;
+    }
+
+    private async getShipCategoryForTypeId(typeId?: number, context?: KillContext): Promise<ShipCategory | null> {
+        if (typeId == null) {
+            return null;
+        }
+        const groupId = context ? await context.groupId(typeId) : await this.getGroupIdForEntityId(typeId);
+        const categoryId = context ? await context.categoryId(groupId) : await this.getCategoryIdForGroupId(groupId);
+        return getShipCategory(groupId, categoryId);
+    }
+
+    public checkJumpsFromRegionBorder(subscription: Subscription, data: ZkData, trace?: FilterTraceEntry[]): boolean {
+        if (hasLimitType(subscription, LimitType.JUMPS_FROM_REGION_BORDER)) {
+            const [regionId, maxJumps] = (<string>getLimitType(subscription, LimitType.JUMPS_FROM_REGION_BORDER)).split(':').map(Number);
+            if (this.starMap == null) {
+                return traceFilter(trace, LimitType.JUMPS_FROM_REGION_BORDER, false, 'no star map loaded');
+            }
+            const jumps = this.starMap.jumpsFromRegionBorder(data.solar_system_id, regionId, maxJumps);
+            return traceFilter(trace, LimitType.JUMPS_FROM_REGION_BORDER, jumps != null,
+                jumps != null ? `${jumps} jumps from the border of ${regionId}` : `more than ${maxJumps} jumps from the border of ${regionId}`);
+        }
+        return true;
+    }
+
+    public checkJumpsFromSystem(subscription: Subscription, data: ZkData, trace?: FilterTraceEntry[]): boolean {
+        if (hasLimitType(subscription, LimitType.JUMPS_FROM_SYSTEM)) {
+            const [systemId, maxJumps] = (<string>getLimitType(subscription, LimitType.JUMPS_FROM_SYSTEM)).split(':').map(Number);
+            if (this.starMap == null) {
+                return traceFilter(trace, LimitType.JUMPS_FROM_SYSTEM, false, 'no star map loaded');
+            }
+            const jumps = this.starMap.jumpsFromSystem(data.solar_system_id, systemId, maxJumps);
+            return traceFilter(trace, LimitType.JUMPS_FROM_SYSTEM, jumps != null,
+                jumps != null ? `${jumps} jumps from ${systemId}` : `more than ${maxJumps} jumps from ${systemId}`);
+        }
+        return true;
+    }
+
+    public async checkLyRange(
+        subscription: Subscription,
+        data: ZkData,
+        context: KillContext = this.createKillContext(data),
+        trace?: FilterTraceEntry[],
+    ): Promise<boolean> {
+        if (hasLimitType(subscription, LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME)) {
+            const value = <string>getLimitType(subscription, LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME);
+            const range = parseLyRange(value, this.jumpDriveCalibrationLevel);
+            if (range == null) {
+                return traceFilter(trace, LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME, false, `invalid range ${value}`);
+            }
+            if (isAbyssalSystemId(data.solar_system_id)) {
+                return traceFilter(trace, LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME, false, 'abyssal pockets have no jump range');
+            }
+            const distance = this.lightYearsFrom(range.systemName, data.solar_system_id);
+            if (distance == null) {
+                return traceFilter(trace, LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME, false, `no star map loaded or unknown system ${range.systemName}`);
             }
+            // Cynosural fields can not be lit in highsec, wormholes, Pochven and abyssal pockets
+            const spaceType = getSpaceType(await context.system());
+            const jumpable = spaceType === SpaceType.LOWSEC || spaceType === SpaceType.NULLSEC;
+            return traceFilter(trace, LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME, jumpable && distance <= range.maxLy,
+                `${distance.toFixed(2)} LY from ${range.systemName} in ${spaceType}, maximum ${range.maxLy.toFixed(2)} LY`);
         }
         return true;
     }
 
+    // Null if no star map is loaded
+    public isKnownSystemName(systemName: string): boolean | null {
+        return this.starMap ? this.starMap.findSystemByName(systemName) != null : null;
+    }
+
+    private lightYearsFrom(systemName: string, systemId: number): number | null {
+        const from = this.starMap?.findSystemByName(systemName);
+        if (this.starMap == null || from == null) {
+            return null;
+        }
+        return this.starMap.lightYearsBetween(from.id, systemId);
+    }
+
+    // Capital classes that can jump to the kill from the system of the subscription's LY range filter
+    private jumpRangeText(subscription: Subscription, system: SolarSystem, locale: Locale): string | null {
+        const range = parseLyRange(getLimitType(subscription, LimitType.LY_RANGE_TO_SYSTEM_WITH_NAME) ?? '', this.jumpDriveCalibrationLevel);
+        if (range == null) {
+            return null;
+        }
+        const distance = this.lightYearsFrom(range.systemName, system.id);
+        const spaceType = getSpaceType(system);
+        if (distance == null || (spaceType !== SpaceType.LOWSEC && spaceType !== SpaceType.NULLSEC)) {
+            return null;
+        }
+        const classes = range.shipClass ? [range.shipClass] : classesInJumpRange(distance, this.jumpDriveCalibrationLevel);
+        if (classes.length === 0) {
+            return null;
+        }
+        return translate(locale, 'embed.jumpRange', {distance: distance.toFixed(1), system: range.systemName, classes: classes.join(', ')});
+    }
+
+    // Tier, quantum core and loot of an Upwell structure victim, null for any other victim
+    public structureDetails(data: ZkData, locale: Locale, guildId?: string): string | null {
+        const structure = classifyStructure(data.victim.ship_type_id);
+        if (structure == null) {
+            return null;
+        }
+        const kind = translate(locale, `structure.kind.${structure.kind}`);
+        let details = structure.size != null ? `${structure.size} ${kind}\n` : `${kind}\n`;
+        if (structure.coreTypeId != null) {
+            details += translate(locale, `embed.structure.core.${quantumCoreStatus(structure, data.victim.items ?? [])}`) + '\n';
+        }
+        details += translate(locale, 'embed.structure.loot', {value: this.formatValue(guildId, data.zkb.droppedValue)});
+        return details;
+    }
+
+    // EFT block of the victim's fit, null if nothing was fitted
+    private async fittingText(data: ZkData, shipName: string): Promise<string | null> {
+        const items = (data.victim.items ?? []).filter(item => getFittingSlot(item.flag) != null);
+        if (items.length === 0) {
+            return null;
+        }
+        const names = new Map<number, string>();
+        const charges = new Set<number>();
+        for (const typeId of Array.from(new Set(items.map(item => item.item_type_id)))) {
+            names.set(typeId, await this.getNameForEntityId(typeId));
+            if (await this.getCategoryIdForGroupId(await this.getGroupIdForEntityId(typeId)) === CHARGE_CATEGORY_ID) {
+                charges.add(typeId);
+            }
+        }
+        // Embed field values are limited to 1024 characters, including the code block
+        const eft = formatEft(shipName, items, names, charges);
+        return '```\n' + (eft.length > 1010 ? eft.substring(0, 1007) + '...' : eft) + '\n```';
+    }
+
+    private async getShipInclusionGroupIds(subscription: Subscription): Promise<number[]> {
+        const typeIds = <string>getLimitType(subscription, LimitType.SHIP_INCLUSION_TYPE_ID);
+        const resolved = subscription.shipInclusionGroups;
+        if (resolved && resolved.typeIds === typeIds) {
+            return resolved.groupIds.split(',').map(Number);
+        }
+        return await this.resolveShipGroupIds(typeIds);
+    }
+
+    private async resolveShipGroupIds(typeIds: string): Promise<number[]> {
+        const groupIds: number[] = [];
+        for (const typeId of typeIds.split(',')) {
+            const groupId = await this.getGroupIdForEntityId(Number(typeId));
+            if (groupIds.indexOf(groupId) === -1) {
+                groupIds.push(groupId);
+            }
+        }
+        return groupIds;
+    }
+
+    // Resolves and stores the group IDs of the ship inclusion filter, if they are missing or outdated
+    public async resolveSubscriptionShipGroups(guildId: string, subscription: Subscription) {
+        if (!hasLimitType(subscription, LimitType.SHIP_INCLUSION_TYPE_ID)) {
+            return;
+        }
+        const typeIds = <string>getLimitType(subscription, LimitType.SHIP_INCLUSION_TYPE_ID);
+        if (subscription.shipInclusionGroups?.typeIds === typeIds) {
+            return;
+        }
+        const groupIds = await this.resolveShipGroupIds(typeIds);
+        subscription.shipInclusionGroups = {typeIds, groupIds: groupIds.join(',')};
+        this.saveGuild(guildId);
+    }
+
+    // Re-resolves the ship groups of all subscriptions, e.g. after the ships cache was rebuilt
+    public async resolveAllShipGroups(force = false) {
+        const subscriptions: [string, Subscription][] = [];
+        this.subscriptions.forEach((guild, guildId) => {
+            guild.channels.forEach((channel) => {
+                channel.subscriptions.forEach((subscription) => subscriptions.push([guildId, subscription]));
+            });
+        });
+        for (const [guildId, subscription] of subscriptions) {
+            if (force) {
+                subscription.shipInclusionGroups = undefined;
+            }
+            try {
+                await this.resolveSubscriptionShipGroups(guildId, subscription);
+            } catch (e) {
+                logger.info(`failed to resolve ship groups for subscription ${subscription.id}: ${e}`);
+            }
+        }
+    }
+
     private async sendIfAnyShipsMatchLimitFilter(
         data: ZkData,
-        limitIds: string,
+        permittedGroupIds: number[],
         nameFragment: string,
         alsoCompareAttackers: boolean,
-        alsoCompareAttackerWeapons: boolean
+        alsoCompareAttackerWeapons: boolean,
+        context: KillContext,
     ) {
-        const limitGroupOfShipIds = limitIds?.split(',') || [];
         const shouldCheckNameFragment = nameFragment != null && nameFragment != '';
         const shipTypeId = data.victim.ship_type_id;
         if (shipTypeId == null) {
-            console.log('WARNING: shipTypeId is null');
+            logger.info('WARNING: shipTypeId is null');
             return {
                 requireSend: false,
                 color: <ColorResolvable>'GREEN',
@@ -563,16 +2158,15 @@ export class ZKillSubscriber {
             };
         }
 
-        for (const permittedGroupOfShipIds of limitGroupOfShipIds) {
-            const permittedGroupOfShipId = await this.getGroupIdForEntityId(Number(permittedGroupOfShipIds));
+        for (const permittedGroupOfShipId of permittedGroupIds) {
 
             // Check if the victim's ship matches the criteria
-            if (await this.isShipMatch(shipTypeId, permittedGroupOfShipId, shouldCheckNameFragment, nameFragment)) {
+            if (await this.isShipMatch(shipTypeId, permittedGroupOfShipId, shouldCheckNameFragment, nameFragment, context)) {
                 return {
                     requireSend: true,
                     color: <ColorResolvable>'RED',
                     matchedShip: {
-                        shipName: await this.getNameForEntityId(shipTypeId),
+                        shipName: await context.name(shipTypeId),
                         typeId: shipTypeId,
                         corpId: data.victim.corporation_id,
                         allianceId: data.victim.alliance_id,
@@ -583,17 +2177,17 @@ export class ZKillSubscriber {
             // If the victim's ship doesn't match, check the attackers' ships
             if (alsoCompareAttackers) {
                 for (const attacker of data.attackers) {
-                    if (await this.isShipMatch(attacker.ship_type_id, permittedGroupOfShipId, shouldCheckNameFragment, nameFragment)) {
+                    if (await this.isShipMatch(attacker.ship_type_id, permittedGroupOfShipId, shouldCheckNameFragment, nameFragment, context)) {
                         const id = attacker.ship_type_id;
                         if (id == null) {
-                            console.log('WARNING: attacker.ship_type_id is null but matched?');
+                            logger.info('WARNING: attacker.ship_type_id is null but matched?');
                             continue;
                         }
                         return {
                             requireSend: true,
                             color: <ColorResolvable>'GREEN',
                             matchedShip: {
-                                shipName: await this.getNameForEntityId(id),
+                                shipName: await context.name(id),
                                 typeId: id,
                                 corpId: attacker.corporation_id,
                                 allianceId: attacker.alliance_id
@@ -601,17 +2195,17 @@ export class ZKillSubscriber {
                             matchedTypeId: id,
                         };
                     }
-                    if ((alsoCompareAttackerWeapons && await this.isShipMatch(attacker.weapon_type_id, permittedGroupOfShipId, shouldCheckNameFragment, nameFragment))) {
+                    if ((alsoCompareAttackerWeapons && await this.isShipMatch(attacker.weapon_type_id, permittedGroupOfShipId, shouldCheckNameFragment, nameFragment, context))) {
                         const id = attacker.weapon_type_id;
                         if (id == null) {
-                            console.log('WARNING: attacker.weapon_type_id is null but matched?');
+                            logger.info('WARNING: attacker.weapon_type_id is null but matched?');
                             continue;
                         }
                         return {
                             requireSend: true,
                             color: <ColorResolvable>'GREEN',
                             matchedShip: {
-                                shipName: await this.getNameForEntityId(id),
+                                shipName: await context.name(id),
                                 typeId: id,
                                 corpId: attacker.corporation_id,
                                 allianceId: attacker.alliance_id
@@ -630,12 +2224,12 @@ export class ZKillSubscriber {
         };
     }
 
-    private async isShipMatch(shipTypeId: number | undefined, permittedGroupOfShipId: number, shouldCheckNameFragment: boolean, nameFragment: string) {
+    private async isShipMatch(shipTypeId: number | undefined, permittedGroupOfShipId: number, shouldCheckNameFragment: boolean, nameFragment: string, context: KillContext) {
         if (shipTypeId != null) {
-            const groupId = await this.getGroupIdForEntityId(shipTypeId);
+            const groupId = await context.groupId(shipTypeId);
             if (groupId === permittedGroupOfShipId) {
                 if (shouldCheckNameFragment) {
-                    const shipName = await this.getNameForEntityId(shipTypeId);
+                    const shipName = await context.name(shipTypeId);
                     return shipName.includes(nameFragment);
                 }
                 return true;
@@ -644,6 +2238,25 @@ export class ZKillSubscriber {
         return false;
     }
 
+    // Logs what the subscription would have posted, and tells its creator if the dry run sends direct messages
+    private async reportDryRun(subscription: Subscription, data: ZkData, target: string, userId?: string) {
+        const text = dryRunText(subscriptionIdent(subscription), target, data, this.abbreviateNumber(data.zkb.totalValue));
+        logger.info(text);
+        if (subscription.dryRun !== DryRunMode.DM || !userId) {
+            return;
+        }
+        if (!this.dmRateLimiter.tryConsume(userId)) {
+            logger.info(`direct message limit of ${this.dmRateLimiter.limit} per hour reached, not sending the dry run`);
+            return;
+        }
+        try {
+            const user = await this.doClient.users.fetch(userId);
+            await user.send(text);
+        } catch (e) {
+            logger.info(e);
+        }
+    }
+
     public async sendMessageToDiscord(
         guildId: string,
         channelId: string,
@@ -652,18 +2265,51 @@ export class ZKillSubscriber {
         matchedShip: FilterShipMatch | null = null,
         minNumInvolved: number | null = null,
         messageColor: ColorResolvable = 'GREY',
+        matchedAttackerCount: number | null = null,
+        // Counts the post towards the statistics of the subscription, unset for mirror channels
+        statsKey?: string,
+        // Further subscriptions of the channel that matched the kill with their statistics keys, named in the message
+        others: [Subscription, string][] = [],
     ) {
-        await this.asyncLock.acquire('sendKill', async (done) => {
-            const cacheKey = `${channelId}_${data.killmail_id}`;
+        const subscriptions = [subscription, ...others.map(([other]) => other)];
+        // Sends to different channels run concurrently, the lock only keeps a channel from posting a kill twice
+        await this.asyncLock.acquire(`sendKill_${channelId}`, async (done) => {
+            const cacheKey = this.subscriptions.get(guildId)?.postDuplicates ?
+                `${channelId}_${data.killmail_id}_${subscriptionIdent(subscription)}` :
+                `${channelId}_${data.killmail_id}`;
             if (MemoryCache.get(cacheKey)) {
                 // Mail was already sent, prevent from sending twice
                 done();
                 return;
             }
 
+            // Only the subscriptions suppressing pods need the category, it may take an ESI request
+            const victimCategory = subscription.suppressPodsAfterLoss ?
+                await this.getShipCategoryForTypeId(data.victim.ship_type_id).catch(() => null) : null;
+            const podKey = `pod_${channelId}_${data.victim.character_id}`;
+            if (subscription.suppressPodsAfterLoss &&
+                victimCategory === ShipCategory.CAPSULE &&
+                data.victim.character_id != null &&
+                MemoryCache.get(podKey)) {
+                logger.info('not sending pod kill, the ship loss was posted shortly before');
+                done();
+                return;
+            }
+            const maxAge = maxKillAgeMinutes(subscription.maxKillAgeMinutes, this.maxKillAgeMinutes);
+            const stale = isStaleKill(data.killmail_time, maxAge);
+            if (stale && subscription.staleKills !== StaleKillAction.UNPINGED) {
+                logger.info(`not sending kill, it is older than ${maxAge} minutes`);
+                done();
+                return;
+            }
+
             const channel = <TextChannel>this.doClient.channels.cache.get(channelId);
             if (!channel) {
-                await this.unsubscribe(subscription.subType, guildId, channelId, subscription.id);
+                // The channel cache misses channels during gateway reconnects, so one miss does not unsubscribe
+                if (this.channelFailures.fail(channelId)) {
+                    await this.disableChannel(guildId, channelId, 'the channel was not found');
+                    this.removeMirrorChannelFromGuild(guildId, channelId);
+                }
                 done();
                 return;
             }
@@ -678,43 +2324,337 @@ export class ZKillSubscriber {
                 matchedShip,
                 minNumInvolved,
                 messageColor,
+                matchedAttackerCount,
+                matchedBy: others.length > 0 ? subscriptions.map(subscriptionIdent) : undefined,
             };
             const content: MessageOptions = await this.prepareMessageContent(params);
+            content.allowedMentions = this.hostedMode.allowedMentions(guildId);
+            const mention = mergedMention(subscriptions, data.zkb.totalValue);
+            const pinged = mention != null && !stale && this.hostedMode.canPing(guildId);
+            if (pinged) {
+                content.content = <string>mention;
+            }
+            if (subscription.matchSummary) {
+                const summary = await this.matchSummaryText(guildId, subscription, data, matchedShip, messageColor)
+                    .catch(e => {
+                        logger.info(`failed to summarize the match: ${e}`);
+                        return null;
+                    });
+                if (summary) {
+                    content.content = content.content ? `${content.content} ${summary}` : summary;
+                }
+            }
+            const components: MessageActionRow[] = [];
+            if (subscription.linkButtons) {
+                components.push(this.linkButtonRow(data, this.subscriptions.get(guildId)?.mapProvider));
+            }
+            if (subscription.srpButton && messageColor === 'RED' && data.victim.character_id != null) {
+                components.push(new MessageActionRow().addComponents(new MessageButton()
+                    .setCustomId(srpButtonId(data.killmail_id))
+                    .setLabel(translate(this.getGuildLocale(guildId), 'srp.request'))
+                    .setStyle('PRIMARY')));
+            }
+            if (components.length > 0) {
+                content.components = components;
+            }
 
-            try {
-                console.log('content: ' + util.inspect(content, {depth: 5}));
-                await channel.send(content);
+            const battle = subscription.battleReportThreshold ? this.battleDetector.add(channelId, data) : null;
+            let target: TextChannel | ThreadChannel = channel;
+            if (battle?.threadId) {
+                target = channel.threads.cache.get(battle.threadId) ?? channel;
+            }
+
+            const onSent = () => {
+                this.channelFailures.succeed(channelId);
+                this.deliveryLatency.recordPosted(data.killmail_time);
                 MemoryCache.put(cacheKey, 'send', 60000); // Prevent from sending again, cache it for 1 min
+                if (subscription.suppressPodsAfterLoss && victimCategory !== ShipCategory.CAPSULE && data.victim.character_id != null) {
+                    MemoryCache.put(podKey, 'send', POD_SUPPRESSION_MS);
+                }
+                this.killStats.record(guildId, {
+                    killmailId: data.killmail_id,
+                    time: data.killmail_time,
+                    channelId,
+                    subscriptionId: subscription.id != null ? String(subscription.id) : undefined,
+                    systemId: data.solar_system_id,
+                    victimAllianceId: data.victim.alliance_id,
+                    victimCorporationId: data.victim.corporation_id,
+                    totalValue: data.zkb.totalValue,
+                    loss: messageColor === 'RED',
+                    attackerAllianceIds: data.attackers
+                        .map(attacker => attacker.alliance_id)
+                        .filter((id, index, ids): id is number => id != null && ids.indexOf(id) === index),
+                });
+                if (subscription.tracking) {
+                    subscription.tracking = withTrackedKill(subscription.tracking, data);
+                    this.markGuildDirty(guildId);
+                }
+                subscriptions
+                    .map(matching => matching.campaign)
+                    .filter((name, index, names): name is string => name != null && names.indexOf(name) === index)
+                    .forEach(name => this.recordCampaignKill(guildId, name, data));
+                if (statsKey) {
+                    [statsKey, ...others.map(([, otherStatsKey]) => otherStatsKey)]
+                        .forEach(key => this.subscriptionStats.recordPost(key, pinged));
+                    this.cacheWriter.markDirty('./config/subscription_stats.json', () => this.subscriptionStats.toJson());
+                }
+            };
+            // Counted before sending so concurrent sends cannot exceed the quota, given back if the send fails
+            if (!this.hostedMode.tryConsumePost(guildId)) {
+                logger.info(`daily post quota of ${this.hostedMode.maxPostsPerDay} reached, not sending`);
+                done();
+                return;
+            }
+            try {
+                logger.info('content: ' + util.inspect(content, {depth: 5}));
+                await target.send(content);
+                onSent();
             } catch (e) {
-                if (e instanceof DiscordAPIError && e.httpStatus === 403) {
-                    await this.handlePermissionError(channel);
+                const kind = classifySendError(e);
+                if (kind === SendErrorKind.RETRYABLE) {
+                    logger.info(`failed to send kill ${data.killmail_id} to ${channelId}, retrying: ${e}`);
+                    // Keeps other subscriptions of the channel from posting the kill while it is retried
+                    MemoryCache.put(cacheKey, 'retry', this.sendRetries.totalDelayMs() + 60000);
+                    this.sendRetries.retry(async () => {
+                        await target.send(content);
+                    }).then(async failure => {
+                        if (failure == null) {
+                            onSent();
+                        } else {
+                            this.hostedMode.refundPost(guildId);
+                            await this.handleSendFailure(channel, failure, `kill ${data.killmail_id} failed after ${this.sendRetries.maxAttempts} retries`);
+                        }
+                    }).catch(e => logger.error(e));
                 } else {
-                    console.log(e);
+                    this.hostedMode.refundPost(guildId);
+                    await this.handleSendFailure(channel, kind, e);
+                }
+            }
+            if (battle && !battle.reported && battle.kills.length >= <number>subscription.battleReportThreshold) {
+                battle.reported = true;
+                try {
+                    await this.sendBattleSummary(channel, battle, subscription.battleReportThreads === true);
+                } catch (e) {
+                    logger.info(e);
                 }
             }
             done();
         });
     }
 
-    private async prepareMessageContent(params: PrepareEmbedFields): Promise<MessageOptions> {
-        if (params.matchedShip != null || params.minNumInvolved != null) {
-            return {
-                embeds: await this.prepareEmbedFields(params)
-            };
-        } else if (params.embedding?.error === false) {
-            console.log('defaulting to standard embed');
-            return {
-                embeds: [{
-                    title: params.embedding?.result.ogTitle,
-                    description: params.embedding?.result.ogDescription,
-                    thumbnail: {
-                        // eslint-disable-next-line @typescript-eslint/ban-ts-comment
-                        // @ts-ignore
-                        url: params.embedding?.result.ogImage?.url,
-                        // eslint-disable-next-line @typescript-eslint/ban-ts-comment
-                        // @ts-ignore
-                        height: params.embedding?.result.ogImage?.height,
-                        // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+    // Who lost or killed what where, e.g. `Pandemic Legion (Revelation) killed Goonswarm Federation's Nyx in 1DQ1-A`
+    private async matchSummaryText(
+        guildId: string,
+        subscription: Subscription,
+        data: ZkData,
+        matchedShip: FilterShipMatch | null,
+        messageColor: ColorResolvable,
+    ): Promise<string> {
+        const locale = this.getGuildLocale(guildId);
+        const system = (await this.getSystemData(data.solar_system_id)).systemName;
+        const ship = await this.getNameForEntityId(data.victim.ship_type_id);
+        const victim = await this.affiliationName(data.victim);
+        // Kills without entity or ship filters are grey, they are told from the victim's side like losses
+        if (messageColor !== 'GREEN') {
+            return translate(locale, 'summary.lost', {entity: victim, ship, system});
+        }
+        const entityIds = [LimitType.CHARACTER, LimitType.CORPORATION, LimitType.ALLIANCE]
+            .map(limitType => (getLimitType(subscription, limitType) ?? '').split(',').filter(id => id.trim() !== '').map(Number))
+            .reduce((ids, more) => ids.concat(more), []);
+        const attacker = summaryAttacker(data, entityIds);
+        const entity = matchedShip ?
+            await this.affiliationName({corporation_id: matchedShip.corpId, alliance_id: matchedShip.allianceId}) :
+            attacker ? await this.affiliationName(attacker) : '?';
+        const attackerShip = matchedShip?.shipName ??
+            (attacker?.ship_type_id != null ? await this.getNameForEntityId(attacker.ship_type_id) : null);
+        return translate(locale, attackerShip ? 'summary.killedWith' : 'summary.killed', {entity, attackerShip: attackerShip ?? undefined, victim, ship, system});
+    }
+
+    // Alliance, else corporation, else pilot name
+    private async affiliationName(entity: { character_id?: number, corporation_id?: number | null, alliance_id?: number | null }): Promise<string> {
+        if (entity.alliance_id != null) {
+            return this.getNameForAlliance(entity.alliance_id);
+        }
+        if (entity.corporation_id != null) {
+            return this.getNameForCorporation(entity.corporation_id);
+        }
+        return entity.character_id != null ? this.getNameForCharacter(entity.character_id) : '?';
+    }
+
+    private async sendDirectMessage(userId: string, subscription: Subscription, data: ZkData, evaluation: SubscriptionEvaluation) {
+        await this.asyncLock.acquire(`sendDm_${userId}`, async (done) => {
+            const cacheKey = `dm_${userId}_${data.killmail_id}`;
+            if (MemoryCache.get(cacheKey)) {
+                // Several subscriptions of the user matched the kill
+                done();
+                return;
+            }
+            const maxAge = maxKillAgeMinutes(subscription.maxKillAgeMinutes, this.maxKillAgeMinutes);
+            // Direct messages never ping, a stale kill is only sent if the subscription posts them without pings
+            if (isStaleKill(data.killmail_time, maxAge) && subscription.staleKills !== StaleKillAction.UNPINGED) {
+                logger.info(`not sending kill, it is older than ${maxAge} minutes`);
+                done();
+                return;
+            }
+            if (!this.dmRateLimiter.tryConsume(userId)) {
+                logger.info(`direct message limit of ${this.dmRateLimiter.limit} per hour reached, not sending`);
+                done();
+                return;
+            }
+            const embedding = await ogs({url: data.zkb.url}).catch(() => null);
+            const content = await this.prepareMessageContent({
+                guildId: '',
+                channelId: userId,
+                subscription,
+                embedding,
+                data,
+                matchedShip: evaluation.matchedShip,
+                minNumInvolved: evaluation.minNumInvolved,
+                messageColor: evaluation.color,
+                matchedAttackerCount: evaluation.matchedAttackerCount,
+            });
+            try {
+                const user = await this.doClient.users.fetch(userId);
+                await user.send(content);
+                MemoryCache.put(cacheKey, 'send', 60000);
+            } catch (e) {
+                // 50007: the user blocked the bot, left all shared servers or disabled direct messages
+                if (e instanceof DiscordAPIError && e.code === 50007) {
+                    logger.info('unable to send direct messages to the user, removing their subscriptions');
+                    this.dmSubscriptions.delete(userId);
+                    this.saveDmSubscriptions(userId);
+                } else {
+                    logger.info(e);
+                }
+            }
+            done();
+        });
+    }
+
+    private async sendBattleSummary(channel: TextChannel, battle: Battle, withThread: boolean) {
+        const system = await this.getSystemData(battle.systemId);
+        const message = await channel.send({
+            embeds: [await this.prepareBattleSummary(battle, system)],
+            allowedMentions: this.hostedMode.allowedMentions(channel.guild.id),
+        });
+        if (withThread) {
+            const thread = await message.startThread({name: `Battle in ${system.systemName}`});
+            battle.threadId = thread.id;
+        }
+    }
+
+    private async prepareBattleSummary(battle: Battle, system: SolarSystem): Promise<MessageEmbedOptions> {
+        // Losses per victim alliance, or corporation if the victim is not in an alliance
+        const losses = new Map<string, { count: number, value: number }>();
+        const ships = new Map<string, number>();
+        let totalValue = 0;
+        for (const kill of battle.kills) {
+            totalValue += kill.zkb.totalValue;
+            let side = 'Unknown';
+            try {
+                if (kill.victim.alliance_id) {
+                    side = await this.getNameForAlliance(kill.victim.alliance_id);
+                } else if (kill.victim.corporation_id) {
+                    side = await this.getNameForCorporation(kill.victim.corporation_id);
+                }
+            } catch (e) {
+                logger.info(`Error getting victim affiliation name: ${e}`);
+            }
+            const sideLosses = losses.get(side) || {count: 0, value: 0};
+            sideLosses.count++;
+            sideLosses.value += kill.zkb.totalValue;
+            losses.set(side, sideLosses);
+            if (kill.victim.ship_type_id != null) {
+                try {
+                    const shipName = await this.getNameForEntityId(kill.victim.ship_type_id);
+                    ships.set(shipName, (ships.get(shipName) || 0) + 1);
+                } catch (e) {
+                    logger.info(`Error getting ship name: ${e}`);
+                }
+            }
+        }
+        let lossDetails = '';
+        Array.from(losses.entries()).sort((a, b) => b[1].value - a[1].value).slice(0, 8).forEach(([side, sideLosses]) => {
+            lossDetails += `${side.substring(0, 26)}: ${sideLosses.count} (${this.abbreviateNumber(sideLosses.value)})\n`;
+        });
+        let shipDetails = '';
+        Array.from(ships.entries()).sort((a, b) => b[1] - a[1]).slice(0, 8).forEach(([shipName, count]) => {
+            shipDetails += `${count}x ${shipName}\n`;
+        });
+        return {
+            title: `Battle in ${system.systemName} (${system.regionName}): ${battle.kills.length} kills`,
+            url: strBattleReport(battle),
+            description: `${this.abbreviateNumber(totalValue)} ISK destroyed, [related kills on br.evetools.org](${strBattleReport(battle)})`,
+            color: 'ORANGE',
+            fields: [
+                {name: '__Losses__', value: lossDetails || '-', inline: true},
+                {name: '__Ships Destroyed__', value: shipDetails || '-', inline: true},
+            ],
+            timestamp: battle.lastKillTime,
+        };
+    }
+
+    private async prepareMessageContent(params: PrepareEmbedFields): Promise<MessageOptions> {
+        const content = await this.prepareUndecoratedMessageContent(params);
+        const settings = this.getGuildSettings(params.guildId);
+        const outcome = killOutcome(params.messageColor);
+        const outcomeLabel = outcome ? translate(this.getGuildLocale(params.guildId), 'embed.outcome.' + outcome) : '';
+        if (content.embeds) {
+            content.embeds = content.embeds.map(embed =>
+                applyDisplaySettings(<MessageEmbedOptions>embed, settings, outcome, outcomeLabel, !!params.subscription.embedColor));
+        }
+        if (params.matchedBy) {
+            const matchedBy = translate(this.getGuildLocale(params.guildId), 'embed.matchedBy', {subscriptions: params.matchedBy.join(', ')});
+            const embed = <MessageEmbedOptions | undefined>content.embeds?.[0];
+            if (embed) {
+                embed.footer = {...embed.footer, text: embed.footer?.text ? `${embed.footer.text} • ${matchedBy}` : matchedBy};
+            } else {
+                content.content = `${content.content ?? ''}\n${matchedBy}`;
+            }
+        }
+        const tracking = params.subscription.tracking;
+        const trackingEmbed = <MessageEmbedOptions | undefined>content.embeds?.[0];
+        if (tracking && trackingEmbed) {
+            // The totals include this kill, they are stored once it was posted
+            const totals = withTrackedKill(tracking, params.data);
+            const text = translate(this.getGuildLocale(params.guildId), 'embed.tracking', {
+                since: totals.since.substring(0, 10),
+                destroyed: this.abbreviateNumber(totals.iskDestroyed),
+                lost: this.abbreviateNumber(totals.iskLost),
+            });
+            trackingEmbed.footer = {...trackingEmbed.footer, text: trackingEmbed.footer?.text ? `${trackingEmbed.footer.text} • ${text}` : text};
+        }
+        // Large fleet fights exceed the embed limits, Discord would reject the whole message
+        if (content.embeds) {
+            content.embeds = content.embeds.map(embed => fitEmbed(<MessageEmbedOptions>embed, params.data.zkb.url));
+        }
+        return content;
+    }
+
+    private async prepareUndecoratedMessageContent(params: PrepareEmbedFields): Promise<MessageOptions> {
+        if (params.subscription.embedColor) {
+            params = {...params, messageColor: <ColorResolvable>params.subscription.embedColor};
+        }
+        if (usesFullEmbed(params)) {
+            return {
+                embeds: await this.prepareEmbedFields(params)
+            };
+        }
+        const details = await this.previewDetails(params);
+        if (params.embedding?.error === false) {
+            logger.info('defaulting to standard embed');
+            return {
+                embeds: [{
+                    title: params.embedding?.result.ogTitle,
+                    description: [params.embedding?.result.ogDescription, ...details].filter(line => line).join('\n'),
+                    thumbnail: {
+                        // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+                        // @ts-ignore
+                        url: params.embedding?.result.ogImage?.url,
+                        // eslint-disable-next-line @typescript-eslint/ban-ts-comment
+                        // @ts-ignore
+                        height: params.embedding?.result.ogImage?.height,
+                        // eslint-disable-next-line @typescript-eslint/ban-ts-comment
                         // @ts-ignore
                         width: params.embedding?.result.ogImage?.width
                     },
@@ -723,71 +2663,148 @@ export class ZKillSubscriber {
                 }]
             };
         } else {
-            return {content: params.data.zkb.url};
+            return {content: [params.data.zkb.url, ...details].join('\n')};
         }
     }
 
+    // Lines added to the zKillboard preview, showing what the full embed shows in its fields
+    public async previewDetails(params: PrepareEmbedFields): Promise<string[]> {
+        const locale = this.getGuildLocale(params.guildId);
+        const lines: string[] = [];
+        const tickers: string[] = [];
+        const victimTickers = await this.affiliationTickers(params.data.victim);
+        if (victimTickers) {
+            tickers.push(translate(locale, 'embed.preview.victimTickers', {tickers: victimTickers}));
+        }
+        const finalBlow = params.data.attackers.find(attacker => attacker.final_blow) ?? params.data.attackers[0];
+        const attackerTickers = finalBlow ? await this.affiliationTickers(finalBlow) : '';
+        if (attackerTickers) {
+            tickers.push(translate(locale, 'embed.preview.attackerTickers', {tickers: attackerTickers}));
+        }
+        if (tickers.length > 0) {
+            lines.push(tickers.join(' • '));
+        }
+        const structureDetails = this.structureDetails(params.data, locale, params.guildId);
+        if (structureDetails != null) {
+            lines.push(structureDetails);
+        }
+        const lastSeen = await this.lastSeenText(params.guildId, params.subscription, params.data, locale).catch(e => {
+            logger.info(`failed to render the last sighting: ${e}`);
+            return null;
+        });
+        if (lastSeen) {
+            lines.push(lastSeen);
+        }
+        if (attackingPilotCount(params.data) > 1) {
+            const composition = await this.fleetCompositionText(params.data);
+            if (composition != null) {
+                lines.push(translate(locale, 'embed.fleet', {composition}));
+            }
+        }
+        return lines;
+    }
+
+    // Corporation and alliance tickers, e.g. `[VICT] [ALLY]`, empty if neither is known
+    private async affiliationTickers(entity: { corporation_id?: number | null, alliance_id?: number | null }): Promise<string> {
+        const corporation = entity.corporation_id != null ? await this.tickerSuffix(this.getTickerForCorporation(entity.corporation_id)) : '';
+        const alliance = entity.alliance_id != null ? await this.tickerSuffix(this.getTickerForAlliance(entity.alliance_id)) : '';
+        return (corporation + alliance).trim();
+    }
+
     private async prepareEmbedFields(params: PrepareEmbedFields): Promise<(MessageEmbed | MessageEmbedOptions | APIEmbed)[]> {
-        console.log('prepareEmbedFields');
+        logger.info('prepareEmbedFields');
+        const locale = this.getGuildLocale(params.guildId);
+        const provider = this.subscriptions.get(params.guildId)?.mapProvider;
         const systemRegion = await this.getSystemData(params.data.solar_system_id);
         let victimDetails = '';
         let attackerDetails = '';
         let locationDetails = '';
         let victimShipName = '';
 
-        const closestCelestial = await this.getClosestCelestial(
-            systemRegion.id,
-            params.data.victim.position.x,
-            params.data.victim.position.y,
-            params.data.victim.position.z
-        );
-        const distance = (closestCelestial.distance / 1000);
-        let distanceInUnits;
-        if (distance > 1500000) {
-            distanceInUnits = (distance / 150000000).toFixed(2) + ' au';
+        // Abyssal pockets have no celestials to measure the distance to
+        if (getSpaceType(systemRegion) === SpaceType.ABYSSAL) {
+            locationDetails += translate(locale, 'embed.location.abyssal') + '\n';
         } else {
-            distanceInUnits = Math.round(distance) + ' km';
+            const closestCelestial = await this.getClosestCelestial(
+                systemRegion.id,
+                params.data.victim.position.x,
+                params.data.victim.position.y,
+                params.data.victim.position.z
+            );
+            const distance = (closestCelestial.distance / 1000);
+            let distanceInUnits;
+            if (distance > 1500000) {
+                distanceInUnits = (distance / 150000000).toFixed(2) + ' au';
+            } else {
+                distanceInUnits = Math.round(distance) + ' km';
+            }
+            const closestCelestialName = closestCelestial.itemName;
+            locationDetails += translate(locale, 'embed.location.celestial', {
+                celestial: `[${closestCelestialName}](${this.strLocation(closestCelestial.itemId)})`,
+                distance: distanceInUnits,
+            }) + '\n';
+        }
+        locationDetails += translate(locale, 'embed.location.system', {
+            system: `[${systemRegion.systemName}](${systemMapUrl(systemRegion.id, systemRegion.systemName, provider)})`,
+            region: `[${systemRegion.regionName}](${regionMapUrl(systemRegion.regionId, systemRegion.regionName, provider)})`,
+        });
+        const securityBorder = this.securityBorderText(systemRegion);
+        if (securityBorder) {
+            locationDetails += `, ${securityBorder}`;
+        }
+        const jumpRange = this.jumpRangeText(params.subscription, systemRegion, locale);
+        if (jumpRange) {
+            locationDetails += '\n' + jumpRange;
+        }
+        const lastSeen = await this.lastSeenText(params.guildId, params.subscription, params.data, locale).catch(e => {
+            logger.info(`failed to render the last sighting: ${e}`);
+            return null;
+        });
+        if (lastSeen) {
+            locationDetails += '\n' + lastSeen;
         }
-        const closestCelestialName = closestCelestial.itemName;
-        locationDetails += `on [${closestCelestialName}](${this.strLocation(closestCelestial.itemId)}) ${distanceInUnits} away\n`;
-        locationDetails += `in [${systemRegion.systemName}](${this.strSystemDotlan(systemRegion.id)}) ([${systemRegion.regionName}](${this.strRegionDotlan(systemRegion.regionId)}))`;
 
         if (params.data.victim.ship_type_id != null) {
             try {
                 victimShipName = await this.getNameForEntityId(params.data.victim.ship_type_id);
                 // victimDetails += `Ship: [${victimShipName.substring(0, 18)}](${params.data.zkb.url})\n`;
             } catch (e) {
-                console.log(e);
+                logger.info(e);
             }
         }
         if (params.data.victim.alliance_id != null) {
             try {
                 const victimAllianceName = await this.getNameForAlliance(params.data.victim.alliance_id);
-                victimDetails += `Alliance: [${victimAllianceName.substring(0, 18)}](${this.strAllianceZk(params.data.victim.alliance_id)})\n`;
+                const victimAllianceTicker = await this.tickerSuffix(this.getTickerForAlliance(params.data.victim.alliance_id));
+                victimDetails += `${translate(locale, 'embed.alliance')}: [${victimAllianceName.substring(0, 18)}](${this.strAllianceZk(params.data.victim.alliance_id)})${victimAllianceTicker}\n`;
             } catch (e) {
-                console.log(e);
+                logger.info(e);
             }
         }
         if (params.data.victim.corporation_id != null) {
             try {
                 const victimCorporationName = await this.getNameForCorporation(params.data.victim.corporation_id);
-                victimDetails += `Corp: [${victimCorporationName.substring(0, 18)}](${this.strCorpZk(params.data.victim.corporation_id)})\n`;
+                const victimCorporationTicker = await this.tickerSuffix(this.getTickerForCorporation(params.data.victim.corporation_id));
+                const victimCorporationSize = await this.getCorporationMemberCount(params.data.victim.corporation_id)
+                    .then(count => ' ' + translate(locale, 'embed.corpSize', {count}))
+                    .catch(() => '');
+                victimDetails += `${translate(locale, 'embed.corporation')}: [${victimCorporationName.substring(0, 18)}](${this.strCorpZk(params.data.victim.corporation_id)})${victimCorporationTicker}${victimCorporationSize}\n`;
             } catch (e) {
-                console.log(e);
+                logger.info(e);
             }
         }
         if (params.data.victim.character_id != null) {
             try {
                 const victimCharacterName = await this.getNameForCharacter(params.data.victim.character_id);
-                victimDetails += `Pilot: [${victimCharacterName.substring(0, 18)}](${this.strPilotZk(params.data.victim.character_id)})\n`;
+                victimDetails += `${translate(locale, 'embed.pilot')}: [${victimCharacterName.substring(0, 18)}](${this.strPilotZk(params.data.victim.character_id)})\n`;
             } catch (e) {
-                console.log(e);
+                logger.info(e);
             }
         }
-        console.log('victimparams.dataDone');
+        logger.info('victimparams.dataDone');
 
 
-        console.log('attackerparams.data');
+        logger.info('attackerparams.data');
         let lastHitAttacker = null;
         for (const attacker of params.data.attackers) {
             if (attacker.final_blow) {
@@ -796,7 +2813,7 @@ export class ZKillSubscriber {
             }
         }
         if (lastHitAttacker == null) {
-            console.log('No final blow attacker found, using first attacker as last hit attacker');
+            logger.info('No final blow attacker found, using first attacker as last hit attacker');
             lastHitAttacker = params.data.attackers[0];
         }
         // if (lastHitAttacker.ship_type_id != null) {
@@ -804,86 +2821,68 @@ export class ZKillSubscriber {
         //         const attackerShipName = await this.getNameForEntityId(lastHitAttacker.ship_type_id);
         //         attackerDetails += `Ship: [${attackerShipName}](${this.strShipZk(lastHitAttacker.ship_type_id)})\n`;
         //     } catch (e) {
-        //         console.log(e);
+        //         logger.info(e);
         //     }
         // }
         if (lastHitAttacker.alliance_id != null) {
             try {
                 const attackerAllianceName = await this.getNameForAlliance(lastHitAttacker.alliance_id);
-                attackerDetails += `Alliance: [${attackerAllianceName.substring(0, 18)}](${this.strAllianceZk(lastHitAttacker.alliance_id)})\n`;
+                const attackerAllianceTicker = await this.tickerSuffix(this.getTickerForAlliance(lastHitAttacker.alliance_id));
+                attackerDetails += `${translate(locale, 'embed.alliance')}: [${attackerAllianceName.substring(0, 18)}](${this.strAllianceZk(lastHitAttacker.alliance_id)})${attackerAllianceTicker}\n`;
             } catch (e) {
-                console.log(e);
+                logger.info(e);
             }
         }
         if (lastHitAttacker.corporation_id != null) {
             try {
                 const attackerCorporationName = await this.getNameForCorporation(lastHitAttacker.corporation_id);
-                attackerDetails += `Corp: [${attackerCorporationName.substring(0, 18)}](${this.strCorpZk(lastHitAttacker.corporation_id)})\n`;
+                const attackerCorporationTicker = await this.tickerSuffix(this.getTickerForCorporation(lastHitAttacker.corporation_id));
+                attackerDetails += `${translate(locale, 'embed.corporation')}: [${attackerCorporationName.substring(0, 18)}](${this.strCorpZk(lastHitAttacker.corporation_id)})${attackerCorporationTicker}\n`;
             } catch (e) {
-                console.log(e);
+                logger.info(e);
             }
         }
         if (lastHitAttacker.character_id != null) {
             try {
                 const attackerCharacterName = await this.getNameForCharacter(lastHitAttacker.character_id);
-                attackerDetails += `Pilot: [${attackerCharacterName.substring(0, 18)}](${this.strPilotZk(lastHitAttacker.character_id)})\n`;
+                attackerDetails += `${translate(locale, 'embed.pilot')}: [${attackerCharacterName.substring(0, 18)}](${this.strPilotZk(lastHitAttacker.character_id)})\n`;
             } catch (e) {
-                console.log(e);
+                logger.info(e);
             }
         }
         const mostCommonShip = this.findMostCommonShipTypeIdAndCount(params.data.attackers);
-        console.log(`Most common ship type ID among attackers: ${mostCommonShip}`);
+        logger.info(`Most common ship type ID among attackers: ${mostCommonShip}`);
 
         let idOfIconToRender: number;
-        let affiliationIconURLToRender: string;
+        // Whose alliance or corporation logo is shown next to the author line
+        let iconOwner: ThumbnailOwner;
         if (params.matchedShip?.typeId != null) {
             idOfIconToRender = params.matchedShip.typeId;
-            if (params.matchedShip.allianceId) {
-                affiliationIconURLToRender = this.strAllianceIconById(params.matchedShip.allianceId);
-            } else if (params.matchedShip.corpId) {
-                affiliationIconURLToRender = this.strCorporationIconById(params.matchedShip.corpId);
-            } else {
-                affiliationIconURLToRender = this.strItemRenderById(idOfIconToRender);
-            }
+            iconOwner = {corporation_id: params.matchedShip.corpId || null, alliance_id: params.matchedShip.allianceId || null};
         } else if (params.data.victim.ship_type_id != null) {
             idOfIconToRender = params.data.victim.ship_type_id;
-            if (params.data.victim.alliance_id != null) {
-                affiliationIconURLToRender = this.strAllianceIconById(params.data.victim.alliance_id);
-            } else if (params.data.victim.corporation_id != null) {
-                affiliationIconURLToRender = this.strCorporationIconById(params.data.victim.corporation_id);
-            } else {
-                affiliationIconURLToRender = this.strItemRenderById(idOfIconToRender);
-            }
+            iconOwner = params.data.victim;
         } else if (lastHitAttacker.ship_type_id != null) {
             idOfIconToRender = lastHitAttacker.ship_type_id;
-            if (lastHitAttacker.alliance_id != null) {
-                affiliationIconURLToRender = this.strAllianceIconById(lastHitAttacker.alliance_id);
-            } else if (lastHitAttacker.corporation_id != null) {
-                affiliationIconURLToRender = this.strCorporationIconById(lastHitAttacker.corporation_id);
-            } else {
-                affiliationIconURLToRender = this.strItemRenderById(idOfIconToRender);
-            }
+            iconOwner = lastHitAttacker;
         } else if (lastHitAttacker.weapon_type_id != null) {
             idOfIconToRender = lastHitAttacker.weapon_type_id;
-            if (lastHitAttacker.alliance_id != null) {
-                affiliationIconURLToRender = this.strAllianceIconById(lastHitAttacker.alliance_id);
-            } else if (lastHitAttacker.corporation_id != null) {
-                affiliationIconURLToRender = this.strCorporationIconById(lastHitAttacker.corporation_id);
-            } else {
-                affiliationIconURLToRender = this.strItemRenderById(idOfIconToRender);
-            }
+            iconOwner = lastHitAttacker;
         } else {
-            console.log(`failed to find an icon to render for ${params.data.zkb.url}`);
+            logger.info(`failed to find an icon to render for ${params.data.zkb.url}`);
             throw new Error('failed to find an icon to render');
         }
-        console.log('rendering icon: ' + this.strItemRenderById(idOfIconToRender));
+        // Logos the image server lacks, e.g. of new alliances, fall back to the corporation logo and the ship icon
+        const affiliationIconURLToRender = await this.images.firstAvailable(this.images.affiliationImageChain(iconOwner, idOfIconToRender));
+        const thumbnailURL = await this.thumbnailUrlForType(idOfIconToRender, params.subscription.thumbnailSource, params.data.victim);
+        logger.info('rendering icon: ' + thumbnailURL);
 
         let affiliation = locationDetails + '```';
         const allianceCountMap = new Map<string, number>();
         for (const attacker of params.data.attackers) {
             const id = attacker.alliance_id ? attacker.alliance_id : attacker.corporation_id;
             if (id == null) {
-                console.log(`id for attacker ${attacker} is null, skipping`);
+                logger.info(`id for attacker ${attacker} is null, skipping`);
                 continue;
             }
             let name = '';
@@ -891,14 +2890,14 @@ export class ZKillSubscriber {
                 try {
                     name = await this.getNameForAlliance(id);
                 } catch (e) {
-                    console.log(`Error getting alliance name for id ${id}: ${e}`);
+                    logger.info(`Error getting alliance name for id ${id}: ${e}`);
                     name = 'Unknown';
                 }
             } else {
                 try {
                     name = await this.getNameForCorporation(id);
                 } catch (e) {
-                    console.log(`Error getting corporation name for id ${id}: ${e}`);
+                    logger.info(`Error getting corporation name for id ${id}: ${e}`);
                     name = 'Unknown';
                 }
             }
@@ -921,7 +2920,7 @@ export class ZKillSubscriber {
         if (maxNameLength >= 26) {
             maxNameLength = 26;
         }
-        console.log('maxNameLength: ' + maxNameLength);
+        logger.info('maxNameLength: ' + maxNameLength);
         const sortedEntries = Array.from(allianceCountMap.entries()).sort((a, b) => b[1] - a[1]);
         const padding = 3;
         let othersCount = 0;
@@ -942,77 +2941,78 @@ export class ZKillSubscriber {
             affiliation += `${others}${' '.repeat(spaces)}x${othersCount}\n`;
         }
         affiliation += '```';
-        console.log('attackerparams.dataDone');
+        if (attackingPilotCount(params.data) > 1) {
+            const composition = await this.fleetCompositionText(params.data);
+            const fleetLine = composition != null ? translate(locale, 'embed.fleet', {composition}) : '';
+            // Field values are limited to 1024 characters
+            if (fleetLine && affiliation.length + fleetLine.length < 1024) {
+                affiliation += '\n' + fleetLine;
+            }
+        }
+        logger.info('attackerparams.dataDone');
 
-        console.log(systemRegion);
+        logger.info(systemRegion);
         // convert params.data.killmail_time into a relative time
         const killmailTime = new Date(params.data.killmail_time);
-        const now = new Date();
-        const diff = now.getTime() - killmailTime.getTime();
-        const seconds = Math.floor(diff / 1000);
-        const minutes = Math.floor(seconds / 60);
-        const hours = Math.floor(minutes / 60);
-        const days = Math.floor(hours / 24);
-        const weeks = Math.floor(days / 7);
-        const months = Math.floor(weeks / 4);
-        const years = Math.floor(months / 12);
-        let relativeTime: string;
-        if (years > 1) {
-            relativeTime = years + ' years';
-        } else if (years === 1) {
-            relativeTime = '1 year';
-        } else if (months > 1) {
-            relativeTime = months + ' months';
-        } else if (months === 1) {
-            relativeTime = '1 month';
-        } else if (weeks > 1) {
-            relativeTime = weeks + ' weeks';
-        } else if (weeks === 1) {
-            relativeTime = '1 week';
-        } else if (days > 1) {
-            relativeTime = days + ' days';
-        } else if (days === 1) {
-            relativeTime = '1 day';
-        } else if (hours > 1) {
-            relativeTime = hours + ' hours';
-        } else if (hours === 1) {
-            relativeTime = '1 hour';
-        } else if (minutes > 1) {
-            relativeTime = minutes + ' minutes';
-        } else if (minutes === 1) {
-            relativeTime = '1 minute';
-        } else if (seconds > 1) {
-            relativeTime = seconds + ' seconds';
-        } else {
-            relativeTime = '1 second';
-        }
-        relativeTime = `posted ${relativeTime} later`;
+        const relativeTime = translate(locale, 'embed.postedLater', {
+            time: relativeTimeText(locale, new Date().getTime() - killmailTime.getTime()),
+        });
 
         // convert the killmail_time `2023-01-17T01:53:02Z` to YYYY/MM/DD HH:MM
         // const killmailTimeFormatted = killmailTime.toISOString().replace(/T/, ' ').replace(/\..+/, '');
 
-        console.log('total value: ' + params.data.zkb.totalValue);
-        const killmail_value = this.abbreviateNumber(params.data.zkb.totalValue);
-        console.log('killmail_value: ' + killmail_value);
+        logger.info('total value: ' + params.data.zkb.totalValue);
+
+        let engagementName = translate(locale, 'embed.engagement', {count: params.data.attackers.length});
+        if (hasLimitType(params.subscription, LimitType.ATTACKER_AVG_SEC_STATUS_MAX)) {
+            const averageSecurityStatus = getAttackerAverageSecurityStatus(params.data.attackers);
+            if (averageSecurityStatus != null) {
+                engagementName += translate(locale, 'embed.engagement.avgSecStatus', {value: averageSecurityStatus.toFixed(1)});
+            }
+        }
+        if (params.matchedAttackerCount != null) {
+            engagementName += translate(locale, 'embed.engagement.trackedAttackers', {count: params.matchedAttackerCount});
+        }
 
         const fields: { inline: boolean; name: string; value: string }[] = [];
         [
             {
-                name: `__Engagement__ - ${params.data.attackers.length} pilots involved`,
+                name: engagementName,
                 value: affiliation,
                 inline: false,
             },
             {
-                name: '__Attacker (Final Blow)__',
+                name: translate(locale, 'embed.attacker'),
                 value: attackerDetails,
                 inline: true
             },
             {
-                name: '__Victim__',
+                name: translate(locale, 'embed.victim'),
                 value: victimDetails,
                 inline: true
             },
         ].forEach((field) => fields.push(field));
+        if (params.subscription.showFitting) {
+            const fitting = await this.fittingText(params.data, victimShipName).catch(e => {
+                logger.info(`failed to render the fitting: ${e}`);
+                return null;
+            });
+            if (fitting != null) {
+                fields.push({
+                    name: translate(locale, 'embed.fitting'),
+                    value: fitting,
+                    inline: false,
+                });
+            }
+        }
+        const structureDetails = this.structureDetails(params.data, locale, params.guildId);
+        if (structureDetails != null) {
+            fields.push({
+                name: translate(locale, 'embed.structure'),
+                value: structureDetails,
+                inline: false,
+            });
+        }
 
         let title: string;
         let authorText: string;
@@ -1043,30 +3043,34 @@ export class ZKillSubscriber {
         //     authorText = '';
         // }
         if (params.minNumInvolved != null) {
-            authorText = `${params.data.attackers.length}+ ships killed ${victimShipName} in ${systemRegion.systemName} (${systemRegion.regionName})`;
+            authorText = translate(locale, 'embed.fleetKilled', {
+                count: params.data.attackers.length, ship: victimShipName, system: systemRegion.systemName, region: systemRegion.regionName,
+            });
             if (mostCommonShip != null) {
                 const mostCommonShipName = await this.getNameForEntityId(mostCommonShip.shipTypeId);
-                title = `${mostCommonShip.count}x \`${mostCommonShipName}\` most common ships in the fleet, ${relativeTime}`;
+                title = translate(locale, 'embed.mostCommonShips', {count: mostCommonShip.count, ship: mostCommonShipName, time: relativeTime});
             } else {
-                title = `Died ${relativeTime}`;
+                title = translate(locale, 'embed.died', {time: relativeTime});
             }
         } else if (params.matchedShip?.shipName != null) {
             if (params.messageColor === 'GREEN') {
-                authorText = `${params.matchedShip.shipName} attacking in ${systemRegion.systemName} (${systemRegion.regionName})`;
-                title = `\`${victimShipName}\` destroyed, ${relativeTime}`;
+                authorText = translate(locale, 'embed.attacking', {ship: params.matchedShip.shipName, system: systemRegion.systemName, region: systemRegion.regionName});
+                title = translate(locale, 'embed.destroyed', {ship: victimShipName, time: relativeTime});
             } else {
-                authorText = `${params.matchedShip.shipName} killed in ${systemRegion.systemName} (${systemRegion.regionName})`;
+                authorText = translate(locale, 'embed.killedIn', {ship: params.matchedShip.shipName, system: systemRegion.systemName, region: systemRegion.regionName});
                 if (mostCommonShip != null) {
                     const mostCommonShipName = await this.getNameForEntityId(mostCommonShip.shipTypeId);
-                    title = `Died to ${mostCommonShip.count}x \`${mostCommonShipName}\`, ${relativeTime}`;
+                    title = translate(locale, 'embed.diedTo', {count: mostCommonShip.count, ship: mostCommonShipName, time: relativeTime});
                 } else {
-                    title = `Died ${relativeTime}`;
+                    title = translate(locale, 'embed.died', {time: relativeTime});
                 }
             }
         } else {
             title = params.embedding?.result.ogTitle;
             authorText = '';
         }
+        const gangSizeText = translate(locale, 'embed.gangSize.' + classifyGangSize(attackingPilotCount(params.data)));
+        authorText = authorText ? `${authorText} • ${gangSizeText}` : gangSizeText;
 
         return [{
             title: title,
@@ -1076,7 +3080,7 @@ export class ZKillSubscriber {
                 url: params.data.zkb.url,
             },
             thumbnail: {
-                url: this.strItemRenderById(idOfIconToRender),
+                url: thumbnailURL,
                 height: params.embedding?.result.ogImage?.height,
                 width: params.embedding?.result.ogImage?.width
             },
@@ -1085,17 +3089,100 @@ export class ZKillSubscriber {
             fields: fields,
             timestamp: killmailTime.getTime(),
             footer: {
-                text: `Value: ${killmail_value} • EVE Time: ${killmailTime.toLocaleString('en-GB', { year: '2-digit', month: '2-digit', day: '2-digit', hour: '2-digit', minute: '2-digit' })}`,
+                text: `${this.valueFooterText(params.subscription, params.data.zkb, locale, params.data.victim.ship_type_id, params.guildId)} • ${translate(locale, 'embed.eveTime')}: ${killmailTime.toLocaleString('en-GB', { year: '2-digit', month: '2-digit', day: '2-digit', hour: '2-digit', minute: '2-digit' })}`,
             }
         }];
     }
 
-    public abbreviateNumber(n: number) {
-        if (n < 1e3) return n;
-        if (n >= 1e3 && n < 1e6) return +(n / 1e3).toFixed(1) + 'K';
-        if (n >= 1e6 && n < 1e9) return +(n / 1e6).toFixed(1) + 'mil';
-        if (n >= 1e9 && n < 1e12) return +(n / 1e9).toFixed(1) + 'bil';
-        if (n >= 1e12) return +(n / 1e12).toFixed(1) + 'tril';
+    // Ship classes of the attackers, the ship groups are cached like the victim's
+    private async fleetCompositionText(data: ZkData): Promise<string | null> {
+        const groupIds = await Promise.all(data.attackers
+            .filter(attacker => attacker.ship_type_id != null)
+            .map(attacker => this.getGroupIdForEntityId(<number>attacker.ship_type_id).catch(() => null)));
+        return fleetComposition(groupIds);
+    }
+
+    // First working image of the fallback chain victim portrait or logo → render → icon → icon of the group → generic image
+    private async thumbnailUrlForType(typeId: number, source = ThumbnailSource.RENDER, owner: ThumbnailOwner = {}): Promise<string> {
+        const groupId = this.ships.get(typeId);
+        let groupTypeId: number | undefined;
+        if (groupId != null) {
+            groupTypeId = this.ships.toArray()
+                .find(([otherTypeId, otherGroupId]) => otherGroupId === groupId && otherTypeId !== typeId &&
+                    !this.images.isKnownBad(this.strItemRenderById(otherTypeId)))?.[0];
+        }
+        return this.images.firstAvailable([
+            ...this.images.ownerImageChain(owner, source),
+            ...this.images.typeImageChain(typeId, groupTypeId, source),
+        ]);
+    }
+
+    // Describes how close a highsec kill is to lowsec and vice versa, e.g. `2 jumps from lowsec`
+    private securityBorderText(system: SolarSystem): string | null {
+        if (this.starMap == null) {
+            return null;
+        }
+        const spaceType = getSpaceType(system);
+        let otherSpaceType: SpaceType;
+        if (spaceType === SpaceType.HIGHSEC) {
+            otherSpaceType = SpaceType.LOWSEC;
+        } else if (spaceType === SpaceType.LOWSEC) {
+            otherSpaceType = SpaceType.HIGHSEC;
+        } else {
+            return null;
+        }
+        const jumps = this.starMap.jumpsToSpaceType(system.id, otherSpaceType, 5);
+        if (jumps == null) {
+            return null;
+        }
+        return `${jumps} ${jumps === 1 ? 'jump' : 'jumps'} from ${otherSpaceType}`;
+    }
+
+    // Link buttons survive embed truncation, unlike the links in the embed fields
+    public linkButtonRow(data: ZkData, provider?: MapProviderKind): MessageActionRow {
+        const link = (label: string, url: string) => new MessageButton().setStyle('LINK').setLabel(label).setURL(url);
+        const row = new MessageActionRow().addComponents(link('zKillboard', data.zkb.url));
+        // The map sites have no pages for abyssal pockets
+        if (!isAbyssalSystemId(data.solar_system_id)) {
+            const systemName = this.systems.get(data.solar_system_id)?.systemName;
+            row.addComponents(link(mapProvider(provider).label, systemMapUrl(data.solar_system_id, systemName, provider)));
+        }
+        row.addComponents(link('Related', strRelatedKills(data.solar_system_id, new Date(data.killmail_time))));
+        if (data.victim.character_id != null) {
+            row.addComponents(link('EveWho', this.strEveWho(data.victim.character_id)));
+        }
+        return row;
+    }
+
+    public valueFooterText(subscription: Subscription, zkb: Zkb, locale = Locale.EN, hullTypeId?: number, guildId?: string): string {
+        const total = this.formatValue(guildId, zkb.totalValue);
+        if (!subscription.showValueBreakdown) {
+            return translate(locale, 'embed.value', {total});
+        }
+        // zKillboard does not report the hull value on its own, the fitted value includes the hull. The current market
+        // price of the hull splits it off, without one the fit is shown with the hull
+        const hullValue = this.marketPrices.price(hullTypeId);
+        if (hullValue == null) {
+            return translate(locale, 'embed.valueBreakdownNoHull', {fit: this.formatValue(guildId, zkb.fittedValue), total});
+        }
+        return translate(locale, 'embed.valueBreakdown', {
+            hull: this.formatValue(guildId, hullValue),
+            fit: this.formatValue(guildId, Math.max(0, zkb.fittedValue - hullValue)),
+            total,
+        });
+    }
+
+    public abbreviateNumber(n: number): string {
+        return abbreviateValue(n);
+    }
+
+    // ISK value in the value format of the guild, abbreviated outside of guilds
+    public formatValue(guildId: string | undefined, n: number): string {
+        const guild = guildId ? this.subscriptions.get(guildId) : undefined;
+        if (guild == null) {
+            return abbreviateValue(n);
+        }
+        return formatValue(n, guild, parseLocale(guild.locale), this.marketPrices.price(PLEX_TYPE_ID));
     }
 
     findMostCommonShipTypeIdAndCount(attackers: Attacker[]): {shipTypeId: number, count: number} | null {
@@ -1121,97 +3208,876 @@ export class ZKillSubscriber {
             }
         }
 
-        return mostCommonShipTypeId ? {shipTypeId: mostCommonShipTypeId, count: maxCount} : null;
+        return mostCommonShipTypeId ? {shipTypeId: mostCommonShipTypeId, count: maxCount} : null;
+    }
+
+    public getArticle(word: string, capitalize = true): string {
+        logger.info(word);
+        const vowels = ['a', 'e', 'i', 'o', 'u'];
+        let res = vowels.includes(word[0].toLowerCase()) ? 'An' : 'A';
+        if (!capitalize) {
+            res = res.toLowerCase();
+        }
+        return res;
+    }
+    // Unsubscribes the channel only after it failed repeatedly, a single 403 or 404 can be a Discord hiccup
+    private async handleSendFailure(channel: TextChannel, kind: SendErrorKind, error: unknown) {
+        logger.info(error);
+        if (kind === SendErrorKind.CHANNEL_UNAVAILABLE && this.channelFailures.fail(channel.id)) {
+            await this.disableChannel(channel.guild.id, channel.id, 'the bot is not able to write in it');
+        }
+    }
+
+    // Disables instead of deleting the subscriptions of the channel, so fixing a permission mistake keeps the filters
+    public async disableChannel(guildId: string, channelId: string, reason: string, now = new Date()) {
+        const subscriptions = this.subscriptions.get(guildId)?.channels.get(channelId)?.subscriptions;
+        const disabled: Subscription[] = [];
+        subscriptions?.forEach(subscription => {
+            if (!subscription.disabledAt) {
+                subscription.disabledReason = reason;
+                subscription.disabledAt = now.toISOString();
+                disabled.push(subscription);
+            }
+        });
+        if (disabled.length === 0) {
+            return;
+        }
+        this.saveGuild(guildId);
+        const message = `The bot disabled ${disabled.length} subscriptions in <#${channelId}> because ${reason}. ` +
+            `Fix the permissions and run /zkill-resume in the channel within ${this.disabledPurgeDays} days, otherwise they are deleted.`;
+        try {
+            const alertChannelId = this.subscriptions.get(guildId)?.alertChannelId;
+            const alertChannel = alertChannelId ? <TextChannel>this.doClient.channels.cache.get(alertChannelId) : undefined;
+            if (alertChannel) {
+                await alertChannel.send(message);
+            } else {
+                const owner = await this.doClient.guilds.cache.get(guildId)?.fetchOwner();
+                await owner?.send(message);
+            }
+        } catch (e) {
+            logger.info(`failed to notify about the disabled subscriptions in ${channelId}: ${e}`);
+        }
+    }
+
+    // Disables the subscriptions of deleted channels and of channels the bot cannot post embeds in, so the alert
+    // channel learns about them at startup instead of at the next kill. Returns the number of channels that cannot post
+    public async reconcileChannels(): Promise<number> {
+        let disabled = 0;
+        const channels: [string, string][] = [];
+        this.subscriptions.forEach((guild, guildId) => guild.channels.forEach((channel, channelId) => {
+            if (channel.subscriptions.size > 0) {
+                channels.push([guildId, channelId]);
+            }
+        }));
+        for (const [guildId, channelId] of channels) {
+            const guild = this.doClient.guilds.cache.get(guildId);
+            if (!guild) {
+                // Not in the cache yet or no longer a member, the guild events handle that
+                continue;
+            }
+            let reason: string | null = null;
+            const channel = await guild.channels.fetch(channelId).catch(() => null);
+            if (!channel) {
+                reason = 'the channel no longer exists';
+            } else {
+                const missing = missingChannelPermissions(guild.me ? channel.permissionsFor(guild.me) : null);
+                if (missing.length > 0) {
+                    reason = `the bot lacks the ${missing.join(', ')} permissions`;
+                }
+            }
+            if (reason) {
+                logger.info(`subscriptions in ${channelId} cannot post: ${reason}`);
+                await this.disableChannel(guildId, channelId, reason);
+                disabled++;
+            }
+        }
+        return disabled;
+    }
+
+    // Enables the disabled subscriptions of the channel again, returns their number
+    public resumeChannel(guildId: string, channelId: string): number {
+        let resumed = 0;
+        this.subscriptions.get(guildId)?.channels.get(channelId)?.subscriptions.forEach(subscription => {
+            if (subscription.disabledAt) {
+                delete subscription.disabledReason;
+                delete subscription.disabledAt;
+                resumed++;
+            }
+        });
+        if (resumed > 0) {
+            this.channelFailures.succeed(channelId);
+            this.saveGuild(guildId);
+        }
+        return resumed;
+    }
+
+    // Deletes the subscriptions disabled for longer than DISABLED_PURGE_DAYS
+    public async purgeDisabledSubscriptions(now = new Date()) {
+        const cutoff = now.getTime() - this.disabledPurgeDays * 24 * 60 * 60 * 1000;
+        const purge: [string, string, Subscription][] = [];
+        this.subscriptions.forEach((guild, guildId) => guild.channels.forEach((channel, channelId) => {
+            channel.subscriptions.forEach(subscription => {
+                if (subscription.disabledAt && new Date(subscription.disabledAt).getTime() < cutoff) {
+                    purge.push([guildId, channelId, subscription]);
+                }
+            });
+        }));
+        for (const [guildId, channelId, subscription] of purge) {
+            logger.info(`deleting subscription ${subscription.id} in ${channelId}, disabled since ${subscription.disabledAt}`);
+            await this.unsubscribe(subscription.subType, guildId, channelId, subscription.id);
+        }
+    }
+
+    public setGuildAlertChannel(guildId: string, channelId?: string) {
+        this.updateGuildSettings(guildId, {alertChannelId: channelId});
+    }
+
+    public static getInstance(client?: Client, connect = true) {
+        if (!this.instance && client)
+            this.instance = new ZKillSubscriber(client, connect);
+        else if (!this.instance) {
+            throw new Error('Instance needs to be created with a client once.');
+        }
+
+        return this.instance;
+    }
+
+    public subscribe(
+        subType: SubscriptionType,
+        guildId: string,
+        channel: string,
+        limitTypes: Map<LimitType, string>,
+        flags: SubscriptionFlags,
+        id?: string,
+        minValue = 0,
+        createdBy?: string,
+    ) {
+        if (!this.subscriptions.has(guildId)) {
+            this.subscriptions.set(guildId, {channels: new Map<string, SubscriptionChannel>()});
+        }
+        const guild = this.subscriptions.get(guildId);
+        if (!guild?.channels.has(channel)) {
+            guild?.channels.set(channel, {subscriptions: new Map<string, Subscription>()});
+        }
+        const guildChannel = guild?.channels.get(channel);
+        const ident = `${subType}${id ? id : ''}`;
+        if (!guildChannel?.subscriptions.has(ident)) {
+            const subscription: Subscription = {
+                subType,
+                id,
+                createdBy,
+                minValue,
+                limitTypes,
+                inclusionLimitAlsoComparesAttacker: flags.inclusionLimitAlsoComparesAttacker,
+                inclusionLimitAlsoComparesAttackerWeapons: flags.inclusionLimitAlsoComparesAttackerWeapons,
+                exclusionLimitAlsoComparesAttacker: flags.exclusionLimitAlsoComparesAttacker,
+                exclusionLimitAlsoComparesAttackerWeapons: flags.exclusionLimitAlsoComparesAttackerWeapons,
+                pingRoleId: guild?.defaultPingRoleId,
+                pingRoleValue: guild?.defaultPingRoleValue,
+            };
+            guildChannel?.subscriptions.set(ident, subscription);
+            this.resolveSubscriptionShipGroups(guildId, subscription).catch((e) => {
+                logger.info(`failed to resolve ship groups for subscription ${id}: ${e}`);
+            });
+        }
+        this.saveGuild(guildId);
+    }
+
+    public subscribeDm(userId: string, limitTypes: Map<LimitType, string>, id: string, minValue = 0, dryRun?: DryRunMode) {
+        if (!this.dmSubscriptions.has(userId)) {
+            this.dmSubscriptions.set(userId, {subscriptions: new Map<string, Subscription>()});
+        }
+        const ident = `${SubscriptionType.PUBLIC}${id}`;
+        (<SubscriptionChannel>this.dmSubscriptions.get(userId)).subscriptions.set(ident, {
+            subType: SubscriptionType.PUBLIC,
+            id,
+            createdBy: userId,
+            minValue,
+            limitTypes,
+            dryRun,
+            ...this.init_subscription_flags(),
+        });
+        this.saveDmSubscriptions(userId);
+    }
+
+    // Returns false if the user has no direct message subscription with the ID
+    public unsubscribeDm(userId: string, id: string): boolean {
+        const removed = this.dmSubscriptions.get(userId)?.subscriptions.delete(`${SubscriptionType.PUBLIC}${id}`) ?? false;
+        if (removed) {
+            if (this.dmSubscriptions.get(userId)?.subscriptions.size === 0) {
+                this.dmSubscriptions.delete(userId);
+            }
+            this.saveDmSubscriptions(userId);
+        }
+        return removed;
+    }
+
+    public countDmSubscriptions(userId: string): number {
+        return this.dmSubscriptions.get(userId)?.subscriptions.size ?? 0;
+    }
+
+    public canSubscribeDm(userId: string): boolean {
+        return this.hostedMode.canSubscribe(this.countDmSubscriptions(userId));
+    }
+
+    // Stored below dm/, so the files are not loaded as guild configs
+    private saveDmSubscriptions(userId: string, base_dir = './config/') {
+        this.invalidateSubscriptionIndex();
+        const file = base_dir + 'dm/' + userId + '.json';
+        const dm = this.dmSubscriptions.get(userId);
+        if (!dm) {
+            if (fs.existsSync(file)) {
+                fs.unlinkSync(file);
+            }
+            return;
+        }
+        if (!fs.existsSync(base_dir + 'dm/')) {
+            fs.mkdirSync(base_dir + 'dm/', {recursive: true});
+        }
+        fs.writeFileSync(file, JSON.stringify(this.generateObject(dm)), 'utf8');
+    }
+
+    private saveGuild(guildId: string) {
+        this.invalidateSubscriptionIndex();
+        const guild = this.subscriptions.get(guildId);
+        if (guild) {
+            this.cacheWriter.discard('./config/' + guildId + '.json');
+            fs.writeFileSync('./config/' + guildId + '.json', JSON.stringify(this.generateObject(guild)), 'utf8');
+        }
+    }
+
+    // Writes the guild in the background, for changes with every posted kill that do not touch the subscriptions
+    private markGuildDirty(guildId: string) {
+        const guild = this.subscriptions.get(guildId);
+        if (guild) {
+            this.cacheWriter.markDirty('./config/' + guildId + '.json', () => JSON.stringify(this.generateObject(guild)));
+        }
+    }
+
+    private getSubscription(subType: SubscriptionType, guildId: string, channel: string, id?: string): Subscription | undefined {
+        return this.subscriptions.get(guildId)?.channels.get(channel)?.subscriptions.get(`${subType}${id ? id : ''}`);
+    }
+
+    // Lowest numeric subscription ID not used in the channel yet
+    public freeSubscriptionId(subType: SubscriptionType, guildId: string, channel: string): string {
+        let id = 1;
+        while (this.getSubscription(subType, guildId, channel, String(id))) {
+            id++;
+        }
+        return String(id);
+    }
+
+    // Copies the subscription into the target channel, false if it does not exist or the target already has the ID
+    public cloneSubscriptionTo(
+        subType: SubscriptionType,
+        guildId: string,
+        channel: string,
+        targetChannel: string,
+        overrides: Map<LimitType, string>,
+        id?: string,
+        targetId = id,
+        createdBy?: string,
+    ): boolean {
+        const subscription = this.getSubscription(subType, guildId, channel, id);
+        const guild = this.subscriptions.get(guildId);
+        if (!subscription || !guild || this.getSubscription(subType, guildId, targetChannel, targetId)) {
+            return false;
+        }
+        if (!guild.channels.has(targetChannel)) {
+            guild.channels.set(targetChannel, {subscriptions: new Map<string, Subscription>()});
+        }
+        const clone = cloneSubscription(subscription, targetId, overrides, createdBy);
+        (<SubscriptionChannel>guild.channels.get(targetChannel)).subscriptions.set(`${subType}${targetId ? targetId : ''}`, clone);
+        this.resolveSubscriptionShipGroups(guildId, clone).catch((e) => {
+            logger.info(`failed to resolve ship groups for subscription ${targetId}: ${e}`);
+        });
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    public configureSubscription(
+        subType: SubscriptionType,
+        guildId: string,
+        channel: string,
+        settings: SubscriptionSettings,
+        id?: string,
+    ): boolean {
+        const subscription = this.getSubscription(subType, guildId, channel, id);
+        if (!subscription) {
+            return false;
+        }
+        Object.assign(subscription, settings);
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    // Adds a mirror channel, or replaces the ping settings of an existing one
+    public setMirrorChannel(subType: SubscriptionType, guildId: string, channel: string, mirror: MirrorChannel, id?: string): boolean {
+        const subscription = this.getSubscription(subType, guildId, channel, id);
+        if (!subscription) {
+            return false;
+        }
+        subscription.mirrorChannels = (subscription.mirrorChannels ?? []).filter(existing => existing.channelId !== mirror.channelId);
+        subscription.mirrorChannels.push(mirror);
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    // False if the subscription does not exist or does not post to the mirror channel
+    public removeMirrorChannel(subType: SubscriptionType, guildId: string, channel: string, mirrorChannelId: string, id?: string): boolean {
+        const subscription = this.getSubscription(subType, guildId, channel, id);
+        if (!subscription?.mirrorChannels?.some(mirror => mirror.channelId === mirrorChannelId)) {
+            return false;
+        }
+        subscription.mirrorChannels = subscription.mirrorChannels.filter(mirror => mirror.channelId !== mirrorChannelId);
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    // Adds a webhook, or replaces the kind and secret of an existing one with the URL
+    public setWebhook(subType: SubscriptionType, guildId: string, channel: string, webhook: Webhook, webhookOnly: boolean, id?: string): boolean {
+        const subscription = this.getSubscription(subType, guildId, channel, id);
+        if (!subscription) {
+            return false;
+        }
+        subscription.webhooks = (subscription.webhooks ?? []).filter(existing => existing.url !== webhook.url);
+        subscription.webhooks.push(webhook);
+        subscription.webhookOnly = webhookOnly;
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    // False if the subscription does not exist or does not post to the webhook. Without webhooks the channel is posted
+    // to again
+    public removeWebhook(subType: SubscriptionType, guildId: string, channel: string, url: string, id?: string): boolean {
+        const subscription = this.getSubscription(subType, guildId, channel, id);
+        if (!subscription?.webhooks?.some(webhook => webhook.url === url)) {
+            return false;
+        }
+        subscription.webhooks = subscription.webhooks.filter(webhook => webhook.url !== url);
+        if (subscription.webhooks.length === 0) {
+            delete subscription.webhookOnly;
+        }
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    public setNotifierTarget(subType: SubscriptionType, guildId: string, channel: string, target: NotifierTarget, id?: string): boolean {
+        const subscription = this.getSubscription(subType, guildId, channel, id);
+        if (!subscription) {
+            return false;
+        }
+        subscription.notifierTargets = (subscription.notifierTargets ?? [])
+            .filter(existing => existing.kind !== target.kind || existing.target !== target.target);
+        subscription.notifierTargets.push(target);
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    // False if the subscription does not exist or does not post to the target
+    public removeNotifierTarget(subType: SubscriptionType, guildId: string, channel: string, target: NotifierTarget, id?: string): boolean {
+        const subscription = this.getSubscription(subType, guildId, channel, id);
+        const matches = (existing: NotifierTarget) => existing.kind === target.kind && existing.target === target.target;
+        if (!subscription?.notifierTargets?.some(matches)) {
+            return false;
+        }
+        subscription.notifierTargets = subscription.notifierTargets.filter(existing => !matches(existing));
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    // Stops every subscription of the guild from posting to a deleted channel
+    private removeMirrorChannelFromGuild(guildId: string, mirrorChannelId: string) {
+        let removed = false;
+        this.subscriptions.get(guildId)?.channels.forEach(channel => channel.subscriptions.forEach(subscription => {
+            if (subscription.mirrorChannels?.some(mirror => mirror.channelId === mirrorChannelId)) {
+                subscription.mirrorChannels = subscription.mirrorChannels.filter(mirror => mirror.channelId !== mirrorChannelId);
+                removed = true;
+            }
+        }));
+        if (removed) {
+            this.saveGuild(guildId);
+        }
+    }
+
+    // Sets or, if value is null, removes a single limit type of an existing subscription
+    public setSubscriptionLimit(
+        subType: SubscriptionType,
+        guildId: string,
+        channel: string,
+        limitType: LimitType,
+        value: string | null,
+        id?: string,
+    ): boolean {
+        const subscription = this.getSubscription(subType, guildId, channel, id);
+        if (!subscription) {
+            return false;
+        }
+        if (value == null) {
+            subscription.limitTypes.delete(limitType);
+        } else {
+            subscription.limitTypes.set(limitType, value);
+        }
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    public getGuildLocale(guildId: string): Locale {
+        return parseLocale(this.subscriptions.get(guildId)?.locale);
+    }
+
+    public setGuildLocale(guildId: string, locale: Locale) {
+        this.updateGuildSettings(guildId, {locale});
+    }
+
+    public setGuildAdminRole(guildId: string, roleId?: string) {
+        this.updateGuildSettings(guildId, {adminRoleId: roleId});
+    }
+
+    public getGuildSettings(guildId: string): GuildSettings {
+        const guild = this.subscriptions.get(guildId);
+        return {
+            locale: guild?.locale,
+            adminRoleId: guild?.adminRoleId,
+            subscriberRoleIds: guild?.subscriberRoleIds,
+            alertChannelId: guild?.alertChannelId,
+            ownEntityIds: guild?.ownEntityIds,
+            defaultPingRoleId: guild?.defaultPingRoleId,
+            defaultPingRoleValue: guild?.defaultPingRoleValue,
+            embedMode: guild?.embedMode,
+            hideThumbnails: guild?.hideThumbnails,
+            colorBlind: guild?.colorBlind,
+            mapProvider: guild?.mapProvider,
+            postDuplicates: guild?.postDuplicates,
+            valueFormat: guild?.valueFormat,
+            showPlexValue: guild?.showPlexValue,
+        };
+    }
+
+    // Like configureSubscription, settings set to undefined are removed
+    public updateGuildSettings(guildId: string, settings: GuildSettings) {
+        if (!this.subscriptions.has(guildId)) {
+            this.subscriptions.set(guildId, {channels: new Map<string, SubscriptionChannel>()});
+        }
+        Object.assign(<SubscriptionGuild>this.subscriptions.get(guildId), settings);
+        this.saveGuild(guildId);
+    }
+
+    public getEntityLists(guildId: string): EntityLists {
+        return this.subscriptions.get(guildId)?.entityLists ?? {};
+    }
+
+    public getEntityListsUpdatedAt(guildId: string): { [name: string]: string } {
+        return this.subscriptions.get(guildId)?.entityListsUpdatedAt ?? {};
+    }
+
+    // Adds the IDs to the list, creating it if needed. Returns the size of the list
+    public addToEntityList(guildId: string, name: string, ids: number[]): number {
+        if (!this.subscriptions.has(guildId)) {
+            this.subscriptions.set(guildId, {channels: new Map<string, SubscriptionChannel>()});
+        }
+        const guild = <SubscriptionGuild>this.subscriptions.get(guildId);
+        guild.entityLists = guild.entityLists ?? {};
+        const list = guild.entityLists[name] ?? [];
+        guild.entityLists[name] = list.concat(ids.filter((id, index) => !list.includes(id) && ids.indexOf(id) === index));
+        guild.entityListsUpdatedAt = {...guild.entityListsUpdatedAt, [name]: new Date().toISOString()};
+        this.saveGuild(guildId);
+        return guild.entityLists[name].length;
+    }
+
+    // Removes the IDs from the list, an emptied list is deleted. Null if the list does not exist
+    public removeFromEntityList(guildId: string, name: string, ids: number[]): number | null {
+        const guild = this.subscriptions.get(guildId);
+        const entityLists = guild?.entityLists;
+        if (!guild || !entityLists || !entityLists[name]) {
+            return null;
+        }
+        entityLists[name] = entityLists[name].filter(id => !ids.includes(id));
+        const size = entityLists[name].length;
+        guild.entityListsUpdatedAt = {...guild.entityListsUpdatedAt, [name]: new Date().toISOString()};
+        if (size === 0) {
+            delete entityLists[name];
+            delete guild.entityListsUpdatedAt[name];
+        }
+        this.saveGuild(guildId);
+        return size;
+    }
+
+    public getReport(guildId: string, channelId: string): Report | undefined {
+        return this.subscriptions.get(guildId)?.reports?.[channelId];
+    }
+
+    // Replaces the report of the channel, or removes it if report is null
+    public setReport(guildId: string, channelId: string, report: Report | null) {
+        if (!this.subscriptions.has(guildId)) {
+            this.subscriptions.set(guildId, {channels: new Map<string, SubscriptionChannel>()});
+        }
+        const guild = <SubscriptionGuild>this.subscriptions.get(guildId);
+        guild.reports = guild.reports ?? {};
+        if (report) {
+            guild.reports[channelId] = report;
+        } else {
+            delete guild.reports[channelId];
+        }
+        this.saveGuild(guildId);
+    }
+
+    // Posts the reports whose scheduled time passed since their last run
+    public async postDueReports(now = new Date()) {
+        const due: [string, string, Report][] = [];
+        this.subscriptions.forEach((guild, guildId) => Object.keys(guild.reports ?? {}).forEach(channelId => {
+            const report = (<{ [channelId: string]: Report }>guild.reports)[channelId];
+            if (isReportDue(report, now)) {
+                due.push([guildId, channelId, report]);
+            }
+        }));
+        for (const [guildId, channelId, report] of due) {
+            report.lastRun = now.toISOString();
+            this.saveGuild(guildId);
+            const channel = <TextChannel>this.doClient.channels.cache.get(channelId);
+            if (!channel) {
+                this.setReport(guildId, channelId, null);
+                continue;
+            }
+            try {
+                await channel.send({embeds: [await this.reportEmbed(guildId, channelId, report, now)]});
+            } catch (e) {
+                logger.info(`failed to post the report in ${channelId}: ${e}`);
+            }
+        }
+    }
+
+    public async reportEmbed(guildId: string, channelId: string, report: Report, now = new Date()): Promise<MessageEmbedOptions> {
+        const days = reportPeriodDays(report);
+        const summary = this.killStats.summarize(guildId, days, now, channelId);
+        const list = async (counts: { id: number, count: number }[], name: (id: number) => Promise<string>) => {
+            let text = '';
+            for (const {id, count} of counts) {
+                text += `${await name(id).catch(() => String(id))}: ${count}\n`;
+            }
+            return text || '-';
+        };
+        return {
+            title: `${days === 1 ? 'Daily' : 'Weekly'} intel report`,
+            description: `${summary.kills} kills matched, ${this.formatValue(guildId, summary.totalValue - summary.lostValue)} ISK destroyed, ` +
+                `${this.formatValue(guildId, summary.lostValue)} ISK lost`,
+            fields: [
+                {name: '__Most Active Systems__', value: await list(summary.topSystems, async id => (await this.getSystemData(id)).systemName), inline: true},
+                {name: '__Top Hostile Alliances__', value: await list(summary.topHostileAlliances, id => this.getNameForAlliance(id)), inline: true},
+                {name: '__Top Victim Alliances__', value: await list(summary.topVictimAlliances, id => this.getNameForAlliance(id)), inline: true},
+            ],
+            footer: {text: `Posted ${describeSchedule(report)}`},
+            timestamp: now,
+        };
+    }
+
+    public getCampaign(guildId: string, name: string): Campaign | undefined {
+        return this.subscriptions.get(guildId)?.campaigns?.[name];
+    }
+
+    // Subscribes the channel to the kills of the campaign and starts counting them. False if a running campaign has the
+    // name, a stopped one is replaced
+    public startCampaign(
+        guildId: string,
+        channelId: string,
+        name: string,
+        friendlyIds: number[],
+        limitTypes: Map<LimitType, string>,
+        minValue: number,
+        createdBy: string,
+    ): boolean {
+        const existing = this.getCampaign(guildId, name);
+        if (existing && !existing.stoppedAt) {
+            return false;
+        }
+        const campaign = newCampaign(channelId, name, friendlyIds);
+        this.subscribe(SubscriptionType.PUBLIC, guildId, channelId, limitTypes, this.init_subscription_flags(),
+            campaign.subscriptionId, minValue, createdBy);
+        const subscription = this.subscriptions.get(guildId)?.channels.get(channelId)?.subscriptions
+            .get(`${SubscriptionType.PUBLIC}${campaign.subscriptionId}`);
+        if (subscription) {
+            subscription.campaign = name;
+        }
+        const guild = <SubscriptionGuild>this.subscriptions.get(guildId);
+        guild.campaigns = guild.campaigns ?? {};
+        guild.campaigns[name] = campaign;
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    // Relays every kill and loss of the entity to the channel, false if the channel already tracks it
+    public trackEntity(guildId: string, channelId: string, tracking: EntityTracking, createdBy: string): boolean {
+        const id = trackingSubscriptionId(tracking.entityId);
+        if (this.getSubscription(SubscriptionType.PUBLIC, guildId, channelId, id)) {
+            return false;
+        }
+        const limitTypes = new Map<LimitType, string>([[trackingLimitType(tracking.type), String(tracking.entityId)]]);
+        this.subscribe(SubscriptionType.PUBLIC, guildId, channelId, limitTypes, this.init_subscription_flags(), id, 0, createdBy);
+        const subscription = this.getSubscription(SubscriptionType.PUBLIC, guildId, channelId, id);
+        if (subscription) {
+            subscription.tracking = tracking;
+            subscription.matchSummary = true;
+        }
+        this.saveGuild(guildId);
+        return true;
+    }
+
+    // Removes the tracking subscription of the entity with the ID or name, null if the channel does not track it
+    public async untrackEntity(guildId: string, channelId: string, entity: string): Promise<EntityTracking | null> {
+        const channel = this.subscriptions.get(guildId)?.channels.get(channelId);
+        const subscription = Array.from(channel?.subscriptions.values() ?? [])
+            .find(candidate => candidate.tracking != null && matchesTrackedEntity(candidate.tracking, entity));
+        if (!subscription?.tracking) {
+            return null;
+        }
+        await this.unsubscribe(SubscriptionType.PUBLIC, guildId, channelId, subscription.id);
+        return subscription.tracking;
+    }
+
+    // Freezes the totals and removes the subscription of the campaign, null if there is no running campaign with the name
+    public async stopCampaign(guildId: string, name: string, now = new Date()): Promise<Campaign | null> {
+        const campaign = this.getCampaign(guildId, name);
+        if (!campaign || campaign.stoppedAt) {
+            return null;
+        }
+        campaign.stoppedAt = now.toISOString();
+        await this.unsubscribe(SubscriptionType.PUBLIC, guildId, campaign.channelId, campaign.subscriptionId);
+        this.saveGuild(guildId);
+        return campaign;
+    }
+
+    private recordCampaignKill(guildId: string, name: string, data: ZkData) {
+        const campaign = this.getCampaign(guildId, name);
+        if (campaign) {
+            recordCampaignKill(campaign, data);
+            this.saveGuild(guildId);
+        }
+    }
+
+    public async campaignEmbed(name: string, campaign: Campaign, now = new Date()): Promise<MessageEmbedOptions> {
+        const efficiency = iskEfficiency(campaign);
+        let pilots = '';
+        for (const {characterId, kills} of topParticipants(campaign, 10)) {
+            pilots += `${await this.getNameForCharacter(characterId).catch(() => String(characterId))}: ${kills}\n`;
+        }
+        const end = campaign.stoppedAt ? new Date(campaign.stoppedAt) : now;
+        const days = Math.max(1, Math.ceil((end.getTime() - new Date(campaign.startedAt).getTime()) / (24 * 60 * 60 * 1000)));
+        return {
+            title: `Campaign ${name}${campaign.stoppedAt ? ' (stopped)' : ''}`,
+            description: `${campaign.kills} kills, ${campaign.losses} losses over ${days} days, ` +
+                `${Object.keys(campaign.participants).length} pilots participated`,
+            fields: [
+                {name: '__ISK Destroyed__', value: String(this.abbreviateNumber(campaign.iskDestroyed)), inline: true},
+                {name: '__ISK Lost__', value: String(this.abbreviateNumber(campaign.iskLost)), inline: true},
+                {name: '__ISK Efficiency__', value: efficiency != null ? `${efficiency}%` : '-', inline: true},
+                {name: '__Top Pilots__', value: pilots || '-', inline: false},
+            ],
+            footer: {text: `Started ${campaign.startedAt.substring(0, 10)}`},
+            timestamp: now,
+        };
+    }
+
+    public async lastSeenEmbed(name: string, sighting: Sighting, now = new Date()): Promise<MessageEmbedOptions> {
+        const system = await this.getSystemData(sighting.systemId);
+        const ship = sighting.shipTypeId ? await this.getNameForEntityId(sighting.shipTypeId).catch(() => null) : null;
+        return {
+            title: `${name} last seen ${relativeTimeText(Locale.EN, now.getTime() - Date.parse(sighting.time))} ago`,
+            url: sighting.url,
+            fields: [
+                {name: '__System__', value: `[${system.systemName}](${systemMapUrl(system.id, system.systemName)})`, inline: true},
+                {name: '__Region__', value: system.regionName, inline: true},
+                {name: '__Ship__', value: ship ?? '-', inline: true},
+                {name: '__Side__', value: sighting.loss ? 'Victim' : 'Attacker', inline: true},
+            ],
+            timestamp: new Date(sighting.time),
+        };
+    }
+
+    // Null if this instance does not archive kills
+    public async searchKillArchive(query: KillQuery): Promise<KillSearchResult | null> {
+        if (!this.killArchive) {
+            return null;
+        }
+        // The buffered kills are searched as well
+        this.killArchive.flush();
+        return searchArchive(this.killArchive.dir, query, typeId => this.getGroupIdForEntityId(typeId).catch(() => null));
+    }
+
+    public async resolveSystemId(name: string): Promise<number | null> {
+        const system = this.starMap?.findSystemByName(name);
+        if (system) {
+            return system.id;
+        }
+        const systems = (await this.esiClient.getIdsForName(name)).systems ?? [];
+        return systems.length > 0 ? systems[0].id : null;
+    }
+
+    public async killSearchEmbed(result: KillSearchResult, page: number): Promise<MessageEmbedOptions> {
+        let lines = '';
+        for (const data of pageOf(result.kills, page)) {
+            const ship = await this.getNameForEntityId(data.victim.ship_type_id).catch(() => String(data.victim.ship_type_id));
+            const system = this.systems.get(data.solar_system_id)?.systemName ?? String(data.solar_system_id);
+            lines += `${data.killmail_time.substring(0, 16).replace('T', ' ')} [${ship}](${data.zkb.url}) in ${system}, ` +
+                `${this.abbreviateNumber(data.zkb.totalValue)} ISK\n`;
+        }
+        const pages = pageCount(result.kills.length);
+        return {
+            title: `${result.kills.length} kills, ${this.abbreviateNumber(result.totalValue)} ISK destroyed`,
+            description: lines || 'No archived kill matches',
+            footer: {text: `Page ${Math.min(Math.max(page, 1), pages)} of ${pages}`},
+        };
+    }
+
+    // Null if this instance does not serve feeds
+    public getFeedUrl(token: string): string | null {
+        return this.feedServer ? this.feedServer.feedUrl(token) : null;
     }
 
-    public getArticle(word: string, capitalize = true): string {
-        console.log(word);
-        const vowels = ['a', 'e', 'i', 'o', 'u'];
-        let res = vowels.includes(word[0].toLowerCase()) ? 'An' : 'A';
-        if (!capitalize) {
-            res = res.toLowerCase();
+    private async getFeed(token: string): Promise<AtomFeed | null> {
+        let found: { guildId: string, channelId: string, subscription: Subscription } | null = null;
+        this.subscriptions.forEach((guild, guildId) => {
+            guild.channels.forEach((channel, channelId) => {
+                channel.subscriptions.forEach(subscription => {
+                    if (subscription.feedToken === token) {
+                        found = {guildId, channelId, subscription};
+                    }
+                });
+            });
+        });
+        if (!found || !this.feedServer) {
+            return null;
         }
-        return res;
-    }
-    private async handlePermissionError(channel: TextChannel) {
-        const owner = await channel.guild.fetchOwner();
-        await owner.send(`The bot unsubscribed from channel ${channel.name} on ${channel.guild.name} because it was not able to write in it! Fix the permissions and subscribe again!`);
-        const subscriptionsInChannel = this.subscriptions.get(channel.guild.id)?.channels.get(channel.id);
-        if (subscriptionsInChannel) {
-            subscriptionsInChannel.subscriptions.forEach((subscription) => {
-                this.unsubscribe(subscription.subType, channel.guild.id, channel.id, subscription.id);
+        const {guildId, channelId, subscription} = found as { guildId: string, channelId: string, subscription: Subscription };
+        const entries: AtomEntry[] = [];
+        for (const entry of this.killStats.subscriptionEntries(guildId, channelId, String(subscription.id), 50)) {
+            let systemName = String(entry.systemId);
+            try {
+                systemName = (await this.getSystemData(entry.systemId)).systemName;
+            } catch (e) {
+                logger.info(`Error getting system name for feed: ${e}`);
+            }
+            const value = this.abbreviateNumber(entry.totalValue);
+            entries.push({
+                id: `https://zkillboard.com/kill/${entry.killmailId}/`,
+                title: `${value} ISK kill in ${systemName}`,
+                link: `https://zkillboard.com/kill/${entry.killmailId}/`,
+                updated: new Date(entry.time).toISOString(),
+                summary: `Killmail ${entry.killmailId} in ${systemName}, ${value} ISK destroyed`,
             });
         }
+        return {
+            title: `zKill feed ${subscription.subType} ${subscription.id}`,
+            selfLink: this.feedServer.feedUrl(token),
+            entries,
+        };
     }
 
-    public static getInstance(client?: Client, connect = true) {
-        if (!this.instance && client)
-            this.instance = new ZKillSubscriber(client, connect);
-        else if (!this.instance) {
-            throw new Error('Instance needs to be created with a client once.');
-        }
+    // False if the guild reached its subscription quota on a hosted instance
+    public canSubscribe(guildId: string): boolean {
+        let count = 0;
+        this.subscriptions.get(guildId)?.channels.forEach((channel) => {
+            count += channel.subscriptions.size;
+        });
+        return this.hostedMode.canSubscribe(count);
+    }
 
-        return this.instance;
+    // False if mentions are blocked for the guild on a hosted instance
+    public canPing(guildId: string): boolean {
+        return this.hostedMode.canPing(guildId);
     }
 
-    public subscribe(
-        subType: SubscriptionType,
-        guildId: string,
-        channel: string,
-        limitTypes: Map<LimitType, string>,
-        flags: SubscriptionFlags,
-        id?: string,
-        minValue = 0,
-    ) {
+    // False if the channel has no such subscription
+    public async unsubscribe(subType: SubscriptionType, guildId: string, channel: string, id?: string): Promise<boolean> {
         if (!this.subscriptions.has(guildId)) {
-            this.subscriptions.set(guildId, {channels: new Map<string, SubscriptionChannel>()});
+            return false;
         }
         const guild = this.subscriptions.get(guildId);
         if (!guild?.channels.has(channel)) {
-            guild?.channels.set(channel, {subscriptions: new Map<string, Subscription>()});
+            return false;
         }
-        const guildChannel = guild?.channels.get(channel);
+        const guildChannel = guild.channels.get(channel);
         const ident = `${subType}${id ? id : ''}`;
         if (!guildChannel?.subscriptions.has(ident)) {
-            guildChannel?.subscriptions.set(ident, {
-                subType,
-                id,
-                minValue,
-                limitTypes,
-                inclusionLimitAlsoComparesAttacker: flags.inclusionLimitAlsoComparesAttacker,
-                inclusionLimitAlsoComparesAttackerWeapons: flags.inclusionLimitAlsoComparesAttackerWeapons,
-                exclusionLimitAlsoComparesAttacker: flags.exclusionLimitAlsoComparesAttacker,
-                exclusionLimitAlsoComparesAttackerWeapons: flags.exclusionLimitAlsoComparesAttackerWeapons
-            });
+            return false;
         }
-        fs.writeFileSync('./config/' + guildId + '.json', JSON.stringify(this.generateObject(guild)), 'utf8');
+        guildChannel.subscriptions.delete(ident);
+        this.saveGuild(guildId);
+        return true;
     }
 
-    public async unsubscribe(subType: SubscriptionType, guildId: string, channel: string, id?: string) {
-        if (!this.subscriptions.has(guildId)) {
-            return;
-        }
+    // Subscriptions of all channels of the guild containing the text
+    public findSubscriptions(guildId: string, text: string): SubscriptionSearchResult[] {
         const guild = this.subscriptions.get(guildId);
-        if (!guild?.channels.has(channel)) {
-            return;
+        return guild ? searchSubscriptions(guild.channels, text) : [];
+    }
+
+    // Removes the subscriptions with the IDs, or all of them if no IDs are given, from the channel. Returns the
+    // idents of the removed subscriptions
+    public bulkUnsubscribe(guildId: string, channel: string, ids?: string[]): string[] {
+        const subscriptions = this.subscriptions.get(guildId)?.channels.get(channel)?.subscriptions;
+        if (!subscriptions) {
+            return [];
         }
-        const guildChannel = guild.channels.get(channel);
-        const ident = `${subType}${id ? id : ''}`;
-        if (!guildChannel?.subscriptions.has(ident)) {
-            return;
+        const idents = ids ? ids.map(id => `${SubscriptionType.PUBLIC}${id}`) : Array.from(subscriptions.keys());
+        const removed = idents.filter(ident => subscriptions.delete(ident));
+        if (removed.length > 0) {
+            this.saveGuild(guildId);
         }
-        guildChannel.subscriptions.delete(ident);
-        fs.writeFileSync('./config/' + guildId + '.json', JSON.stringify(this.generateObject(guild)), 'utf8');
+        return removed;
     }
 
     public async unsubscribeGuild(guildId: string) {
         if (this.subscriptions.has(guildId)) {
             this.subscriptions.delete(guildId);
+            this.invalidateSubscriptionIndex();
+            this.cacheWriter.discard('./config/' + guildId + '.json');
             fs.unlinkSync('./config/' + guildId + '.json');
             return;
         }
     }
 
+    // Fetches a kill from zKillboard and ESI, e.g. to preview it through the subscriptions of a channel
+    public async fetchKill(killmailId: number, hash?: string): Promise<ZkData> {
+        const zkb = await this.zkbApi.getZkb(killmailId);
+        const killmail = await this.esiClient.getKillmail(killmailId, hash || zkb.hash);
+        return {...killmail, zkb};
+    }
+
+    // ID and exact name of a character, corporation or alliance, null if ESI knows no such name
+    public async resolveEntity(type: IntelEntityType, name: string): Promise<{ id: number, name: string } | null> {
+        const ids = await this.esiClient.getIdsForName(name);
+        const category = type === IntelEntityType.CORPORATION ? 'corporations' :
+            type === IntelEntityType.ALLIANCE ? 'alliances' : 'characters';
+        const entities = ids[category] ?? [];
+        return entities.length > 0 ? entities[0] : null;
+    }
+
+    public async getEntityIntel(type: IntelEntityType, id: number): Promise<EntityIntel> {
+        return this.zkbApi.getEntityIntel(type, id);
+    }
+
+    // Builds the message the subscription would post for the kill, without sending it or counting it towards quotas
+    public async previewMessage(
+        guildId: string,
+        channelId: string,
+        subscription: Subscription,
+        data: ZkData,
+        evaluation: SubscriptionEvaluation,
+    ): Promise<MessageOptions> {
+        const embedding = await ogs({url: data.zkb.url}).catch(() => null);
+        return this.prepareMessageContent({
+            guildId,
+            channelId,
+            subscription,
+            embedding,
+            data,
+            matchedShip: evaluation.matchedShip,
+            minNumInvolved: evaluation.minNumInvolved,
+            messageColor: evaluation.color,
+            matchedAttackerCount: evaluation.matchedAttackerCount,
+        });
+    }
+
     public async listGuildChannelSubscriptions(guildId: string, channel: string) {
         if (this.subscriptions.has(guildId)) {
             const guild = this.subscriptions.get(guildId);
@@ -1228,7 +4094,7 @@ export class ZKillSubscriber {
             if (object[key] instanceof Map) {
                 newObject[key] = this.generateObject(Object.fromEntries(object[key]));
             } else if (Array.isArray(object[key])) {
-                newObject[key] = this.generateObject(object[key]);
+                newObject[key] = object[key].map((item: any) => typeof item === 'object' ? this.generateObject(item) : item);
             } else if (typeof object[key] === 'object') {
                 newObject[key] = this.generateObject(object[key]);
             } else {
@@ -1248,46 +4114,85 @@ export class ZKillSubscriber {
     }
 
     private createSubscriptionMap(object: any): Map<string, Subscription> {
-        console.log('Creating subscription map');
+        logger.info('Creating subscription map');
         const map = new Map<string, Subscription>();
         const keys = Object.keys(object);
         for (const key of keys) {
-            console.log('Creating subscription for ' + key);
+            logger.info('Creating subscription for ' + key);
             if (object[key].limitTypes === undefined) {
                 object[key].limitTypes = new Map<LimitType, string>;
             }
             if (object[key].limitTypes instanceof Object) {
-                console.log('Converting limitTypes from Object to Map');
+                logger.info('Converting limitTypes from Object to Map');
                 const properties = Object.entries(object[key].limitTypes);
                 object[key].limitTypes = new Map(properties);
-                console.log('LimitTypes = ' + object[key].limitTypes);
+                logger.info('LimitTypes = ' + object[key].limitTypes);
             }
             map.set(key, object[key]);
         }
         return map;
     }
 
-    private async getSystemData(systemId: number): Promise<SolarSystem> {
-        return await this.asyncLock.acquire('fetchSystem', async (done) => {
-            let system = this.systems.get(systemId);
-            if (!system) {
-                console.log('found undefined system with id ' + systemId);
-                system = await this.esiClient.getSystemInfo(systemId);
-                this.systems.set(systemId, system);
-                fs.writeFileSync('./config/systems.json', JSON.stringify(Object.fromEntries(this.systems)), 'utf8');
-            }
-            if (system.securityStatus >= 0.45) {
-                console.log('rounding security status: ' + system.securityStatus);
-                // round to nearest tenth decimal
-                system.securityStatus = Math.round(system.securityStatus * 10) / 10;
-            }
-            done(undefined, system);
-            return;
-        });
+    // Writes the pending cache changes, called on shutdown
+    public flushCaches() {
+        this.cacheWriter.flush();
+        this.killArchive?.flush();
+    }
+
+    public getKillStats(): KillStats {
+        return this.killStats;
+    }
+
+    public getSrpQueue(): SrpQueue {
+        return this.srpQueue;
+    }
+
+    // Records a ship replacement request for a loss, null if the kill was already requested
+    public async requestSrp(guildId: string, killmailId: number, requestedBy: string): Promise<SrpRequest | null> {
+        if (this.srpQueue.has(guildId, killmailId)) {
+            return null;
+        }
+        const data = await this.fetchKill(killmailId);
+        if (data.victim.character_id == null) {
+            throw new Error('the victim is not a pilot');
+        }
+        const request: SrpRequest = {
+            killmailId,
+            url: data.zkb.url,
+            characterId: data.victim.character_id,
+            pilotName: await this.getNameForCharacter(data.victim.character_id).catch(() => String(data.victim.character_id)),
+            shipTypeId: data.victim.ship_type_id,
+            shipName: data.victim.ship_type_id != null ?
+                await this.getNameForEntityId(data.victim.ship_type_id).catch(() => String(data.victim.ship_type_id)) : 'Unknown',
+            totalValue: data.zkb.totalValue,
+            requestedBy,
+            requestedAt: new Date().toISOString(),
+            status: SrpStatus.PENDING,
+        };
+        return this.srpQueue.add(guildId, request) ? request : null;
+    }
+
+    public async getSystemData(systemId: number): Promise<SolarSystem> {
+        let system = this.systems.get(systemId);
+        if (!system) {
+            system = await this.systemLookups.do(String(systemId), async () => {
+                logger.info('found undefined system with id ' + systemId);
+                const fetched = await this.esiClient.getSystemInfo(systemId);
+                this.systems.set(systemId, fetched);
+                this.cacheWriter.markDirty('./config/systems.json', () => this.systems.toJson());
+                return fetched;
+            });
+        }
+        if (system.securityStatus >= 0.45) {
+            logger.info('rounding security status: ' + system.securityStatus);
+            // round to nearest tenth decimal
+            system.securityStatus = Math.round(system.securityStatus * 10) / 10;
+        }
+        return system;
     }
 
-    private async isInLocationLimit(subscription: Subscription, solar_system_id: number) {
-        const systemData = await this.getSystemData(solar_system_id);
+    private async isInLocationLimit(subscription: Subscription, context: KillContext) {
+        const systemData = await context.system();
         if (hasLimitType(subscription, LimitType.SYSTEM) &&
             (getLimitType(subscription, LimitType.SYSTEM)?.split(',') || []).indexOf(systemData.id.toString()) !== -1) {
             return true;
@@ -1304,87 +4209,165 @@ export class ZKillSubscriber {
     }
 
     private async getGroupIdForEntityId(shipId: number): Promise<number> {
-        return await this.asyncLock.acquire('fetchShip', async (done) => {
-
-            let group = this.ships.get(shipId);
-            if (group) {
-                done(undefined, group);
-                return;
-            }
-            group = await this.esiClient.getTypeGroupId(shipId);
+        const cached = this.ships.get(shipId);
+        if (cached) {
+            return cached;
+        }
+        return await this.groupLookups.do(String(shipId), async () => {
+            const group = await this.esiClient.getTypeGroupId(shipId);
             this.ships.set(shipId, group);
-            fs.writeFileSync('./config/ships.json', JSON.stringify(Object.fromEntries(this.ships)), 'utf8');
-
-            done(undefined, group);
+            this.cacheWriter.markDirty('./config/ships.json', () => this.ships.toJson());
+            return group;
         });
     }
 
-    private async getNameForEntityId(shipId: number): Promise<string> {
-        return await this.asyncLock.acquire('fetchName', async (done) => {
+    private async getMetaGroupIdForTypeId(typeId: number): Promise<number | null> {
+        if (this.metaGroups.has(typeId)) {
+            return <number | null>this.metaGroups.get(typeId);
+        }
+        return await this.metaGroupLookups.do(String(typeId), async () => {
+            const metaGroupId = await this.esiClient.getTypeMetaGroupId(typeId);
+            this.metaGroups.set(typeId, metaGroupId);
+            return metaGroupId;
+        });
+    }
 
-            let name = this.names.get(shipId);
-            if (name) {
-                done(undefined, name);
-                return;
-            }
-            name = await this.esiClient.getTypeName(shipId);
-            this.names.set(shipId, name);
-            fs.writeFileSync('./config/names.json', JSON.stringify(Object.fromEntries(this.names)), 'utf8');
+    private async getCategoryIdForGroupId(groupId: number): Promise<number> {
+        const cached = this.categories.get(groupId);
+        if (cached) {
+            return cached;
+        }
+        return await this.categoryLookups.do(String(groupId), async () => {
+            const category = await this.esiClient.getGroupCategoryId(groupId);
+            this.categories.set(groupId, category);
+            this.cacheWriter.markDirty('./config/categories.json', () => JSON.stringify(Object.fromEntries(this.categories)));
+            return category;
+        });
+    }
 
-            done(undefined, name);
+    private async getGroupName(groupId: number): Promise<string> {
+        const cached = this.groupNames.get(groupId);
+        if (cached) {
+            return cached;
+        }
+        return await this.groupNameLookups.do(String(groupId), async () => {
+            const name = await this.esiClient.getGroupName(groupId);
+            this.groupNames.set(groupId, name);
+            return name;
         });
     }
 
-    private async getNameForAlliance(allianceId: number): Promise<string> {
-        return await this.asyncLock.acquire('fetchName', async (done) => {
+    private async getNameForEntityId(shipId: number): Promise<string> {
+        const cached = this.names.get(shipId);
+        if (cached) {
+            return cached;
+        }
+        return await this.nameLookups.do(String(shipId), async () => {
+            const name = await this.esiClient.getTypeName(shipId);
+            this.names.set(shipId, name);
+            this.cacheWriter.markDirty('./config/names.json', () => this.names.toJson());
+            return name;
+        });
+    }
 
-            let name = this.names.get(allianceId);
-            if (name) {
-                done(undefined, name);
-                return;
-            }
-            name = await this.esiClient.getAllianceName(allianceId);
+    public async getNameForAlliance(allianceId: number): Promise<string> {
+        const cached = this.names.get(allianceId);
+        if (cached) {
+            return cached;
+        }
+        return await this.nameLookups.do(String(allianceId), async () => {
+            const name = await this.esiClient.getAllianceName(allianceId);
             this.names.set(allianceId, name);
-            fs.writeFileSync('./config/names.json', JSON.stringify(Object.fromEntries(this.names)), 'utf8');
-
-            done(undefined, name);
+            this.cacheWriter.markDirty('./config/names.json', () => this.names.toJson());
+            return name;
         });
     }
 
-    private async getNameForCorporation(corporationId: number): Promise<string> {
-        return await this.asyncLock.acquire('fetchName', async (done) => {
-
-            let name = this.names.get(corporationId);
-            if (name) {
-                done(undefined, name);
-                return;
-            }
-            name = await this.esiClient.getCorporationName(corporationId);
+    public async getNameForCorporation(corporationId: number): Promise<string> {
+        const cached = this.names.get(corporationId);
+        if (cached) {
+            return cached;
+        }
+        return await this.nameLookups.do(String(corporationId), async () => {
+            const name = await this.esiClient.getCorporationName(corporationId);
             this.names.set(corporationId, name);
-            fs.writeFileSync('./config/names.json', JSON.stringify(Object.fromEntries(this.names)), 'utf8');
+            this.cacheWriter.markDirty('./config/names.json', () => this.names.toJson());
+            return name;
+        });
+    }
 
-            done(undefined, name);
+    public async getNameForCharacter(characterId: number): Promise<string> {
+        const cached = this.names.get(characterId);
+        if (cached) {
+            return cached;
+        }
+        return await this.nameLookups.do(String(characterId), async () => {
+            const name = await this.esiClient.getCharacterName(characterId);
+            this.names.set(characterId, name);
+            this.cacheWriter.markDirty('./config/names.json', () => this.names.toJson());
+            return name;
         });
     }
 
-    private async getNameForCharacter(characterId: number): Promise<string> {
-        return await this.asyncLock.acquire('fetchName', async (done) => {
+    private async getTickerForAlliance(allianceId: number): Promise<string> {
+        const cached = this.tickers.get(allianceId);
+        if (cached) {
+            return cached;
+        }
+        return await this.tickerLookups.do(String(allianceId), async () => {
+            const ticker = await this.esiClient.getAllianceTicker(allianceId);
+            this.tickers.set(allianceId, ticker);
+            this.cacheWriter.markDirty('./config/tickers.json', () => JSON.stringify(Object.fromEntries(this.tickers)));
+            return ticker;
+        });
+    }
 
-            let name = this.names.get(characterId);
-            if (name) {
-                done(undefined, name);
-                return;
-            }
-            name = await this.esiClient.getCharacterName(characterId);
-            this.names.set(characterId, name);
-            fs.writeFileSync('./config/names.json', JSON.stringify(Object.fromEntries(this.names)), 'utf8');
+    private async getTickerForCorporation(corporationId: number): Promise<string> {
+        const cached = this.tickers.get(corporationId);
+        if (cached) {
+            return cached;
+        }
+        return await this.tickerLookups.do(String(corporationId), async () => {
+            const ticker = await this.esiClient.getCorporationTicker(corporationId);
+            this.tickers.set(corporationId, ticker);
+            this.cacheWriter.markDirty('./config/tickers.json', () => JSON.stringify(Object.fromEntries(this.tickers)));
+            return ticker;
+        });
+    }
 
-            done(undefined, name);
+    private async getCorporationMemberCount(corporationId: number): Promise<number> {
+        const cacheKey = `members_${corporationId}`;
+        const cached: number | null = MemoryCache.get(cacheKey);
+        if (cached != null) {
+            return cached;
+        }
+        return await this.memberCountLookups.do(String(corporationId), async () => {
+            const members = await this.esiClient.getCorporationMemberCount(corporationId);
+            MemoryCache.put(cacheKey, members, MEMBER_COUNT_CACHE_MS);
+            return members;
         });
     }
 
+    // ` [TICKR]` after an affiliation name, empty if the ticker could not be fetched
+    private async tickerSuffix(lookup: Promise<string>): Promise<string> {
+        try {
+            const ticker = await lookup;
+            return ticker ? ` [${ticker}]` : '';
+        } catch (e) {
+            logger.info(e);
+            return '';
+        }
+    }
+
     private async getClosestCelestial(systemId: number, x: number, y: number, z: number): Promise<ClosestCelestial> {
-        return await this.esiClient.getCelestial(systemId, x, y, z);
+        const cacheKey = `celestial_${systemId}_${x}_${y}_${z}`;
+        const cached: ClosestCelestial | null = MemoryCache.get(cacheKey);
+        if (cached) {
+            return cached;
+        }
+        const celestial = await this.esiClient.getCelestial(systemId, x, y, z);
+        MemoryCache.put(cacheKey, celestial, CELESTIAL_CACHE_MS);
+        return celestial;
     }
 
     public withConfig(base_dir = './config/'): ZKillSubscriber {
@@ -1395,13 +4378,41 @@ export class ZKillSubscriber {
                 if (guildId && guildId.length > 0 && guildId[0]) {
                     const fileContent = fs.readFileSync(base_dir + file.name, 'utf8');
                     const parsedFileContent = JSON.parse(fileContent);
-                    this.subscriptions.set(guildId[1], {channels: this.createChannelMap(parsedFileContent.channels)});
+                    this.subscriptions.set(guildId[1], {
+                        channels: this.createChannelMap(parsedFileContent.channels),
+                        locale: parsedFileContent.locale,
+                        adminRoleId: parsedFileContent.adminRoleId,
+                        subscriberRoleIds: parsedFileContent.subscriberRoleIds,
+                        alertChannelId: parsedFileContent.alertChannelId,
+                        ownEntityIds: parsedFileContent.ownEntityIds,
+                        defaultPingRoleId: parsedFileContent.defaultPingRoleId,
+                        defaultPingRoleValue: parsedFileContent.defaultPingRoleValue,
+                        embedMode: parsedFileContent.embedMode,
+                        hideThumbnails: parsedFileContent.hideThumbnails,
+                        colorBlind: parsedFileContent.colorBlind,
+                        mapProvider: parsedFileContent.mapProvider,
+                        postDuplicates: parsedFileContent.postDuplicates,
+                        valueFormat: parsedFileContent.valueFormat,
+                        showPlexValue: parsedFileContent.showPlexValue,
+                        entityLists: parsedFileContent.entityLists,
+                        entityListsUpdatedAt: parsedFileContent.entityListsUpdatedAt,
+                        reports: parsedFileContent.reports,
+                        campaigns: parsedFileContent.campaigns,
+                    });
                 }
             }
         }
         return this;
     }
 
+    // Rewrites a cache file holding more entries than its cache keeps, e.g. after its maximum size was lowered
+    private pruneCacheFile(file: string, cache: BoundedCache<number, unknown>, loaded: number) {
+        if (cache.size < loaded) {
+            logger.info(`${file} holds ${loaded} entries, pruning it to ${cache.size}`);
+            this.cacheWriter.markDirty(file, () => cache.toJson());
+        }
+    }
+
     public withSystems(base_dir = './config/'): ZKillSubscriber {
         if (fs.existsSync(base_dir + 'systems.json')) {
             const fileContent = fs.readFileSync(base_dir + 'systems.json', 'utf8');
@@ -1410,8 +4421,9 @@ export class ZKillSubscriber {
                 for (const key in data) {
                     this.systems.set(Number.parseInt(key), data[key] as SolarSystem);
                 }
+                this.pruneCacheFile(base_dir + 'systems.json', this.systems, Object.keys(data).length);
             } catch (e) {
-                console.log('failed to parse systems.json');
+                logger.info('failed to parse systems.json');
             }
         }
         return this;
@@ -1425,8 +4437,133 @@ export class ZKillSubscriber {
                 for (const key in data) {
                     this.ships.set(Number.parseInt(key), data[key]);
                 }
+                this.pruneCacheFile(base_dir + 'ships.json', this.ships, Object.keys(data).length);
+            } catch (e) {
+                logger.info('failed to parse ships.json');
+            }
+        }
+        return this;
+    }
+
+    public withStarMap(base_dir = './config/'): ZKillSubscriber {
+        this.starMap = StarMap.fromSde(base_dir);
+        if (this.starMap == null) {
+            logger.info('no SDE map dumps found, jump based filters are disabled');
+        }
+        return this;
+    }
+
+    private healthProbe(): HealthProbe {
+        return {
+            lastKillAt: () => this.lastKillReceivedAt,
+            discordConnected: () => this.doClient.ws.status === Constants.Status.READY,
+            esiReachable: async () => {
+                if (this.esiStatus == null || Date.now() - this.esiStatus.time > 60 * 1000) {
+                    this.esiStatus = {reachable: await this.esiClient.isReachable(), time: Date.now()};
+                }
+                return this.esiStatus.reachable;
+            },
+        };
+    }
+
+    // Refreshes the market prices every MARKET_PRICE_REFRESH_MINUTES for the hull value filter and footer
+    public withMarketPrices(): ZKillSubscriber {
+        this.marketPrices.start();
+        return this;
+    }
+
+    // Fills the type, group and category lookups from the static data export in SDE_DIR, so only types added
+    // since the export are looked up on ESI
+    public withSde(dir = process.env.SDE_DIR): ZKillSubscriber {
+        if (!dir) {
+            return this;
+        }
+        const sde = loadSde(dir);
+        sde.typeGroups.forEach((groupId, typeId) => this.ships.set(typeId, groupId));
+        sde.typeNames.forEach((name, typeId) => this.names.set(typeId, name));
+        sde.groupCategories.forEach((categoryId, groupId) => this.categories.set(groupId, categoryId));
+        sde.groupNames.forEach((name, groupId) => this.groupNames.set(groupId, name));
+        // The meta types list every type that is not Tech I, so the SDE types missing there need no ESI request
+        if (sde.typeMetaGroups.size > 0) {
+            sde.typeGroups.forEach((groupId, typeId) => this.metaGroups.set(typeId, sde.typeMetaGroups.get(typeId) ?? null));
+        }
+        logger.info(`loaded ${sde.typeGroups.size} types and ${sde.groupCategories.size} groups from the SDE`);
+        return this;
+    }
+
+    // Deletes long disabled subscriptions at startup and then every hour
+    public withDisabledPurge(): ZKillSubscriber {
+        const purge = () => this.purgeDisabledSubscriptions().catch(e => logger.error(`failed to purge disabled subscriptions: ${e}`));
+        purge();
+        setInterval(purge, 60 * 60 * 1000);
+        return this;
+    }
+
+    // Checks every minute for reports to post
+    public withReports(): ZKillSubscriber {
+        setInterval(() => {
+            this.postDueReports().catch(e => logger.error(`failed to post the reports: ${e}`));
+        }, 60 * 1000);
+        return this;
+    }
+
+    // Type and system lookups only use the caches and the SDE, a lookup they do not cover fails instead of asking ESI
+    public withOfflineLookups(): ZKillSubscriber {
+        this.esiClient.setOffline();
+        return this;
+    }
+
+    // Serves /healthz if HEALTH_PORT is set, and reports when no kill arrived for WATCHDOG_MINUTES
+    public withHealthCheck(): ZKillSubscriber {
+        const probe = this.healthProbe();
+        this.healthServer = HealthServer.fromEnv(probe);
+        this.healthServer?.start();
+        const maxKillAgeMs = Number(process.env.WATCHDOG_MINUTES || 15) * 60 * 1000;
+        this.killWatchdog = new KillWatchdog(maxKillAgeMs, probe, minutes => {
+            logger.error(`no kill received from ${this.killSource.type} for ${minutes} minutes`);
+            const channelId = process.env.WATCHDOG_CHANNEL_ID;
+            const channel = channelId ? <TextChannel>this.doClient.channels.cache.get(channelId) : undefined;
+            channel?.send(`No kill received from zKillboard (${this.killSource.type}) for ${minutes} minutes`)
+                .catch(e => logger.error(`failed to notify the watchdog channel: ${e}`));
+        });
+        this.killWatchdog.start();
+        return this;
+    }
+
+    public withFeedServer(): ZKillSubscriber {
+        this.feedServer = FeedServer.fromEnv(token => this.getFeed(token), process.env, this.imageProxy);
+        this.feedServer?.start();
+        return this;
+    }
+
+    public withCategories(base_dir = './config/'): ZKillSubscriber {
+        if (fs.existsSync(base_dir + 'categories.json')) {
+            const fileContent = fs.readFileSync(base_dir + 'categories.json', 'utf8');
+            try {
+                const data = JSON.parse(fileContent);
+                for (const key in data) {
+                    this.categories.set(Number.parseInt(key), data[key]);
+                }
             } catch (e) {
-                console.log('failed to parse ships.json');
+                logger.info('failed to parse categories.json');
+            }
+        }
+        return this;
+    }
+
+    public withDmSubscriptions(base_dir = './config/'): ZKillSubscriber {
+        if (!fs.existsSync(base_dir + 'dm/')) {
+            return this;
+        }
+        for (const file of fs.readdirSync(base_dir + 'dm/')) {
+            const userId = file.match(/^(\d+)\.json$/);
+            if (userId) {
+                try {
+                    const parsedFileContent = JSON.parse(fs.readFileSync(base_dir + 'dm/' + file, 'utf8'));
+                    this.dmSubscriptions.set(userId[1], {subscriptions: this.createSubscriptionMap(parsedFileContent.subscriptions)});
+                } catch (e) {
+                    logger.info(`failed to parse dm/${file}`);
+                }
             }
         }
         return this;
@@ -1440,8 +4577,66 @@ export class ZKillSubscriber {
                 for (const key in data) {
                     this.names.set(Number.parseInt(key), data[key]);
                 }
+                this.pruneCacheFile(base_dir + 'names.json', this.names, Object.keys(data).length);
+            } catch (e) {
+                logger.info('failed to parse names.json');
+            }
+        }
+        return this;
+    }
+
+    // Loads the counters after withConfig, dropping those of deleted subscriptions
+    public withSubscriptionStats(base_dir = './config/'): ZKillSubscriber {
+        if (fs.existsSync(base_dir + 'subscription_stats.json')) {
+            try {
+                this.subscriptionStats = SubscriptionStats.fromJson(fs.readFileSync(base_dir + 'subscription_stats.json', 'utf8'));
+            } catch (e) {
+                logger.info('failed to parse subscription_stats.json');
+            }
+        }
+        const keys = new Set<string>();
+        this.subscriptions.forEach((guild, guildId) => guild.channels.forEach((channel, channelId) => {
+            channel.subscriptions.forEach((subscription, ident) => keys.add(SubscriptionStats.key(guildId, channelId, ident)));
+        }));
+        this.subscriptionStats.retain(keys);
+        return this;
+    }
+
+    public getSubscriptionCounters(guildId: string, channelId: string, ident: string): SubscriptionCounters | undefined {
+        return this.subscriptionStats.get(SubscriptionStats.key(guildId, channelId, ident));
+    }
+
+    public getDeliveryDiagnostics(): DeliveryDiagnostics {
+        return {
+            received: this.deliveryLatency.received.summary(),
+            posted: this.deliveryLatency.posted.summary(),
+            queueId: this.killSource.type === KillSourceType.REDISQ ? this.killSource.queueId : undefined,
+            queuedKills: this.killQueue.size,
+            cacheSizes: {
+                systems: this.systems.size,
+                ships: this.ships.size,
+                categories: this.categories.size,
+                names: this.names.size,
+                tickers: this.tickers.size,
+            },
+            cacheStats: {
+                systems: this.systems.stats(),
+                ships: this.ships.stats(),
+                names: this.names.stats(),
+            },
+        };
+    }
+
+    public withTickers(base_dir = './config/'): ZKillSubscriber {
+        if (fs.existsSync(base_dir + 'tickers.json')) {
+            const fileContent = fs.readFileSync(base_dir + 'tickers.json', 'utf8');
+            try {
+                const data = JSON.parse(fileContent);
+                for (const key in data) {
+                    this.tickers.set(Number.parseInt(key), data[key]);
+                }
             } catch (e) {
-                console.log('failed to parse names.json');
+                logger.info('failed to parse tickers.json');
             }
         }
         return this;
@@ -1455,6 +4650,10 @@ export class ZKillSubscriber {
         }
     }
 
+    strEveWho(characterId: number): string {
+        return `https://evewho.com/character/${characterId}`;
+    }
+
     strCorpZk(corporationId: number): string {
         try {
             return `https://zkillboard.com/corporation/${corporationId.toString()}/`;
@@ -1487,22 +4686,6 @@ export class ZKillSubscriber {
         }
     }
 
-    strSystemDotlan(systemId: number): string {
-        try {
-            return `http://evemaps.dotlan.net/system/${systemId.toString()}`;
-        } catch {
-            return '';
-        }
-    }
-
-    strRegionDotlan(regionId: number): string {
-        try {
-            return `http://evemaps.dotlan.net/region/${regionId.toString()}`;
-        } catch {
-            return '';
-        }
-    }
-
     strItemRenderById(itemId: number): string {
         try {
             return `https://images.evetech.net/types/${itemId.toString()}/icon`;
@@ -1510,22 +4693,6 @@ export class ZKillSubscriber {
             return '';
         }
     }
-
-    strAllianceIconById(allianceId: number): string {
-        try {
-            return `https://images.evetech.net/alliances/${allianceId.toString()}/logo?size=64`;
-        } catch {
-            return '';
-        }
-    }
-
-    strCorporationIconById(corporationId: number): string {
-        try {
-            return `https://images.evetech.net/corporations/${corporationId.toString()}/logo?size=64`;
-        } catch {
-            return '';
-        }
-    }
 }
 
 